@@ -1,4 +1,4 @@
-use panoramix::elements::{Button, Checkbox, ComponentOutput, ElementList, Label, Toggled};
+use panoramix::elements::{Button, Checkbox, CheckboxToggled, ComponentOutput, ElementList, Label};
 use panoramix::flex::{CrossAxisAlignment, FlexContainerParams, MainAxisAlignment};
 use panoramix::{component, CompCtx, Element, ElementExt, NoEvent, RootHandler, Row, Tuple};
 
@@ -39,7 +39,7 @@ impl Default for AppState {
     }
 }
 
-type RowEvent = Toggled;
+type RowEvent = CheckboxToggled;
 #[derive(Debug, Default, Clone, PartialEq)]
 struct RowProps {
     list_item: ListItem,
@@ -52,10 +52,13 @@ fn MyListRow(ctx: &CompCtx, props: RowProps) -> impl Element<Event = RowEvent> {
     let age = ctx.get_local_state(md);
 
     let row = Row!(
-        Checkbox::new("", props.is_selected).map_event(md, |state: &mut u16, event| {
-            *state += 1;
-            Some(event)
-        }),
+        Checkbox::new("").with_checked(props.is_selected).map_event(
+            md,
+            |state: &mut u16, event| {
+                *state += 1;
+                Some(event)
+            }
+        ),
         Label::new(format!("{} - age={}", &props.list_item.text, age)),
         Label::new(format!("id={}", props.list_item.id)),
     )
@@ -106,7 +109,7 @@ fn AwesomeEditableList(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEven
         };
 
         MyListRow::new(row_props).on::<RowEvent, _, _, _>(md, move |state: &mut AppState, event| {
-            if event.new_value {
+            if event.0 {
                 state.selected_row = Some(i);
             } else {
                 state.selected_row = None;
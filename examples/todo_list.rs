@@ -1,5 +1,5 @@
 use panoramix::elements::{
-    Button, Checkbox, ComponentOutput, ElementList, Label, TextBox, TextChanged, Toggled,
+    Button, Checkbox, CheckboxToggled, ComponentOutput, ElementList, Label, TextBox, TextChanged,
 };
 use panoramix::flex::{CrossAxisAlignment, FlexContainerParams, MainAxisAlignment};
 use panoramix::{component, Column, CompCtx, Element, ElementExt, NoEvent, RootHandler, Row};
@@ -49,7 +49,7 @@ impl Default for AppState {
     }
 }
 
-type ItemEvent = Toggled;
+type ItemEvent = CheckboxToggled;
 
 #[component]
 fn TaskRow(ctx: &CompCtx, props: TaskItem) -> impl Element<Event = ItemEvent> {
@@ -61,7 +61,9 @@ fn TaskRow(ctx: &CompCtx, props: TaskItem) -> impl Element<Event = ItemEvent> {
     };
 
     let row = Row!(
-        Checkbox::new("", props.is_completed).bubble_up::<ItemEvent, _, _>(md),
+        Checkbox::new("")
+            .with_checked(props.is_completed)
+            .bubble_up::<ItemEvent, _, _>(md),
         Label::new(text),
     )
     .with_flex_container_params(ROW_FLEX_PARAMS);
@@ -73,12 +75,11 @@ fn AwesomeEditableList(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEven
     let md = ctx.use_metadata::<NoEvent, AppState>();
     let state = ctx.get_local_state(md);
 
-    let checkbox_priority = Checkbox::new("High priority", state.high_priority).on(
-        md,
-        |state: &mut AppState, event: Toggled| {
-            state.high_priority = event.new_value;
-        },
-    );
+    let checkbox_priority = Checkbox::new("High priority")
+        .with_checked(state.high_priority)
+        .on_toggle(md, |state: &mut AppState, checked| {
+            state.high_priority = checked;
+        });
     // TODO - Add "validate on enter" feature
     let textbox_task_name = TextBox::new(state.task_name.clone()).on_text_changed(
         md,
@@ -115,7 +116,7 @@ fn AwesomeEditableList(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEven
         TaskRow::new(task_item.clone()).on::<ItemEvent, _, _, _>(
             md,
             move |state: &mut AppState, event| {
-                state.tasks[i].is_completed = event.new_value;
+                state.tasks[i].is_completed = event.0;
             },
         )
     });
@@ -1,4 +1,4 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use std::fmt::Display;
 use syn::Error;
@@ -10,9 +10,11 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
         Err(Error::new_spanned(tokens, message))
     }
 
-    if !attr.is_empty() {
-        return error(attr, "#[component] attribute doesn't take parameters");
-    }
+    let initial_state_fn = if attr.is_empty() {
+        None
+    } else {
+        Some(syn::parse::Parser::parse2(parse_initial_state_attr, attr)?)
+    };
 
     // Types used:
     // - syn::ItemFn
@@ -116,7 +118,7 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
     }
     let props_ty = *props_arg.ty.clone();
 
-    let fn_output = match fn_output {
+    let mut fn_output = match fn_output {
         syn::ReturnType::Type(_, ty) => *ty,
         syn::ReturnType::Default => {
             return error(
@@ -126,7 +128,8 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
         }
     };
 
-    let local_event_ty = if let Some(local_event_ty) = parse_return_ty(fn_output.clone())? {
+    let (local_event_ty, local_state_ty) = parse_return_ty(fn_output.clone())?;
+    let local_event_ty = if let Some(local_event_ty) = local_event_ty {
         local_event_ty
     } else {
         return error(
@@ -135,6 +138,24 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
         );
     };
 
+    // If the return type doesn't declare the local state, it's the state passed to
+    // use_metadata for components which return a ComponentOutput, and NoState otherwise. The
+    // bound is added to the return type, so that ComponentHolder can check it.
+    let local_state_ty = if let Some(local_state_ty) = local_state_ty {
+        local_state_ty
+    } else {
+        let fn_tokens = fn_block.to_token_stream();
+        let local_state_ty = if contains_ident(fn_tokens.clone(), "ComponentOutput") {
+            find_metadata_state(fn_tokens)?
+        } else {
+            None
+        };
+        let local_state_ty =
+            local_state_ty.unwrap_or_else(|| syn::parse_quote!(panoramix::NoState));
+        add_component_state_bound(&mut fn_output, &local_state_ty);
+        local_state_ty
+    };
+
     // TODO
     // - Error message if user tries to do MyComponent(props) instead of MyComponent::new(props)
 
@@ -143,6 +164,14 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
     let ComponentName_literal = proc_macro2::Literal::string(&component_name.to_string());
     let PropsType = props_ty;
     let LocalEvent = local_event_ty;
+    let LocalState = local_state_ty;
+    let initial_state_impl = initial_state_fn.map(|initial_state_fn| {
+        quote! {
+            fn initial_state(props: &Self::Props) -> Self::LocalState {
+                (#initial_state_fn)(props)
+            }
+        }
+    });
 
     Ok(quote! {
         #[derive(Debug, Default, Clone, PartialEq, Hash)]
@@ -166,6 +195,7 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
         impl panoramix::elements::Component for #ComponentName {
             type Props = #PropsType;
             type LocalEvent = #LocalEvent;
+            type LocalState = #LocalState;
 
             fn new(
                 props: Self::Props,
@@ -176,6 +206,8 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
                 )
             }
 
+            #initial_state_impl
+
             fn name() -> &'static str {
                 #ComponentName_literal
             }
@@ -183,8 +215,25 @@ pub fn component(attr: TokenStream, fn_item: syn::ItemFn) -> Result<TokenStream,
     })
 }
 
-fn parse_return_ty(return_ty: syn::Type) -> Result<Option<syn::Type>, Error> {
-    fn error(tokens: impl ToTokens, message: impl Display) -> Result<Option<syn::Type>, Error> {
+// Parses `initial_state = my_fn`, and returns `my_fn`.
+fn parse_initial_state_attr(input: syn::parse::ParseStream) -> Result<syn::Expr, Error> {
+    let name: syn::Ident = input.parse()?;
+    if name != "initial_state" {
+        return Err(Error::new_spanned(
+            name,
+            "#[component] attribute only takes an `initial_state = /* ... */` parameter",
+        ));
+    }
+    input.parse::<syn::Token![=]>()?;
+    input.parse()
+}
+
+// Returns the types bound to `Event` and `ComponentState` in `impl Element</* ... */>`.
+fn parse_return_ty(return_ty: syn::Type) -> Result<(Option<syn::Type>, Option<syn::Type>), Error> {
+    fn error(
+        tokens: impl ToTokens,
+        message: impl Display,
+    ) -> Result<(Option<syn::Type>, Option<syn::Type>), Error> {
         Err(Error::new_spanned(tokens, message))
     }
 
@@ -216,28 +265,108 @@ fn parse_return_ty(return_ty: syn::Type) -> Result<Option<syn::Type>, Error> {
     // AngleBracketedGenericArguments
     let elements_ty_args: Vec<_> = match elements_ty_args {
         syn::PathArguments::None => {
-            return Ok(None);
+            return Ok((None, None));
         }
         syn::PathArguments::AngleBracketed(elements_ty_args) => {
             elements_ty_args.args.into_iter().collect()
         }
         syn::PathArguments::Parenthesized(_) => {
-            return Ok(None);
+            return Ok((None, None));
         }
     };
 
-    if elements_ty_args.len() == 0 {
+    let find_binding = |name: &str| {
+        elements_ty_args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Binding(binding) if binding.ident.to_string() == name => {
+                Some(binding.ty.clone())
+            }
+            _ => None,
+        })
+    };
+
+    Ok((find_binding("Event"), find_binding("ComponentState")))
+}
+
+// Adds `ComponentState = local_state_ty` to a return type accepted by parse_return_ty.
+fn add_component_state_bound(return_ty: &mut syn::Type, local_state_ty: &syn::Type) {
+    if let syn::Type::ImplTrait(impl_trait) = return_ty {
+        if let Some(syn::TypeParamBound::Trait(element_trait)) = impl_trait.bounds.first_mut() {
+            let last_segment = element_trait.path.segments.last_mut().unwrap();
+            if let syn::PathArguments::AngleBracketed(elements_ty_args) =
+                &mut last_segment.arguments
+            {
+                elements_ty_args
+                    .args
+                    .push(syn::parse_quote!(ComponentState = #local_state_ty));
+            }
+        }
+    }
+}
+
+fn contains_ident(tokens: TokenStream, name: &str) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Group(group) => contains_ident(group.stream(), name),
+        TokenTree::Ident(ident) => ident == name,
+        _ => false,
+    })
+}
+
+// Returns the state type of the first `use_metadata::<Event, State>` call in `tokens`.
+fn find_metadata_state(tokens: TokenStream) -> Result<Option<syn::Type>, Error> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => {
+                if let Some(local_state_ty) = find_metadata_state(group.stream())? {
+                    return Ok(Some(local_state_ty));
+                }
+            }
+            TokenTree::Ident(ident) if ident == "use_metadata" => {
+                return parse_metadata_args(&tokens[i + 1..]);
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+// Parses the `::<Event, State>` after `use_metadata`, and returns `State`.
+fn parse_metadata_args(tokens: &[TokenTree]) -> Result<Option<syn::Type>, Error> {
+    let is_punct = |token: Option<&TokenTree>, c: char| match token {
+        Some(TokenTree::Punct(punct)) => punct.as_char() == c,
+        _ => false,
+    };
+    if !(is_punct(tokens.get(0), ':')
+        && is_punct(tokens.get(1), ':')
+        && is_punct(tokens.get(2), '<'))
+    {
+        // Eg `ctx.use_metadata()`, with the types inferred from a `let` annotation; the state
+        // must then be declared in the return type
         return Ok(None);
     }
 
-    let local_event_ty = elements_ty_args.into_iter().find_map(|arg| match arg {
-        syn::GenericArgument::Binding(local_event_bind)
-            if local_event_bind.ident.to_string() == "Event" =>
-        {
-            Some(local_event_bind.ty)
+    let mut depth = 0;
+    let mut args = vec![TokenStream::new()];
+    for token in &tokens[3..] {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+            TokenTree::Punct(punct) if punct.as_char() == '>' => {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' && depth == 0 => {
+                args.push(TokenStream::new());
+                continue;
+            }
+            _ => {}
         }
-        _ => None,
-    });
+        args.last_mut().unwrap().extend(Some(token.clone()));
+    }
 
-    Ok(local_event_ty)
+    match args.get(1) {
+        Some(local_state_ty) => syn::parse2(local_state_ty.clone()).map(Some),
+        None => Ok(None),
+    }
 }
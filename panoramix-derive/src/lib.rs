@@ -43,6 +43,29 @@ use tracing::trace;
 /// MyComponent::new(my_props)
 /// # ;
 /// ```
+///
+/// ## Local state
+///
+/// The local state of the component is the `ComponentState` of the returned element. It can be
+/// declared in the return type, eg `impl Element<Event = MyEventType, ComponentState = i32>`.
+/// Otherwise, it's the state type passed to `use_metadata::<MyEventType, MyStateType>()` if the
+/// function uses [`ComponentOutput`](panoramix::elements::ComponentOutput), and
+/// [`NoState`](panoramix::NoState) if it doesn't. Returning an element with a different state
+/// is a compile error.
+///
+/// The state is [`Default`] when a component instance is first built, unless the attribute is
+/// given a function which computes it from the props:
+///
+/// ```rust
+/// # use panoramix::{component, CompCtx, Element, NoEvent};
+/// # use panoramix::elements::{ComponentOutput, Label};
+/// #[component(initial_state = |start: &i32| *start)]
+/// fn Counter(ctx: &CompCtx, start: i32) -> impl Element<Event = NoEvent> {
+///     let md = ctx.use_metadata::<NoEvent, i32>();
+///     let count = ctx.get_local_state(md);
+///     ComponentOutput::new(md, Label::new(format!("Count: {}", count)))
+/// }
+/// ```
 #[allow(rustdoc::broken_intra_doc_links)]
 #[proc_macro_attribute]
 pub fn component(attr: TokenStream, item: TokenStream) -> TokenStream {
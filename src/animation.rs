@@ -0,0 +1,116 @@
+//! Values animated by components with [`CompCtx::use_animated`](crate::CompCtx::use_animated).
+//!
+//! Like intervals, animations don't own a widget: the root widget requests animation frames
+//! while any value is still moving, and passes the time elapsed since the previous frame
+//! down the tree in [`GlobalEventCx`](crate::glue::GlobalEventCx). Each component then
+//! advances its values, which changes its state, so it's built again with the new values.
+
+use druid::EventCtx;
+use std::cell::Cell;
+use std::time::Duration;
+use tracing::trace;
+
+thread_local! {
+    // Set when a value is moving, until the root widget next has an EventCtx.
+    static ANIM_FRAME_REQUESTED: Cell<bool> = Cell::new(false);
+}
+
+/// Request an animation frame if an animated value has moved since the last call.
+pub(crate) fn request_anim_frame(ctx: &mut EventCtx) {
+    if ANIM_FRAME_REQUESTED.with(|requested| requested.replace(false)) {
+        ctx.request_anim_frame();
+    }
+}
+
+fn queue_anim_frame_request() {
+    ANIM_FRAME_REQUESTED.with(|requested| requested.set(true));
+}
+
+/// The values animated by a component, in the order they were registered.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AnimationState {
+    values: Vec<AnimatedValue>,
+}
+
+// A linear tween from `start` to `target`.
+#[derive(Clone, Debug, PartialEq)]
+struct AnimatedValue {
+    current: f64,
+    start: f64,
+    target: f64,
+    duration: Duration,
+    elapsed: Duration,
+}
+
+impl AnimationState {
+    /// Register the next animated value, moving towards `target` over `duration`.
+    ///
+    /// `prev_state` holds the values of the previous build. If the target has changed, the
+    /// value starts moving from where it currently is, so that the animation is retargeted
+    /// without jumping. Returns the current value.
+    pub(crate) fn push(
+        &mut self,
+        prev_state: &AnimationState,
+        target: f64,
+        duration: Duration,
+    ) -> f64 {
+        let value = match prev_state.values.get(self.values.len()) {
+            Some(prev) if prev.target == target => AnimatedValue {
+                duration,
+                ..prev.clone()
+            },
+            Some(prev) => {
+                trace!("Animating from {} to {}", prev.current, target);
+                AnimatedValue {
+                    current: prev.current,
+                    start: prev.current,
+                    target,
+                    duration,
+                    elapsed: Duration::ZERO,
+                }
+            }
+            // The first value isn't animated
+            None => AnimatedValue {
+                current: target,
+                start: target,
+                target,
+                duration,
+                elapsed: Duration::ZERO,
+            },
+        };
+        if !value.is_settled() {
+            queue_anim_frame_request();
+        }
+        let current = value.current;
+        self.values.push(value);
+        current
+    }
+
+    /// Move every value forward by `interval` nanoseconds.
+    ///
+    /// Returns whether any value moved.
+    pub(crate) fn advance(&mut self, interval: u64) -> bool {
+        let mut moved = false;
+        for value in &mut self.values {
+            if value.is_settled() {
+                continue;
+            }
+            moved = true;
+            value.elapsed += Duration::from_nanos(interval);
+            if value.elapsed >= value.duration {
+                value.current = value.target;
+            } else {
+                let progress = value.elapsed.as_nanos() as f64 / value.duration.as_nanos() as f64;
+                value.current = value.start + (value.target - value.start) * progress;
+                queue_anim_frame_request();
+            }
+        }
+        moved
+    }
+}
+
+impl AnimatedValue {
+    fn is_settled(&self) -> bool {
+        self.current == self.target
+    }
+}
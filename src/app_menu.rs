@@ -0,0 +1,90 @@
+//! The application menu declared with a [`MenuBar`](crate::elements::MenuBar).
+//!
+//! The menu bar element registers a description of its menus when it's reconciled; the
+//! window's native menu is built from that description. Selecting an item submits
+//! [`MENU_ITEM_SELECTED`] to the window, which the menu bar turns into an event during the
+//! next event pass.
+
+use crate::glue::DruidAppData;
+use druid::menu::{Menu, MenuEventCtx, MenuItem};
+use druid::{Env, RawMods, Selector};
+use std::cell::{Cell, RefCell};
+use tracing::trace;
+
+/// Command submitted when an item of the application menu is selected.
+///
+/// The payload is the index of the menu, and the index of the item in that menu.
+pub(crate) const MENU_ITEM_SELECTED: Selector<(usize, usize)> =
+    Selector::new("panoramix.menu-item-selected");
+
+/// The part of a menu that the native menu is built from.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MenuSpec {
+    pub title: String,
+    pub items: Vec<MenuItemSpec>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MenuItemSpec {
+    pub text: String,
+    pub enabled: bool,
+    pub hotkey: Option<(RawMods, String)>,
+}
+
+thread_local! {
+    static MENU_BAR: RefCell<Vec<MenuSpec>> = Default::default();
+    // Set when the menus change, until the native menu is rebuilt
+    static MENU_CHANGED: Cell<bool> = Cell::new(false);
+}
+
+/// Replace the menus of the application menu.
+pub(crate) fn set_menu_bar(menus: Vec<MenuSpec>) {
+    trace!("Setting application menu: {:?}", menus);
+    MENU_BAR.with(|menu_bar| *menu_bar.borrow_mut() = menus);
+    MENU_CHANGED.with(|changed| changed.set(true));
+}
+
+/// Find the item with the given text in the menu with the given title.
+///
+/// Returns the index of the menu and of the item, and whether the item is enabled.
+pub(crate) fn find_item(menu_title: &str, item_text: &str) -> Option<(usize, usize, bool)> {
+    MENU_BAR.with(|menu_bar| {
+        let menu_bar = menu_bar.borrow();
+        let menu_index = menu_bar.iter().position(|menu| menu.title == menu_title)?;
+        let items = &menu_bar[menu_index].items;
+        let item_index = items.iter().position(|item| item.text == item_text)?;
+        Some((menu_index, item_index, items[item_index].enabled))
+    })
+}
+
+/// Build the native menu from the registered menus.
+///
+/// The menu is rebuilt whenever the registered menus change.
+pub(crate) fn build_menu() -> Menu<DruidAppData> {
+    MENU_CHANGED.with(|changed| changed.set(false));
+    let menus = MENU_BAR.with(|menu_bar| menu_bar.borrow().clone());
+
+    let mut menu_bar = Menu::empty().rebuild_on(
+        |_old_data: &DruidAppData, _data: &DruidAppData, _env: &Env| {
+            MENU_CHANGED.with(|changed| changed.get())
+        },
+    );
+    for (menu_index, menu) in menus.into_iter().enumerate() {
+        let mut native_menu = Menu::new(menu.title);
+        for (item_index, item) in menu.items.into_iter().enumerate() {
+            let mut native_item = MenuItem::new(item.text)
+                .on_activate(
+                    move |ctx: &mut MenuEventCtx, _data: &mut DruidAppData, _env: &Env| {
+                        ctx.submit_command(MENU_ITEM_SELECTED.with((menu_index, item_index)));
+                    },
+                )
+                .enabled(item.enabled);
+            if let Some((mods, key)) = item.hotkey {
+                native_item = native_item.hotkey(mods, key.as_str());
+            }
+            native_menu = native_menu.entry(native_item);
+        }
+        menu_bar = menu_bar.entry(native_menu);
+    }
+    menu_bar
+}
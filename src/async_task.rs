@@ -0,0 +1,189 @@
+//! Background tasks spawned by components with [`CompCtx::spawn_async`](crate::CompCtx::spawn_async).
+//!
+//! Each task runs its future to completion on its own thread, then stores the output and asks
+//! the root widget to process events again through druid's [`ExtEventSink`]. The output is
+//! handed to the component when events are processed, on the UI thread.
+
+use druid::{ExtEventSink, Selector, Target};
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::Thread;
+use tracing::trace;
+
+/// Command sent to the root widget when a background task completes.
+pub(crate) const ASYNC_TASK_DONE: Selector = Selector::new("panoramix.async-task-done");
+
+thread_local! {
+    // Handle used by background tasks to wake up the UI thread.
+    static EXT_EVENT_SINK: RefCell<Option<ExtEventSink>> = RefCell::new(None);
+
+    // Number of tasks spawned from this thread which haven't completed yet.
+    static TASKS_IN_FLIGHT: Arc<AtomicUsize> = Default::default();
+}
+
+/// Store the handle used by background tasks spawned from this thread to wake up the UI.
+pub(crate) fn set_ext_event_sink(sink: ExtEventSink) {
+    EXT_EVENT_SINK.with(|ext_sink| *ext_sink.borrow_mut() = Some(sink));
+}
+
+/// Number of tasks spawned from this thread which are still running.
+pub(crate) fn tasks_in_flight() -> usize {
+    TASKS_IN_FLIGHT.with(|count| count.load(Ordering::SeqCst))
+}
+
+type TaskOutput = Box<dyn Any + Send>;
+type TaskCallback = Rc<dyn Fn(&mut dyn Any, TaskOutput)>;
+
+/// The background task of a component, if it has one.
+///
+/// Stored in the component's state, so that the task can be cancelled when the component is
+/// unmounted, or when it spawns another task.
+#[derive(Clone, Default)]
+pub struct AsyncTaskState {
+    task: Option<AsyncTask>,
+}
+
+#[derive(Clone)]
+struct AsyncTask {
+    shared: Arc<SharedTaskState>,
+    worker: Thread,
+    on_output: TaskCallback,
+}
+
+#[derive(Default)]
+struct SharedTaskState {
+    output: Mutex<Option<TaskOutput>>,
+    cancelled: AtomicBool,
+}
+
+impl AsyncTaskState {
+    /// Run `future` on a background thread.
+    ///
+    /// Once the future completes, its output is passed to `on_output` along with the
+    /// component's local state, the next time events are processed.
+    pub(crate) fn spawn<ComponentState: 'static, Output: Send + 'static>(
+        future: impl Future<Output = Output> + Send + 'static,
+        on_output: impl Fn(&mut ComponentState, Output) + 'static,
+    ) -> Self {
+        let shared = Arc::new(SharedTaskState::default());
+        let task_shared = shared.clone();
+        let sink = EXT_EVENT_SINK.with(|sink| sink.borrow().clone());
+        let in_flight = TASKS_IN_FLIGHT.with(|count| count.clone());
+
+        in_flight.fetch_add(1, Ordering::SeqCst);
+        let worker = std::thread::spawn(move || {
+            let output = block_on(future, &task_shared.cancelled);
+            if let Some(output) = output {
+                {
+                    // Checked under the lock, so that a concurrent cancel() always discards the output
+                    let mut stored_output = task_shared.output.lock().unwrap();
+                    if !task_shared.cancelled.load(Ordering::SeqCst) {
+                        *stored_output = Some(Box::new(output));
+                    }
+                }
+                if let Some(sink) = sink {
+                    // The app may have been closed in the meantime
+                    let _ = sink.submit_command(ASYNC_TASK_DONE, (), Target::Auto);
+                }
+            }
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        });
+        let worker = worker.thread().clone();
+
+        let on_output: TaskCallback = Rc::new(move |state: &mut dyn Any, output: TaskOutput| {
+            let state = state
+                .downcast_mut::<ComponentState>()
+                .expect("internal type error: spawn_async got the wrong component state type");
+            let output = output
+                .downcast::<Output>()
+                .expect("internal type error: spawn_async got the wrong output type");
+            on_output(state, *output);
+        });
+
+        AsyncTaskState {
+            task: Some(AsyncTask {
+                shared,
+                worker,
+                on_output,
+            }),
+        }
+    }
+
+    pub(crate) fn is_none(&self) -> bool {
+        self.task.is_none()
+    }
+
+    /// Stop the task. Its output, if it's produced, is discarded.
+    pub(crate) fn cancel(&self) {
+        if let Some(task) = &self.task {
+            trace!("Cancelling async task");
+            task.shared.cancelled.store(true, Ordering::SeqCst);
+            task.shared.output.lock().unwrap().take();
+            // Wake the worker up if it's waiting on the future, so that it stops
+            task.worker.unpark();
+        }
+    }
+
+    /// If the task has completed, pass its output to the callback it was spawned with.
+    pub(crate) fn deliver_output(&mut self, state: &mut dyn Any) {
+        let output = match &self.task {
+            Some(task) => task.shared.output.lock().unwrap().take(),
+            None => None,
+        };
+        if let Some(output) = output {
+            let task = self.task.take().unwrap();
+            (task.on_output)(state, output);
+        }
+    }
+}
+
+impl std::fmt::Debug for AsyncTaskState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncTaskState")
+            .field("running", &self.task.is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for AsyncTaskState {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.task, &other.task) {
+            (Some(task), Some(other_task)) => Arc::ptr_eq(&task.shared, &other_task.shared),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+// --- Executor
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run the future on the current thread, until it completes or `cancelled` is set.
+fn block_on<F: Future>(future: F, cancelled: &AtomicBool) -> Option<F::Output> {
+    let waker: Waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future: Pin<Box<F>> = Box::pin(future);
+
+    loop {
+        if cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return Some(output),
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Focus requested by text inputs built with `.autofocus()`, eg
+//! [`TextBox::autofocus`](crate::elements::TextBox::autofocus).
+//!
+//! An element only requests focus the first time it's built with autofocus, and only if no
+//! other element requested it earlier in the same build pass, so the first autofocus element
+//! in tree order wins. Widgets created by [`init_tree`](crate::internals::VirtualDom::init_tree)
+//! don't have an EventCtx yet, so their request is queued, then sent to druid by the root
+//! widget with [`request_focus`].
+
+use crate::glue::WidgetId;
+use druid::EventCtx;
+use std::cell::Cell;
+use tracing::trace;
+
+thread_local! {
+    // Set once an element has requested focus in the current build pass.
+    static AUTOFOCUS_CLAIMED: Cell<bool> = Cell::new(false);
+    // The widget to focus the next time the root widget has an EventCtx.
+    static FOCUS_REQUEST: Cell<Option<WidgetId>> = Cell::new(None);
+}
+
+/// Start a new build pass, in which one element may request focus.
+pub(crate) fn reset_autofocus() {
+    AUTOFOCUS_CLAIMED.with(|claimed| claimed.set(false));
+}
+
+/// Returns true if no other element has requested focus in the current build pass.
+pub(crate) fn claim_autofocus() -> bool {
+    !AUTOFOCUS_CLAIMED.with(|claimed| claimed.replace(true))
+}
+
+pub(crate) fn queue_focus(id: WidgetId) {
+    FOCUS_REQUEST.with(|request| request.set(Some(id)));
+}
+
+/// Focus the widget queued since the last call, if any.
+pub(crate) fn request_focus(ctx: &mut EventCtx) {
+    if let Some(id) = FOCUS_REQUEST.with(|request| request.take()) {
+        trace!("Autofocus on {:?}", id);
+        ctx.set_focus(id);
+    }
+}
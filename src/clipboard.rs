@@ -0,0 +1,48 @@
+//! Text copied to and pasted from the system clipboard.
+//!
+//! Components access the clipboard through [`CompCtx`](crate::CompCtx), and elements
+//! wrapped with [`on_paste`](crate::ElementExt::on_paste) read it when they get a paste
+//! command. Only text is supported: other clipboard contents are read as `None`.
+
+use druid::Application;
+use std::cell::RefCell;
+use tracing::{trace, warn};
+
+thread_local! {
+    // If set, the system clipboard isn't used, and this is read and written instead.
+    static STUBBED_CLIPBOARD: RefCell<Option<Option<String>>> = Default::default();
+}
+
+/// Returns the text in the clipboard, or `None` if it holds something else.
+pub(crate) fn get_clipboard() -> Option<String> {
+    if let Some(contents) = STUBBED_CLIPBOARD.with(|stubbed| stubbed.borrow().clone()) {
+        return contents;
+    }
+    // There's no application when elements are built outside of a window
+    Application::try_global()?.clipboard().get_string()
+}
+
+/// Replace the contents of the clipboard with `text`.
+pub(crate) fn set_clipboard(text: String) {
+    trace!("Copying {:?} to the clipboard", text);
+    let stubbed = STUBBED_CLIPBOARD.with(|stubbed| match &mut *stubbed.borrow_mut() {
+        Some(contents) => {
+            *contents = Some(text.clone());
+            true
+        }
+        None => false,
+    });
+    if stubbed {
+        return;
+    }
+    match Application::try_global() {
+        Some(application) => application.clipboard().put_string(text),
+        None => warn!("Could not copy to the clipboard: no running application"),
+    }
+}
+
+/// Make the clipboard functions in the current thread use `contents` instead of the system
+/// clipboard. `None` simulates a clipboard holding something other than text.
+pub(crate) fn stub_clipboard(contents: Option<String>) {
+    STUBBED_CLIPBOARD.with(|stubbed| *stubbed.borrow_mut() = Some(contents));
+}
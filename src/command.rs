@@ -0,0 +1,94 @@
+//! Druid commands submitted and handled by components, with
+//! [`CompCtx::submit_command`](crate::CompCtx::submit_command) and
+//! [`CompCtx::on_command`](crate::CompCtx::on_command).
+//!
+//! Like timers, commands are sent by the root widget: commands submitted while the element tree
+//! is built are queued, then sent to druid with [`submit_commands`]. When the root widget
+//! receives a command, it's passed down the tree in [`GlobalEventCx`](crate::glue::GlobalEventCx),
+//! and components run the handlers registered for its selector.
+
+use druid::{Command, EventCtx, Selector};
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tracing::trace;
+
+thread_local! {
+    // Commands to submit the next time the root widget has an EventCtx.
+    static COMMAND_REQUESTS: RefCell<Vec<Command>> = Default::default();
+}
+
+/// Submit the commands queued by components since the last call.
+pub(crate) fn submit_commands(ctx: &mut EventCtx) {
+    let commands = COMMAND_REQUESTS.with(|requests| requests.take());
+    for command in commands {
+        ctx.submit_command(command);
+    }
+}
+
+pub(crate) fn queue_command(command: Command) {
+    COMMAND_REQUESTS.with(|requests| requests.borrow_mut().push(command));
+}
+
+type CommandHandler = Rc<dyn Fn(&mut dyn Any, &Command)>;
+
+/// The command handlers registered by a component, in the order they were registered.
+///
+/// Handlers are registered again every time the component is built, so this state is replaced
+/// on every build.
+#[derive(Clone, Default)]
+pub struct CommandState {
+    handlers: Vec<CommandHandler>,
+}
+
+impl CommandState {
+    /// Register `callback` to be called with the component's local state and the payload of
+    /// commands matching `selector`.
+    pub(crate) fn add_handler<ComponentState: 'static, T: Any>(
+        &mut self,
+        selector: Selector<T>,
+        callback: impl Fn(&mut ComponentState, &T) + 'static,
+    ) {
+        let handler: CommandHandler = Rc::new(move |state: &mut dyn Any, command: &Command| {
+            // The selector is typed, so a command matching it always has a payload of type T
+            if let Some(payload) = command.get(selector) {
+                trace!("Running command handler");
+                let state = state
+                    .downcast_mut::<ComponentState>()
+                    .expect("internal type error: on_command got the wrong component state type");
+                callback(state, payload);
+            }
+        });
+        self.handlers.push(handler);
+    }
+
+    /// If `command` matches the selector of some handlers, call them.
+    pub(crate) fn handle(&self, command: Option<&Command>, state: &mut dyn Any) {
+        if let Some(command) = command {
+            for handler in &self.handlers {
+                handler(state, command);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for CommandState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandState")
+            .field("handlers", &self.handlers.len())
+            .finish()
+    }
+}
+
+// Handlers are only replaced when a component is built, never when events are processed, so
+// comparing them by identity is enough.
+impl PartialEq for CommandState {
+    fn eq(&self, other: &Self) -> bool {
+        self.handlers.len() == other.handlers.len()
+            && self
+                .handlers
+                .iter()
+                .zip(other.handlers.iter())
+                .all(|(handler, other_handler)| Rc::ptr_eq(handler, other_handler))
+    }
+}
@@ -1,13 +1,222 @@
-use crate::glue::DruidAppData;
+use crate::animation::AnimationState;
+use crate::async_task::AsyncTaskState;
+use crate::command::CommandState;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::hotkey::HotkeyState;
+use crate::interval::IntervalState;
 use crate::metadata::{Metadata, NoState};
-use druid::{Env, EventCtx};
+use crate::reducer::{Dispatch, ReducerState};
+use druid::{Env, EventCtx, HotKey, Selector};
 use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Values passed down the element tree by [`Provider`](crate::elements::Provider), keyed by type.
+pub(crate) type ContextMap = HashMap<TypeId, Rc<dyn Any>>;
+
+thread_local! {
+    // Values provided by the Provider elements currently being built.
+    //
+    // Elements are built depth-first, in a single pass, so this stack-like map always holds the
+    // values of the providers enclosing the element being built.
+    static PROVIDED_CONTEXT: RefCell<ContextMap> = Default::default();
+}
+
+/// Build `build_child` with `value` provided to all components it contains.
+///
+/// The value shadows any value of the same type provided by an enclosing provider, and is
+/// removed once `build_child` returns.
+pub(crate) fn with_provided_context<T: 'static, R>(value: T, build_child: impl FnOnce() -> R) -> R {
+    let type_id = TypeId::of::<T>();
+    let prev_value = PROVIDED_CONTEXT.with(|context| {
+        context
+            .borrow_mut()
+            .insert(type_id, Rc::new(value) as Rc<dyn Any>)
+    });
+
+    let output = build_child();
+
+    PROVIDED_CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        match prev_value {
+            Some(prev_value) => context.insert(type_id, prev_value),
+            None => context.remove(&type_id),
+        }
+    });
+    output
+}
+
+/// Returns a copy of the values provided by the enclosing providers.
+pub(crate) fn provided_context() -> ContextMap {
+    PROVIDED_CONTEXT.with(|context| context.borrow().clone())
+}
+
+/// Replace the values provided by the enclosing providers, eg after a panic skipped the
+/// cleanup of [`with_provided_context`].
+pub(crate) fn set_provided_context(context: ContextMap) {
+    PROVIDED_CONTEXT.with(|provided| provided.replace(context));
+}
+
+thread_local! {
+    // The environment of the window whose element tree is being built.
+    static BUILD_ENV: RefCell<Option<Env>> = Default::default();
+}
+
+/// Build `build_root` with `env` available to all components it contains.
+pub(crate) fn with_build_env<R>(env: &Env, build_root: impl FnOnce() -> R) -> R {
+    let prev_env = BUILD_ENV.with(|build_env| build_env.replace(Some(env.clone())));
+    let output = build_root();
+    BUILD_ENV.with(|build_env| build_env.replace(prev_env));
+    output
+}
+
+/// Returns the environment of the window being built, or an empty environment if the
+/// elements are built outside of a window.
+pub(crate) fn build_env() -> Env {
+    BUILD_ENV
+        .with(|build_env| build_env.borrow().clone())
+        .unwrap_or_default()
+}
+
+thread_local! {
+    // The local states of the components whose returned elements are being built, innermost
+    // last.
+    static BUILDING_STATES: RefCell<Vec<Rc<dyn Any>>> = Default::default();
+}
+
+// Pops the state pushed by `with_building_state`, even if building the child panics and the
+// panic is caught by an ErrorBoundary.
+struct BuildingStateGuard;
+
+impl Drop for BuildingStateGuard {
+    fn drop(&mut self) {
+        BUILDING_STATES.with(|states| states.borrow_mut().pop());
+    }
+}
+
+/// Build `build_child` with `state` readable by the elements it contains, eg bound text boxes.
+pub(crate) fn with_building_state<R>(state: Rc<dyn Any>, build_child: impl FnOnce() -> R) -> R {
+    BUILDING_STATES.with(|states| states.borrow_mut().push(state));
+    let _guard = BuildingStateGuard;
+    build_child()
+}
+
+/// Call `read` with the local state of the innermost component being built whose state has
+/// type `T`, if there is one.
+pub(crate) fn read_building_state<T: 'static, R>(read: impl FnOnce(&T) -> R) -> Option<R> {
+    let state = BUILDING_STATES.with(|states| {
+        states
+            .borrow()
+            .iter()
+            .rev()
+            .find(|state| state.is::<T>())
+            .cloned()
+    })?;
+    state.downcast_ref::<T>().map(read)
+}
+
+thread_local! {
+    // Whether the inputs of each form being built are valid so far, innermost last.
+    static FORM_VALIDITY: RefCell<Vec<bool>> = Default::default();
+}
+
+// Pops the entry pushed by `with_form_validation`, even if building the child panics and the
+// panic is caught by an ErrorBoundary.
+struct FormValidityGuard;
+
+impl Drop for FormValidityGuard {
+    fn drop(&mut self) {
+        FORM_VALIDITY.with(|forms| forms.borrow_mut().pop());
+    }
+}
+
+/// Build `build_child`, and return whether all the validated inputs it contains reported
+/// valid content.
+pub(crate) fn with_form_validation<R>(build_child: impl FnOnce() -> R) -> (R, bool) {
+    FORM_VALIDITY.with(|forms| forms.borrow_mut().push(true));
+    let _guard = FormValidityGuard;
+    let output = build_child();
+    let valid = FORM_VALIDITY
+        .with(|forms| forms.borrow().last().copied())
+        .unwrap_or(true);
+    (output, valid)
+}
+
+/// Whether a form is being built.
+///
+/// Components inside a form aren't skipped, so that all its inputs report their validity.
+pub(crate) fn is_building_form() -> bool {
+    FORM_VALIDITY.with(|forms| !forms.borrow().is_empty())
+}
+
+/// Report the validity of an input to the innermost form being built, if there is one.
+pub(crate) fn report_validity(valid: bool) {
+    FORM_VALIDITY.with(|forms| {
+        if let Some(form_valid) = forms.borrow_mut().last_mut() {
+            *form_valid &= valid;
+        }
+    });
+}
 
 /// Context type passed to all components when building them.
 pub struct CompCtx<'a> {
     // Yeah, we're using a cell, sorry. It's very local, though.
     pub(crate) called_use_metadata: std::cell::Cell<bool>,
     pub(crate) local_state: &'a dyn Any,
+    pub(crate) is_mounted: bool,
+    pub(crate) on_unmount: std::cell::RefCell<Option<Rc<dyn Fn()>>>,
+    pub(crate) prev_effects: &'a EffectsState,
+    pub(crate) effects: std::cell::RefCell<EffectsState>,
+    pub(crate) context: ContextMap,
+    pub(crate) env: Env,
+    pub(crate) async_task: std::cell::RefCell<Option<AsyncTaskState>>,
+    pub(crate) interval: std::cell::RefCell<Option<IntervalState>>,
+    pub(crate) commands: std::cell::RefCell<CommandState>,
+    pub(crate) prev_reducer: &'a ReducerState,
+    // The registered reducer, and the initial state to use if the component isn't mounted yet
+    pub(crate) reducer: std::cell::RefCell<Option<(ReducerState, Box<dyn Any>)>>,
+    pub(crate) prev_animations: &'a AnimationState,
+    pub(crate) animations: std::cell::RefCell<AnimationState>,
+    pub(crate) hotkeys: std::cell::RefCell<HotkeyState>,
+    pub(crate) prev_ids: &'a IdsState,
+    pub(crate) ids: std::cell::RefCell<IdsState>,
+    pub(crate) selectors: std::cell::RefCell<SelectorsState>,
+    // Set if the component reads values which aren't part of its props or state, in which case
+    // it's never skipped when it's built again.
+    pub(crate) read_context: std::cell::Cell<bool>,
+}
+
+/// Dependencies and cleanup callbacks of the effects registered with
+/// [`CompCtx::use_effect`], in the order they were registered.
+#[derive(Clone, Default)]
+pub struct EffectsState {
+    effects: Vec<EffectSlot>,
+}
+
+/// Selectors registered with [`CompCtx::use_selector`], with the values they returned, in the
+/// order they were registered.
+#[derive(Clone, Default)]
+pub struct SelectorsState {
+    // Each slot runs its selector on the given context, and returns true if the result differs
+    // from the one the component was built with.
+    slots: Vec<Rc<dyn Fn(&ContextMap) -> bool>>,
+}
+
+/// Widget ids minted with [`CompCtx::gen_id`], in the order they were minted.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IdsState {
+    ids: Vec<WidgetId>,
+}
+
+#[derive(Clone)]
+struct EffectSlot {
+    deps: Rc<dyn Any>,
+    // Shared with the component's unmount callback; the cleanup is taken when it runs, so it
+    // only runs once.
+    cleanup: Rc<RefCell<Option<Box<dyn FnOnce()>>>>,
 }
 
 impl<'a> CompCtx<'a> {
@@ -56,16 +265,463 @@ impl<'a> CompCtx<'a> {
         ))
     }
 
+    /// Registers callbacks for the mounting and unmounting of the current component instance.
+    ///
+    /// `on_mount` is called the first time the component instance is built. `on_unmount` is
+    /// called when the instance is removed from the element tree.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn use_lifecycle<ComponentEvent: 'static, ComponentState: 'static>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        on_mount: impl FnOnce(),
+        on_unmount: impl Fn() + 'static,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_lifecycle' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        if !self.is_mounted {
+            on_mount();
+        }
+        *self.on_unmount.borrow_mut() = Some(Rc::new(on_unmount));
+    }
+
+    /// Runs a side effect when the component is first built, and again whenever `deps` changes.
+    ///
+    /// `effect` may return a cleanup closure, which is called before the effect runs again, and
+    /// when the component instance is removed from the element tree. The effect isn't run if
+    /// `deps` is equal to the value passed the previous time the component was built.
+    ///
+    /// Effects are identified by the order in which they're registered, so this method must
+    /// be called the same number of times, in the same order, every time the component is built.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn use_effect<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Deps: PartialEq + 'static,
+    >(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        deps: Deps,
+        effect: impl FnOnce() -> Option<Box<dyn FnOnce()>>,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_effect' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        let mut effects = self.effects.borrow_mut();
+        let prev_slot = self.prev_effects.effects.get(effects.effects.len());
+
+        if let Some(prev_slot) = prev_slot {
+            if prev_slot.deps.downcast_ref::<Deps>() == Some(&deps) {
+                effects.effects.push(prev_slot.clone());
+                return;
+            }
+            prev_slot.run_cleanup();
+        }
+
+        let cleanup = effect();
+        effects.effects.push(EffectSlot {
+            deps: Rc::new(deps),
+            cleanup: Rc::new(RefCell::new(cleanup)),
+        });
+    }
+
+    /// Returns the value of type `T` provided by the nearest enclosing
+    /// [`Provider`](crate::elements::Provider), if there is one.
+    ///
+    /// If several enclosing providers provide a value of type `T`, the innermost one wins.
+    pub fn use_context<T: Clone + 'static>(&self) -> Option<T> {
+        self.read_context.set(true);
+        self.context
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
+
+    /// Returns the result of `selector` applied to the value of type `T` provided by the
+    /// nearest enclosing [`Provider`](crate::elements::Provider), if there is one.
+    ///
+    /// Unlike [`use_context`](Self::use_context), this doesn't force the component to be
+    /// called every time it's built: when its props and state are unchanged, the selector is
+    /// run on the new context value, and the component is only called again if the result
+    /// differs from the previous one. This avoids building the component again when a large
+    /// context value changes in a way it doesn't care about.
+    ///
+    /// Selectors are run every time the component is built, so they should be cheap.
+    pub fn use_selector<T: 'static, R: Clone + PartialEq + 'static>(
+        &self,
+        selector: impl Fn(&T) -> R + 'static,
+    ) -> Option<R> {
+        let select = move |context: &ContextMap| {
+            context
+                .get(&TypeId::of::<T>())
+                .and_then(|value| value.downcast_ref::<T>())
+                .map(&selector)
+        };
+        let value = select(&self.context);
+        let prev_value = value.clone();
+        self.selectors
+            .borrow_mut()
+            .slots
+            .push(Rc::new(move |context| select(context) != prev_value));
+        value
+    }
+
+    /// Returns the druid environment of the window the component is in, which holds the
+    /// colors, fonts and sizes of the theme.
+    ///
+    /// When the environment changes, the whole element tree is built again, so components
+    /// always see the current theme.
+    ///
+    /// When elements are built outside of a window (eg in unit tests), the environment is
+    /// empty, and reading theme keys panics.
+    pub fn env(&self) -> &Env {
+        self.read_context.set(true);
+        &self.env
+    }
+
+    /// Runs `future` on a background thread, and passes its output to `on_output` once it
+    /// completes.
+    ///
+    /// `on_output` is called on the UI thread, with the component's local state, which it can
+    /// modify. A component only has one background task at a time: spawning a task cancels the
+    /// previous one if it's still running. The task is also cancelled when the component
+    /// instance is removed from the element tree.
+    ///
+    /// Since the component function is called every time the component is built, this method
+    /// should usually only be called when some condition changes, eg in a
+    /// [`use_effect`](Self::use_effect) block.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn spawn_async<ComponentEvent: 'static, ComponentState: 'static, Output: Send + 'static>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        future: impl Future<Output = Output> + Send + 'static,
+        on_output: impl Fn(&mut ComponentState, Output) + 'static,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'spawn_async' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        let task = AsyncTaskState::spawn(future, on_output);
+        if let Some(prev_task) = self.async_task.borrow_mut().replace(task) {
+            prev_task.cancel();
+        }
+    }
+
+    /// Calls `callback` with the component's local state every `period`, which it can modify.
+    ///
+    /// The interval keeps running as long as this method is called every time the component
+    /// is built; calling it again with the same period only replaces the callback, and keeps
+    /// the running timer. Changing the period restarts the timer. The timer is stopped if the
+    /// method isn't called during a build, or when the component instance is removed from the
+    /// element tree.
+    ///
+    /// A component only has one interval; if this method is called several times in a single
+    /// build, the last call wins.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn use_interval<ComponentEvent: 'static, ComponentState: 'static>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        period: Duration,
+        callback: impl Fn(&mut ComponentState) + 'static,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_interval' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        *self.interval.borrow_mut() = Some(IntervalState::new(period, callback));
+    }
+
+    /// Submits a druid command, with `payload` as its payload.
+    ///
+    /// The command is sent once the element tree is built, and can be handled by druid
+    /// widgets, or by components with [`on_command`](Self::on_command). Since the component
+    /// function is called every time the component is built, this method should usually only
+    /// be called when some condition changes, eg in a [`use_effect`](Self::use_effect) block.
+    pub fn submit_command<T: Any>(&self, selector: Selector<T>, payload: T) {
+        crate::command::queue_command(selector.with(payload));
+    }
+
+    /// Returns the text in the system clipboard.
+    ///
+    /// Returns `None` if the clipboard is empty or holds something other than text, eg an
+    /// image.
+    ///
+    /// A component which reads the clipboard is never skipped when it's built again, so it
+    /// shows the current contents whenever its parent is built. Changes to the clipboard don't
+    /// make the component build again by themselves, though.
+    pub fn get_clipboard(&self) -> Option<String> {
+        self.read_context.set(true);
+        crate::clipboard::get_clipboard()
+    }
+
+    /// Replaces the contents of the system clipboard with `text`.
+    ///
+    /// Like [`submit_command`](Self::submit_command), this should usually only be called
+    /// when some condition changes, eg in a [`use_effect`](Self::use_effect) block.
+    pub fn set_clipboard(&self, text: impl Into<String>) {
+        crate::clipboard::set_clipboard(text.into());
+    }
+
+    /// Calls `callback` with the component's local state, which it can modify, and the
+    /// payload of every druid command matching `selector` received by the application.
+    ///
+    /// This lets components handle commands sent by druid, eg menu commands. The handler is
+    /// registered for the current build only, so this method must be called every time the
+    /// component is built. A component can register handlers for several selectors.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn on_command<ComponentEvent: 'static, ComponentState: 'static, T: Any>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        selector: Selector<T>,
+        callback: impl Fn(&mut ComponentState, &T) + 'static,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'on_command' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        self.commands.borrow_mut().add_handler(selector, callback);
+    }
+
+    /// Returns the local state of the current component instance, and a handle to dispatch
+    /// actions which modify it.
+    ///
+    /// Actions dispatched from event callbacks are queued, then passed to `reducer` in the
+    /// order they were dispatched, once the current event is processed; each call computes the
+    /// next state from the previous one. The state is set to `initial` the first time the
+    /// component is built. The reducer is registered for the current build only, so this
+    /// method must be called every time the component is built.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput),
+    /// or if this method is called several times in a single build.
+    pub fn use_reducer<
+        ComponentEvent: 'static,
+        ComponentState: Clone + 'static,
+        Action: 'static,
+    >(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        reducer: impl Fn(&ComponentState, Action) -> ComponentState + 'static,
+        initial: ComponentState,
+    ) -> (ComponentState, Dispatch<Action>) {
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_reducer' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        if self.reducer.borrow().is_some() {
+            panic!("error: 'use_reducer' can only be called once per component")
+        }
+        let reducer_state = ReducerState::new(self.prev_reducer, reducer);
+        let dispatch = reducer_state.dispatch();
+        // Checks the state type even before the local state is seeded
+        let local_state = self.get_local_state(md);
+        let state = if self.is_mounted {
+            local_state.clone()
+        } else {
+            initial.clone()
+        };
+        let initial_state: Box<dyn Any> = Box::new(initial);
+        *self.reducer.borrow_mut() = Some((reducer_state, initial_state));
+        (state, dispatch)
+    }
+
+    /// Calls `callback` with the component's local state, which it can modify, when `hotkey`
+    /// is pressed, wherever the keyboard focus is.
+    ///
+    /// Modifiers must match exactly: a shortcut for Ctrl+S isn't triggered by Ctrl+Shift+S.
+    /// When the shortcut is pressed, the key isn't sent to the focused widget. If several
+    /// components register the same shortcut, only the most recently registered one is
+    /// called. The shortcut is registered for the current build only, so this method must be
+    /// called every time the component is built; it's removed when the component is unmounted.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn use_hotkey<ComponentEvent: 'static, ComponentState: 'static>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        hotkey: HotKey,
+        callback: impl Fn(&mut ComponentState) + 'static,
+    ) {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_hotkey' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        self.hotkeys.borrow_mut().add(hotkey, callback);
+    }
+
+    /// Returns a value which moves linearly towards `target`, over `duration`.
+    ///
+    /// The value starts at the first target it's given. When the target changes, the value
+    /// moves from wherever it currently is towards the new target, even if it was still
+    /// moving towards the previous one. The component is built again on every animation
+    /// frame until the value reaches its target, so the returned value can be used to drive
+    /// sizes, offsets, opacities, etc.
+    ///
+    /// Animated values are identified by the order in which they're registered, so this
+    /// method must be called the same number of times, in the same order, every time the
+    /// component is built.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn use_animated<ComponentEvent: 'static, ComponentState: 'static>(
+        &self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        target: f64,
+        duration: Duration,
+    ) -> f64 {
+        #![allow(unused_variables)]
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'use_animated' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        self.animations
+            .borrow_mut()
+            .push(self.prev_animations, target, duration)
+    }
+
+    /// Returns a widget id tied to this instance of the component.
+    ///
+    /// Like other hooks, ids are matched by call order: the n-th call returns the same id
+    /// every time the component is built, for as long as it's mounted, and each instance of
+    /// the component gets its own ids. Pass the id to
+    /// [`ElementExt::with_id`](crate::ElementExt::with_id) to target a widget with commands,
+    /// or to find it in tests.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the root element of the component isn't [`ComponentOutput`](crate::elements::ComponentOutput).
+    pub fn gen_id(&self) -> WidgetId {
+        if (*self.local_state).type_id() == TypeId::of::<NoState>() {
+            panic!("error: 'gen_id' cannot be called for a component whose root element isn't ComponentOutput")
+        }
+        self.ids.borrow_mut().push(self.prev_ids)
+    }
+
     // TODO - add methods
-    // use_lifecycle
     // get_vdom_context
 }
 
+impl EffectsState {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Call the cleanup closures of all effects that have one.
+    pub(crate) fn run_cleanups(&self) {
+        for slot in &self.effects {
+            slot.run_cleanup();
+        }
+    }
+}
+
+impl SelectorsState {
+    /// Whether any selector returns a different value for `context`.
+    pub(crate) fn has_changed(&self, context: &ContextMap) -> bool {
+        self.slots.iter().any(|slot| slot(context))
+    }
+}
+
+impl IdsState {
+    /// Return the next id, reusing the one at the same position in `prev_state` if there is one.
+    pub(crate) fn push(&mut self, prev_state: &IdsState) -> WidgetId {
+        let id = match prev_state.ids.get(self.ids.len()) {
+            Some(prev_id) => *prev_id,
+            None => WidgetId::next(),
+        };
+        self.ids.push(id);
+        id
+    }
+}
+
+impl EffectSlot {
+    fn run_cleanup(&self) {
+        let cleanup = self.cleanup.borrow_mut().take();
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+}
+
+impl std::fmt::Debug for EffectsState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EffectsState")
+            .field("effects", &self.effects.len())
+            .finish()
+    }
+}
+
+// Effects are only updated when a component is built, never when events are processed, so
+// comparing dependencies by identity is enough.
+impl PartialEq for EffectsState {
+    fn eq(&self, other: &Self) -> bool {
+        self.effects.len() == other.effects.len()
+            && self
+                .effects
+                .iter()
+                .zip(other.effects.iter())
+                .all(|(slot, other_slot)| Rc::ptr_eq(&slot.deps, &other_slot.deps))
+    }
+}
+
+impl std::fmt::Debug for SelectorsState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SelectorsState")
+            .field("selectors", &self.slots.len())
+            .finish()
+    }
+}
+
+// A new state is created every time the component is called, so comparing selectors by
+// identity is enough.
+impl PartialEq for SelectorsState {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots.len() == other.slots.len()
+            && self
+                .slots
+                .iter()
+                .zip(other.slots.iter())
+                .all(|(slot, other_slot)| Rc::ptr_eq(slot, other_slot))
+    }
+}
+
 /// Context required by [`VirtualDom::reconcile`](crate::internals::VirtualDom::reconcile)
 pub struct ReconcileCtx<'a, 'b, 'c, 'd, 'e> {
     pub event_ctx: &'a mut EventCtx<'d, 'e>,
     pub data: &'b mut DruidAppData,
     pub env: &'c Env,
+    pub stats: ReconcileStats,
+}
+
+/// How many children were created, updated or removed during a reconcile pass.
+///
+/// Only elements whose children can come and go (lists, options, conditionals, etc) count
+/// them. A child whose widgets are kept counts as updated; a child whose widgets are created
+/// from scratch with [`init_tree`](crate::internals::VirtualDom::init_tree) counts as created.
+///
+/// See [`Harness::last_reconcile_stats`](crate::test_harness::Harness::last_reconcile_stats).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ReconcileStats {
+    pub created: usize,
+    pub updated: usize,
+    pub removed: usize,
 }
 
 pub struct ProcessEventCtx<'e, 's> {
@@ -77,15 +733,15 @@ impl<'e, 's> ProcessEventCtx<'e, 's> {
     pub fn event_queue<ComponentEvent: 'static, ComponentState: 'static>(
         &mut self,
         md: Metadata<ComponentEvent, ComponentState>,
-    ) -> &mut Vec<ComponentEvent> {
+    ) -> &mut VecDeque<ComponentEvent> {
         #![allow(unused_variables)]
         let type_id = (*self.event_queue).type_id();
         self.event_queue
-            .downcast_mut::<Vec<ComponentEvent>>()
+            .downcast_mut::<VecDeque<ComponentEvent>>()
             .expect(&format!(
                 "internal type error: event handler expected {:?} ({}), parent component gave {:?}",
-                TypeId::of::<Vec<ComponentEvent>>(),
-                type_name::<Vec<ComponentEvent>>(),
+                TypeId::of::<VecDeque<ComponentEvent>>(),
+                type_name::<VecDeque<ComponentEvent>>(),
                 type_id,
             ))
     }
@@ -114,18 +770,30 @@ mod tests {
     #[derive(Debug, PartialEq, Eq)]
     struct MyEvent(i32);
 
+    #[test]
+    fn building_state_popped_after_panic() {
+        let result = std::panic::catch_unwind(|| {
+            with_building_state(Rc::new(42_u32), || {
+                assert_eq!(read_building_state(|state: &u32| *state), Some(42));
+                panic!("broken component");
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(read_building_state(|state: &u32| *state), None);
+    }
+
     #[test]
     fn event_queue() {
         let md: Metadata<MyEvent, NoState> = Default::default();
 
-        let mut event_queue = Vec::<MyEvent>::new();
+        let mut event_queue = VecDeque::<MyEvent>::new();
         let mut ctx = ProcessEventCtx {
             event_queue: &mut event_queue,
             state: &mut NoState,
         };
-        ctx.event_queue(md).push(MyEvent(42));
+        ctx.event_queue(md).push_back(MyEvent(42));
 
-        assert_eq!(event_queue, vec![MyEvent(42)],);
+        assert_eq!(event_queue, VecDeque::from(vec![MyEvent(42)]),);
     }
 
     #[test]
@@ -134,7 +802,7 @@ mod tests {
 
         let mut state = 12345_i64;
         let mut ctx = ProcessEventCtx {
-            event_queue: &mut Vec::<NoEvent>::new(),
+            event_queue: &mut VecDeque::<NoEvent>::new(),
             state: &mut state,
         };
 
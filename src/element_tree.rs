@@ -1,9 +1,18 @@
-use crate::ctx::{ProcessEventCtx, ReconcileCtx};
-use crate::glue::GlobalEventCx;
+use crate::animation::AnimationState;
+use crate::async_task::AsyncTaskState;
+use crate::command::CommandState;
+use crate::ctx::{EffectsState, IdsState, ProcessEventCtx, ReconcileCtx};
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::hotkey::HotkeyState;
+use crate::interval::IntervalState;
 use crate::metadata::Metadata;
+use crate::reducer::ReducerState;
 use crate::widget_sequence::WidgetSequence;
+use druid::{Color, Point, Rect, UnitPoint};
 
 use std::fmt::Debug;
+use std::rc::Rc;
+use std::time::Duration;
 
 /// The trait implemented by all GUI elements.
 ///
@@ -23,7 +32,7 @@ pub trait Element: Debug + Clone + 'static {
     ///
     /// The Event associated type is the type that eg the callback passed to [`ElementExt::on`]
     /// takes as parameter.
-    type Event: Debug;
+    type Event: Debug + 'static;
 
     type ComponentState: Clone + Default + Debug + PartialEq + 'static;
     type AggregateChildrenState: Clone + Default + Debug + PartialEq;
@@ -40,6 +49,71 @@ pub trait Element: Debug + Clone + 'static {
     fn get_component_state(_state: &Self::AggregateChildrenState) -> Option<&Self::ComponentState> {
         None
     }
+
+    /// The key identifying this element within a group, if it has one.
+    ///
+    /// See [`ElementExt::key`].
+    fn element_key(&self) -> Option<&str> {
+        None
+    }
+
+    /// The key of the element which last built this state, if it had one.
+    fn get_state_key(_state: &Self::AggregateChildrenState) -> Option<&str> {
+        None
+    }
+
+    fn init_component_state(
+        _state: &mut Self::AggregateChildrenState,
+        _initial_state: Self::ComponentState,
+    ) {
+    }
+
+    fn set_unmount_callback(_output: &mut Self::BuildOutput, _callback: Rc<dyn Fn()>) {}
+
+    fn get_effects_state(_state: &Self::AggregateChildrenState) -> Option<&EffectsState> {
+        None
+    }
+
+    fn set_effects_state(_state: &mut Self::AggregateChildrenState, _effects: EffectsState) {}
+
+    fn get_async_task_state(_state: &Self::AggregateChildrenState) -> Option<&AsyncTaskState> {
+        None
+    }
+
+    fn set_async_task_state(_state: &mut Self::AggregateChildrenState, _task: AsyncTaskState) {}
+
+    fn get_interval_state(_state: &Self::AggregateChildrenState) -> Option<&IntervalState> {
+        None
+    }
+
+    fn set_interval_state(_state: &mut Self::AggregateChildrenState, _interval: IntervalState) {}
+
+    fn set_command_state(_state: &mut Self::AggregateChildrenState, _commands: CommandState) {}
+
+    fn get_reducer_state(_state: &Self::AggregateChildrenState) -> Option<&ReducerState> {
+        None
+    }
+
+    fn set_reducer_state(_state: &mut Self::AggregateChildrenState, _reducer: ReducerState) {}
+
+    fn get_animation_state(_state: &Self::AggregateChildrenState) -> Option<&AnimationState> {
+        None
+    }
+
+    fn set_animation_state(_state: &mut Self::AggregateChildrenState, _animations: AnimationState) {
+    }
+
+    fn get_hotkey_state(_state: &Self::AggregateChildrenState) -> Option<&HotkeyState> {
+        None
+    }
+
+    fn set_hotkey_state(_state: &mut Self::AggregateChildrenState, _hotkeys: HotkeyState) {}
+
+    fn get_ids_state(_state: &Self::AggregateChildrenState) -> Option<&IdsState> {
+        None
+    }
+
+    fn set_ids_state(_state: &mut Self::AggregateChildrenState, _ids: IdsState) {}
 }
 
 // TODO - Include documentation about what a Virtual DOM is and where the name comes from.
@@ -69,6 +143,34 @@ pub trait VirtualDom: Debug {
         ctx: &mut ReconcileCtx,
     );
 
+    /// The key identifying this element within a group, if it has one.
+    fn key(&self) -> Option<&str> {
+        None
+    }
+
+    /// Record the key of the [`Keyed`](crate::elements::Keyed) wrapping this element.
+    ///
+    /// The key is only used in debug output. Components store it; elements which return their
+    /// child unchanged, like component holders, forward it.
+    fn set_debug_key(&mut self, key: &str) {
+        #![allow(unused_variables)]
+    }
+
+    /// Called when this element is removed from the element tree.
+    ///
+    /// Elements with children must forward this call to them.
+    fn unmount(&self) {}
+
+    /// Find the layout rect of the built descendant with the given key.
+    ///
+    /// The rect is in the coordinate space of the parent of `widget_seq`'s widgets. Groups,
+    /// lists, flex containers and components look for the key in their children; other
+    /// elements don't, so keys below them aren't found.
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        #![allow(unused_variables)]
+        None
+    }
+
     // TODO - Rename methods
     fn process_event(
         &self,
@@ -91,7 +193,17 @@ pub trait VirtualDom: Debug {
     }
 }
 
-use crate::elements::internals::{ParentEvent, WithBubbleEvent, WithCallbackEvent, WithMapEvent};
+use crate::elements::internals::{
+    ClickableArea, ContextMenu, Draggable, DropTarget, ParentEvent, WithAnyEvent, WithBubbleEvent,
+    WithCallbackEvent, WithDoubleClickEvent, WithFlatMapEvent, WithFocusEvent, WithHoverEvent,
+    WithKeyEvent, WithLongPressEvent, WithMapEvent, WithMappedState, WithMouseMoveEvent,
+    WithPasteEvent, WithRightClickEvent,
+};
+use crate::elements::{
+    Aligned, Badge, ClickEvent, Container, Debounce, DoubleClick, Flexed, KeyDown, Keyed,
+    LongPress, Memoize, MenuItem, Opacity, Scroll, ScrollDirection, SizedBox, Span, TabIndex,
+    Tooltip, Transition, Visibility, WithId,
+};
 
 /// Helper methods that can be called on all elements.
 pub trait ElementExt: Element + Sized {
@@ -151,6 +263,33 @@ pub trait ElementExt: Element + Sized {
         }
     }
 
+    /// Map events from the element to any number of events of the parent component.
+    ///
+    /// Like [`map_event`](Self::map_event), except that the function returns a `Vec`; the
+    /// parent component emits every returned event, in order.
+    fn flat_map_event<
+        EventParam,
+        EventReturn,
+        Cb: Fn(&mut ComponentState, EventParam) -> Vec<EventReturn> + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithFlatMapEvent<ComponentEvent, ComponentState, EventParam, EventReturn, Self, Cb>
+    where
+        Self::Event: ParentEvent<EventParam>,
+        ComponentEvent: ParentEvent<EventReturn>,
+    {
+        WithFlatMapEvent {
+            element: self,
+            callback,
+            _metadata: md,
+            _marker: Default::default(),
+        }
+    }
+
     /// Passes events from the element to the parent component.
     ///
     /// When an event is emitted that matches the Event type (TODO - see [`ParentEvent`] for
@@ -172,6 +311,425 @@ pub trait ElementExt: Element + Sized {
             _marker: Default::default(),
         }
     }
+
+    /// Handle every event from the element, whatever its type.
+    ///
+    /// Unlike [`on`](Self::on) and [`map_event`](Self::map_event), the callback is given the
+    /// element's own event type, without going through [`ParentEvent`]. It can inspect the
+    /// event, and return `Some(...)` for the parent component to emit an event, or `None` to
+    /// swallow it.
+    fn on_any<
+        Cb: Fn(&mut ComponentState, Self::Event) -> Option<ComponentEvent> + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithAnyEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithAnyEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Run the callbacks of the element against part of the component's local state.
+    ///
+    /// Callbacks bound within the element with metadata from [`Metadata::scoped`] are given
+    /// the value returned by `project`, instead of the whole local state. This way, a reusable
+    /// element can be written against eg a `String`, and used in a component which stores that
+    /// string in a field of its state.
+    ///
+    /// Callbacks only ever need mutable access to the state, so a single projection is enough.
+    fn map_state<
+        SubState,
+        Project: Fn(&mut ComponentState) -> &mut SubState + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        project: Project,
+    ) -> WithMappedState<ComponentEvent, ComponentState, SubState, Self, Project> {
+        WithMappedState {
+            element: self,
+            project,
+            _metadata: md,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Bind callback to key presses.
+    ///
+    /// The callback is called with the pressed key when the element's widget, or one of its
+    /// children, has keyboard focus. The element takes focus when it's clicked.
+    fn on_key_down<Cb: Fn(&mut ComponentState, KeyDown) + Clone, ComponentEvent, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithKeyEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithKeyEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to text being pasted into the element.
+    ///
+    /// The callback is called with the text in the clipboard when the user pastes, with the
+    /// paste shortcut or the Edit menu, while the element's widget or one of its children has
+    /// focus. The element takes focus when it's clicked. Nothing is called if the clipboard
+    /// holds something other than text, or if a child handles the paste itself, eg a text box.
+    fn on_paste<Cb: Fn(&mut ComponentState, String) + Clone, ComponentEvent, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithPasteEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithPasteEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to the mouse pointer entering or leaving the element.
+    ///
+    /// The callback is called with `true` when the pointer enters the element's widget, and
+    /// with `false` when it leaves.
+    fn on_hover<Cb: Fn(&mut ComponentState, bool) + Clone, ComponentEvent, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithHoverEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithHoverEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to the mouse pointer moving over the element.
+    ///
+    /// The callback is called with the position of the pointer, relative to the top-left
+    /// corner of the element's widget, on every move while the pointer is over the element.
+    fn on_mouse_move<Cb: Fn(&mut ComponentState, Point) + Clone, ComponentEvent, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithMouseMoveEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithMouseMoveEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to the element gaining or losing keyboard focus.
+    ///
+    /// The callback is called with `true` when the element's widget, or one of its children,
+    /// gains focus, and with `false` when none of them has focus anymore.
+    fn on_focus_change<
+        Cb: Fn(&mut ComponentState, bool) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithFocusEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithFocusEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to double clicks.
+    ///
+    /// The callback is called when the element's widget is clicked twice within
+    /// [`DOUBLE_CLICK_INTERVAL`](crate::elements::DOUBLE_CLICK_INTERVAL). Single clicks are still
+    /// handled by the element as usual.
+    fn on_double_click<
+        Cb: Fn(&mut ComponentState, DoubleClick) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithDoubleClickEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithDoubleClickEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to the pointer being held down over the element.
+    ///
+    /// The callback is called once when the left mouse button is held down over the element's
+    /// widget for `duration`. Releasing the button or moving the pointer more than a few pixels
+    /// before that cancels the press. Clicks are still handled by the element as usual.
+    fn on_long_press<
+        Cb: Fn(&mut ComponentState, LongPress) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        duration: Duration,
+        callback: Cb,
+    ) -> WithLongPressEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithLongPressEvent {
+            element: self,
+            duration,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Make the element a drag source, carrying `payload`.
+    ///
+    /// The payload can be dropped on elements wrapped with [`drop_target`](Self::drop_target)
+    /// accepting its type.
+    fn draggable<Payload: Clone + Debug + 'static, ComponentEvent, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        payload: Payload,
+    ) -> Draggable<ComponentEvent, ComponentState, Self, Payload> {
+        Draggable {
+            element: self,
+            payload,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to payloads of type `Payload` being dropped on the element.
+    ///
+    /// The callback is called with a clone of the payload when the mouse is released over
+    /// the element, during a drag started by a [`draggable`](Self::draggable) element.
+    fn drop_target<
+        Payload: Clone + 'static,
+        Cb: Fn(&mut ComponentState, Payload) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> DropTarget<ComponentEvent, ComponentState, Self, Payload, Cb> {
+        DropTarget {
+            element: self,
+            callback,
+            _metadata: md,
+            _payload: Default::default(),
+        }
+    }
+
+    /// Bind callback to clicks with the secondary mouse button.
+    ///
+    /// The callback is called with the position of the click, relative to the element's
+    /// widget, when the button is pressed. Left clicks are handled by the element as usual.
+    fn on_right_click<
+        Cb: Fn(&mut ComponentState, Point) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> WithRightClickEvent<ComponentEvent, ComponentState, Self, Cb> {
+        WithRightClickEvent {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Open a menu with the given items when the element is right-clicked.
+    ///
+    /// Selecting an enabled item makes the component emit the item's event. Pressing the
+    /// mouse outside of the menu closes it without emitting anything.
+    fn context_menu<ComponentEvent: Clone + Debug + 'static, ComponentState>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        items: Vec<MenuItem<ComponentEvent>>,
+    ) -> ContextMenu<ComponentEvent, ComponentState, Self> {
+        ContextMenu {
+            element: self,
+            items,
+            _metadata: md,
+        }
+    }
+
+    /// Bind callback to clicks anywhere on the element.
+    ///
+    /// Unlike wrapping the element in a [Button](crate::elements::Button), this doesn't change
+    /// the element's appearance. Clicks on a clickable widget inside the element, eg a button
+    /// or a nested click area, are handled by that widget only, so the innermost handler wins.
+    fn on_click_area<
+        Cb: Fn(&mut ComponentState, ClickEvent) + Clone,
+        ComponentEvent,
+        ComponentState,
+    >(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: Cb,
+    ) -> ClickableArea<ComponentEvent, ComponentState, Self, Cb> {
+        ClickableArea {
+            element: self,
+            callback,
+            _metadata: md,
+        }
+    }
+
+    /// Give the element a key identifying it within a [Tuple](crate::Tuple),
+    /// [Row](crate::Row) or [Column](crate::Column).
+    ///
+    /// When the children of the group are reordered, the element's local state and widgets
+    /// follow its key instead of staying at the same position. See [`Keyed`] for details.
+    fn key(self, key: impl Into<String>) -> Keyed<Self> {
+        Keyed::new(key, self)
+    }
+
+    /// Only rebuild the element when `deps` changes.
+    ///
+    /// If `deps` is equal to the value passed the previous time the element was built, the
+    /// element isn't built again, and its widgets are left as they were.
+    fn memoize<Deps: Clone + Debug + PartialEq + 'static>(self, deps: Deps) -> Memoize<Deps, Self> {
+        Memoize::new(self, deps)
+    }
+
+    /// Hold back the element's events until they stop firing for `delay`.
+    ///
+    /// Only the last event emitted during that time is passed on.
+    fn debounce(self, delay: Duration) -> Debounce<Self> {
+        Debounce::new(self, delay)
+    }
+
+    /// Give the element a flex factor in its parent [`Flex`](crate::elements::Flex) container.
+    ///
+    /// Instead of being laid out with its preferred size, the element shares the space left
+    /// by non-flex siblings with other flex children, in proportion to its flex factor.
+    fn flex(self, flex: f64) -> Flexed<Self> {
+        Flexed::new(self, flex)
+    }
+
+    /// Make the element take up the given number of columns in its parent [`Grid`](crate::elements::Grid).
+    fn span(self, span: usize) -> Span<Self> {
+        Span::new(self, span)
+    }
+
+    /// Place the element within the space its parent gives it.
+    ///
+    /// In a [`Stack`](crate::elements::Stack), this sets where the element is placed.
+    fn align(self, alignment: UnitPoint) -> Aligned<Self> {
+        Aligned::new(self, alignment)
+    }
+
+    /// Center the element within the space its parent gives it.
+    fn center(self) -> Aligned<Self> {
+        Aligned::new(self, UnitPoint::CENTER)
+    }
+
+    /// Paint a background color behind the element.
+    fn background(self, color: Color) -> Container<Self> {
+        Container::new(self).background(color)
+    }
+
+    /// Paint a border of the given color and width around the element.
+    fn border(self, color: Color, width: f64) -> Container<Self> {
+        Container::new(self).border(color, width)
+    }
+
+    /// Round the corners of the element's background and border.
+    fn rounded(self, radius: f64) -> Container<Self> {
+        Container::new(self).rounded(radius)
+    }
+
+    /// Force the element's widget to have the given width.
+    fn fix_width(self, width: f64) -> SizedBox<Self> {
+        SizedBox::new(self).with_width(width)
+    }
+
+    /// Force the element's widget to have the given height.
+    fn fix_height(self, height: f64) -> SizedBox<Self> {
+        SizedBox::new(self).with_height(height)
+    }
+
+    /// Force the element's widget to have the given width and height.
+    fn fix_size(self, width: f64, height: f64) -> SizedBox<Self> {
+        SizedBox::new(self).with_width(width).with_height(height)
+    }
+
+    /// Wrap the element in a container that scrolls along both axes.
+    fn scroll(self) -> Scroll<Self> {
+        Scroll::new(self)
+    }
+
+    /// Wrap the element in a container that scrolls vertically.
+    fn scroll_vertical(self) -> Scroll<Self> {
+        Scroll::new(self).with_direction(ScrollDirection::Vertical)
+    }
+
+    /// Wrap the element in a container that scrolls horizontally.
+    fn scroll_horizontal(self) -> Scroll<Self> {
+        Scroll::new(self).with_direction(ScrollDirection::Horizontal)
+    }
+
+    /// Paint the element with the given opacity, from `0.0` (invisible) to `1.0` (opaque).
+    ///
+    /// The element keeps its place in the layout, even when invisible.
+    fn opacity(self, opacity: f64) -> Opacity<Self> {
+        Opacity::new(self, opacity)
+    }
+
+    /// Show a text next to the mouse cursor while it hovers the element.
+    fn tooltip(self, text: impl Into<String>) -> Tooltip<Self> {
+        Tooltip::new(self, text)
+    }
+
+    /// Set the position of the element in the focus order.
+    ///
+    /// Tab moves the focus through elements with a tab index first, from the lowest index to
+    /// the highest, then through the other elements in tree order.
+    fn tab_index(self, index: u32) -> TabIndex<Self> {
+        TabIndex::new(self, index)
+    }
+
+    /// Display `count` in a small badge over the top-right corner of the element.
+    ///
+    /// The badge is hidden when the count is zero; see [`Badge::show_zero`].
+    fn badge(self, count: u32) -> Badge<Self> {
+        Badge::new(self, count)
+    }
+
+    /// Fade the element in or out over `duration` when `visible` changes.
+    ///
+    /// Once faded out, the element is removed from the tree.
+    fn transition(self, visible: bool, duration: Duration) -> Transition<Self> {
+        Transition::new(self, visible, duration)
+    }
+
+    /// Show or hide the element without removing it.
+    ///
+    /// Unlike [`If`](crate::elements::If), hiding the element keeps its widgets and their
+    /// state, eg the content of a text box. Hidden elements take no space and can't be
+    /// interacted with.
+    fn visible(self, visible: bool) -> Visibility<Self> {
+        Visibility::new(self, visible)
+    }
+
+    /// Give the element's root widget a fixed id, kept across updates.
+    ///
+    /// The id can then be used to target the widget with commands. See
+    /// [`CompCtx::gen_id`](crate::CompCtx::gen_id) to get an id which is unique to a
+    /// component.
+    fn with_id(self, id: WidgetId) -> WithId<Self> {
+        WithId::new(self, id)
+    }
 }
 
 impl<ET: Element> ElementExt for ET {}
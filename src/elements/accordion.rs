@@ -0,0 +1,491 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{AccordionWidget, SingleAccordionWidget};
+
+use druid::EventCtx;
+use tracing::{debug_span, info, instrument, trace};
+
+/// A column of collapsible sections, each with a header above its body.
+///
+/// Sections are given as `(header, body)` pairs; like with [Tabs](crate::elements::Tabs), all
+/// bodies must have the same type. Clicking a header expands or collapses its section. Only
+/// the bodies of expanded sections are built; collapsing a section destroys the widgets and
+/// local state of its body. All sections start collapsed.
+///
+/// By default, any number of sections can be expanded at once. With
+/// [`single`](Accordion::single), expanding a section collapses the others.
+///
+/// ## About headers
+///
+/// Headers are used as keys: the expansion state of each section is tracked by header, so
+/// sections can be added, removed or reordered without collapsing the others. Headers must be
+/// unique.
+///
+/// ## Events
+///
+/// Emits [SectionToggled] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accordion<Child: Element> {
+    pub sections: Vec<(String, Child)>,
+    pub single: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccordionData<Child: VirtualDom> {
+    pub titles: Vec<String>,
+    pub bodies: Vec<Option<Child>>,
+    pub single: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when the header of an [Accordion] section is clicked.
+///
+/// Holds the index of the section, which has been expanded or collapsed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SectionToggled(pub usize);
+
+//
+// --- IMPLS
+
+impl<Child: Element> Accordion<Child> {
+    /// Build an accordion from a list of `(header, body)` pairs.
+    ///
+    /// Use the [.on_toggle](Accordion::on_toggle) method to provide a closure to be called when a header is clicked.
+    pub fn new(sections: Vec<(String, Child)>) -> Self {
+        Accordion {
+            sections,
+            single: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Only allow one section to be expanded at a time.
+    pub fn single(self) -> Self {
+        Accordion {
+            single: true,
+            ..self
+        }
+    }
+
+    /// Change the way the element's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Accordion {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Accordion {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a header is clicked.
+    ///
+    /// The closure is given the index of the toggled section.
+    pub fn on_toggle<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, usize) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: SectionToggled| callback(state, event.0),
+        )
+    }
+}
+
+impl<Child: Element> Element for Accordion<Child> {
+    type Event = SectionToggled;
+
+    type ComponentState = NoState;
+    // The header of each section, with the state of its body if it's expanded
+    type AggregateChildrenState = Vec<(String, Option<Child::AggregateChildrenState>)>;
+    type BuildOutput = AccordionData<Child::BuildOutput>;
+
+    #[instrument(name = "Accordion", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let mut prev_state = prev_state;
+        let mut titles = Vec::with_capacity(self.sections.len());
+        let mut bodies = Vec::with_capacity(self.sections.len());
+        let mut new_state = Vec::with_capacity(self.sections.len());
+        let mut any_expanded = false;
+        for (title, child) in self.sections {
+            let child_prev_state = prev_state
+                .iter()
+                .position(|(prev_title, _)| *prev_title == title)
+                .and_then(|index| prev_state.swap_remove(index).1);
+
+            // In single mode, only the first expanded section stays expanded, eg after the
+            // mode was switched
+            let child_prev_state = child_prev_state.filter(|_| !(self.single && any_expanded));
+            if let Some(child_prev_state) = child_prev_state {
+                any_expanded = true;
+                let (body, child_state) = child.build(child_prev_state);
+                bodies.push(Some(body));
+                new_state.push((title.clone(), Some(child_state)));
+            } else {
+                bodies.push(None);
+                new_state.push((title.clone(), None));
+            }
+            titles.push(title);
+        }
+
+        (
+            AccordionData {
+                titles,
+                bodies,
+                single: self.single,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            new_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> AccordionData<Child> {
+    /// Expand or collapse the section at `index`.
+    fn toggle(
+        &self,
+        children_state: &mut [(String, Option<Child::AggregateChildrenState>)],
+        index: usize,
+    ) {
+        let expand = match children_state.get(index) {
+            Some((_, body_state)) => body_state.is_none(),
+            None => return,
+        };
+        if expand && self.single {
+            for (_, body_state) in children_state.iter_mut() {
+                *body_state = None;
+            }
+        }
+        children_state[index].1 = if expand {
+            Some(Default::default())
+        } else {
+            None
+        };
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for AccordionData<Child> {
+    type Event = SectionToggled;
+    type AggregateChildrenState = Vec<(String, Option<Child::AggregateChildrenState>)>;
+    type TargetWidgetSeq = SingleAccordionWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Accordion", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let bodies = self
+            .bodies
+            .iter()
+            .map(|body| body.as_ref().map(|body| body.init_tree()))
+            .collect();
+        SingleAccordionWidget::new(
+            AccordionWidget::new(self.titles.clone(), bodies, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Accordion", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.titles != prev_value.titles {
+            let titles = self.titles.clone();
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut AccordionWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_titles(titles.clone(), ctx);
+                },
+            );
+        }
+        widget_seq.flex = self.flex;
+
+        // Bodies are matched by header, so that sections keep their widgets when they move
+        let body_index = |title: &String| self.titles.iter().position(|other| other == title);
+        let mut prev_widget_bodies = std::mem::take(&mut widget_seq.widget_mut().bodies);
+        for (prev_index, prev_body) in prev_value.bodies.iter().enumerate() {
+            let prev_body = match prev_body {
+                Some(prev_body) => prev_body,
+                None => continue,
+            };
+            let still_expanded = body_index(&prev_value.titles[prev_index])
+                .and_then(|index| self.bodies[index].as_ref())
+                .is_some();
+            if !still_expanded {
+                prev_body.unmount();
+                prev_widget_bodies[prev_index] = None;
+                ctx.stats.removed += 1;
+            }
+        }
+
+        let mut children_changed = false;
+        let mut widget_bodies = Vec::with_capacity(self.bodies.len());
+        for (title, body) in self.titles.iter().zip(&self.bodies) {
+            let body = match body {
+                Some(body) => body,
+                None => {
+                    widget_bodies.push(None);
+                    continue;
+                }
+            };
+            let prev = prev_value
+                .titles
+                .iter()
+                .position(|prev_title| prev_title == title)
+                .and_then(|prev_index| {
+                    let prev_body = prev_value.bodies[prev_index].as_ref()?;
+                    let prev_widget_body = prev_widget_bodies[prev_index].take()?;
+                    Some((prev_body, prev_widget_body))
+                });
+            match prev {
+                Some((prev_body, mut body_widget_seq)) => {
+                    body.reconcile(prev_body, &mut body_widget_seq, ctx);
+                    ctx.stats.updated += 1;
+                    widget_bodies.push(Some(body_widget_seq));
+                }
+                None => {
+                    ctx.stats.created += 1;
+                    children_changed = true;
+                    debug_span!("init_tree").in_scope(|| {
+                        info!("creating body of section {:?}", title);
+                        widget_bodies.push(Some(body.init_tree()));
+                    });
+                }
+            }
+        }
+        widget_seq.widget_mut().bodies = widget_bodies;
+
+        if children_changed || self.bodies.len() != prev_value.bodies.len() {
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.children_changed();
+                });
+        } else {
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        let mut prev_bodies: Vec<_> = std::mem::take(&mut self.titles)
+            .into_iter()
+            .zip(std::mem::take(&mut self.bodies))
+            .collect();
+        self.bodies = other
+            .titles
+            .iter()
+            .zip(other.bodies)
+            .map(|(title, body)| {
+                let prev_body = prev_bodies
+                    .iter()
+                    .position(|(prev_title, _)| prev_title == title)
+                    .and_then(|index| prev_bodies.swap_remove(index).1);
+                match (prev_body, body) {
+                    (Some(mut prev_body), Some(body)) => {
+                        prev_body.update_value(body);
+                        Some(prev_body)
+                    }
+                    (_, body) => body,
+                }
+            })
+            .collect();
+        self.titles = other.titles;
+        self.single = other.single;
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        for body in self.bodies.iter().flatten() {
+            body.unmount();
+        }
+    }
+
+    #[instrument(
+        name = "Accordion",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        for ((body, (_, body_state)), body_widget_seq) in self
+            .bodies
+            .iter()
+            .zip(children_state.iter_mut())
+            .zip(widget_seq.widget_mut().bodies.iter_mut())
+        {
+            if let (Some(body), Some(body_state), Some(body_widget_seq)) =
+                (body, body_state, body_widget_seq)
+            {
+                body.process_event(comp_ctx, body_state, body_widget_seq, cx);
+            }
+        }
+
+        // The section is toggled even if nothing handles the event; the event itself is
+        // emitted by process_local_event
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        match cx.app_data.dequeue_action(id) {
+            Some(Action::Selected(index)) => {
+                trace!("Toggling section {}", index);
+                self.toggle(children_state, index);
+                cx.app_data.queue_action(id, Action::Toggled(index));
+            }
+            Some(action) => cx.app_data.queue_action(id, action),
+            None => {}
+        }
+    }
+
+    #[instrument(name = "Accordion", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<SectionToggled> {
+        let id = widget_seq.widget().id();
+        if let Some(Action::Toggled(index)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed section toggle: {}", index);
+            Some(SectionToggled(index))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::Button;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    fn button_accordion() -> Accordion<Button> {
+        Accordion::new(
+            (1..=3)
+                .map(|index| {
+                    (
+                        format!("Section {}", index),
+                        Button::new(format!("Body {}", index))
+                            .with_reserved_id(WidgetId::reserved(index)),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn expanded_bodies(harness: &mut Harness<'_, '_, impl Element>) -> Vec<u16> {
+        (1..=3)
+            .filter(|index| harness.try_get_state(WidgetId::reserved(*index)).is_some())
+            .collect()
+    }
+
+    #[test]
+    fn new_accordion() {
+        let sections = vec![
+            (String::from("First"), MockComponent::new()),
+            (String::from("Second"), MockComponent::new()),
+        ];
+        let (accordion_data, state) = Accordion::new(sections.clone()).build(Default::default());
+        assert_eq!(accordion_data.titles, vec!["First", "Second"]);
+        assert!(accordion_data.bodies.iter().all(Option::is_none));
+
+        // Expansion follows the headers, and keeps the state of expanded bodies
+        let prev_state = vec![
+            (String::from("Second"), Some(MockState::new("changed"))),
+            (String::from("First"), Some(MockState::default())),
+        ];
+        let (accordion_data, state_2) = Accordion::new(sections.clone()).build(prev_state.clone());
+        assert!(accordion_data.bodies.iter().all(Option::is_some));
+        assert_eq!(
+            state_2[1],
+            (String::from("Second"), Some(MockState::new("changed")))
+        );
+        assert_eq!(state.len(), state_2.len());
+
+        // In single mode, only the first expanded section is kept
+        let (accordion_data, _) = Accordion::new(sections).single().build(prev_state);
+        assert!(accordion_data.bodies[0].is_some());
+        assert!(accordion_data.bodies[1].is_none());
+    }
+
+    #[test]
+    fn accordion_multi_widget() {
+        Harness::run_test_window(button_accordion(), |harness| {
+            assert_eq!(expanded_bodies(harness), Vec::<u16>::new());
+
+            harness.click_on("Section 1");
+            assert_eq!(
+                harness.take_emitted_events::<SectionToggled>(),
+                vec![SectionToggled(0)]
+            );
+            assert_eq!(expanded_bodies(harness), vec![1]);
+
+            // Expanding a section doesn't rebuild its siblings
+            harness.click_on("Section 3");
+            assert_eq!(expanded_bodies(harness), vec![1, 3]);
+            assert_eq!(harness.last_reconcile_stats().created, 1);
+            assert_eq!(harness.last_reconcile_stats().removed, 0);
+
+            harness.click_on("Section 1");
+            assert_eq!(expanded_bodies(harness), vec![3]);
+            assert!(harness.find_by_name("Body 3").is_some());
+        });
+    }
+
+    #[test]
+    fn accordion_single_widget() {
+        Harness::run_test_window(button_accordion().single(), |harness| {
+            harness.click_on("Section 1");
+            assert_eq!(expanded_bodies(harness), vec![1]);
+
+            // Expanding a section collapses the others
+            harness.click_on("Section 2");
+            assert_eq!(
+                harness.take_emitted_events::<SectionToggled>(),
+                vec![SectionToggled(0), SectionToggled(1)]
+            );
+            assert_eq!(expanded_bodies(harness), vec![2]);
+
+            harness.click_on("Section 3");
+            assert_eq!(expanded_bodies(harness), vec![3]);
+
+            harness.click_on("Section 3");
+            assert_eq!(expanded_bodies(harness), Vec::<u16>::new());
+        });
+    }
+}
@@ -0,0 +1,217 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{AlignedWidget, SingleAlignedWidget};
+
+use druid::{EventCtx, UnitPoint};
+use tracing::instrument;
+
+/// Wrapper which places its child within the space it's given.
+///
+/// The wrapper takes all the space its parent allows, and places the child inside according
+/// to its alignment. In a [Stack](crate::elements::Stack), it fills the stack, which overrides
+/// the stack's own alignment for this child. If the child doesn't create any widget, eg if
+/// it's an [EmptyElement](crate::elements::EmptyElement), the wrapper takes no space.
+///
+/// Usually created with [`ElementExt::align`](crate::ElementExt::align) or
+/// [`ElementExt::center`](crate::ElementExt::center).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aligned<Child: Element> {
+    pub child: Child,
+    pub alignment: UnitPoint,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlignedData<Child: VirtualDom> {
+    pub child: Child,
+    pub alignment: UnitPoint,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Aligned<Child> {
+    pub fn new(child: Child, alignment: UnitPoint) -> Self {
+        Aligned {
+            child,
+            alignment,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Aligned {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Aligned<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = AlignedData<Child::BuildOutput>;
+
+    #[instrument(name = "Aligned", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            AlignedData {
+                child,
+                alignment: self.alignment,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for AlignedData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleAlignedWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Aligned", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleAlignedWidget::new(
+            AlignedWidget::new(self.child.init_tree(), self.alignment, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Aligned", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.alignment != prev_value.alignment {
+            widget_seq.widget_mut().alignment = self.alignment;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.alignment = other.alignment;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Aligned", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Aligned", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::label::{Label, LabelData};
+    use crate::elements::EmptyElement;
+    use crate::test_harness::Harness;
+    use druid::Size;
+    use test_env_log::test;
+
+    #[test]
+    fn new_aligned() {
+        let (aligned_data, _) = Label::new("Hello")
+            .align(UnitPoint::BOTTOM_RIGHT)
+            .build(Default::default());
+
+        assert_eq!(
+            aligned_data,
+            AlignedData {
+                child: LabelData::new("Hello"),
+                alignment: UnitPoint::BOTTOM_RIGHT,
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn centered_label() {
+        let box_id = WidgetId::reserved(1);
+        let centered = Label::new("Hello")
+            .center()
+            .fix_size(200.0, 100.0)
+            .with_reserved_id(box_id);
+
+        Harness::run_test_window(centered, |harness| {
+            let box_rect = harness.get_state(box_id).layout_rect();
+            let label_rect = harness.find_by_name("Hello").unwrap().layout_rect();
+
+            assert!(label_rect.width() < box_rect.width());
+            assert!((label_rect.center() - box_rect.center()).hypot() <= 1.0);
+        });
+    }
+
+    #[test]
+    fn centered_empty_element() {
+        let aligned_id = WidgetId::reserved(1);
+        let centered = EmptyElement::new().center().with_reserved_id(aligned_id);
+
+        Harness::run_test_window(centered, |harness| {
+            let rect = harness.get_state(aligned_id).layout_rect();
+            assert_eq!(rect.size(), Size::ZERO);
+        });
+    }
+}
@@ -6,6 +6,7 @@ use crate::widgets::WidgetSeqBox;
 
 use std::any::Any;
 use std::fmt::Debug;
+use tracing::{debug_span, info};
 
 // --- STATE ---
 
@@ -125,17 +126,16 @@ impl<Child: Element + 'static> AnyElement for ErasedElement<Child> {
     ) {
         let child = self.child.take().unwrap();
 
-        let prev_state = if let Some(mut prev_state) = prev_state {
-            std::mem::take(
+        // If the previous element had a different type, its state is dropped
+        let prev_state = prev_state
+            .and_then(|mut prev_state| {
                 prev_state
                     .value
                     .as_mut_any()
                     .downcast_mut::<Child::AggregateChildrenState>()
-                    .unwrap(),
-            )
-        } else {
-            Default::default()
-        };
+                    .map(std::mem::take)
+            })
+            .unwrap_or_default();
 
         let (output, state) = child.build(prev_state);
 
@@ -228,10 +228,14 @@ pub trait AnyVirtualDom: Any + Debug {
 
     fn as_any(&self) -> &dyn Any;
 
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+
     fn print_type(&self) {
         println!("{:#?}", std::any::type_name::<Self>());
     }
 
+    fn has_same_type(&self, other: &Box<dyn AnyVirtualDom<Event = Self::Event>>) -> bool;
+
     fn init_tree(&self) -> WidgetSeqBox;
 
     fn reconcile(
@@ -241,6 +245,10 @@ pub trait AnyVirtualDom: Any + Debug {
         ctx: &mut ReconcileCtx,
     );
 
+    fn update_value(&mut self, other: Box<dyn AnyVirtualDom<Event = Self::Event>>);
+
+    fn unmount(&self);
+
     fn process_event(
         &self,
         comp_ctx: &mut ProcessEventCtx,
@@ -264,6 +272,14 @@ impl<Child: VirtualDom + 'static> AnyVirtualDom for ErasedVirtualDom<Child> {
         self
     }
 
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn has_same_type(&self, other: &Box<dyn AnyVirtualDom<Event = Self::Event>>) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some()
+    }
+
     fn init_tree(&self) -> WidgetSeqBox {
         WidgetSeqBox {
             value: Box::new(self.child.init_tree()),
@@ -285,6 +301,15 @@ impl<Child: VirtualDom + 'static> AnyVirtualDom for ErasedVirtualDom<Child> {
         self.child.reconcile(&prev_value.child, widget_seq, ctx);
     }
 
+    fn update_value(&mut self, other: Box<dyn AnyVirtualDom<Event = Self::Event>>) {
+        let other = other.into_any().downcast::<Self>().unwrap();
+        self.child.update_value(other.child);
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
     fn process_event(
         &self,
         comp_ctx: &mut ProcessEventCtx,
@@ -366,7 +391,30 @@ impl<Event: Debug> VirtualDom for VirtualDomBox<Event> {
         widget_seq: &mut Self::TargetWidgetSeq,
         ctx: &mut ReconcileCtx,
     ) {
-        self.child.reconcile(&prev_value.child, widget_seq, ctx);
+        if self.child.has_same_type(&prev_value.child) {
+            self.child.reconcile(&prev_value.child, widget_seq, ctx);
+        } else {
+            // The boxed element has changed type; its widgets can't be reused
+            prev_value.child.unmount();
+            ctx.stats.removed += 1;
+            ctx.stats.created += 1;
+            debug_span!("init_tree").in_scope(|| {
+                info!("boxed element changed type, creating child");
+                *widget_seq = self.child.init_tree();
+            });
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        if self.child.has_same_type(&other.child) {
+            self.child.update_value(other.child);
+        } else {
+            self.child = other.child;
+        }
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
     }
 
     fn process_event(
@@ -459,5 +507,55 @@ mod tests {
         });
     }
 
-    // FIXME - Test equivalent of Either
+    #[test]
+    fn boxed_type_change() {
+        use crate::elements::{Divider, Skeleton};
+        use crate::glue::WidgetId;
+
+        let divider_id = WidgetId::reserved(1);
+        let skeleton_id = WidgetId::reserved(2);
+        let divider = || ElementBox::new(Divider::horizontal().with_reserved_id(divider_id));
+        let skeleton = || ElementBox::new(Skeleton::new(80.0, 20.0).with_reserved_id(skeleton_id));
+
+        Harness::run_test_window(divider(), |harness| {
+            harness.update_root_element(divider());
+            assert_eq!(harness.last_reconcile_stats().created, 0);
+
+            // The divider's widget is torn down, and the skeleton's is created
+            harness.update_root_element(skeleton());
+            assert_eq!(harness.last_reconcile_stats().removed, 1);
+            assert_eq!(harness.last_reconcile_stats().created, 1);
+            assert!(harness.try_get_state(divider_id).is_none());
+            assert!(harness.try_get_state(skeleton_id).is_some());
+
+            harness.update_root_element(ElementBox::new(Label::new("Hello")));
+            assert!(harness.try_get_state(skeleton_id).is_none());
+            assert!(harness.find_by_name("Hello").is_some());
+        });
+    }
+
+    #[test]
+    fn boxed_list() {
+        use crate::elements::{ElementList, Skeleton};
+
+        let list = |items: &[&str]| {
+            ElementList::from_pairs(items.iter().map(|item| {
+                let child = match *item {
+                    "skeleton" => ElementBox::new(Skeleton::new(80.0, 20.0)),
+                    text => ElementBox::new(Label::new(text)),
+                };
+                (item.to_string(), child)
+            }))
+        };
+
+        Harness::run_test_window(list(&["aaa", "skeleton", "bbb"]), |harness| {
+            assert!(harness.find_by_name("aaa").is_some());
+            assert_eq!(harness.get_root_debug_state().children.len(), 3);
+
+            harness.update_root_element(list(&["skeleton", "bbb", "ccc"]));
+            assert!(harness.find_by_name("aaa").is_none());
+            assert!(harness.find_by_name("ccc").is_some());
+            assert_eq!(harness.get_root_debug_state().children.len(), 3);
+        });
+    }
 }
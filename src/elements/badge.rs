@@ -0,0 +1,228 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::element_tuple::{ElementTupleData, ElementTuple_2};
+use crate::elements::internals::{
+    AlignedData, ContainerData, EmptyElementData, LabelData, StackData,
+};
+use crate::elements::{Aligned, Container, Label, Stack};
+use crate::glue::GlobalEventCx;
+use crate::metadata::NoState;
+
+use druid::{Color, UnitPoint};
+use tracing::instrument;
+
+const BADGE_COLOR: Color = Color::rgb8(0xe0, 0x4f, 0x4f);
+const BADGE_RADIUS: f64 = 8.0;
+
+/// Wrapper which displays a small count over the top-right corner of its child.
+///
+/// The badge is hidden while the count is zero, unless [`show_zero`](Badge::show_zero) is
+/// set. It's laid over the child with a [Stack], so it doesn't change the child's layout.
+///
+/// Usually created with [`ElementExt::badge`](crate::ElementExt::badge).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Badge<Child: Element> {
+    pub child: Child,
+    pub count: u32,
+    pub show_zero: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BadgeData<Child: VirtualDom> {
+    pub stack: BadgeStackData<Child>,
+    pub count: u32,
+    pub show_zero: bool,
+}
+
+type BadgeIndicator = Option<Aligned<Container<Label>>>;
+
+type BadgeStackData<Child> = StackData<
+    ElementTupleData<
+        Child,
+        Option<AlignedData<ContainerData<LabelData>>>,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+        EmptyElementData,
+    >,
+>;
+
+//
+// --- IMPLS
+
+impl<Child: Element> Badge<Child> {
+    pub fn new(child: Child, count: u32) -> Self {
+        Badge {
+            child,
+            count,
+            show_zero: false,
+        }
+    }
+
+    /// Display the badge even when the count is zero.
+    pub fn show_zero(self) -> Self {
+        Badge {
+            show_zero: true,
+            ..self
+        }
+    }
+
+    fn indicator(&self) -> BadgeIndicator {
+        if self.count == 0 && !self.show_zero {
+            return None;
+        }
+        let indicator = Label::new(self.count.to_string())
+            .with_text_color(Color::WHITE)
+            .background(BADGE_COLOR)
+            .rounded(BADGE_RADIUS)
+            .align(UnitPoint::TOP_RIGHT);
+        Some(indicator)
+    }
+}
+
+impl<Child: Element> Element for Badge<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState =
+        <Stack<ElementTuple_2<Child, BadgeIndicator>> as Element>::AggregateChildrenState;
+    type BuildOutput = BadgeData<Child::BuildOutput>;
+
+    #[instrument(name = "Badge", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let indicator = self.indicator();
+        let (stack, state) = Stack::new(ElementTuple_2(self.child, indicator)).build(prev_state);
+        (
+            BadgeData {
+                stack,
+                count: self.count,
+                show_zero: self.show_zero,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for BadgeData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = <BadgeStackData<Child> as VirtualDom>::AggregateChildrenState;
+    type TargetWidgetSeq = <BadgeStackData<Child> as VirtualDom>::TargetWidgetSeq;
+
+    #[instrument(name = "Badge", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.stack.init_tree()
+    }
+
+    // The indicator's label is reconciled like any other, which updates its text
+    #[instrument(name = "Badge", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.stack.reconcile(&prev_value.stack, widget_seq, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.stack.update_value(other.stack);
+        self.count = other.count;
+        self.show_zero = other.show_zero;
+    }
+
+    fn unmount(&self) {
+        self.stack.unmount();
+    }
+
+    #[instrument(name = "Badge", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.stack
+            .process_event(comp_ctx, children_state, widget_seq, cx);
+    }
+
+    // The stack doesn't emit events, so the child's events are taken from it directly
+    #[instrument(name = "Badge", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.stack.child.0.process_local_event(
+            &mut children_state.0,
+            &mut widget_seq.widget_mut().children_seq.0,
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::{Button, ButtonClick};
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_badge() {
+        let (badge_data, _) = Label::new("Inbox").badge(3).build(Default::default());
+        assert_eq!(badge_data.count, 3);
+        assert!(badge_data.stack.child.1.is_some());
+
+        let (badge_data, _) = Label::new("Inbox").badge(0).build(Default::default());
+        assert!(badge_data.stack.child.1.is_none());
+
+        let (badge_data, _) = Label::new("Inbox")
+            .badge(0)
+            .show_zero()
+            .build(Default::default());
+        assert!(badge_data.stack.child.1.is_some());
+    }
+
+    #[test]
+    fn badge_widget() {
+        let (event_sender, event_receiver) = channel();
+        let inbox = |count| {
+            EventLogger::new(
+                event_sender.clone(),
+                Button::new("Inbox").fix_size(100.0, 40.0).badge(count),
+            )
+        };
+
+        Harness::run_test_window(inbox(3), |harness| {
+            assert!(harness.find_by_name("3").is_some());
+
+            harness.update_root_element(inbox(5));
+            assert!(harness.find_by_name("3").is_none());
+            assert!(harness.find_by_name("5").is_some());
+
+            // The badge hides when the count drops to zero
+            harness.update_root_element(inbox(0));
+            assert!(harness.find_by_name("0").is_none());
+
+            // The child's events go through the badge
+            harness.click_on("Inbox");
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+        });
+    }
+}
@@ -15,6 +15,7 @@ use tracing::{instrument, trace};
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Button {
     pub text: String,
+    pub disabled: bool,
     pub flex: FlexParams,
     pub reserved_widget_id: Option<WidgetId>,
 }
@@ -22,6 +23,7 @@ pub struct Button {
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct ButtonData {
     pub text: String,
+    pub disabled: bool,
     pub flex: FlexParams,
     pub reserved_widget_id: Option<WidgetId>,
 }
@@ -42,6 +44,7 @@ impl Button {
     pub fn new(text: impl Into<String>) -> Self {
         Button {
             text: text.into(),
+            disabled: false,
             flex: FlexParams {
                 flex: None,
                 alignment: None,
@@ -50,6 +53,13 @@ impl Button {
         }
     }
 
+    /// Set whether the button is disabled.
+    ///
+    /// A disabled button is greyed out, and doesn't emit events.
+    pub fn disabled(self, disabled: bool) -> Self {
+        Button { disabled, ..self }
+    }
+
     /// Change the way the button's size is calculated
     pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
         Button {
@@ -92,6 +102,7 @@ impl Element for Button {
         (
             ButtonData {
                 text: self.text,
+                disabled: self.disabled,
                 flex: self.flex,
                 reserved_widget_id: self.reserved_widget_id,
             },
@@ -108,12 +119,17 @@ impl VirtualDom for ButtonData {
     #[instrument(name = "Button", skip(self))]
     fn init_tree(&self) -> ButtonWidget {
         let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
-        ButtonWidget::new(self.text.clone(), self.flex, id)
+        let mut button = ButtonWidget::new(self.text.clone(), self.flex, id);
+        button.disabled = self.disabled;
+        button
     }
 
-    #[instrument(name = "Button", skip(self, _prev_value, _widget, _ctx))]
-    fn reconcile(&self, _prev_value: &Self, _widget: &mut ButtonWidget, _ctx: &mut ReconcileCtx) {
+    #[instrument(name = "Button", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut ButtonWidget, ctx: &mut ReconcileCtx) {
         //widget.set_text(self.text.clone());
+        if self.disabled != prev_value.disabled {
+            widget.set_disabled(self.disabled, ctx.event_ctx);
+        }
     }
 
     #[instrument(name = "Button", skip(self, _children_state, widget, cx))]
@@ -126,6 +142,10 @@ impl VirtualDom for ButtonData {
         // FIXME - Rework event dispatching
         let id = widget.id();
         if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            // The click may have been queued before the button was disabled
+            if self.disabled {
+                return None;
+            }
             trace!("Processed button press");
             Some(ButtonClick)
         } else {
@@ -195,4 +215,44 @@ mod tests {
             assert_debug_snapshot!(click_event);
         });
     }
+
+    #[test]
+    fn button_disabled() {
+        use crate::elements::event_logger::EventLogger;
+        use druid::MouseButton;
+        use std::sync::mpsc::channel;
+
+        let (event_sender, event_receiver) = channel();
+        let button_id = WidgetId::reserved(1);
+        let button = |disabled: bool| {
+            EventLogger::new(
+                event_sender.clone(),
+                Button::new("Hello")
+                    .disabled(disabled)
+                    .with_reserved_id(button_id),
+            )
+        };
+
+        Harness::run_test_window(button(true), |harness| {
+            let query = harness.find_by_name("Hello").unwrap();
+            assert_eq!(query.debug_state().other_values["disabled"], "true");
+
+            harness.mouse_click_on(button_id);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            harness.update_root_element(button(false));
+            let query = harness.find_by_name("Hello").unwrap();
+            assert!(!query.debug_state().other_values.contains_key("disabled"));
+
+            harness.mouse_click_on(button_id);
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+
+            // The button is disabled between the press and the release
+            harness.mouse_move_to(button_id);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.update_root_element(button(true));
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
 }
@@ -0,0 +1,272 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{CanvasWidget, PaintFn, SingleCanvasWidget};
+
+use derivative::Derivative;
+use druid::{EventCtx, PaintCtx, Point, Size};
+use std::fmt::Debug;
+use std::rc::Rc;
+use tracing::{instrument, trace};
+
+/// An area painted by a custom function.
+///
+/// The paint function is given the size of the canvas, and the state the canvas was built
+/// with, which is usually derived from the component's local state. Since functions can't be
+/// compared, the state is what decides when the canvas is painted again: the canvas is only
+/// repainted when it's built with a state which isn't equal to the previous one. The paint
+/// function itself is replaced every time the canvas is built.
+///
+/// The canvas fills the space it's given; use a [`SizedBox`](crate::elements::SizedBox) to
+/// give it a fixed size.
+///
+/// ## Events
+///
+/// Emits [CanvasMouse] events.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::elements::Canvas;
+/// # use druid::{Color, Rect, RenderContext};
+/// let progress = 0.5;
+/// let bar = Canvas::new(progress, |ctx, size, progress: &f64| {
+///     let filled = Rect::new(0.0, 0.0, size.width * progress, size.height);
+///     ctx.fill(filled, &Color::rgb8(0x40, 0x80, 0xff));
+/// });
+/// ```
+#[derive(Derivative)]
+#[derivative(Clone(bound = "State: Clone"), Debug(bound = "State: Debug"))]
+pub struct Canvas<State> {
+    pub state: State,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub paint: PaintFn<State>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "State: Clone"), Debug(bound = "State: Debug"))]
+pub struct CanvasData<State> {
+    pub state: State,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub paint: PaintFn<State>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when the mouse is used on a [Canvas].
+///
+/// Positions are relative to the canvas. Once the mouse is pressed on the canvas, moves and
+/// the release are reported even if the mouse leaves it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasMouse {
+    Down(Point),
+    Move(Point),
+    Up(Point),
+}
+
+//
+// --- IMPLS
+
+impl<State: Clone + Debug + PartialEq + 'static> Canvas<State> {
+    /// Build a canvas painted by `paint`, which is passed `state`.
+    ///
+    /// Use the [.on_mouse](Canvas::on_mouse) method to provide a closure to be called when
+    /// the mouse is used on the canvas.
+    pub fn new(state: State, paint: impl Fn(&mut PaintCtx, Size, &State) + 'static) -> Self {
+        Canvas {
+            state,
+            paint: Rc::new(paint),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the canvas' size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Canvas {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The canvas created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same canvas is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Canvas {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when the mouse is pressed, moved or released on the
+    /// canvas.
+    pub fn on_mouse<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, CanvasMouse) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+impl<State: Clone + Debug + PartialEq + 'static> Element for Canvas<State> {
+    type Event = CanvasMouse;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = CanvasData<State>;
+
+    #[instrument(name = "Canvas", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (CanvasData<State>, ()) {
+        (
+            CanvasData {
+                state: self.state,
+                paint: self.paint,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl<State: Clone + Debug + PartialEq + 'static> VirtualDom for CanvasData<State> {
+    type Event = CanvasMouse;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleCanvasWidget<State>;
+
+    #[instrument(name = "Canvas", skip(self))]
+    fn init_tree(&self) -> SingleCanvasWidget<State> {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleCanvasWidget::new(
+            CanvasWidget::new(self.state.clone(), self.paint.clone(), id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Canvas", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleCanvasWidget<State>,
+        ctx: &mut ReconcileCtx,
+    ) {
+        // The new function doesn't trigger a repaint by itself, but is used by the next one
+        widget.widget_mut().set_paint(self.paint.clone());
+        if self.state != prev_value.state {
+            let state = self.state.clone();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut CanvasWidget<State>, ctx: &mut EventCtx| {
+                    widget.set_state(state.clone(), ctx);
+                },
+            );
+        }
+        widget.flex = self.flex;
+    }
+
+    #[instrument(name = "Canvas", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleCanvasWidget<State>,
+        cx: &mut GlobalEventCx,
+    ) -> Option<CanvasMouse> {
+        let id = widget.widget().id();
+        if let Some(Action::CanvasMouse(mouse_event)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed canvas mouse event: {:?}", mouse_event);
+            Some(mouse_event)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use druid::{Color, MouseButton, Rect, RenderContext, Vec2};
+    use std::cell::RefCell;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_canvas() {
+        let canvas = Canvas::new(3, |_ctx, _size, _state: &i32| {});
+        let (canvas_data, ()) = canvas.build(());
+        assert_eq!(canvas_data.state, 3);
+    }
+
+    #[test]
+    fn canvas_repaint() {
+        let canvas_id = WidgetId::reserved(1);
+        // The size and state of each paint
+        let paints = Rc::new(RefCell::new(Vec::new()));
+        let canvas = |width: i32| {
+            let paints = paints.clone();
+            Canvas::new(width, move |ctx, size, width: &i32| {
+                paints.borrow_mut().push((size, *width));
+                let rect = Rect::new(0.0, 0.0, *width as f64, size.height);
+                ctx.fill(rect, &Color::WHITE);
+            })
+            .with_reserved_id(canvas_id)
+        };
+
+        Harness::run_test_window(canvas(10), |harness| {
+            harness.druid_harness.paint();
+            let canvas_size = harness.get_state(canvas_id).layout_rect().size();
+            assert_eq!(paints.borrow().last(), Some(&(canvas_size, 10)));
+
+            // A new state requests a repaint, which sees that state
+            paints.borrow_mut().clear();
+            harness.update_root_element(canvas(20));
+            harness.druid_harness.paint_invalid();
+            assert_eq!(*paints.borrow(), vec![(canvas_size, 20)]);
+            assert_eq!(harness.get_debug_state(canvas_id).main_value, "20");
+        });
+    }
+
+    #[test]
+    fn canvas_mouse() {
+        let (event_sender, event_receiver) = channel();
+        let canvas_id = WidgetId::reserved(1);
+        let canvas = EventLogger::new(
+            event_sender,
+            Canvas::new((), |_ctx, _size, _state: &()| {}).with_reserved_id(canvas_id),
+        );
+
+        Harness::run_test_window(canvas, |harness| {
+            let origin = harness.get_state(canvas_id).layout_rect().origin();
+
+            harness.mouse_move(origin + Vec2::new(10.0, 10.0));
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(origin + Vec2::new(30.0, 20.0));
+            harness.mouse_button_release(MouseButton::Left);
+
+            let events: Vec<_> = event_receiver.try_iter().collect();
+            assert_eq!(
+                events,
+                vec![
+                    CanvasMouse::Move(Point::new(10.0, 10.0)),
+                    CanvasMouse::Down(Point::new(10.0, 10.0)),
+                    CanvasMouse::Move(Point::new(30.0, 20.0)),
+                    CanvasMouse::Up(Point::new(30.0, 20.0)),
+                ]
+            );
+        });
+    }
+}
@@ -8,18 +8,22 @@ use crate::widgets::{CheckboxWidget, SingleCheckboxWidget};
 use tracing::{instrument, trace};
 
 // TODO - Handle the anti-pattern where the user does something like
-// Checkbox::new(false)
+// Checkbox::new("Foobar").with_checked(false)
 // In other words, enforce two-ways bindings
 
 /// A checkbox with a text label.
 ///
+/// The checked state of the box is usually bound to a value in the component's local state,
+/// with [.with_checked](Checkbox::with_checked) to read it and [.on_toggle](Checkbox::on_toggle)
+/// to write it back.
+///
 /// ## Events
 ///
-/// Emits [Toggled] events.
+/// Emits [CheckboxToggled] events.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Checkbox {
     pub text: String,
-    pub value: bool,
+    pub checked: bool,
     pub flex: FlexParams,
     pub reserved_widget_id: Option<WidgetId>,
 }
@@ -27,30 +31,28 @@ pub struct Checkbox {
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct CheckboxData {
     pub text: String,
-    pub value: bool,
+    pub checked: bool,
     pub flex: FlexParams,
     pub reserved_widget_id: Option<WidgetId>,
 }
 
-/// Event emitted when a [Checkbox] is clicked.
+/// Event emitted when a [Checkbox] is toggled.
 ///
-/// Note: Might hold data like "mouse position" or "checkbox id" future versions.
+/// Holds the new checked state of the box.
 #[derive(Debug, Clone, Default, PartialEq)]
-pub struct Toggled {
-    pub new_value: bool,
-}
+pub struct CheckboxToggled(pub bool);
 
 //
 // --- IMPLS
 
 impl Checkbox {
-    /// Build a checkbox with the given label.
+    /// Build an unchecked checkbox with the given label.
     ///
-    /// Use the [.on_toggled](Checkbox::on_toggled) method to provide a closure to be called when the box is toggled.
-    pub fn new(text: impl Into<String>, value: bool) -> Self {
+    /// Use the [.on_toggle](Checkbox::on_toggle) method to provide a closure to be called when the box is toggled.
+    pub fn new(text: impl Into<String>) -> Self {
         Checkbox {
             text: text.into(),
-            value,
+            checked: false,
             flex: FlexParams {
                 flex: None,
                 alignment: None,
@@ -59,6 +61,11 @@ impl Checkbox {
         }
     }
 
+    /// Set whether the box is checked.
+    pub fn with_checked(self, checked: bool) -> Self {
+        Checkbox { checked, ..self }
+    }
+
     /// Change the way the checkbox's size is calculated
     pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
         Checkbox {
@@ -80,17 +87,22 @@ impl Checkbox {
     }
 
     /// Provide a closure to be called when this checkbox is toggled.
-    pub fn on_toggled<ComponentEvent: 'static, ComponentState: 'static>(
+    ///
+    /// The closure is given the new checked state of the box.
+    pub fn on_toggle<ComponentEvent: 'static, ComponentState: 'static>(
         self,
         md: Metadata<ComponentEvent, ComponentState>,
-        callback: impl Fn(&mut ComponentState, Toggled) + Clone + 'static,
+        callback: impl Fn(&mut ComponentState, bool) + Clone + 'static,
     ) -> impl Element {
-        self.on(md, callback)
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: CheckboxToggled| callback(state, event.0),
+        )
     }
 }
 
 impl Element for Checkbox {
-    type Event = Toggled;
+    type Event = CheckboxToggled;
 
     type ComponentState = NoState;
     type AggregateChildrenState = ();
@@ -101,7 +113,7 @@ impl Element for Checkbox {
         (
             CheckboxData {
                 text: self.text,
-                value: self.value,
+                checked: self.checked,
                 flex: self.flex,
                 reserved_widget_id: self.reserved_widget_id,
             },
@@ -111,7 +123,7 @@ impl Element for Checkbox {
 }
 
 impl VirtualDom for CheckboxData {
-    type Event = Toggled;
+    type Event = CheckboxToggled;
     type AggregateChildrenState = ();
 
     type TargetWidgetSeq = SingleCheckboxWidget;
@@ -120,7 +132,7 @@ impl VirtualDom for CheckboxData {
     fn init_tree(&self) -> SingleCheckboxWidget {
         let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
         SingleCheckboxWidget::new(
-            CheckboxWidget::new(self.text.clone(), self.value, id),
+            CheckboxWidget::new(self.text.clone(), self.checked, id),
             self.flex,
         )
     }
@@ -132,15 +144,15 @@ impl VirtualDom for CheckboxData {
         widget: &mut SingleCheckboxWidget,
         ctx: &mut ReconcileCtx,
     ) {
-        let checkbox_widget = widget.widget_mut();
         if self.text != prev_value.text {
             // TODO
-            //checkbox_widget.pod.widget_mut().set_text(self.text.clone());
+            //widget.widget_mut().pod.widget_mut().set_text(self.text.clone());
+        }
+        if self.checked != prev_value.checked {
+            widget.widget_mut().value = self.checked;
+            widget.request_druid_update(ctx.event_ctx);
+            widget.widget_mut().request_druid_update(ctx.event_ctx);
         }
-        checkbox_widget.value = self.value;
-        // TODO - check diff with previous value
-        widget.request_druid_update(ctx.event_ctx);
-        widget.widget_mut().request_druid_update(ctx.event_ctx);
     }
 
     #[instrument(name = "Checkbox", skip(self, _children_state, widget, cx))]
@@ -149,13 +161,13 @@ impl VirtualDom for CheckboxData {
         _children_state: &mut Self::AggregateChildrenState,
         widget: &mut SingleCheckboxWidget,
         cx: &mut GlobalEventCx,
-    ) -> Option<Toggled> {
+    ) -> Option<CheckboxToggled> {
         // FIXME - Rework event dispatching
         let id = widget.widget().id();
         if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
-            let new_value = widget.widget().value;
-            trace!("Processed checkbox toggle: {}", new_value);
-            Some(Toggled { new_value })
+            let checked = widget.widget().value;
+            trace!("Processed checkbox toggle: {}", checked);
+            Some(CheckboxToggled(checked))
         } else {
             None
         }
@@ -173,7 +185,7 @@ mod tests {
 
     #[test]
     fn new_checkbox() {
-        let checkbox = Checkbox::new("Hello", false);
+        let checkbox = Checkbox::new("Hello");
         let (checkbox_data, ()) = checkbox.clone().build(());
 
         assert_debug_snapshot!(checkbox);
@@ -183,7 +195,7 @@ mod tests {
             checkbox_data,
             CheckboxData {
                 text: String::from("Hello"),
-                value: false,
+                checked: false,
                 flex: FlexParams {
                     flex: None,
                     alignment: None,
@@ -195,13 +207,13 @@ mod tests {
 
     #[test]
     fn checkbox_widget() {
-        let checkbox = Checkbox::new("Hello", false);
+        let checkbox = Checkbox::new("Hello");
 
         Harness::run_test_window(checkbox, |harness| {
             let checkbox_state = harness.get_root_debug_state();
             assert_debug_snapshot!(checkbox_state);
 
-            let new_checkbox = Checkbox::new("Hello", true);
+            let new_checkbox = Checkbox::new("Hello").with_checked(true);
             harness.update_root_element(new_checkbox);
 
             let checkbox_state_2 = harness.get_root_debug_state();
@@ -211,8 +223,7 @@ mod tests {
         });
     }
 
-    // FIXME - Test currently doesn't work; checkbox doesn't emit events
-    #[allow(dead_code)]
+    #[test]
     fn checkbox_press() {
         use crate::elements::event_logger::EventLogger;
         use std::sync::mpsc::channel;
@@ -221,18 +232,15 @@ mod tests {
         let checkbox_id = WidgetId::reserved(1);
         let checkbox = EventLogger::new(
             event_sender,
-            Checkbox::new("Hello", false).with_reserved_id(checkbox_id),
+            Checkbox::new("Hello").with_reserved_id(checkbox_id),
         );
 
         Harness::run_test_window(checkbox, |harness| {
-            assert_debug_snapshot!(harness.get_root_debug_state());
-
             harness.mouse_click_on(checkbox_id);
+            assert_eq!(event_receiver.try_recv(), Ok(CheckboxToggled(true)));
 
-            let click_event = event_receiver.try_recv();
-            assert_debug_snapshot!(click_event);
-
-            // TODO - test data persistence, somehow?
+            harness.mouse_click_on(checkbox_id);
+            assert_eq!(event_receiver.try_recv(), Ok(CheckboxToggled(false)));
         });
     }
 }
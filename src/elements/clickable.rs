@@ -106,6 +106,15 @@ impl<Child: VirtualDom> VirtualDom for ClickableData<Child> {
             .reconcile(&prev_value.child, widget_seq.children_mut(), ctx);
     }
 
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
     #[instrument(name = "Clickable", skip(self, _children_state, widget, cx))]
     fn process_local_event(
         &self,
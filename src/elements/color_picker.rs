@@ -0,0 +1,275 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{ColorPickerWidget, SingleColorPickerWidget};
+
+use druid::{Color, EventCtx};
+use tracing::{instrument, trace};
+
+/// A color preview, with a slider for each channel of the color.
+///
+/// The alpha channel can only be picked if [.with_alpha](ColorPicker::with_alpha) is set;
+/// otherwise, picked colors are opaque.
+///
+/// ## Events
+///
+/// Emits [ColorChanged] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPicker {
+    pub color: Color,
+    pub alpha: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorPickerData {
+    pub color: Color,
+    pub alpha: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a channel of a [ColorPicker] is moved.
+///
+/// Holds the new color; all its channels are within `0.0..=1.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorChanged(pub Color);
+
+//
+// --- IMPLS
+
+impl ColorPicker {
+    /// Build a color picker displaying `initial`.
+    ///
+    /// Use the [.on_change](ColorPicker::on_change) method to provide a closure to be called when a color is picked.
+    pub fn new(initial: Color) -> Self {
+        ColorPicker {
+            color: initial,
+            alpha: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the displayed color.
+    pub fn with_color(self, color: Color) -> Self {
+        ColorPicker { color, ..self }
+    }
+
+    /// Set whether the picker has a slider for the alpha channel.
+    pub fn with_alpha(self, alpha: bool) -> Self {
+        ColorPicker { alpha, ..self }
+    }
+
+    /// Change the way the picker's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        ColorPicker {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The picker created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same picker is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        ColorPicker {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a color is picked.
+    ///
+    /// The closure is given the new color.
+    pub fn on_change<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, Color) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: ColorChanged| callback(state, event.0),
+        )
+    }
+}
+
+impl ColorPickerData {
+    fn clamp_color(&self, color: &Color) -> Color {
+        let clamp = |channel: f64| channel.max(0.0).min(1.0);
+        let (r, g, b, a) = color.as_rgba();
+        let a = if self.alpha { clamp(a) } else { 1.0 };
+        Color::rgba(clamp(r), clamp(g), clamp(b), a)
+    }
+}
+
+impl Element for ColorPicker {
+    type Event = ColorChanged;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = ColorPickerData;
+
+    #[instrument(name = "ColorPicker", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (ColorPickerData, ()) {
+        (
+            ColorPickerData {
+                color: self.color,
+                alpha: self.alpha,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for ColorPickerData {
+    type Event = ColorChanged;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleColorPickerWidget;
+
+    #[instrument(name = "ColorPicker", skip(self))]
+    fn init_tree(&self) -> SingleColorPickerWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleColorPickerWidget::new(
+            ColorPickerWidget::new(&self.color, self.alpha, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "ColorPicker", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleColorPickerWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.alpha != prev_value.alpha {
+            let alpha = self.alpha;
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ColorPickerWidget, ctx: &mut EventCtx| {
+                    widget.set_alpha(alpha, ctx);
+                },
+            );
+        }
+        if self.color != prev_value.color {
+            let color = self.color.clone();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ColorPickerWidget, ctx: &mut EventCtx| {
+                    widget.set_color(&color, ctx);
+                },
+            );
+        }
+        widget.flex = self.flex;
+    }
+
+    #[instrument(name = "ColorPicker", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleColorPickerWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<ColorChanged> {
+        let id = widget.widget().id();
+        if let Some(Action::ColorChanged(color)) = cx.app_data.dequeue_action(id) {
+            let color = self.clamp_color(&color);
+            trace!("Processed color change: {:?}", color);
+            Some(ColorChanged(color))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use crate::widgets::color_picker_track_rect;
+    use druid::{MouseButton, Point};
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn clamp_color() {
+        let (picker_data, ()) = ColorPicker::new(Color::BLACK).build(());
+        let color = Color::rgba(0.2, 0.4, 0.6, 0.5);
+        assert_eq!(picker_data.clamp_color(&color), Color::rgb(0.2, 0.4, 0.6));
+
+        let (picker_data, ()) = ColorPicker::new(Color::BLACK).with_alpha(true).build(());
+        assert_eq!(picker_data.clamp_color(&color), color);
+    }
+
+    #[test]
+    fn color_picker_widget() {
+        let picker_id = WidgetId::reserved(1);
+        let picker = |color| ColorPicker::new(color).with_reserved_id(picker_id);
+
+        Harness::run_test_window(picker(Color::rgb8(0x80, 0x40, 0x20)), |harness| {
+            assert_eq!(harness.get_debug_state(picker_id).main_value, "#804020ff");
+
+            harness.update_root_element(picker(Color::rgb8(0x10, 0x20, 0x30)));
+            assert_eq!(harness.get_debug_state(picker_id).main_value, "#102030ff");
+        });
+    }
+
+    #[test]
+    fn color_picker_drag() {
+        let (event_sender, event_receiver) = channel();
+        let picker_id = WidgetId::reserved(1);
+        let picker = EventLogger::new(
+            event_sender,
+            ColorPicker::new(Color::rgb8(0x80, 0x80, 0x80))
+                .with_alpha(true)
+                .with_reserved_id(picker_id),
+        );
+
+        Harness::run_test_window(picker, |harness| {
+            let picker_rect = harness.find_by_name("#808080ff").unwrap().layout_rect();
+            let track_pos = |channel, x: f64| {
+                let track = color_picker_track_rect(channel, picker_rect.width());
+                Point::new(picker_rect.x0 + x, picker_rect.y0 + track.center().y)
+            };
+
+            // Set red to zero
+            harness.mouse_move(track_pos(0, 10.0));
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(track_pos(0, 0.0));
+            harness.mouse_button_release(MouseButton::Left);
+            let last_event = event_receiver.try_iter().last();
+            assert_eq!(
+                last_event,
+                Some(ColorChanged(Color::rgba8(0x00, 0x80, 0x80, 0xff)))
+            );
+
+            // Dragging past the end of the alpha track clamps the channel
+            harness.mouse_move(track_pos(3, picker_rect.width() / 2.0));
+            harness.mouse_button_press(MouseButton::Left);
+            let press_event = event_receiver.try_recv().ok();
+            assert_eq!(
+                press_event,
+                Some(ColorChanged(Color::rgba8(0x00, 0x80, 0x80, 0x80)))
+            );
+            harness.mouse_move(track_pos(3, -50.0));
+            harness.mouse_button_release(MouseButton::Left);
+            let last_event = event_receiver.try_iter().last();
+            assert_eq!(
+                last_event,
+                Some(ColorChanged(Color::rgba8(0x00, 0x80, 0x80, 0x00)))
+            );
+        });
+    }
+}
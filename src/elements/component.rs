@@ -1,21 +1,52 @@
-use crate::ctx::{CompCtx, ProcessEventCtx, ReconcileCtx};
+use crate::animation::AnimationState;
+use crate::async_task::AsyncTaskState;
+use crate::command::CommandState;
+use crate::ctx::{
+    build_env, is_building_form, provided_context, with_building_state, CompCtx, EffectsState,
+    IdsState, ProcessEventCtx, ReconcileCtx, SelectorsState,
+};
 use crate::element_tree::{Element, VirtualDom};
 use crate::elements::ElementBox;
 use crate::glue::GlobalEventCx;
+use crate::hotkey::HotkeyState;
+use crate::interval::IntervalState;
 use crate::metadata::{Metadata, NoState};
+use crate::reducer::ReducerState;
 
 use derivative::Derivative;
+use druid::Rect;
+use std::cell::{Ref, RefCell};
+use std::collections::VecDeque;
 use std::fmt::Debug;
+use std::rc::Rc;
+use tracing::trace;
+use tracing_unwrap::ResultExt;
 
 /// Trait that all components implement.
 ///
 /// To implement this trait, use the [`#[component]`](crate::component) macro.
+///
+/// When a component instance is built again with props equal to the previous ones, and no event
+/// has changed its local state or the state of the elements it returned since, the component
+/// function isn't called again, and the previous element tree is kept. Components which read
+/// [`use_context`](crate::CompCtx::use_context) or [`env`](crate::CompCtx::env) are always
+/// called, since those values aren't compared; components which read
+/// [`use_selector`](crate::CompCtx::use_selector) are called when a selector's result changes.
+/// Components inside a [`Form`](crate::elements::Form) are always called, so that the form
+/// knows whether its inputs are valid.
 pub trait Component: Debug + Clone {
     type Props: Clone + Debug + PartialEq + 'static;
     type LocalEvent: Clone + Debug + PartialEq + 'static;
+    /// The `ComponentState` of the element returned by the component function.
+    type LocalState: Clone + Default + Debug + PartialEq + 'static;
 
     fn new(props: Self::Props) -> ElementBox<Self::LocalEvent>;
 
+    /// Returns the local state of a component instance the first time it's built.
+    fn initial_state(_props: &Self::Props) -> Self::LocalState {
+        Default::default()
+    }
+
     fn name() -> &'static str;
 }
 
@@ -23,7 +54,7 @@ pub trait Component: Debug + Clone {
 #[derivative(Clone(bound = "Comp::Props: Clone"))]
 pub struct ComponentHolder<
     Comp: Component,
-    ReturnedTree: Element<Event = Comp::LocalEvent>,
+    ReturnedTree: Element<Event = Comp::LocalEvent, ComponentState = Comp::LocalState>,
     CompFn: Clone + Fn(&CompCtx, Comp::Props) -> ReturnedTree,
 > {
     component_fn: CompFn,
@@ -31,6 +62,25 @@ pub struct ComponentHolder<
     _marker: std::marker::PhantomData<Comp>,
 }
 
+/// Virtual DOM node of [`ComponentHolder`].
+///
+/// If the component function was skipped when it was built, the child is the one built by the
+/// previous call, which the holder keeps in its state.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), PartialEq(bound = "Child: PartialEq"))]
+pub struct ComponentHolderData<Comp: Component, Child: VirtualDom> {
+    child: SharedChild<Child>,
+    #[derivative(PartialEq = "ignore")]
+    _marker: std::marker::PhantomData<Comp>,
+}
+
+/// The virtual DOM returned by the last call to a component function.
+///
+/// It's shared between the [`ComponentHolderData`] and the state of the holder, so that it can
+/// be returned again when the call is skipped. Two copies are equal if they share the same
+/// virtual DOM.
+pub struct SharedChild<Child>(Rc<RefCell<Child>>);
+
 /// The root of the element hierarchy returned by a component.
 ///
 /// Any component which uses metadata (so components with local state, or components that emit
@@ -57,13 +107,15 @@ pub struct ComponentOutput<
     pub _metadata: Metadata<ComponentEvent, ComponentState>,
 }
 
-#[derive(Derivative, Hash)]
+// Debug is implemented manually, so that the key and local state only appear when they're
+// relevant.
+#[derive(Derivative)]
 #[derivative(
-    Debug(bound = ""),
     Clone(bound = "Child: Clone"),
     Default(bound = "Child: Default"),
     PartialEq(bound = "Child: PartialEq"),
-    Eq(bound = "Child: Eq")
+    Eq(bound = "Child: Eq"),
+    Hash(bound = "Child: std::hash::Hash")
 )]
 pub struct ComponentOutputData<
     ComponentEvent: Clone + Debug + PartialEq,
@@ -71,8 +123,15 @@ pub struct ComponentOutputData<
     Child: VirtualDom,
 > {
     pub child: Child,
-    #[derivative(Debug = "ignore")]
     pub _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(PartialEq = "ignore", Hash = "ignore")]
+    pub(crate) on_unmount: Option<Rc<dyn Fn()>>,
+    /// The key of the Keyed wrapping the component, if any.
+    #[derivative(PartialEq = "ignore", Hash = "ignore")]
+    pub(crate) key: Option<String>,
+    /// The local state the component was built with, if it isn't the default.
+    #[derivative(PartialEq = "ignore", Hash = "ignore")]
+    pub(crate) local_state: Option<ComponentState>,
 }
 
 // ---
@@ -94,7 +153,7 @@ impl<
 
 impl<
         Comp: Component,
-        ReturnedTree: Element<Event = Comp::LocalEvent>,
+        ReturnedTree: Element<Event = Comp::LocalEvent, ComponentState = Comp::LocalState>,
         CompFn: Clone + Fn(&CompCtx, Comp::Props) -> ReturnedTree,
     > ComponentHolder<Comp, ReturnedTree, CompFn>
 {
@@ -109,7 +168,7 @@ impl<
 
 impl<
         Comp: Component,
-        ReturnedTree: Element<Event = Comp::LocalEvent>,
+        ReturnedTree: Element<Event = Comp::LocalEvent, ComponentState = Comp::LocalState>,
         CompFn: Clone + Fn(&CompCtx, Comp::Props) -> ReturnedTree,
     > std::fmt::Debug for ComponentHolder<Comp, ReturnedTree, CompFn>
 {
@@ -121,34 +180,349 @@ impl<
     }
 }
 
+impl<
+        ComponentEvent: Clone + Debug + PartialEq,
+        ComponentState: Clone + Default + Debug + PartialEq,
+        Child: VirtualDom,
+    > std::fmt::Debug for ComponentOutputData<ComponentEvent, ComponentState, Child>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut f = f.debug_struct("ComponentOutputData");
+        if let Some(key) = &self.key {
+            f.field("key", key);
+        }
+        // The state is printed on a single line, to keep snapshots short
+        if let Some(local_state) = &self.local_state {
+            f.field("state", &format_args!("{:?}", local_state));
+        }
+        f.field("child", &self.child).finish()
+    }
+}
+
+impl<Comp: Component, Child: VirtualDom> std::fmt::Debug for ComponentHolderData<Comp, Child> {
+    // The holder is transparent, so that it doesn't clutter debug output
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.child().fmt(f)
+    }
+}
+
+impl<Comp: Component, Child: VirtualDom> ComponentHolderData<Comp, Child> {
+    /// The virtual DOM returned by the component function.
+    pub fn child(&self) -> Ref<'_, Child> {
+        self.child.0.borrow()
+    }
+}
+
+impl<Child> SharedChild<Child> {
+    fn new(child: Child) -> Self {
+        SharedChild(Rc::new(RefCell::new(child)))
+    }
+}
+
+impl<Child> Clone for SharedChild<Child> {
+    fn clone(&self) -> Self {
+        SharedChild(self.0.clone())
+    }
+}
+
+impl<Child> PartialEq for SharedChild<Child> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+// The virtual DOM is already printed by the holder
+impl<Child> std::fmt::Debug for SharedChild<Child> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SharedChild")
+    }
+}
+
 // ---
 
 impl<
         Comp: Component + 'static,
-        ReturnedTree: Element<Event = Comp::LocalEvent>,
+        ReturnedTree: Element<Event = Comp::LocalEvent, ComponentState = Comp::LocalState>,
         CompFn: Clone + Fn(&CompCtx, Comp::Props) -> ReturnedTree + 'static,
     > Element for ComponentHolder<Comp, ReturnedTree, CompFn>
 {
     type Event = Comp::LocalEvent;
     type ComponentState = NoState;
-    type AggregateChildrenState = ReturnedTree::AggregateChildrenState;
-    type BuildOutput = ReturnedTree::BuildOutput;
+    // The props, generation, selectors and returned virtual DOM of the last call to the
+    // component function, if it can be skipped when they're unchanged, the generation, which
+    // is bumped whenever an event changes the state of the returned tree, and that state
+    type AggregateChildrenState = (
+        Option<(
+            Comp::Props,
+            u64,
+            SelectorsState,
+            SharedChild<ReturnedTree::BuildOutput>,
+        )>,
+        u64,
+        ReturnedTree::AggregateChildrenState,
+    );
+    type BuildOutput = ComponentHolderData<Comp, ReturnedTree::BuildOutput>;
 
     // TODO - add spans
     fn build(
         self,
         prev_state: Self::AggregateChildrenState,
     ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (prev_call, generation, mut prev_state) = prev_state;
+        if let Some((prev_props, prev_generation, prev_selectors, prev_child)) = &prev_call {
+            if *prev_props == self.props
+                && *prev_generation == generation
+                && !prev_selectors.has_changed(&provided_context())
+                && !is_building_form()
+            {
+                trace!("Props, state and selected context unchanged, skipping component function");
+                return (
+                    ComponentHolderData {
+                        child: prev_child.clone(),
+                        _marker: Default::default(),
+                    },
+                    (prev_call, generation, prev_state),
+                );
+            }
+        }
+
+        // The local state is only set once the component has been built, so it doubles as a
+        // mount flag.
+        let is_mounted = ReturnedTree::get_component_state(&prev_state).is_some();
+        if !is_mounted {
+            // First time this instance is built: seed its local state
+            ReturnedTree::init_component_state(&mut prev_state, Comp::initial_state(&self.props));
+        }
+
         let default_state = Default::default();
         let local_state = ReturnedTree::get_component_state(&prev_state).unwrap_or(&default_state);
+        let default_effects = Default::default();
+        let prev_effects = ReturnedTree::get_effects_state(&prev_state).unwrap_or(&default_effects);
+        let default_reducer = Default::default();
+        let prev_reducer = ReturnedTree::get_reducer_state(&prev_state).unwrap_or(&default_reducer);
+        let default_animations = Default::default();
+        let prev_animations =
+            ReturnedTree::get_animation_state(&prev_state).unwrap_or(&default_animations);
+        let default_ids = Default::default();
+        let prev_ids = ReturnedTree::get_ids_state(&prev_state).unwrap_or(&default_ids);
 
         let ctx = CompCtx {
             called_use_metadata: std::cell::Cell::new(false),
             local_state: local_state,
+            is_mounted,
+            on_unmount: Default::default(),
+            prev_effects,
+            effects: Default::default(),
+            context: provided_context(),
+            env: build_env(),
+            async_task: Default::default(),
+            interval: Default::default(),
+            commands: Default::default(),
+            prev_reducer,
+            reducer: Default::default(),
+            prev_animations,
+            animations: Default::default(),
+            hotkeys: Default::default(),
+            prev_ids,
+            ids: Default::default(),
+            selectors: Default::default(),
+            read_context: Default::default(),
+        };
+        let element_tree = (self.component_fn)(&ctx, self.props.clone());
+        let read_context = ctx.read_context.get();
+        let on_unmount = ctx.on_unmount.take();
+        let effects = ctx.effects.take();
+        let new_task = ctx.async_task.take();
+        let new_interval = ctx.interval.take();
+        let commands = ctx.commands.take();
+        let reducer = ctx.reducer.take();
+        let animations = ctx.animations.take();
+        let new_hotkeys = ctx.hotkeys.take();
+        let ids = ctx.ids.take();
+        let selectors = ctx.selectors.take();
+
+        let (mut output, mut state) = element_tree.build(prev_state);
+
+        // A newly spawned task replaces the one that was running
+        let async_task = match new_task {
+            Some(new_task) => {
+                if let Some(prev_task) = ReturnedTree::get_async_task_state(&state) {
+                    prev_task.cancel();
+                }
+                new_task
+            }
+            None => ReturnedTree::get_async_task_state(&state)
+                .cloned()
+                .unwrap_or_default(),
+        };
+
+        // The interval keeps its timer if it's registered again with the same period
+        let prev_interval = ReturnedTree::get_interval_state(&state)
+            .cloned()
+            .unwrap_or_default();
+        let interval = prev_interval.replace(new_interval.unwrap_or_default());
+
+        // Shortcuts registered again keep their place in the registry
+        let prev_hotkeys = ReturnedTree::get_hotkey_state(&state)
+            .cloned()
+            .unwrap_or_default();
+        let hotkeys = prev_hotkeys.replace(new_hotkeys);
+
+        // Effect cleanups run after the user's unmount callback
+        let on_unmount: Option<Rc<dyn Fn()>> = if effects.is_empty()
+            && async_task.is_none()
+            && interval.is_none()
+            && hotkeys.is_empty()
+        {
+            on_unmount
+        } else {
+            let effects = effects.clone();
+            let async_task = async_task.clone();
+            let interval = interval.clone();
+            let hotkeys = hotkeys.clone();
+            Some(Rc::new(move || {
+                if let Some(on_unmount) = &on_unmount {
+                    on_unmount();
+                }
+                effects.run_cleanups();
+                async_task.cancel();
+                interval.cancel();
+                hotkeys.cancel();
+            }))
+        };
+        if let Some(on_unmount) = on_unmount {
+            ReturnedTree::set_unmount_callback(&mut output, on_unmount);
+        }
+        ReturnedTree::set_effects_state(&mut state, effects);
+        ReturnedTree::set_async_task_state(&mut state, async_task);
+        ReturnedTree::set_interval_state(&mut state, interval);
+        ReturnedTree::set_command_state(&mut state, commands);
+        ReturnedTree::set_animation_state(&mut state, animations);
+        ReturnedTree::set_hotkey_state(&mut state, hotkeys);
+        ReturnedTree::set_ids_state(&mut state, ids);
+
+        // The reducer's initial state replaces the default local state
+        let reducer_state = match reducer {
+            Some((reducer_state, initial_state)) => {
+                if !is_mounted {
+                    // use_reducer checks its state type against the local state
+                    let initial_state = initial_state
+                        .downcast::<Comp::LocalState>()
+                        .expect_or_log("use_reducer was given the wrong state type");
+                    ReturnedTree::init_component_state(&mut state, *initial_state);
+                }
+                reducer_state
+            }
+            None => Default::default(),
+        };
+        ReturnedTree::set_reducer_state(&mut state, reducer_state);
+
+        let child = SharedChild::new(output);
+        let call = if read_context {
+            None
+        } else {
+            Some((self.props, generation, selectors, child.clone()))
         };
-        let element_tree = (self.component_fn)(&ctx, self.props);
+        (
+            ComponentHolderData {
+                child,
+                _marker: Default::default(),
+            },
+            (call, generation, state),
+        )
+    }
+}
+
+impl<Comp: Component + 'static, Child: VirtualDom> VirtualDom for ComponentHolderData<Comp, Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = (
+        Option<(Comp::Props, u64, SelectorsState, SharedChild<Child>)>,
+        u64,
+        Child::AggregateChildrenState,
+    );
+    type TargetWidgetSeq = Child::TargetWidgetSeq;
+
+    fn update_value(&mut self, other: Self) {
+        if self.child == other.child {
+            // The component function was skipped
+            return;
+        }
+        // The new child is kept in the holder's state, so the previous one is updated in
+        // its place
+        let prev_child = std::mem::replace(&mut self.child, other.child);
+        match Rc::try_unwrap(prev_child.0) {
+            Ok(prev_child) => {
+                let mut child = self.child.0.borrow_mut();
+                let new_child = std::mem::replace(&mut *child, prev_child.into_inner());
+                child.update_value(new_child);
+            }
+            Err(_) => trace!("Previous child still shared, replacing it"),
+        }
+    }
+
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.child().init_tree()
+    }
+
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.child == prev_value.child {
+            trace!("Component function skipped, skipping reconcile");
+            return;
+        }
+        self.child().reconcile(&prev_value.child(), widget_seq, ctx);
+    }
+
+    fn set_debug_key(&mut self, key: &str) {
+        self.child.0.borrow_mut().set_debug_key(key);
+    }
+
+    fn unmount(&self) {
+        self.child().unmount();
+    }
+
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        self.child().find_key_rect(key, widget_seq)
+    }
+
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let action_count = cx.app_data.action_count();
+        let state_changed = std::mem::take(&mut cx.state_changed);
+        self.child()
+            .process_event(comp_ctx, &mut children_state.2, widget_seq, cx);
 
-        element_tree.build(prev_state)
+        // Elements change their state when they consume the actions of their widgets
+        if cx.state_changed || cx.app_data.action_count() < action_count {
+            children_state.1 = children_state.1.wrapping_add(1);
+            cx.state_changed = true;
+        }
+        cx.state_changed |= state_changed;
+    }
+
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let event = self
+            .child()
+            .process_local_event(&mut children_state.2, widget_seq, cx);
+        // The event was taken from the queue of the returned tree
+        if event.is_some() {
+            children_state.1 = children_state.1.wrapping_add(1);
+        }
+        event
     }
 }
 
@@ -165,8 +539,16 @@ impl<
     type ComponentState = ComponentState;
     // TODO - Store Event queue somewhere else?
     type AggregateChildrenState = (
-        Vec<ComponentEvent>,
-        ComponentState,
+        VecDeque<ComponentEvent>,
+        Option<ComponentState>,
+        EffectsState,
+        AsyncTaskState,
+        IntervalState,
+        CommandState,
+        ReducerState,
+        AnimationState,
+        HotkeyState,
+        IdsState,
         Child::AggregateChildrenState,
     );
     type BuildOutput = ComponentOutputData<ComponentEvent, ComponentState, Child::BuildOutput>;
@@ -175,19 +557,128 @@ impl<
         self,
         prev_state: Self::AggregateChildrenState,
     ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
-        let (_, prev_local_state, children_prev_state) = prev_state;
-        let (child, children_state) = self.child.build(children_prev_state);
+        let (
+            _,
+            prev_local_state,
+            prev_effects,
+            prev_async_task,
+            prev_interval,
+            prev_commands,
+            prev_reducer,
+            prev_animations,
+            prev_hotkeys,
+            prev_ids,
+            children_prev_state,
+        ) = prev_state;
+        // Bound elements read their value from the local state while they're built
+        let local_state = Rc::new(prev_local_state.unwrap_or_default());
+        let (child, children_state) = with_building_state(local_state.clone(), || {
+            self.child.build(children_prev_state)
+        });
+        let local_state =
+            Rc::try_unwrap(local_state).unwrap_or_else(|local_state| (*local_state).clone());
+        let non_default_state = if local_state != ComponentState::default() {
+            Some(local_state.clone())
+        } else {
+            None
+        };
         (
             ComponentOutputData {
                 child,
                 _metadata: Default::default(),
+                on_unmount: None,
+                key: None,
+                local_state: non_default_state,
             },
-            (vec![], prev_local_state, children_state),
+            (
+                VecDeque::new(),
+                Some(local_state),
+                prev_effects,
+                prev_async_task,
+                prev_interval,
+                prev_commands,
+                prev_reducer,
+                prev_animations,
+                prev_hotkeys,
+                prev_ids,
+                children_state,
+            ),
         )
     }
 
     fn get_component_state(state: &Self::AggregateChildrenState) -> Option<&Self::ComponentState> {
-        Some(&state.1)
+        state.1.as_ref()
+    }
+
+    fn init_component_state(
+        state: &mut Self::AggregateChildrenState,
+        initial_state: Self::ComponentState,
+    ) {
+        state.1 = Some(initial_state);
+    }
+
+    fn set_unmount_callback(output: &mut Self::BuildOutput, callback: Rc<dyn Fn()>) {
+        output.on_unmount = Some(callback);
+    }
+
+    fn get_effects_state(state: &Self::AggregateChildrenState) -> Option<&EffectsState> {
+        Some(&state.2)
+    }
+
+    fn set_effects_state(state: &mut Self::AggregateChildrenState, effects: EffectsState) {
+        state.2 = effects;
+    }
+
+    fn get_async_task_state(state: &Self::AggregateChildrenState) -> Option<&AsyncTaskState> {
+        Some(&state.3)
+    }
+
+    fn set_async_task_state(state: &mut Self::AggregateChildrenState, task: AsyncTaskState) {
+        state.3 = task;
+    }
+
+    fn get_interval_state(state: &Self::AggregateChildrenState) -> Option<&IntervalState> {
+        Some(&state.4)
+    }
+
+    fn set_interval_state(state: &mut Self::AggregateChildrenState, interval: IntervalState) {
+        state.4 = interval;
+    }
+
+    fn set_command_state(state: &mut Self::AggregateChildrenState, commands: CommandState) {
+        state.5 = commands;
+    }
+
+    fn get_reducer_state(state: &Self::AggregateChildrenState) -> Option<&ReducerState> {
+        Some(&state.6)
+    }
+
+    fn set_reducer_state(state: &mut Self::AggregateChildrenState, reducer: ReducerState) {
+        state.6 = reducer;
+    }
+
+    fn get_animation_state(state: &Self::AggregateChildrenState) -> Option<&AnimationState> {
+        Some(&state.7)
+    }
+
+    fn set_animation_state(state: &mut Self::AggregateChildrenState, animations: AnimationState) {
+        state.7 = animations;
+    }
+
+    fn get_hotkey_state(state: &Self::AggregateChildrenState) -> Option<&HotkeyState> {
+        Some(&state.8)
+    }
+
+    fn set_hotkey_state(state: &mut Self::AggregateChildrenState, hotkeys: HotkeyState) {
+        state.8 = hotkeys;
+    }
+
+    fn get_ids_state(state: &Self::AggregateChildrenState) -> Option<&IdsState> {
+        Some(&state.9)
+    }
+
+    fn set_ids_state(state: &mut Self::AggregateChildrenState, ids: IdsState) {
+        state.9 = ids;
     }
 }
 
@@ -199,8 +690,16 @@ impl<
 {
     type Event = ComponentEvent;
     type AggregateChildrenState = (
-        Vec<ComponentEvent>,
-        ComponentState,
+        VecDeque<ComponentEvent>,
+        Option<ComponentState>,
+        EffectsState,
+        AsyncTaskState,
+        IntervalState,
+        CommandState,
+        ReducerState,
+        AnimationState,
+        HotkeyState,
+        IdsState,
         Child::AggregateChildrenState,
     );
     type TargetWidgetSeq = Child::TargetWidgetSeq;
@@ -219,6 +718,28 @@ impl<
         self.child.reconcile(&prev_value.child, widget_seq, ctx);
     }
 
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.on_unmount = other.on_unmount;
+        self.key = other.key;
+        self.local_state = other.local_state;
+    }
+
+    fn set_debug_key(&mut self, key: &str) {
+        self.key = Some(key.to_string());
+    }
+
+    fn unmount(&self) {
+        if let Some(on_unmount) = &self.on_unmount {
+            on_unmount();
+        }
+        self.child.unmount();
+    }
+
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        self.child.find_key_rect(key, widget_seq)
+    }
+
     fn process_local_event(
         &self,
         children_state: &mut Self::AggregateChildrenState,
@@ -226,8 +747,7 @@ impl<
         _cx: &mut GlobalEventCx,
     ) -> Option<Self::Event> {
         let event_queue = &mut children_state.0;
-        // TODO - this is a stack, not a queue; whatever, I'll use VecDeque later
-        event_queue.pop()
+        event_queue.pop_front()
     }
 
     fn process_event(
@@ -237,12 +757,31 @@ impl<
         widget_seq: &mut Self::TargetWidgetSeq,
         cx: &mut GlobalEventCx,
     ) {
+        let prev_local_state = children_state.1.clone();
+        let local_state = children_state.1.get_or_insert_with(Default::default);
+        children_state.3.deliver_output(local_state);
+        children_state.4.fire(cx.fired_timer, local_state);
+        children_state.5.handle(cx.command.as_ref(), local_state);
+        let animated = match cx.anim_frame {
+            Some(interval) => children_state.7.advance(interval),
+            None => false,
+        };
+        children_state.8.handle(cx.hotkey, local_state);
+
         let mut ctx = ProcessEventCtx {
             event_queue: &mut children_state.0,
-            state: &mut children_state.1,
+            state: local_state,
         };
         self.child
-            .process_event(&mut ctx, &mut children_state.2, widget_seq, cx)
+            .process_event(&mut ctx, &mut children_state.10, widget_seq, cx);
+
+        // Actions dispatched by the callbacks above
+        let local_state = children_state.1.get_or_insert_with(Default::default);
+        children_state.6.apply(local_state);
+
+        if animated || !children_state.0.is_empty() || children_state.1 != prev_local_state {
+            cx.state_changed = true;
+        }
     }
 }
 
@@ -266,7 +805,8 @@ mod tests {
         fn render(
             _ctx: &panoramix::CompCtx,
             _my_props: MyPropsType,
-        ) -> impl panoramix::Element<Event = MyLocalEvent> {
+        ) -> impl panoramix::Element<Event = MyLocalEvent, ComponentState = panoramix::NoState>
+        {
             panoramix::elements::EmptyElement::new()
         }
     }
@@ -274,6 +814,7 @@ mod tests {
     impl panoramix::elements::component::Component for MyComponent {
         type Props = MyPropsType;
         type LocalEvent = MyLocalEvent;
+        type LocalState = panoramix::NoState;
 
         fn new(props: Self::Props) -> panoramix::elements::ElementBox<MyLocalEvent> {
             panoramix::elements::ElementBox::new(panoramix::elements::internals::ComponentHolder::<
@@ -288,8 +829,584 @@ mod tests {
         }
     }
 
-    use crate::element_tree::Element;
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Counter;
+
+    impl Counter {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            _initial_count: i32,
+        ) -> ComponentOutput<panoramix::NoEvent, i32, Label> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, i32>();
+            let count = ctx.get_local_state(md);
+            ComponentOutput::new(md, Label::new(format!("Count: {}", count)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Counter {
+        type Props = i32;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = i32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Counter::render,
+                props,
+            ))
+        }
+
+        fn initial_state(initial_count: &i32) -> i32 {
+            *initial_count
+        }
+
+        fn name() -> &'static str {
+            "Counter"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct LifecycleCounts {
+        mount: Rc<Cell<u32>>,
+        unmount: Rc<Cell<u32>>,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Tracked;
+
+    impl Tracked {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            counts: LifecycleCounts,
+        ) -> ComponentOutput<panoramix::NoEvent, (), EmptyElement> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, ()>();
+            let unmount_count = counts.unmount.clone();
+            ctx.use_lifecycle(
+                md,
+                || counts.mount.set(counts.mount.get() + 1),
+                move || unmount_count.set(unmount_count.get() + 1),
+            );
+            ComponentOutput::new(md, EmptyElement::new())
+        }
+    }
+
+    impl panoramix::elements::component::Component for Tracked {
+        type Props = LifecycleCounts;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = ();
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Tracked::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Tracked"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct EffectCounts {
+        run: Rc<Cell<u32>>,
+        cleanup: Rc<Cell<u32>>,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct WithEffect;
+
+    impl WithEffect {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            (deps, counts): (i32, EffectCounts),
+        ) -> ComponentOutput<panoramix::NoEvent, (), EmptyElement> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, ()>();
+            ctx.use_effect(md, deps, || {
+                counts.run.set(counts.run.get() + 1);
+                let cleanup_count = counts.cleanup.clone();
+                Some(Box::new(move || cleanup_count.set(cleanup_count.get() + 1)))
+            });
+            ComponentOutput::new(md, EmptyElement::new())
+        }
+    }
+
+    impl panoramix::elements::component::Component for WithEffect {
+        type Props = (i32, EffectCounts);
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = ();
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &WithEffect::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "WithEffect"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Loader;
+
+    impl Loader {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            value_to_load: i32,
+        ) -> ComponentOutput<panoramix::NoEvent, i32, Label> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, i32>();
+            ctx.use_effect(md, value_to_load, || {
+                ctx.spawn_async(
+                    md,
+                    async move { value_to_load },
+                    |state: &mut i32, value| *state = value,
+                );
+                None
+            });
+            let loaded = ctx.get_local_state(md);
+            ComponentOutput::new(md, Label::new(format!("Loaded: {}", loaded)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Loader {
+        type Props = i32;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = i32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Loader::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Loader"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Ticker;
+
+    impl Ticker {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            ticks: Rc<Cell<u32>>,
+        ) -> ComponentOutput<panoramix::NoEvent, u32, Label> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, u32>();
+            ctx.use_interval(md, Duration::from_millis(100), move |count: &mut u32| {
+                ticks.set(ticks.get() + 1);
+                *count += 1;
+            });
+            let count = ctx.get_local_state(md);
+            ComponentOutput::new(md, Label::new(format!("Ticks: {}", count)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Ticker {
+        type Props = Rc<Cell<u32>>;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = u32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Ticker::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Ticker"
+        }
+    }
+
+    const ADD_TO_TOTAL: druid::Selector<u32> = druid::Selector::new("panoramix-test.add-to-total");
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Totaler;
+
+    impl Totaler {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            _props: (),
+        ) -> ComponentOutput<panoramix::NoEvent, u32, Label> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, u32>();
+            // The component sends itself a first command when it's mounted
+            ctx.use_effect(md, (), || {
+                ctx.submit_command(ADD_TO_TOTAL, 1);
+                None
+            });
+            ctx.on_command(md, ADD_TO_TOTAL, |total: &mut u32, amount: &u32| {
+                *total += amount;
+            });
+            let total = ctx.get_local_state(md);
+            ComponentOutput::new(md, Label::new(format!("Total: {}", total)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Totaler {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = u32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Totaler::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Totaler"
+        }
+    }
+
+    // Counts calls to the component function; all copies of a counter are equal
+    #[derive(Debug, Default, Clone)]
+    struct CallCount(Rc<Cell<u32>>);
+
+    impl PartialEq for CallCount {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.0, &other.0)
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Counted;
+
+    impl Counted {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            (value, calls): (i32, CallCount),
+        ) -> ComponentOutput<panoramix::NoEvent, i32, Label> {
+            calls.0.set(calls.0.get() + 1);
+            let md = ctx.use_metadata::<panoramix::NoEvent, i32>();
+            let clicks = ctx.get_local_state(md);
+            ComponentOutput::new(md, Label::new(format!("{} - {}", value, clicks)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Counted {
+        type Props = (i32, CallCount);
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = i32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Counted::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Counted"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Clicked;
+
+    impl Clicked {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            calls: CallCount,
+        ) -> ComponentOutput<
+            panoramix::NoEvent,
+            i32,
+            impl panoramix::Element<Event = panoramix::NoEvent>,
+        > {
+            calls.0.set(calls.0.get() + 1);
+            let md = ctx.use_metadata::<panoramix::NoEvent, i32>();
+            let clicks = ctx.get_local_state(md);
+            ComponentOutput::new(
+                md,
+                Button::new(format!("Clicks: {}", clicks))
+                    .on_click(md, |clicks: &mut i32, _| *clicks += 1),
+            )
+        }
+    }
+
+    impl panoramix::elements::component::Component for Clicked {
+        type Props = CallCount;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = i32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Clicked::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Clicked"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct ClickedParent;
+
+    impl ClickedParent {
+        fn render(
+            _ctx: &panoramix::CompCtx,
+            calls: CallCount,
+        ) -> impl panoramix::Element<Event = panoramix::NoEvent, ComponentState = panoramix::NoState>
+        {
+            ComponentHolder::<Clicked, _, _>::new(&Clicked::render, calls)
+        }
+    }
+
+    impl panoramix::elements::component::Component for ClickedParent {
+        type Props = CallCount;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = panoramix::NoState;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &ClickedParent::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "ClickedParent"
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum CountAction {
+        Add(i32),
+        Double,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct ReducedCounter;
+
+    impl ReducedCounter {
+        fn reduce(count: &i32, action: CountAction) -> i32 {
+            match action {
+                CountAction::Add(amount) => count + amount,
+                CountAction::Double => count * 2,
+            }
+        }
+
+        fn render(
+            ctx: &panoramix::CompCtx,
+            _props: (),
+        ) -> impl panoramix::Element<Event = panoramix::NoEvent, ComponentState = i32> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, i32>();
+            let (count, dispatch) = ctx.use_reducer(md, ReducedCounter::reduce, 1);
+            ComponentOutput::new(
+                md,
+                Tuple!(
+                    Label::new(format!("Count: {}", count)),
+                    Button::new("Update").on_click(md, move |_, _| {
+                        dispatch.dispatch(CountAction::Add(2));
+                        dispatch.dispatch(CountAction::Double);
+                        dispatch.dispatch(CountAction::Add(1));
+                    }),
+                ),
+            )
+        }
+    }
+
+    impl panoramix::elements::component::Component for ReducedCounter {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = i32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &ReducedCounter::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "ReducedCounter"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Saver;
+
+    impl Saver {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            _props: (),
+        ) -> impl panoramix::Element<Event = panoramix::NoEvent, ComponentState = u32> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, u32>();
+            ctx.use_hotkey(md, HotKey::new(RawMods::Ctrl, "s"), |saves: &mut u32| {
+                *saves += 1;
+            });
+            let saves = ctx.get_local_state(md);
+            ComponentOutput::new(
+                md,
+                Tuple!(
+                    TextBox::new("draft").autofocus(),
+                    Label::new(format!("Saves: {}", saves)),
+                ),
+            )
+        }
+    }
+
+    impl panoramix::elements::component::Component for Saver {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = u32;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Saver::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Saver"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Themed;
+
+    impl Themed {
+        fn render(ctx: &panoramix::CompCtx, _props: ()) -> Label {
+            match ctx.env().try_get(druid::theme::TEXT_SIZE_NORMAL) {
+                Ok(text_size) => Label::new(format!("Text size: {}", text_size)),
+                Err(_) => Label::new("No theme"),
+            }
+        }
+    }
+
+    impl panoramix::elements::component::Component for Themed {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = panoramix::NoState;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Themed::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Themed"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Gauge;
+
+    impl Gauge {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            target: u32,
+        ) -> ComponentOutput<panoramix::NoEvent, (), Label> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, ()>();
+            let width = ctx.use_animated(md, target as f64, Duration::from_millis(100));
+            ComponentOutput::new(md, Label::new(format!("Width: {}", width)))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Gauge {
+        type Props = u32;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = ();
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Gauge::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Gauge"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct ClipboardViewer;
+
+    impl ClipboardViewer {
+        fn render(ctx: &panoramix::CompCtx, _props: ()) -> Label {
+            Label::new(format!(
+                "Clipboard: {}",
+                ctx.get_clipboard().unwrap_or_default()
+            ))
+        }
+    }
+
+    impl panoramix::elements::component::Component for ClipboardViewer {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = panoramix::NoState;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &ClipboardViewer::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "ClipboardViewer"
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct Pinned;
+
+    thread_local! {
+        static PINNED_IDS: std::cell::RefCell<Vec<WidgetId>> = Default::default();
+    }
+
+    impl Pinned {
+        fn render(
+            ctx: &panoramix::CompCtx,
+            text: &'static str,
+        ) -> ComponentOutput<panoramix::NoEvent, (), WithId<Label>> {
+            let md = ctx.use_metadata::<panoramix::NoEvent, ()>();
+            let id = ctx.gen_id();
+            PINNED_IDS.with(|ids| ids.borrow_mut().push(id));
+            ComponentOutput::new(md, Label::new(text).with_id(id))
+        }
+    }
+
+    impl panoramix::elements::component::Component for Pinned {
+        type Props = &'static str;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = ();
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &Pinned::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "Pinned"
+        }
+    }
+
+    use crate::ctx::ProcessEventCtx;
+    use crate::element_tree::{Element, ElementExt, VirtualDom};
+    use crate::elements::internals::{ComponentHolder, ComponentHolderData, ComponentOutputData};
+    use crate::elements::label::LabelData;
+    use crate::elements::{Button, ComponentOutput, EmptyElement, Label, TextBox, WithId};
+    use crate::glue::{DruidAppData, GlobalEventCx, WidgetId};
+    use crate::metadata::{Metadata, NoState};
+    use crate::test_harness::Harness;
+    use crate::Tuple;
+    use druid::{HotKey, KbKey, RawMods};
     use insta::assert_debug_snapshot;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
     use test_env_log::test;
 
     #[test]
@@ -299,15 +1416,426 @@ mod tests {
         let (component_result, _state) = my_component.build(Default::default());
         assert_debug_snapshot!(component_result);
 
-        //let prev_state = (999, Default::default());
-        //let (component_result, component_state) = my_component.build(prev_state);
-        //assert_eq!(component_state.0, 999);
-
-        // TODO - local state
         // TODO - process_event
     }
 
+    #[test]
+    fn keyed_component_debug() {
+        let counter = ComponentHolder::<Counter, _, _>::new(&Counter::render, 42).key("first");
+        let (counter_data, _state) = counter.build(Default::default());
+        assert_debug_snapshot!(counter_data);
+
+        // Default state and missing keys are left out
+        let counter = ComponentHolder::<Counter, _, _>::new(&Counter::render, 0);
+        let (counter_data, _state) = counter.build(Default::default());
+        assert_debug_snapshot!(counter_data);
+    }
+
+    #[test]
+    fn initial_state() {
+        let counter = ComponentHolder::<Counter, _, _>::new(&Counter::render, 42);
+        let (counter_data, counter_state) = counter.build(Default::default());
+
+        assert_eq!(counter_data.child().child.text, "Count: 42");
+        assert_eq!(counter_state.2 .1, Some(42));
+
+        // The initial state is only used the first time a component is built
+        let counter = ComponentHolder::<Counter, _, _>::new(&Counter::render, 100);
+        let (counter_data, counter_state) = counter.build(counter_state);
+
+        assert_eq!(counter_data.child().child.text, "Count: 42");
+        assert_eq!(counter_state.2 .1, Some(42));
+    }
+
+    #[test]
+    fn mount_once() {
+        let counts = LifecycleCounts::default();
+        let tracked = || ComponentHolder::<Tracked, _, _>::new(&Tracked::render, counts.clone());
+
+        let (_, state) = tracked().build(Default::default());
+        let (_, state) = tracked().build(state);
+        let (_, _state) = tracked().build(state);
+
+        assert_eq!(counts.mount.get(), 1);
+        assert_eq!(counts.unmount.get(), 0);
+    }
+
+    #[test]
+    fn unmount_removed_component() {
+        let counts = LifecycleCounts::default();
+        let tracked = Some(ComponentHolder::<Tracked, _, _>::new(
+            &Tracked::render,
+            counts.clone(),
+        ));
+
+        Harness::run_test_window(tracked, |harness| {
+            assert_eq!(counts.mount.get(), 1);
+            assert_eq!(counts.unmount.get(), 0);
+
+            harness.update_root_element(None);
+
+            assert_eq!(counts.mount.get(), 1);
+            assert_eq!(counts.unmount.get(), 1);
+        });
+    }
+
+    #[test]
+    fn effect_runs_when_deps_change() {
+        let counts = EffectCounts::default();
+        let with_effect = |deps| {
+            ComponentHolder::<WithEffect, _, _>::new(&WithEffect::render, (deps, counts.clone()))
+        };
+
+        let (_, state) = with_effect(1).build(Default::default());
+        assert_eq!(counts.run.get(), 1);
+
+        // Rebuilding with the same deps doesn't run the effect again
+        let (_, state) = with_effect(1).build(state);
+        let (_, state) = with_effect(1).build(state);
+        assert_eq!(counts.run.get(), 1);
+        assert_eq!(counts.cleanup.get(), 0);
+
+        // The previous effect is cleaned up before the new one runs
+        let (_, state) = with_effect(2).build(state);
+        assert_eq!(counts.run.get(), 2);
+        assert_eq!(counts.cleanup.get(), 1);
+
+        let (_, _state) = with_effect(2).build(state);
+        assert_eq!(counts.run.get(), 2);
+        assert_eq!(counts.cleanup.get(), 1);
+    }
+
+    #[test]
+    fn effect_cleanup_on_unmount() {
+        let counts = EffectCounts::default();
+        let with_effect = Some(ComponentHolder::<WithEffect, _, _>::new(
+            &WithEffect::render,
+            (1, counts.clone()),
+        ));
+
+        Harness::run_test_window(with_effect, |harness| {
+            assert_eq!(counts.run.get(), 1);
+            assert_eq!(counts.cleanup.get(), 0);
+
+            harness.update_root_element(None);
+
+            assert_eq!(counts.run.get(), 1);
+            assert_eq!(counts.cleanup.get(), 1);
+        });
+    }
+
+    #[test]
+    fn event_queue_order() {
+        let md: Metadata<i32, NoState> = Default::default();
+        let (component_data, mut state) =
+            ComponentOutput::new(md, EmptyElement::new()).build(Default::default());
+        let mut widget_seq = component_data.init_tree();
+
+        // Emit several events in a single pass, like a chain of callbacks would
+        let mut ctx = ProcessEventCtx {
+            event_queue: &mut state.0,
+            state: &mut state.1,
+        };
+        ctx.event_queue(md).push_back(1);
+        ctx.event_queue(md).push_back(2);
+        ctx.event_queue(md).push_back(3);
+
+        let mut app_data = DruidAppData::default();
+        let mut cx = GlobalEventCx::new(&mut app_data);
+        let events: Vec<_> = std::iter::from_fn(|| {
+            component_data.process_local_event(&mut state, &mut widget_seq, &mut cx)
+        })
+        .collect();
+
+        assert_eq!(events, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn spawn_async_updates_state() {
+        let loader = |value| ComponentHolder::<Loader, _, _>::new(&Loader::render, value);
+        let root_text =
+            |harness: &Harness<'_, '_, _>| format!("{:?}", harness.get_root_debug_state());
+
+        Harness::run_test_window(loader(42), |harness| {
+            assert!(root_text(harness).contains("Loaded: 0"));
+
+            harness.pump_async_tasks();
+            assert!(root_text(harness).contains("Loaded: 42"));
+
+            // Changing the props spawns a new task
+            harness.update_root_element(loader(7));
+            harness.pump_async_tasks();
+            assert!(root_text(harness).contains("Loaded: 7"));
+        });
+    }
+
+    #[test]
+    fn spawn_async_cancels_previous_task() {
+        let (_, state) =
+            ComponentHolder::<Loader, _, _>::new(&Loader::render, 1).build(Default::default());
+        let first_task = state.1 .3.clone();
+
+        let (_, state) = ComponentHolder::<Loader, _, _>::new(&Loader::render, 2).build(state);
+        assert_ne!(state.1 .3, first_task);
+
+        // The output of the cancelled task is never delivered
+        let mut first_task = first_task;
+        let mut loaded = 0;
+        first_task.deliver_output(&mut loaded);
+        assert_eq!(loaded, 0);
+    }
+
+    #[test]
+    fn interval_fires_periodically() {
+        let ticks = Rc::new(Cell::new(0));
+        let ticker = || {
+            Some(ComponentHolder::<Ticker, _, _>::new(
+                &Ticker::render,
+                ticks.clone(),
+            ))
+        };
+        let root_text =
+            |harness: &Harness<'_, '_, _>| format!("{:?}", harness.get_root_debug_state());
+
+        Harness::run_test_window(ticker(), |harness| {
+            for _ in 0..5 {
+                harness.move_timers_forward(Duration::from_millis(100));
+            }
+            assert_eq!(ticks.get(), 5);
+            assert!(root_text(harness).contains("Ticks: 5"));
+
+            // Rebuilding the component keeps the same timer instead of adding another one
+            harness.update_root_element(ticker());
+            harness.update_root_element(ticker());
+            for _ in 0..3 {
+                harness.move_timers_forward(Duration::from_millis(100));
+            }
+            assert_eq!(ticks.get(), 8);
+
+            // Nothing fires once the component is unmounted
+            harness.update_root_element(None);
+            harness.move_timers_forward(Duration::from_millis(500));
+            assert_eq!(ticks.get(), 8);
+        });
+    }
+
+    #[test]
+    fn command_updates_state() {
+        let totaler = ComponentHolder::<Totaler, _, _>::new(&Totaler::render, ());
+        let root_text =
+            |harness: &Harness<'_, '_, _>| format!("{:?}", harness.get_root_debug_state());
+
+        Harness::run_test_window(totaler, |harness| {
+            assert!(root_text(harness).contains("Total: 1"));
+
+            harness.submit_command(ADD_TO_TOTAL.with(5));
+            assert!(root_text(harness).contains("Total: 6"));
+
+            // Commands with other selectors are ignored
+            harness.submit_command(druid::Selector::<u32>::new("panoramix-test.other").with(5));
+            assert!(root_text(harness).contains("Total: 6"));
+        });
+    }
+
+    #[test]
+    fn skip_unchanged_props() {
+        let calls = CallCount::default();
+        let counted =
+            |value| ComponentHolder::<Counted, _, _>::new(&Counted::render, (value, calls.clone()));
+        let text = |data: &ComponentHolderData<Counted, ComponentOutputData<_, _, LabelData>>| {
+            data.child().child.text.clone()
+        };
+
+        let (data, state) = counted(1).build(Default::default());
+        assert_eq!(calls.0.get(), 1);
+        assert_eq!(text(&data), "1 - 0");
+
+        // Equal props: the previous element tree is kept
+        let (data_1, state) = counted(1).build(state);
+        let (data_2, mut state) = counted(1).build(state);
+        assert_eq!(calls.0.get(), 1);
+        assert!(data_1.child == data.child);
+        assert!(data_2.child == data.child);
+        assert_eq!(text(&data_2), "1 - 0");
+        assert_eq!(format!("{:?}", data_2), format!("{:?}", data));
+
+        // A change to the local state, eg from a callback, bumps the generation and calls the
+        // component again
+        state.2 .1 = Some(5);
+        state.1 += 1;
+        let (data, state) = counted(1).build(state);
+        assert_eq!(calls.0.get(), 2);
+        assert_eq!(text(&data), "1 - 5");
+
+        let (data, state) = counted(2).build(state);
+        assert_eq!(calls.0.get(), 3);
+        assert_eq!(text(&data), "2 - 5");
+
+        let (_, _state) = counted(2).build(state);
+        assert_eq!(calls.0.get(), 3);
+    }
+
+    #[test]
+    fn skipped_component_widget() {
+        let calls = CallCount::default();
+        let counted =
+            |value| ComponentHolder::<Counted, _, _>::new(&Counted::render, (value, calls.clone()));
+
+        Harness::run_test_window(counted(1), |harness| {
+            assert!(harness.find_by_name("1 - 0").is_some());
+
+            // The skipped component keeps its widgets
+            harness.update_root_element(counted(1));
+            assert_eq!(calls.0.get(), 1);
+            assert!(harness.find_by_name("1 - 0").is_some());
+
+            harness.update_root_element(counted(2));
+            assert_eq!(calls.0.get(), 2);
+            assert!(harness.find_by_name("2 - 0").is_some());
+        });
+    }
+
+    #[test]
+    fn skip_until_state_changed() {
+        let calls = CallCount::default();
+        let parent =
+            || ComponentHolder::<ClickedParent, _, _>::new(&ClickedParent::render, calls.clone());
+
+        Harness::run_test_window(parent(), |harness| {
+            harness.update_root_element(parent());
+            assert_eq!(calls.0.get(), 1);
+
+            // The click changes the state of the child, so the parent isn't skipped either
+            harness.click_on("Clicks: 0");
+            assert_eq!(calls.0.get(), 2);
+            assert!(harness.find_by_name("Clicks: 1").is_some());
+
+            harness.update_root_element(parent());
+            assert_eq!(calls.0.get(), 2);
+            assert!(harness.find_by_name("Clicks: 1").is_some());
+        });
+    }
+
+    #[test]
+    fn reducer_applies_actions_in_order() {
+        let counter = ComponentHolder::<ReducedCounter, _, _>::new(&ReducedCounter::render, ());
+
+        Harness::run_test_window(counter, |harness| {
+            assert!(harness.find_by_name("Count: 1").is_some());
+
+            // (1 + 2) * 2 + 1
+            harness.click_on("Update");
+            assert!(harness.find_by_name("Count: 7").is_some());
+
+            // (7 + 2) * 2 + 1
+            harness.click_on("Update");
+            assert!(harness.find_by_name("Count: 19").is_some());
+        });
+    }
+
+    #[test]
+    fn animated_value_converges() {
+        let gauge = |target| ComponentHolder::<Gauge, _, _>::new(&Gauge::render, target);
+        let frame = Duration::from_millis(25);
+
+        Harness::run_test_window(gauge(0), |harness| {
+            assert!(harness.find_by_name("Width: 0").is_some());
+
+            harness.update_root_element(gauge(100));
+            assert!(harness.find_by_name("Width: 0").is_some());
+            harness.animate_frame(frame);
+            harness.animate_frame(frame);
+            assert!(harness.find_by_name("Width: 50").is_some());
+
+            // Retargeting starts from the current value
+            harness.update_root_element(gauge(0));
+            assert!(harness.find_by_name("Width: 50").is_some());
+            harness.animate_frame(frame);
+            harness.animate_frame(frame);
+            assert!(harness.find_by_name("Width: 25").is_some());
+
+            for _ in 0..10 {
+                harness.animate_frame(frame);
+            }
+            assert!(harness.find_by_name("Width: 0").is_some());
+        });
+    }
+
+    #[test]
+    fn hotkey_fires_regardless_of_focus() {
+        let saver = || Some(ComponentHolder::<Saver, _, _>::new(&Saver::render, ()));
+
+        Harness::run_test_window(saver(), |harness| {
+            // The text box has focus
+            harness.press_key(KbKey::Character("!".to_string()));
+            assert!(harness.find_by_name("draft!").is_some());
+
+            harness.keyboard_shortcut(RawMods::Ctrl, "s");
+            assert!(harness.find_by_name("Saves: 1").is_some());
+
+            // Modifiers must match exactly
+            harness.keyboard_shortcut(RawMods::CtrlShift, "s");
+            harness.keyboard_key("s");
+            assert!(harness.find_by_name("Saves: 1").is_some());
+
+            // The shortcut is registered again when the component is mounted again
+            harness.update_root_element(None);
+            harness.update_root_element(saver());
+            harness.keyboard_shortcut(RawMods::Ctrl, "s");
+            assert!(harness.find_by_name("Saves: 1").is_some());
+        });
+    }
+
+    #[test]
+    fn read_env() {
+        // Outside of a window, the env is empty
+        let themed = ComponentHolder::<Themed, _, _>::new(&Themed::render, ());
+        let (themed_data, _) = themed.clone().build(Default::default());
+        assert_eq!(themed_data.child().text, "No theme");
+
+        Harness::run_test_window(themed, |harness| {
+            assert!(harness.find_by_name("Text size: 15").is_some());
+        });
+    }
+
+    #[test]
+    fn clipboard_read_is_never_skipped() {
+        let viewer = || ComponentHolder::<ClipboardViewer, _, _>::new(&ClipboardViewer::render, ());
+
+        Harness::run_test_window(viewer(), |harness| {
+            harness.stub_clipboard(Some(String::from("first")));
+            harness.update_root_element(viewer());
+            assert!(harness.find_by_name("Clipboard: first").is_some());
+
+            // Same props and state, but the clipboard changed
+            harness.stub_clipboard(Some(String::from("second")));
+            harness.update_root_element(viewer());
+            assert!(harness.find_by_name("Clipboard: second").is_some());
+        });
+    }
+
+    #[test]
+    fn gen_id_is_stable() {
+        let pinned = |text| ComponentHolder::<Pinned, _, _>::new(&Pinned::render, text);
+        let take_ids = || PINNED_IDS.with(|ids| ids.take());
+
+        Harness::run_test_window(Tuple!(pinned("First"), pinned("Second")), |harness| {
+            let ids = take_ids();
+            assert_eq!(ids.len(), 2);
+            assert_ne!(ids[0], ids[1]);
+            assert!(harness.try_get_state(ids[0]).is_some());
+            assert!(harness.try_get_state(ids[1]).is_some());
+
+            // The props change, so the components are built again with the same ids
+            harness.update_root_element(Tuple!(pinned("First!"), pinned("Second!")));
+            harness.update_root_element(Tuple!(pinned("First?"), pinned("Second?")));
+            assert_eq!(take_ids(), [ids.clone(), ids.clone()].concat());
+            assert!(harness.try_get_state(ids[0]).is_some());
+            assert!(harness.try_get_state(ids[1]).is_some());
+            assert!(harness.find_by_name("Second?").is_some());
+        });
+    }
+
     // TODO
     // - Widget test
-    // - Events
 }
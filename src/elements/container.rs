@@ -0,0 +1,324 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::{BorderStyle, ContainerStyle, FlexParams};
+use crate::glue::{DruidAppData, GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{Container as ContainerWidget, ContainerContent, SingleContainerWidget};
+
+use druid::{Color, EventCtx, KeyOrValue};
+use tracing::instrument;
+
+/// Wrapper which paints a background, a border and/or rounded corners around its child.
+///
+/// Usually created with [`ElementExt::background`](crate::ElementExt::background),
+/// [`ElementExt::border`](crate::ElementExt::border) or
+/// [`ElementExt::rounded`](crate::ElementExt::rounded). These methods can be chained; each
+/// call after the first one changes the same container.
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Container<Child: Element> {
+    pub child: Child,
+    pub style: ContainerStyle,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContainerData<Child: VirtualDom> {
+    pub child: Child,
+    pub style: ContainerStyle,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Container<Child> {
+    /// Wrap the child, without any styling.
+    pub fn new(child: Child) -> Self {
+        Container {
+            child,
+            style: ContainerStyle {
+                background: None,
+                border: None,
+                corner_radius: KeyOrValue::Concrete(0.0),
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the color painted behind the child.
+    pub fn background(self, color: Color) -> Self {
+        Container {
+            style: ContainerStyle {
+                background: Some(KeyOrValue::Concrete(color)),
+                ..self.style
+            },
+            ..self
+        }
+    }
+
+    /// Paint a border of the given color and width around the child.
+    pub fn border(self, color: Color, width: f64) -> Self {
+        Container {
+            style: ContainerStyle {
+                border: Some(BorderStyle {
+                    color: KeyOrValue::Concrete(color),
+                    width: KeyOrValue::Concrete(width),
+                }),
+                ..self.style
+            },
+            ..self
+        }
+    }
+
+    /// Round the corners of the background and border.
+    pub fn rounded(self, radius: f64) -> Self {
+        Container {
+            style: ContainerStyle {
+                corner_radius: KeyOrValue::Concrete(radius),
+                ..self.style
+            },
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Container {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Container<Child> {
+    type Event = Child::Event;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = ContainerData<Child::BuildOutput>;
+
+    #[instrument(name = "Container", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, child_state) = self.child.build(prev_state);
+        (
+            ContainerData {
+                child,
+                style: self.style,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            child_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for ContainerData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleContainerWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Container", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let mut container = ContainerWidget::new(ContainerContent::new(self.child.init_tree()));
+        container.set_id(id);
+        if let Some(KeyOrValue::Key(background)) = &self.style.background {
+            container.set_background(background.clone());
+        }
+        if let Some(KeyOrValue::Concrete(background)) = &self.style.background {
+            container.set_background(background.clone());
+        }
+        if let Some(border) = self.style.border.clone() {
+            container.set_border(border.color, border.width);
+        }
+        container.set_rounded(self.style.corner_radius.clone());
+        SingleContainerWidget::new(
+            container,
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Container", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.style != prev_value.style {
+            let (style, prev_style) = (&self.style, &prev_value.style);
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |container: &mut ContainerWidget<DruidAppData, _>, ctx: &mut EventCtx| {
+                    if style.background != prev_style.background {
+                        match &style.background {
+                            Some(KeyOrValue::Key(background)) => {
+                                container.set_background(background.clone())
+                            }
+                            Some(KeyOrValue::Concrete(background)) => {
+                                container.set_background(background.clone())
+                            }
+                            None => container.clear_background(),
+                        }
+                    }
+                    if style.border != prev_style.border {
+                        match &style.border {
+                            Some(border) => {
+                                container.set_border(border.color.clone(), border.width.clone())
+                            }
+                            None => container.clear_border(),
+                        }
+                    }
+                    if style.corner_radius != prev_style.corner_radius {
+                        container.set_rounded(style.corner_radius.clone());
+                    }
+                    // The border width changes the size of the child
+                    ctx.request_layout();
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().child_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.style = other.style;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "Container",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().child_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Container", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child.process_local_event(
+            children_state,
+            &mut widget_seq.widget_mut().child_mut().children,
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::label::{Label, LabelData};
+    use crate::elements::EmptyElement;
+    use crate::test_harness::Harness;
+    use druid::Size;
+    use test_env_log::test;
+
+    #[test]
+    fn new_container() {
+        let container = Label::new("Hello")
+            .background(Color::BLACK)
+            .border(Color::WHITE, 2.0)
+            .rounded(4.0);
+        let (container_data, _) = container.build(Default::default());
+
+        assert_eq!(
+            container_data,
+            ContainerData {
+                child: LabelData::new("Hello"),
+                style: ContainerStyle {
+                    background: Some(KeyOrValue::Concrete(Color::BLACK)),
+                    border: Some(BorderStyle {
+                        color: KeyOrValue::Concrete(Color::WHITE),
+                        width: KeyOrValue::Concrete(2.0),
+                    }),
+                    corner_radius: KeyOrValue::Concrete(4.0),
+                },
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn container_debug_state() {
+        let container_id = WidgetId::reserved(1);
+        let styled = Label::new("Hello")
+            .background(Color::BLACK)
+            .border(Color::WHITE, 2.0)
+            .rounded(4.0)
+            .with_reserved_id(container_id);
+
+        Harness::run_test_window(styled, |harness| {
+            let values = harness.get_debug_state(container_id).other_values;
+            assert_eq!(values["background"], format!("{:?}", Color::BLACK));
+            assert_eq!(values["border_color"], format!("{:?}", Color::WHITE));
+            assert_eq!(values["border_width"], "2.0");
+            assert_eq!(values["corner_radius"], "4.0");
+
+            let unstyled = Label::new("Hello")
+                .background(Color::WHITE)
+                .with_reserved_id(container_id);
+            harness.update_root_element(unstyled);
+
+            let values = harness.get_debug_state(container_id).other_values;
+            assert_eq!(values["background"], format!("{:?}", Color::WHITE));
+            assert!(!values.contains_key("border_color"));
+            assert!(!values.contains_key("corner_radius"));
+        });
+    }
+
+    #[test]
+    fn empty_container() {
+        let container_id = WidgetId::reserved(1);
+        let colored_area = EmptyElement::new()
+            .background(Color::BLACK)
+            .with_reserved_id(container_id)
+            .fix_size(50.0, 30.0);
+
+        Harness::run_test_window(colored_area, |harness| {
+            let rect = harness.get_state(container_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(50.0, 30.0));
+
+            let values = harness.get_debug_state(container_id).other_values;
+            assert_eq!(values["background"], format!("{:?}", Color::BLACK));
+        });
+    }
+}
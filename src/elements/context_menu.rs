@@ -0,0 +1,353 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{ContextMenuWidget, SingleContextMenuWidget};
+
+use derivative::Derivative;
+use druid::{EventCtx, Point, RawMods};
+use std::fmt::Debug;
+use tracing::{instrument, trace};
+
+/// An entry of a context menu; see [`ElementExt::context_menu`](crate::ElementExt::context_menu).
+///
+/// Also used for the items of a [`Menu`](crate::elements::Menu) in the application menu.
+///
+/// Selecting the item makes the component emit `event`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuItem<Event> {
+    pub text: String,
+    pub event: Event,
+    pub enabled: bool,
+    /// The keyboard accelerator of the item. Only used in the application menu.
+    pub hotkey: Option<(RawMods, String)>,
+}
+
+/// Opens a menu when the child element is right-clicked.
+///
+/// The menu is open while the second member of its state holds the position of the right
+/// click. Selecting an item emits its event from the component; pressing the mouse outside
+/// of the menu closes it without emitting anything.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct ContextMenu<
+    ComponentEvent: Clone + Debug + 'static,
+    ComponentState: 'static,
+    Child: Element,
+> {
+    pub element: Child,
+    pub items: Vec<MenuItem<ComponentEvent>>,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct ContextMenuData<
+    ComponentEvent: Clone + Debug + 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+> {
+    element: Child,
+    items: Vec<MenuItem<ComponentEvent>>,
+    menu_pos: Option<Point>,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+//
+// --- IMPLS
+
+impl<Event> MenuItem<Event> {
+    /// Build an enabled menu item.
+    pub fn new(text: impl Into<String>, event: Event) -> Self {
+        MenuItem {
+            text: text.into(),
+            event,
+            enabled: true,
+            hotkey: None,
+        }
+    }
+
+    /// Make the item greyed out. Disabled items can't be selected.
+    pub fn disabled(self) -> Self {
+        MenuItem {
+            enabled: false,
+            ..self
+        }
+    }
+
+    /// Set the keyboard accelerator of the item, eg `with_hotkey(RawMods::Ctrl, "q")`.
+    ///
+    /// Only items of the application menu have accelerators; the shortcut is ignored in
+    /// context menus.
+    pub fn with_hotkey(self, mods: RawMods, key: impl Into<String>) -> Self {
+        MenuItem {
+            hotkey: Some((mods, key.into())),
+            ..self
+        }
+    }
+}
+
+impl<Event, Text: Into<String>> From<(Text, Event)> for MenuItem<Event> {
+    fn from((text, event): (Text, Event)) -> Self {
+        MenuItem::new(text, event)
+    }
+}
+
+impl<ComponentEvent: Clone + Debug + 'static, ComponentState: 'static, Child: VirtualDom>
+    ContextMenuData<ComponentEvent, ComponentState, Child>
+{
+    fn widget_items(&self) -> Vec<(String, bool)> {
+        self.items
+            .iter()
+            .map(|item| (item.text.clone(), item.enabled))
+            .collect()
+    }
+}
+
+impl<ComponentEvent: Clone + Debug + 'static, ComponentState: 'static, Child: Element> Element
+    for ContextMenu<ComponentEvent, ComponentState, Child>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The position of the open menu, if any
+    type AggregateChildrenState = (Child::AggregateChildrenState, Option<Point>);
+    type BuildOutput = ContextMenuData<ComponentEvent, ComponentState, Child::BuildOutput>;
+
+    #[instrument(name = "ContextMenu", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child_prev_state, menu_pos) = prev_state;
+        let (element, state) = self.element.build(child_prev_state);
+        (
+            ContextMenuData {
+                element,
+                items: self.items,
+                menu_pos,
+                _metadata: Default::default(),
+            },
+            (state, menu_pos),
+        )
+    }
+}
+
+impl<ComponentEvent: Clone + Debug + 'static, ComponentState: 'static, Child: VirtualDom> VirtualDom
+    for ContextMenuData<ComponentEvent, ComponentState, Child>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (Child::AggregateChildrenState, Option<Point>);
+    type TargetWidgetSeq = SingleContextMenuWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "ContextMenu", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleContextMenuWidget::new(
+            ContextMenuWidget::new(
+                self.element.init_tree(),
+                self.widget_items(),
+                self.menu_pos,
+                WidgetId::next(),
+            ),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "ContextMenu", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+
+        let items = self.widget_items();
+        if items != prev_value.widget_items() {
+            let mut items = Some(items);
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ContextMenuWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_items(items.take().unwrap_or_default(), ctx);
+                },
+            );
+        }
+        if self.menu_pos != prev_value.menu_pos {
+            let menu_pos = self.menu_pos;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ContextMenuWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_menu_pos(menu_pos, ctx);
+                },
+            );
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.items = other.items;
+        self.menu_pos = other.menu_pos;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "ContextMenu",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let (child_state, menu_pos) = children_state;
+        self.element.process_event(
+            comp_ctx,
+            child_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        match cx.app_data.dequeue_action(id) {
+            Some(Action::RightClicked(pos)) => {
+                trace!("Opening context menu at {}", pos);
+                *menu_pos = Some(pos);
+            }
+            Some(Action::Selected(index)) => {
+                *menu_pos = None;
+                if let Some(item) = self.items.get(index).filter(|item| item.enabled) {
+                    trace!("Processing selection of menu item {:?}", item.text);
+                    comp_ctx.event_queue(md).push_back(item.event.clone());
+                }
+            }
+            Some(Action::Clicked) => {
+                trace!("Closing context menu");
+                *menu_pos = None;
+            }
+            _ => {}
+        }
+    }
+
+    #[instrument(name = "ContextMenu", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element.process_local_event(
+            &mut children_state.0,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{ComponentOutput, Label};
+    use crate::test_harness::Harness;
+    use crate::widgets::CONTEXT_MENU_ITEM_HEIGHT;
+    use druid::{MouseButton, Vec2};
+    use test_env_log::test;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum EditAction {
+        Copy,
+        Paste,
+        Delete,
+    }
+
+    #[test]
+    fn new_menu_item() {
+        let item: MenuItem<_> = ("Copy", EditAction::Copy).into();
+        assert_eq!(item, MenuItem::new("Copy", EditAction::Copy));
+        assert!(item.enabled);
+        assert!(!item.disabled().enabled);
+    }
+
+    #[test]
+    fn context_menu_select() {
+        let md: Metadata<EditAction, ()> = Default::default();
+        let tree = ComponentOutput::new(
+            md,
+            Label::new("Document").context_menu(
+                md,
+                vec![
+                    MenuItem::new("Copy", EditAction::Copy),
+                    MenuItem::new("Paste", EditAction::Paste).disabled(),
+                    MenuItem::new("Delete", EditAction::Delete),
+                ],
+            ),
+        );
+        let menu_is_open = |harness: &Harness<'_, '_, _>| {
+            format!("{:?}", harness.get_root_debug_state()).contains("Copy, Paste, Delete")
+        };
+
+        Harness::run_test_window(tree, |harness| {
+            let click_pos = harness
+                .find_by_name("Document")
+                .unwrap()
+                .layout_rect()
+                .center();
+            let item_pos =
+                |index: f64| click_pos + Vec2::new(10.0, (index + 0.5) * CONTEXT_MENU_ITEM_HEIGHT);
+            let click = |harness: &mut Harness<'_, '_, _>, pos, button| {
+                harness.mouse_move(pos);
+                harness.mouse_button_press(button);
+                harness.mouse_button_release(button);
+            };
+            assert!(!menu_is_open(harness));
+
+            click(harness, click_pos, MouseButton::Right);
+            assert!(menu_is_open(harness));
+
+            // Clicking outside of the menu closes it without emitting anything
+            click(
+                harness,
+                click_pos + Vec2::new(300.0, 300.0),
+                MouseButton::Left,
+            );
+            assert!(!menu_is_open(harness));
+            assert_eq!(
+                harness.take_emitted_events::<EditAction>(),
+                Vec::<EditAction>::new()
+            );
+
+            // Disabled items can't be selected
+            click(harness, click_pos, MouseButton::Right);
+            click(harness, item_pos(1.0), MouseButton::Left);
+            assert!(menu_is_open(harness));
+            assert_eq!(
+                harness.take_emitted_events::<EditAction>(),
+                Vec::<EditAction>::new()
+            );
+
+            click(harness, item_pos(2.0), MouseButton::Left);
+            assert!(!menu_is_open(harness));
+            assert_eq!(
+                harness.take_emitted_events::<EditAction>(),
+                vec![EditAction::Delete]
+            );
+        });
+    }
+}
@@ -0,0 +1,244 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{DebounceWidget, SingleDebounceWidget};
+
+use druid::EventCtx;
+use std::time::{Duration, Instant};
+use tracing::{instrument, trace};
+
+/// Wrapper which holds back the events of its child until they stop firing for a given delay.
+///
+/// Each new event replaces the pending one and restarts the delay. When the delay runs out,
+/// the last event is emitted. This is useful for eg search-as-you-type text boxes.
+///
+/// Usually created with [`ElementExt::debounce`](crate::ElementExt::debounce).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element, delayed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Debounce<Child: Element> {
+    pub child: Child,
+    pub delay: Duration,
+}
+
+/// Virtual DOM node of [`Debounce`].
+///
+/// `deadline` is the time at which the pending event will be emitted, if there is one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DebounceData<Child: VirtualDom> {
+    pub child: Child,
+    pub delay: Duration,
+    pub deadline: Option<Instant>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Debounce<Child> {
+    pub fn new(child: Child, delay: Duration) -> Self {
+        Debounce { child, delay }
+    }
+}
+
+impl<Child: Element> Element for Debounce<Child>
+where
+    Child::Event: Clone + PartialEq,
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The pending event, and the time at which it will be emitted
+    type AggregateChildrenState = (
+        Option<Child::Event>,
+        Option<Instant>,
+        Child::AggregateChildrenState,
+    );
+    type BuildOutput = DebounceData<Child::BuildOutput>;
+
+    #[instrument(name = "Debounce", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (pending_event, deadline, child_prev_state) = prev_state;
+        let (child, child_state) = self.child.build(child_prev_state);
+        (
+            DebounceData {
+                child,
+                delay: self.delay,
+                deadline,
+            },
+            (pending_event, deadline, child_state),
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for DebounceData<Child>
+where
+    Child::Event: Clone + PartialEq,
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (
+        Option<Child::Event>,
+        Option<Instant>,
+        Child::AggregateChildrenState,
+    );
+    type TargetWidgetSeq = SingleDebounceWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Debounce", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleDebounceWidget::new(
+            DebounceWidget::new(self.child.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Debounce", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+
+        if self.deadline != prev_value.deadline {
+            match self.deadline {
+                Some(deadline) => {
+                    let delay = deadline.saturating_duration_since(Instant::now());
+                    trace!("Restarting timer: {:?}", delay);
+                    widget_seq.pod.with_event_context(
+                        ctx.event_ctx,
+                        |widget: &mut DebounceWidget<_>, ctx: &mut EventCtx| {
+                            widget.restart_timer(delay, ctx);
+                        },
+                    );
+                }
+                None => widget_seq.widget_mut().cancel_timer(),
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.delay = other.delay;
+        self.deadline = other.deadline;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "Debounce",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            &mut children_state.2,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Debounce", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let (pending_event, deadline, child_state) = children_state;
+
+        let event =
+            self.child
+                .process_local_event(child_state, &mut widget_seq.widget_mut().children, cx);
+        if let Some(event) = event {
+            // The new deadline is picked up by reconcile, which restarts the timer
+            trace!("Holding back event: {:?}", event);
+            *pending_event = Some(event);
+            *deadline = Some(Instant::now() + self.delay);
+            return None;
+        }
+
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::TimerFired) = cx.app_data.dequeue_action(id) {
+            trace!("Delay elapsed, emitting pending event");
+            *deadline = None;
+            return pending_event.take();
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::button::{Button, ButtonClick};
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_debounce() {
+        let debounce = Button::new("Hello").debounce(Duration::from_millis(300));
+        let (debounce_data, state) = debounce.build(Default::default());
+
+        assert_eq!(debounce_data.delay, Duration::from_millis(300));
+        assert_eq!(debounce_data.deadline, None);
+        assert_eq!(state.0, None);
+    }
+
+    #[test]
+    fn debounce_events() {
+        let (event_sender, event_receiver) = channel();
+        let button_id = WidgetId::reserved(1);
+        // The button's layout rect is relative to the debounce widget; we make the button fill
+        // the width of the test window so that its rect matches window coordinates.
+        let button = EventLogger::new(
+            event_sender,
+            Button::new("Hello")
+                .with_reserved_id(button_id)
+                .fix_width(400.0)
+                .debounce(Duration::from_millis(300)),
+        );
+
+        Harness::run_test_window(button, |harness| {
+            harness.mouse_click_on(button_id);
+            harness.mouse_click_on(button_id);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            // Each click restarts the delay
+            harness.move_timers_forward(Duration::from_millis(200));
+            harness.mouse_click_on(button_id);
+            harness.move_timers_forward(Duration::from_millis(200));
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            // Only the last click is emitted
+            harness.move_timers_forward(Duration::from_millis(200));
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
+}
@@ -0,0 +1,213 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::{Axis, FlexParams};
+use crate::glue::WidgetId;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{DividerWidget, SingleDividerWidget};
+
+use druid::{Color, EventCtx};
+use tracing::instrument;
+
+/// A thin line, to separate sections of a [Flex](crate::elements::Flex) container.
+///
+/// A horizontal divider spans the width of its container, and a vertical divider spans its
+/// height.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Divider {
+    pub axis: Axis,
+    pub color: Option<Color>,
+    pub thickness: f64,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DividerData {
+    pub axis: Axis,
+    pub color: Option<Color>,
+    pub thickness: f64,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl Divider {
+    /// Build a horizontal divider, one pixel thick, in the theme's border color.
+    pub fn horizontal() -> Self {
+        Divider::new(Axis::Horizontal)
+    }
+
+    /// Build a vertical divider, one pixel thick, in the theme's border color.
+    pub fn vertical() -> Self {
+        Divider::new(Axis::Vertical)
+    }
+
+    fn new(axis: Axis) -> Self {
+        Divider {
+            axis,
+            color: None,
+            thickness: 1.0,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the color of the line.
+    pub fn with_color(self, color: Color) -> Self {
+        Divider {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the thickness of the line, in pixels.
+    pub fn with_thickness(self, thickness: f64) -> Self {
+        Divider { thickness, ..self }
+    }
+
+    /// Change the way the divider's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Divider {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The divider created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same divider is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Divider {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl Element for Divider {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = DividerData;
+
+    #[instrument(name = "Divider", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (DividerData, ()) {
+        (
+            DividerData {
+                axis: self.axis,
+                color: self.color,
+                thickness: self.thickness,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for DividerData {
+    type Event = NoEvent;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleDividerWidget;
+
+    #[instrument(name = "Divider", skip(self))]
+    fn init_tree(&self) -> SingleDividerWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleDividerWidget::new(
+            DividerWidget::new(self.axis, self.color.clone(), self.thickness, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Divider", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleDividerWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+        if self.axis != prev_value.axis || self.thickness != prev_value.thickness {
+            widget.widget_mut().axis = self.axis;
+            widget.widget_mut().thickness = self.thickness;
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+        if self.color != prev_value.color {
+            widget.widget_mut().color = self.color.clone();
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_paint();
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use insta::assert_debug_snapshot;
+    use test_env_log::test;
+
+    #[test]
+    fn new_divider() {
+        let divider = Divider::vertical()
+            .with_color(Color::BLACK)
+            .with_thickness(2.0);
+        let (divider_data, ()) = divider.build(());
+
+        assert_eq!(
+            divider_data,
+            DividerData {
+                axis: Axis::Vertical,
+                color: Some(Color::BLACK),
+                thickness: 2.0,
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn divider_between_labels() {
+        let divider_id = WidgetId::reserved(1);
+        let column = Column!(
+            Label::new("Above"),
+            Divider::horizontal()
+                .with_thickness(2.0)
+                .with_reserved_id(divider_id),
+            Label::new("Below"),
+        );
+
+        Harness::run_test_window(column, |harness| {
+            let column_state = harness.get_root_debug_state();
+            assert_debug_snapshot!(column_state);
+
+            let rect = harness.get_state(divider_id).layout_rect();
+            assert_eq!(rect.height(), 2.0);
+        });
+    }
+}
@@ -0,0 +1,407 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{
+    DragSourceWidget, DropTargetWidget, SingleDragSourceWidget, SingleDropTargetWidget,
+};
+
+use derivative::Derivative;
+use std::any::TypeId;
+use std::fmt::Debug;
+use std::rc::Rc;
+use tracing::{instrument, trace};
+
+/// Makes the child element a drag source, carrying the given payload.
+///
+/// A drag starts when the mouse is moved a few pixels with the left button pressed over the
+/// child. Until the button is released, the child is dimmed, and the payload can be dropped
+/// on any [`DropTarget`] accepting its type.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Payload: Clone"), Debug(bound = "Payload: Debug"))]
+pub struct Draggable<ComponentEvent: 'static, ComponentState: 'static, Child: Element, Payload> {
+    pub element: Child,
+    pub payload: Payload,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when a payload of type `Payload` is dropped on the child element.
+///
+/// The child is highlighted while a payload of that type is dragged over it. Drags of other
+/// payload types are ignored.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct DropTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Payload: 'static,
+    Cb: Clone + Fn(&mut ComponentState, Payload),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(Debug = "ignore")]
+    pub _payload: std::marker::PhantomData<Payload>,
+}
+
+#[derive(Derivative)]
+#[derivative(
+    Clone(bound = "Child: Clone, Payload: Clone"),
+    Debug(bound = "Payload: Debug")
+)]
+pub struct DraggableData<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Payload,
+> {
+    element: Child,
+    payload: Payload,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct DropTargetData<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Payload: 'static,
+    Cb: Clone + Fn(&mut ComponentState, Payload),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(Debug = "ignore")]
+    _payload: std::marker::PhantomData<Payload>,
+}
+
+//
+// --- IMPLS
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Payload: Clone + Debug + 'static,
+    > Element for Draggable<ComponentEvent, ComponentState, Child, Payload>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = DraggableData<ComponentEvent, ComponentState, Child::BuildOutput, Payload>;
+
+    #[instrument(name = "Draggable", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            DraggableData {
+                element,
+                payload: self.payload,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Payload: Clone + Debug + 'static,
+    > VirtualDom for DraggableData<ComponentEvent, ComponentState, Child, Payload>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleDragSourceWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Draggable", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleDragSourceWidget::new(
+            DragSourceWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Draggable", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.payload = other.payload;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "Draggable",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        // The payload is shared through the app data, so that any drop target can read it
+        let id = widget_seq.widget().id();
+        if let Some(Action::DragStarted) = cx.app_data.dequeue_action(id) {
+            trace!("Starting drag with payload {:?}", self.payload);
+            cx.app_data.start_drag(Rc::new(self.payload.clone()));
+        }
+    }
+
+    #[instrument(name = "Draggable", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Payload: Clone + 'static,
+        Cb: Clone + Fn(&mut ComponentState, Payload) + 'static,
+    > Element for DropTarget<ComponentEvent, ComponentState, Child, Payload, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput =
+        DropTargetData<ComponentEvent, ComponentState, Child::BuildOutput, Payload, Cb>;
+
+    #[instrument(name = "DropTarget", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            DropTargetData {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+                _payload: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Payload: Clone + 'static,
+        Cb: Clone + Fn(&mut ComponentState, Payload),
+    > VirtualDom for DropTargetData<ComponentEvent, ComponentState, Child, Payload, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleDropTargetWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "DropTarget", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleDropTargetWidget::new(
+            DropTargetWidget::new(
+                self.element.init_tree(),
+                TypeId::of::<Payload>(),
+                WidgetId::next(),
+            ),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "DropTarget", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "DropTarget",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::Dropped) = cx.app_data.dequeue_action(id) {
+            // The widget only queues drops of the accepted type, but the drag may have been
+            // replaced since
+            let payload = cx
+                .app_data
+                .drag_payload()
+                .and_then(|payload| payload.downcast_ref::<Payload>())
+                .cloned();
+            if let Some(payload) = payload {
+                trace!("Processing callback for drop event");
+                (self.callback)(comp_ctx.state(md), payload);
+            }
+        }
+    }
+
+    #[instrument(name = "DropTarget", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::Label;
+    use crate::metadata::NoEvent;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::MouseButton;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Card(u32);
+
+    #[test]
+    fn drag_and_drop() {
+        let (drop_sender, drop_receiver) = channel();
+        let (other_sender, other_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+
+        let screen = Column!(
+            Label::new("Source").draggable(md, Card(42)),
+            Label::new("Nowhere"),
+            Label::new("Target").drop_target(md, move |_state, card: Card| {
+                drop_sender.send(card).unwrap();
+            }),
+            Label::new("Other").drop_target(md, move |_state, text: String| {
+                other_sender.send(text).unwrap();
+            }),
+        );
+
+        Harness::run_test_window(screen, |harness| {
+            let source_rect = harness.find_by_name("Source").unwrap().layout_rect();
+            let nowhere_rect = harness.find_by_name("Nowhere").unwrap().layout_rect();
+            let target_rect = harness.find_by_name("Target").unwrap().layout_rect();
+            let other_rect = harness.find_by_name("Other").unwrap().layout_rect();
+
+            // A click on the target without a drag doesn't drop anything
+            harness.mouse_move(target_rect.center());
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert!(drop_receiver.try_recv().is_err());
+
+            // Released outside of a target
+            harness.mouse_move(source_rect.center());
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(nowhere_rect.center());
+            harness.mouse_button_release(MouseButton::Left);
+            assert!(drop_receiver.try_recv().is_err());
+
+            // Released over a target accepting another payload type
+            harness.mouse_move(source_rect.center());
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(other_rect.center());
+            harness.mouse_button_release(MouseButton::Left);
+            assert!(other_receiver.try_recv().is_err());
+
+            harness.mouse_move(source_rect.center());
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(target_rect.center());
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(drop_receiver.try_recv(), Ok(Card(42)));
+
+            // The drag ended with the drop
+            harness.mouse_move(target_rect.center());
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert!(drop_receiver.try_recv().is_err());
+        });
+    }
+}
@@ -1,13 +1,12 @@
 use crate::ctx::{ProcessEventCtx, ReconcileCtx};
 use crate::element_tree::{Element, VirtualDom};
-use crate::elements::compute_diff::compute_diff;
 use crate::glue::GlobalEventCx;
 use crate::metadata::{NoEvent, NoState};
+use crate::widget_sequence::sequence_layout_rect;
 use crate::widgets::WidgetList;
 
 use derivative::Derivative;
-use either::{Left, Right};
-use std::collections::VecDeque;
+use druid::Rect;
 use tracing::{debug_span, info, instrument};
 
 // TODO - Add arbitrary index types
@@ -91,32 +90,20 @@ impl<Child: Element> Element for ElementList<Child> {
         // FIXE - Handle duplicate keys
         // TODO - Add special case when Child::AggregateChildrenState.sizeof() == 0
 
-        let mutation = compute_diff(&prev_state, &self.children);
-        let mut prev_state_or_default = prev_state;
-
-        // TODO - reserve
-        // TODO - O(N * M) for N the list size and M the mutation count
-        // Use better algo
-        let mut index_diff = 0_isize;
-        for mutation_item in &mutation.items {
-            let index = (mutation_item.index as isize + index_diff) as usize;
-            let range = index..(index + mutation_item.removed_count);
-
-            // Calling .last() runs the entire iterator, which performs the splice
-            let _ = prev_state_or_default
-                .splice(
-                    range,
-                    mutation_item
-                        .inserted_keys
-                        .iter()
-                        .cloned()
-                        .map(|key| (key, Default::default())),
-                )
-                .last();
-
-            index_diff += mutation_item.inserted_keys.len() as isize;
-            index_diff -= mutation_item.removed_count as isize;
-        }
+        // Children are matched by key, so that moved children keep their state
+        // TODO - O(N * M) for N the list size and M the previous list size
+        let mut prev_state = prev_state;
+        let prev_state_or_default: Vec<_> = self
+            .children
+            .iter()
+            .map(|(key, _item)| {
+                let prev_index = prev_state.iter().position(|(prev_key, _)| prev_key == key);
+                match prev_index {
+                    Some(prev_index) => prev_state.swap_remove(prev_index),
+                    None => (key.clone(), Default::default()),
+                }
+            })
+            .collect();
 
         let (children, new_state): (Vec<_>, Vec<_>) = self
             .children
@@ -161,79 +148,92 @@ impl<Child: VirtualDom> VirtualDom for ElementListData<Child> {
         widget_seq: &mut Self::TargetWidgetSeq,
         ctx: &mut ReconcileCtx,
     ) {
-        let mutation = compute_diff(&prev_value.children, &self.children);
-
-        let mut prev_data: Vec<_> = prev_value
+        // Children are matched by key, so that moved children keep their widgets
+        // TODO - O(N * M) for N the list size and M the previous list size
+        let mut prev_children: Vec<_> = prev_value
             .children
             .iter()
-            .zip(widget_seq.children.iter_mut())
-            .map(Left)
+            .zip(std::mem::take(&mut widget_seq.children))
             .collect();
 
-        // TODO - reserve
-        // TODO - O(N * M) for N the list size and M the mutation count
-        // Use better algo
-        // TODO - simplify
-        let mut index_diff = 0_isize;
-        for mutation_item in &mutation.items {
-            let index = (mutation_item.index as isize + index_diff) as usize;
-            let spliced_range = index..(index + mutation_item.removed_count);
-
-            let new_range = index..(index + mutation_item.inserted_keys.len());
-
-            // Calling .last() runs the entire iterator, which performs the splice
-            let _ = prev_data
-                .splice(spliced_range, self.children[new_range].iter().map(Right))
-                .last();
-
-            index_diff += mutation_item.inserted_keys.len() as isize;
-            index_diff -= mutation_item.removed_count as isize;
-        }
+        prev_children.retain(|((prev_key, prev_child), _widget_seq)| {
+            let is_kept = self.children.iter().any(|(key, _)| key == prev_key);
+            if !is_kept {
+                info!("removing child {}", prev_key);
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+            }
+            is_kept
+        });
 
-        let mut widgets_to_insert = VecDeque::new();
-        for item in self.children.iter().zip(prev_data) {
-            let (_key, child_data) = item.0;
-            let child_prev_data = item.1;
-            match child_prev_data {
-                Left(prev_data) => {
-                    let ((_key, child_prev_data), child_widget_seq) = prev_data;
-                    child_data.reconcile(child_prev_data, child_widget_seq, ctx);
-                }
-                Right(new_data) => {
-                    let (_key, child_data) = new_data;
-                    let new_widget_seq = debug_span!("init_tree").in_scope(|| {
-                        info!("creating child");
-                        child_data.init_tree()
-                    });
-                    widgets_to_insert.push_back(new_widget_seq);
+        widget_seq.children = self
+            .children
+            .iter()
+            .map(|(key, child_data)| {
+                let prev_index = prev_children
+                    .iter()
+                    .position(|((prev_key, _), _)| prev_key == key);
+                match prev_index {
+                    Some(prev_index) => {
+                        let ((_key, child_prev_data), mut child_widget_seq) =
+                            prev_children.swap_remove(prev_index);
+                        child_data.reconcile(child_prev_data, &mut child_widget_seq, ctx);
+                        ctx.stats.updated += 1;
+                        child_widget_seq
+                    }
+                    None => {
+                        ctx.stats.created += 1;
+                        debug_span!("init_tree").in_scope(|| {
+                            info!("creating child {}", key);
+                            child_data.init_tree()
+                        })
+                    }
                 }
-            }
-        }
+            })
+            .collect();
+    }
 
-        // TODO - reserve
+    fn update_value(&mut self, other: Self) {
         // TODO - O(N * M) for N the list size and M the mutation count
-        // Use better algo
-        // TODO - simplify
-        let mut index_diff = 0_isize;
-        for mutation_item in &mutation.items {
-            let index = (mutation_item.index as isize + index_diff) as usize;
-            let spliced_range = index..(index + mutation_item.removed_count);
-
-            // Calling .last() runs the entire iterator, which performs the splice
-            //.insert(index, new_widget_seq);
-            let _ = widget_seq
-                .children
-                .splice(
-                    spliced_range,
-                    widgets_to_insert.drain(0..mutation_item.inserted_keys.len()),
-                )
-                .last();
-
-            index_diff += mutation_item.inserted_keys.len() as isize;
-            index_diff -= mutation_item.removed_count as isize;
+        let mut prev_children = std::mem::take(&mut self.children);
+        self.children = other
+            .children
+            .into_iter()
+            .map(|(key, child)| {
+                let prev_index = prev_children
+                    .iter()
+                    .position(|(prev_key, _)| *prev_key == key);
+                match prev_index {
+                    Some(prev_index) => {
+                        let (_, mut prev_child) = prev_children.swap_remove(prev_index);
+                        prev_child.update_value(child);
+                        (key, prev_child)
+                    }
+                    None => (key, child),
+                }
+            })
+            .collect();
+    }
+
+    fn unmount(&self) {
+        for (_key, child) in &self.children {
+            child.unmount();
         }
     }
 
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        self.children
+            .iter()
+            .zip(widget_seq.children.iter_mut())
+            .find_map(|((child_key, child), child_widget_seq)| {
+                if child_key == key {
+                    sequence_layout_rect(child_widget_seq)
+                } else {
+                    child.find_key_rect(key, child_widget_seq)
+                }
+            })
+    }
+
     #[instrument(name = "List", skip(self, comp_ctx, children_state, widget_seq, cx))]
     fn process_event(
         &self,
@@ -362,4 +362,37 @@ mod tests {
             assert_debug_snapshot!(empty_list_state);
         });
     }
+
+    #[test]
+    fn reorder_list_widget() {
+        use crate::ctx::ReconcileStats;
+        use crate::test_harness::Harness;
+        let list = new_label_list(&["aaa", "bbb", "ccc"]);
+
+        Harness::run_test_window(list, |harness| {
+            // Moved children keep their widgets
+            harness.update_root_element(new_label_list(&["ccc", "aaa", "bbb"]));
+            assert_eq!(
+                harness.last_reconcile_stats(),
+                ReconcileStats {
+                    created: 0,
+                    updated: 3,
+                    removed: 0,
+                }
+            );
+            let mut top = |name| harness.find_by_name(name).unwrap().layout_rect().y0;
+            assert!(top("ccc") < top("aaa"));
+            assert!(top("aaa") < top("bbb"));
+
+            harness.update_root_element(new_label_list(&["bbb", "ddd"]));
+            assert_eq!(
+                harness.last_reconcile_stats(),
+                ReconcileStats {
+                    created: 1,
+                    updated: 1,
+                    removed: 2,
+                }
+            );
+        });
+    }
 }
@@ -51,21 +51,38 @@ impl<Child: VirtualDom> VirtualDom for Option<Child> {
         match (self, prev_value) {
             (Some(child), Some(prev_child)) => {
                 child.reconcile(prev_child, &mut widget_seq.as_mut().unwrap_or_log(), ctx);
+                ctx.stats.updated += 1;
             }
             (Some(child), None) => {
+                ctx.stats.created += 1;
                 debug_span!("init_tree").in_scope(|| {
                     info!("creating child");
                     *widget_seq = Some(child.init_tree());
                 });
             }
-            (None, Some(_prev_child)) => {
+            (None, Some(prev_child)) => {
                 info!("removing child");
+                prev_child.unmount();
+                ctx.stats.removed += 1;
                 *widget_seq = None;
             }
             (None, None) => {}
         }
     }
 
+    fn update_value(&mut self, other: Self) {
+        match (self, other) {
+            (Some(child), Some(other_child)) => child.update_value(other_child),
+            (this, other) => *this = other,
+        }
+    }
+
+    fn unmount(&self) {
+        if let Some(child) = self {
+            child.unmount();
+        }
+    }
+
     #[instrument(name = "Option", skip(self, comp_ctx, children_state, widget_seq, cx))]
     fn process_event(
         &self,
@@ -142,6 +159,7 @@ impl<ChildLeft: VirtualDom, ChildRight: VirtualDom> VirtualDom for Either<ChildL
                     "The previous value of this element was Left. Expected Left widget.",
                 );
                 child.reconcile(prev_child, widget_seq, ctx);
+                ctx.stats.updated += 1;
             }
             (Right(child), Right(prev_child)) => {
                 // TODO - Add more detailed log
@@ -149,15 +167,22 @@ impl<ChildLeft: VirtualDom, ChildRight: VirtualDom> VirtualDom for Either<ChildL
                     "The previous value of this element was Right. Expected Right widget.",
                 );
                 child.reconcile(prev_child, widget_seq, ctx);
+                ctx.stats.updated += 1;
             }
 
-            (Left(child), Right(_prev_child)) => {
+            (Left(child), Right(prev_child)) => {
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
                 debug_span!("init_tree").in_scope(|| {
                     info!("creating child");
                     *widget_seq = Left(child.init_tree());
                 });
             }
-            (Right(child), Left(_prev_child)) => {
+            (Right(child), Left(prev_child)) => {
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
                 debug_span!("init_tree").in_scope(|| {
                     info!("creating child");
                     *widget_seq = Right(child.init_tree());
@@ -166,6 +191,21 @@ impl<ChildLeft: VirtualDom, ChildRight: VirtualDom> VirtualDom for Either<ChildL
         }
     }
 
+    fn update_value(&mut self, other: Self) {
+        match (self, other) {
+            (Left(child), Left(other_child)) => child.update_value(other_child),
+            (Right(child), Right(other_child)) => child.update_value(other_child),
+            (this, other) => *this = other,
+        }
+    }
+
+    fn unmount(&self) {
+        match self {
+            Left(child) => child.unmount(),
+            Right(child) => child.unmount(),
+        }
+    }
+
     #[instrument(name = "Either", skip(self, comp_ctx, children_state, widget_seq, cx))]
     fn process_event(
         &self,
@@ -201,12 +241,125 @@ impl<ChildLeft: VirtualDom, ChildRight: VirtualDom> VirtualDom for Either<ChildL
     }
 }
 
+// ----
+
+/// Element which displays one of two elements of different types, and emits the events of
+/// the displayed one.
+///
+/// An [`Either`] can be used as an element, but since its branches may have different event
+/// types, it doesn't emit events. When both branches have the same event type, wrap the
+/// `Either` in a `Branch` to get their events. Like with `Either`, the widgets of the previous
+/// branch are replaced when the branch changes, and reconciled in place otherwise.
+///
+/// ## Events
+///
+/// Emits the events of the displayed branch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Branch<ChildLeft, ChildRight>(pub Either<ChildLeft, ChildRight>);
+
+impl<ChildLeft, ChildRight> Branch<ChildLeft, ChildRight> {
+    /// Display the left branch.
+    pub fn left(child: ChildLeft) -> Self {
+        Branch(Left(child))
+    }
+
+    /// Display the right branch.
+    pub fn right(child: ChildRight) -> Self {
+        Branch(Right(child))
+    }
+}
+
+impl<ChildLeft, ChildRight> From<Either<ChildLeft, ChildRight>> for Branch<ChildLeft, ChildRight> {
+    fn from(either: Either<ChildLeft, ChildRight>) -> Self {
+        Branch(either)
+    }
+}
+
+impl<ChildLeft: Element, ChildRight: Element<Event = ChildLeft::Event>> Element
+    for Branch<ChildLeft, ChildRight>
+{
+    type Event = ChildLeft::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState =
+        Option<Either<ChildLeft::AggregateChildrenState, ChildRight::AggregateChildrenState>>;
+    type BuildOutput = Branch<ChildLeft::BuildOutput, ChildRight::BuildOutput>;
+
+    #[instrument(name = "Branch", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (output, state) = self.0.build(prev_state);
+        (Branch(output), state)
+    }
+}
+
+impl<ChildLeft: VirtualDom, ChildRight: VirtualDom<Event = ChildLeft::Event>> VirtualDom
+    for Branch<ChildLeft, ChildRight>
+{
+    type Event = ChildLeft::Event;
+    type AggregateChildrenState =
+        Option<Either<ChildLeft::AggregateChildrenState, ChildRight::AggregateChildrenState>>;
+    type TargetWidgetSeq = Either<ChildLeft::TargetWidgetSeq, ChildRight::TargetWidgetSeq>;
+
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.0.init_tree()
+    }
+
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.0.reconcile(&prev_value.0, widget_seq, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.0.update_value(other.0);
+    }
+
+    fn unmount(&self) {
+        self.0.unmount();
+    }
+
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.0
+            .process_event(comp_ctx, children_state, widget_seq, cx);
+    }
+
+    #[instrument(name = "Branch", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let children_state = children_state.as_mut()?.as_mut();
+        match &self.0 {
+            Left(child) => {
+                child.process_local_event(children_state.left()?, widget_seq.as_mut().left()?, cx)
+            }
+            Right(child) => {
+                child.process_local_event(children_state.right()?, widget_seq.as_mut().right()?, cx)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::elements::button::{Button, ButtonData};
+    use crate::elements::button::{Button, ButtonClick, ButtonData};
     use crate::elements::label::{Label, LabelData};
     use crate::flex::FlexParams;
+    use crate::glue::WidgetId;
     use crate::test_harness::Harness;
     use insta::assert_debug_snapshot;
     use test_env_log::test;
@@ -315,4 +468,64 @@ mod tests {
             assert_debug_snapshot!(root_state_5);
         });
     }
+
+    fn button_branch(left: bool) -> Branch<Button, Button> {
+        if left {
+            Branch::left(Button::new("Left").with_reserved_id(WidgetId::reserved(1)))
+        } else {
+            Branch::right(Button::new("Right").with_reserved_id(WidgetId::reserved(2)))
+        }
+    }
+
+    #[test]
+    fn new_branch() {
+        let (branch_data, state) = button_branch(true).build(None);
+        assert!(matches!(&branch_data.0, Left(ButtonData { text, .. }) if text == "Left"));
+        assert!(matches!(state, Some(Left(_))));
+
+        let (branch_data, state) = button_branch(false).build(state);
+        assert!(matches!(&branch_data.0, Right(ButtonData { text, .. }) if text == "Right"));
+        assert!(matches!(state, Some(Right(_))));
+    }
+
+    #[test]
+    fn branch_widget() {
+        let left_id = WidgetId::reserved(1);
+        let right_id = WidgetId::reserved(2);
+
+        Harness::run_test_window(button_branch(true), |harness| {
+            assert!(harness.try_get_state(left_id).is_some());
+
+            // Same variant; widgets are reconciled in place
+            harness.update_root_element(button_branch(true));
+            assert!(harness.try_get_state(left_id).is_some());
+            assert_eq!(harness.last_reconcile_stats().created, 0);
+
+            harness.update_root_element(button_branch(false));
+            assert!(harness.try_get_state(left_id).is_none());
+            assert!(harness.try_get_state(right_id).is_some());
+
+            harness.update_root_element(button_branch(true));
+            assert!(harness.try_get_state(left_id).is_some());
+            assert!(harness.try_get_state(right_id).is_none());
+        });
+    }
+
+    #[test]
+    fn branch_events() {
+        Harness::run_test_window(button_branch(false), |harness| {
+            harness.mouse_click_on(WidgetId::reserved(2));
+            assert_eq!(
+                harness.take_emitted_events::<ButtonClick>(),
+                vec![ButtonClick]
+            );
+
+            harness.update_root_element(button_branch(true));
+            harness.mouse_click_on(WidgetId::reserved(1));
+            assert_eq!(
+                harness.take_emitted_events::<ButtonClick>(),
+                vec![ButtonClick]
+            );
+        });
+    }
 }
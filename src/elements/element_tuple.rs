@@ -12,7 +12,9 @@ use crate::glue::GlobalEventCx;
 use crate::metadata::{NoEvent, NoState};
 use crate::widgets::WidgetTuple;
 
-use tracing::instrument;
+use druid::Rect;
+use std::any::{Any, TypeId};
+use tracing::{instrument, trace};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
 pub struct ElementTupleData<
@@ -55,6 +57,80 @@ macro_rules! replace_expr {
     };
 }
 
+// --- KEYED SLOTS
+
+// A tuple's children have different types, so their values are moved around through `dyn Any`.
+// Values are only ever swapped between two slots of the same type.
+type SwapFn = fn(&mut dyn Any, &mut dyn Any);
+
+fn swap_any<T: 'static>(a: &mut dyn Any, b: &mut dyn Any) {
+    std::mem::swap(
+        a.downcast_mut::<T>().unwrap(),
+        b.downcast_mut::<T>().unwrap(),
+    );
+}
+
+/// Compute the swaps which move the previous value of each keyed slot to the slot which now
+/// has its key.
+///
+/// A value can only move to a slot of the same type. Slots which aren't matched by key keep
+/// the previous value at their position, or, if that value moved to another slot, the value
+/// it was swapped with.
+fn keyed_swaps(
+    prev_keys: &[Option<&str>],
+    new_keys: &[Option<&str>],
+    slot_types: &[TypeId],
+) -> Vec<(usize, usize)> {
+    // The index of the previous value currently in each slot
+    let mut positions: Vec<usize> = (0..prev_keys.len()).collect();
+    let mut matched = vec![false; new_keys.len()];
+    let mut swaps = Vec::new();
+
+    for (slot, new_key) in new_keys.iter().enumerate() {
+        let new_key = match new_key {
+            Some(new_key) => *new_key,
+            None => continue,
+        };
+        let other_slot = (0..positions.len()).find(|&other_slot| {
+            !matched[other_slot]
+                && slot_types[other_slot] == slot_types[slot]
+                && prev_keys[positions[other_slot]] == Some(new_key)
+        });
+        if let Some(other_slot) = other_slot {
+            if other_slot != slot {
+                trace!(
+                    "Moving key {:?} from slot {} to {}",
+                    new_key,
+                    other_slot,
+                    slot
+                );
+                positions.swap(slot, other_slot);
+                swaps.push((slot, other_slot));
+            }
+            matched[slot] = true;
+        }
+    }
+    swaps
+}
+
+/// The index of the previous value each slot holds once `swaps` are applied.
+fn swapped_positions(slot_count: usize, swaps: &[(usize, usize)]) -> Vec<usize> {
+    let mut positions: Vec<usize> = (0..slot_count).collect();
+    for (slot, other_slot) in swaps {
+        positions.swap(*slot, *other_slot);
+    }
+    positions
+}
+
+/// Apply `swaps` to `values`, where `swap_fns[slot]` swaps two values of the slot's type.
+fn apply_swaps(swaps: &[(usize, usize)], values: &mut [&mut dyn Any], swap_fns: &[SwapFn]) {
+    for (slot, other_slot) in swaps {
+        let (low, high) = (*slot.min(other_slot), *slot.max(other_slot));
+        let (left, right) = values.split_at_mut(high);
+        swap_fns[*slot](&mut *left[low], &mut *right[0]);
+    }
+}
+
 macro_rules! declare_stuff {
     ( $TupleName:ident ; $( $Type:ident ),* ; $( $Remainder:ident ),* ; $( $index:tt ),* ) => {
 
@@ -101,6 +177,19 @@ impl<
     ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
         let mut state : Self::AggregateChildrenState = Default::default();
 
+        // State follows keys, so that it stays with the same widgets
+        let mut prev_state = prev_state;
+        let swaps = keyed_swaps(
+            &[$($Type::get_state_key(&prev_state.$index),)*],
+            &[$(self.$index.element_key(),)*],
+            &[$(TypeId::of::<$Type>(),)*],
+        );
+        apply_swaps(
+            &swaps,
+            &mut [$(&mut prev_state.$index as &mut dyn Any,)*],
+            &[$(swap_any::<$Type::AggregateChildrenState> as SwapFn,)*],
+        );
+
         let node = ElementTupleData(
             $(
                 {
@@ -263,19 +352,174 @@ macro_rules! Tuple {
     };
 }
 
+/// Groups up to 12 Elements as siblings, without a layout container.
+///
+/// This is the same element as [Tuple]: its children are laid out by the nearest parent
+/// container, as if the parent had been given them directly. This lets a component return
+/// several elements, without wrapping them in a [Row] or [Column] that would change the
+/// layout.
+///
+/// ## Events
+///
+/// Returned element doesn't emit events. Callbacks bound to each child are still called.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::{Column, Fragment};
+/// # use panoramix::elements::{Label, Button};
+/// let fields = Fragment!(
+///     Label::new("Name"),
+///     Button::new("Edit")
+/// );
+/// // The label and the button are laid out in the column, below the title
+/// let column = Column!(Label::new("Title"), fields);
+/// ```
+#[macro_export]
+macro_rules! Fragment {
+    ( $($arg:expr),* $(,)?) => {
+        $crate::Tuple!($($arg,)*)
+    };
+}
+
+impl<
+        C0: VirtualDom + 'static,
+        C1: VirtualDom + 'static,
+        C2: VirtualDom + 'static,
+        C3: VirtualDom + 'static,
+        C4: VirtualDom + 'static,
+        C5: VirtualDom + 'static,
+        C6: VirtualDom + 'static,
+        C7: VirtualDom + 'static,
+        C8: VirtualDom + 'static,
+        C9: VirtualDom + 'static,
+        C10: VirtualDom + 'static,
+        C11: VirtualDom + 'static,
+    > ElementTupleData<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11>
+{
+    fn keys(&self) -> [Option<&str>; 12] {
+        [
+            self.0.key(),
+            self.1.key(),
+            self.2.key(),
+            self.3.key(),
+            self.4.key(),
+            self.5.key(),
+            self.6.key(),
+            self.7.key(),
+            self.8.key(),
+            self.9.key(),
+            self.10.key(),
+            self.11.key(),
+        ]
+    }
+
+    fn slot_types() -> [TypeId; 12] {
+        [
+            TypeId::of::<C0>(),
+            TypeId::of::<C1>(),
+            TypeId::of::<C2>(),
+            TypeId::of::<C3>(),
+            TypeId::of::<C4>(),
+            TypeId::of::<C5>(),
+            TypeId::of::<C6>(),
+            TypeId::of::<C7>(),
+            TypeId::of::<C8>(),
+            TypeId::of::<C9>(),
+            TypeId::of::<C10>(),
+            TypeId::of::<C11>(),
+        ]
+    }
+
+    fn slots(&self) -> [&dyn Any; 12] {
+        [
+            &self.0, &self.1, &self.2, &self.3, &self.4, &self.5, &self.6, &self.7, &self.8,
+            &self.9, &self.10, &self.11,
+        ]
+    }
+
+    fn slots_mut(&mut self) -> [&mut dyn Any; 12] {
+        [
+            &mut self.0,
+            &mut self.1,
+            &mut self.2,
+            &mut self.3,
+            &mut self.4,
+            &mut self.5,
+            &mut self.6,
+            &mut self.7,
+            &mut self.8,
+            &mut self.9,
+            &mut self.10,
+            &mut self.11,
+        ]
+    }
+
+    fn swap_fns() -> [SwapFn; 12] {
+        [
+            swap_any::<C0>,
+            swap_any::<C1>,
+            swap_any::<C2>,
+            swap_any::<C3>,
+            swap_any::<C4>,
+            swap_any::<C5>,
+            swap_any::<C6>,
+            swap_any::<C7>,
+            swap_any::<C8>,
+            swap_any::<C9>,
+            swap_any::<C10>,
+            swap_any::<C11>,
+        ]
+    }
+
+    fn widget_slots(widget_seq: &mut <Self as VirtualDom>::TargetWidgetSeq) -> [&mut dyn Any; 12] {
+        [
+            &mut widget_seq.0,
+            &mut widget_seq.1,
+            &mut widget_seq.2,
+            &mut widget_seq.3,
+            &mut widget_seq.4,
+            &mut widget_seq.5,
+            &mut widget_seq.6,
+            &mut widget_seq.7,
+            &mut widget_seq.8,
+            &mut widget_seq.9,
+            &mut widget_seq.10,
+            &mut widget_seq.11,
+        ]
+    }
+
+    fn widget_swap_fns() -> [SwapFn; 12] {
+        [
+            swap_any::<C0::TargetWidgetSeq>,
+            swap_any::<C1::TargetWidgetSeq>,
+            swap_any::<C2::TargetWidgetSeq>,
+            swap_any::<C3::TargetWidgetSeq>,
+            swap_any::<C4::TargetWidgetSeq>,
+            swap_any::<C5::TargetWidgetSeq>,
+            swap_any::<C6::TargetWidgetSeq>,
+            swap_any::<C7::TargetWidgetSeq>,
+            swap_any::<C8::TargetWidgetSeq>,
+            swap_any::<C9::TargetWidgetSeq>,
+            swap_any::<C10::TargetWidgetSeq>,
+            swap_any::<C11::TargetWidgetSeq>,
+        ]
+    }
+}
+
 impl<
-        C0: VirtualDom,
-        C1: VirtualDom,
-        C2: VirtualDom,
-        C3: VirtualDom,
-        C4: VirtualDom,
-        C5: VirtualDom,
-        C6: VirtualDom,
-        C7: VirtualDom,
-        C8: VirtualDom,
-        C9: VirtualDom,
-        C10: VirtualDom,
-        C11: VirtualDom,
+        C0: VirtualDom + 'static,
+        C1: VirtualDom + 'static,
+        C2: VirtualDom + 'static,
+        C3: VirtualDom + 'static,
+        C4: VirtualDom + 'static,
+        C5: VirtualDom + 'static,
+        C6: VirtualDom + 'static,
+        C7: VirtualDom + 'static,
+        C8: VirtualDom + 'static,
+        C9: VirtualDom + 'static,
+        C10: VirtualDom + 'static,
+        C11: VirtualDom + 'static,
     > VirtualDom for ElementTupleData<C0, C1, C2, C3, C4, C5, C6, C7, C8, C9, C10, C11>
 {
     type Event = NoEvent;
@@ -326,6 +570,7 @@ impl<
         )
     }
 
+    #[rustfmt::skip]
     #[instrument(name = "Tuple", skip(self, prev_value, widget_seq, ctx))]
     fn reconcile(
         &self,
@@ -333,18 +578,77 @@ impl<
         widget_seq: &mut Self::TargetWidgetSeq,
         ctx: &mut ReconcileCtx,
     ) {
-        self.0.reconcile(&prev_value.0, &mut widget_seq.0, ctx);
-        self.1.reconcile(&prev_value.1, &mut widget_seq.1, ctx);
-        self.2.reconcile(&prev_value.2, &mut widget_seq.2, ctx);
-        self.3.reconcile(&prev_value.3, &mut widget_seq.3, ctx);
-        self.4.reconcile(&prev_value.4, &mut widget_seq.4, ctx);
-        self.5.reconcile(&prev_value.5, &mut widget_seq.5, ctx);
-        self.6.reconcile(&prev_value.6, &mut widget_seq.6, ctx);
-        self.7.reconcile(&prev_value.7, &mut widget_seq.7, ctx);
-        self.8.reconcile(&prev_value.8, &mut widget_seq.8, ctx);
-        self.9.reconcile(&prev_value.9, &mut widget_seq.9, ctx);
-        self.10.reconcile(&prev_value.10, &mut widget_seq.10, ctx);
-        self.11.reconcile(&prev_value.11, &mut widget_seq.11, ctx);
+        // Widgets follow keys; each child is reconciled with the previous child it was matched to
+        let swaps = keyed_swaps(&prev_value.keys(), &self.keys(), &Self::slot_types());
+        if !swaps.is_empty() {
+            apply_swaps(&swaps, &mut Self::widget_slots(widget_seq), &Self::widget_swap_fns());
+            ctx.event_ctx.request_layout();
+        }
+        let positions = swapped_positions(12, &swaps);
+        let prev_children = prev_value.slots();
+        let prev = |slot: usize| prev_children[positions[slot]];
+
+        self.0.reconcile(prev(0).downcast_ref().unwrap(), &mut widget_seq.0, ctx);
+        self.1.reconcile(prev(1).downcast_ref().unwrap(), &mut widget_seq.1, ctx);
+        self.2.reconcile(prev(2).downcast_ref().unwrap(), &mut widget_seq.2, ctx);
+        self.3.reconcile(prev(3).downcast_ref().unwrap(), &mut widget_seq.3, ctx);
+        self.4.reconcile(prev(4).downcast_ref().unwrap(), &mut widget_seq.4, ctx);
+        self.5.reconcile(prev(5).downcast_ref().unwrap(), &mut widget_seq.5, ctx);
+        self.6.reconcile(prev(6).downcast_ref().unwrap(), &mut widget_seq.6, ctx);
+        self.7.reconcile(prev(7).downcast_ref().unwrap(), &mut widget_seq.7, ctx);
+        self.8.reconcile(prev(8).downcast_ref().unwrap(), &mut widget_seq.8, ctx);
+        self.9.reconcile(prev(9).downcast_ref().unwrap(), &mut widget_seq.9, ctx);
+        self.10.reconcile(prev(10).downcast_ref().unwrap(), &mut widget_seq.10, ctx);
+        self.11.reconcile(prev(11).downcast_ref().unwrap(), &mut widget_seq.11, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        let swaps = keyed_swaps(&self.keys(), &other.keys(), &Self::slot_types());
+        apply_swaps(&swaps, &mut self.slots_mut(), &Self::swap_fns());
+
+        self.0.update_value(other.0);
+        self.1.update_value(other.1);
+        self.2.update_value(other.2);
+        self.3.update_value(other.3);
+        self.4.update_value(other.4);
+        self.5.update_value(other.5);
+        self.6.update_value(other.6);
+        self.7.update_value(other.7);
+        self.8.update_value(other.8);
+        self.9.update_value(other.9);
+        self.10.update_value(other.10);
+        self.11.update_value(other.11);
+    }
+
+    fn unmount(&self) {
+        self.0.unmount();
+        self.1.unmount();
+        self.2.unmount();
+        self.3.unmount();
+        self.4.unmount();
+        self.5.unmount();
+        self.6.unmount();
+        self.7.unmount();
+        self.8.unmount();
+        self.9.unmount();
+        self.10.unmount();
+        self.11.unmount();
+    }
+
+    #[rustfmt::skip]
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        self.0.find_key_rect(key, &mut widget_seq.0)
+            .or_else(|| self.1.find_key_rect(key, &mut widget_seq.1))
+            .or_else(|| self.2.find_key_rect(key, &mut widget_seq.2))
+            .or_else(|| self.3.find_key_rect(key, &mut widget_seq.3))
+            .or_else(|| self.4.find_key_rect(key, &mut widget_seq.4))
+            .or_else(|| self.5.find_key_rect(key, &mut widget_seq.5))
+            .or_else(|| self.6.find_key_rect(key, &mut widget_seq.6))
+            .or_else(|| self.7.find_key_rect(key, &mut widget_seq.7))
+            .or_else(|| self.8.find_key_rect(key, &mut widget_seq.8))
+            .or_else(|| self.9.find_key_rect(key, &mut widget_seq.9))
+            .or_else(|| self.10.find_key_rect(key, &mut widget_seq.10))
+            .or_else(|| self.11.find_key_rect(key, &mut widget_seq.11))
     }
 
     #[rustfmt::skip]
@@ -441,4 +745,36 @@ mod tests {
             assert_debug_snapshot!(tuple_state_2);
         });
     }
+
+    #[test]
+    fn fragment_events() {
+        use crate::elements::{Button, ComponentOutput};
+        use crate::metadata::{Metadata, NoEvent};
+        use crate::test_harness::Harness;
+        use crate::{Column, Fragment};
+        use std::sync::mpsc::channel;
+
+        let (click_sender, click_receiver) = channel();
+        let first_sender = click_sender.clone();
+        let second_sender = click_sender;
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let buttons = Fragment!(
+            Button::new("First").on_click(md, move |_, _| first_sender.send("First").unwrap()),
+            Button::new("Second").on_click(md, move |_, _| second_sender.send("Second").unwrap()),
+        );
+        let tree = ComponentOutput::new(md, Column!(Label::new("Title"), buttons));
+
+        Harness::run_test_window(tree, |harness| {
+            // The buttons are stacked by the column, instead of being grouped in a row
+            let first_rect = harness.find_by_name("First").unwrap().layout_rect();
+            let second_rect = harness.find_by_name("Second").unwrap().layout_rect();
+            assert!(second_rect.y0 >= first_rect.y1);
+
+            harness.click_on("Second");
+            assert_eq!(click_receiver.try_recv(), Ok("Second"));
+            harness.click_on("First");
+            assert_eq!(click_receiver.try_recv(), Ok("First"));
+            assert!(click_receiver.try_recv().is_err());
+        });
+    }
 }
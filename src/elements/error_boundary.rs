@@ -0,0 +1,344 @@
+use crate::ctx::{provided_context, set_provided_context, ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::glue::GlobalEventCx;
+use crate::metadata::{Metadata, NoState};
+
+use derivative::Derivative;
+use either::{Either, Left, Right};
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::rc::Rc;
+use tracing::{debug_span, error, info, instrument};
+use tracing_unwrap::OptionExt;
+
+/// Element which displays a fallback element if its child panics while it's built.
+///
+/// Component functions are called while their element is built, so a broken component
+/// inside the boundary doesn't take the whole application down. When the child panics, its
+/// state is dropped, and the fallback is built from the panic message instead. The child is
+/// built again from scratch the next time the boundary is built; once it builds successfully,
+/// it's displayed as if the boundary wasn't there.
+///
+/// If the child panics while its widgets are updated, the widgets are created again from
+/// scratch, so that they match the child.
+///
+/// ## Events
+///
+/// Emits [BoundaryCaught] events, the next time events are processed after the child failed
+/// to build. Events of the child and of the fallback are only passed to their callbacks.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct ErrorBoundary<Child: Element, Fallback: Element> {
+    pub child: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub fallback: Rc<dyn Fn(&str) -> Fallback>,
+}
+
+/// Virtual DOM node of [`ErrorBoundary`], holding the child or the fallback, whichever was
+/// built.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ErrorBoundaryData<Child: VirtualDom, Fallback: VirtualDom> {
+    Child(Child),
+    Fallback(Fallback),
+}
+
+/// Event emitted when the child of an [ErrorBoundary] panics while it's built.
+///
+/// Holds the panic message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoundaryCaught(pub String);
+
+//
+// --- IMPLS
+
+impl<Child: Element, Fallback: Element> ErrorBoundary<Child, Fallback> {
+    /// Build a boundary around `child`, calling `fallback` with the panic message if the child
+    /// panics.
+    ///
+    /// Use the [.on_caught](ErrorBoundary::on_caught) method to provide a closure to be called when the child panics.
+    pub fn new(child: Child, fallback: impl Fn(&str) -> Fallback + 'static) -> Self {
+        ErrorBoundary {
+            child,
+            fallback: Rc::new(fallback),
+        }
+    }
+
+    /// Provide a closure to be called when the child panics.
+    pub fn on_caught<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, BoundaryCaught) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+impl<Child: Element, Fallback: Element> Element for ErrorBoundary<Child, Fallback> {
+    type Event = BoundaryCaught;
+    type ComponentState = NoState;
+    // The message of the panic caught in the last build and whether it was emitted, and the
+    // state of the child or of the fallback
+    type AggregateChildrenState = (
+        Option<(String, bool)>,
+        Option<Either<Child::AggregateChildrenState, Fallback::AggregateChildrenState>>,
+    );
+    type BuildOutput = ErrorBoundaryData<Child::BuildOutput, Fallback::BuildOutput>;
+
+    #[instrument(name = "ErrorBoundary", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (prev_caught, prev_state) = prev_state;
+        let (child_prev_state, fallback_prev_state) = match prev_state {
+            Some(Left(child_state)) => (child_state, Default::default()),
+            Some(Right(fallback_state)) => (Default::default(), fallback_state),
+            None => Default::default(),
+        };
+
+        // A panic skips the cleanup of the providers between the boundary and the panic
+        let context = provided_context();
+        let child = self.child;
+        let result = catch_unwind(AssertUnwindSafe(move || child.build(child_prev_state)));
+
+        match result {
+            Ok((output, state)) => (ErrorBoundaryData::Child(output), (None, Some(Left(state)))),
+            Err(payload) => {
+                set_provided_context(context);
+                let message = panic_message(&*payload);
+                error!("Caught panic while building child: {}", message);
+
+                // A broken child usually panics again each time it's built; the event is only
+                // emitted once for the same message
+                let emitted = match prev_caught {
+                    Some((prev_message, emitted)) if prev_message == message => emitted,
+                    _ => false,
+                };
+                let (output, state) = (self.fallback)(&message).build(fallback_prev_state);
+                (
+                    ErrorBoundaryData::Fallback(output),
+                    (Some((message, emitted)), Some(Right(state))),
+                )
+            }
+        }
+    }
+}
+
+impl<Child: VirtualDom, Fallback: VirtualDom> VirtualDom for ErrorBoundaryData<Child, Fallback> {
+    type Event = BoundaryCaught;
+    type AggregateChildrenState = (
+        Option<(String, bool)>,
+        Option<Either<Child::AggregateChildrenState, Fallback::AggregateChildrenState>>,
+    );
+    type TargetWidgetSeq = Either<Child::TargetWidgetSeq, Fallback::TargetWidgetSeq>;
+
+    #[instrument(name = "ErrorBoundary", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        match self {
+            ErrorBoundaryData::Child(child) => Left(child.init_tree()),
+            ErrorBoundaryData::Fallback(fallback) => Right(fallback.init_tree()),
+        }
+    }
+
+    #[instrument(name = "ErrorBoundary", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        match (self, prev_value) {
+            (ErrorBoundaryData::Child(child), ErrorBoundaryData::Child(prev_child)) => {
+                let child_widget_seq = widget_seq.as_mut().left().expect_or_log(
+                    "The previous value of this element was Child. Expected Left widget.",
+                );
+                let result = catch_unwind(AssertUnwindSafe(|| {
+                    child.reconcile(prev_child, child_widget_seq, ctx)
+                }));
+                if let Err(payload) = result {
+                    error!(
+                        "Caught panic while reconciling child: {}",
+                        panic_message(&*payload)
+                    );
+                    debug_span!("init_tree").in_scope(|| {
+                        *widget_seq = Left(child.init_tree());
+                    });
+                }
+            }
+            (ErrorBoundaryData::Fallback(fallback), ErrorBoundaryData::Fallback(prev_fallback)) => {
+                let widget_seq = widget_seq.as_mut().right().expect_or_log(
+                    "The previous value of this element was Fallback. Expected Right widget.",
+                );
+                fallback.reconcile(prev_fallback, widget_seq, ctx);
+            }
+
+            (ErrorBoundaryData::Child(child), ErrorBoundaryData::Fallback(prev_fallback)) => {
+                prev_fallback.unmount();
+                debug_span!("init_tree").in_scope(|| {
+                    info!("child recovered, removing fallback");
+                    *widget_seq = Left(child.init_tree());
+                });
+            }
+            (ErrorBoundaryData::Fallback(fallback), ErrorBoundaryData::Child(prev_child)) => {
+                prev_child.unmount();
+                debug_span!("init_tree").in_scope(|| {
+                    info!("child panicked, switching to fallback");
+                    *widget_seq = Right(fallback.init_tree());
+                });
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        match (self, other) {
+            (ErrorBoundaryData::Child(child), ErrorBoundaryData::Child(other_child)) => {
+                child.update_value(other_child)
+            }
+            (
+                ErrorBoundaryData::Fallback(fallback),
+                ErrorBoundaryData::Fallback(other_fallback),
+            ) => fallback.update_value(other_fallback),
+            (this, other) => *this = other,
+        }
+    }
+
+    fn unmount(&self) {
+        match self {
+            ErrorBoundaryData::Child(child) => child.unmount(),
+            ErrorBoundaryData::Fallback(fallback) => fallback.unmount(),
+        }
+    }
+
+    #[instrument(
+        name = "ErrorBoundary",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let children_state = children_state.1.as_mut().unwrap_or_log().as_mut();
+        match self {
+            ErrorBoundaryData::Child(child) => child.process_event(
+                comp_ctx,
+                children_state.left().unwrap_or_log(),
+                widget_seq.as_mut().left().unwrap_or_log(),
+                cx,
+            ),
+            ErrorBoundaryData::Fallback(fallback) => fallback.process_event(
+                comp_ctx,
+                children_state.right().unwrap_or_log(),
+                widget_seq.as_mut().right().unwrap_or_log(),
+                cx,
+            ),
+        }
+    }
+
+    #[instrument(name = "ErrorBoundary", skip(self, children_state, _widget_seq, _cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        _widget_seq: &mut Self::TargetWidgetSeq,
+        _cx: &mut GlobalEventCx,
+    ) -> Option<BoundaryCaught> {
+        match &mut children_state.0 {
+            Some((message, emitted)) if !*emitted => {
+                *emitted = true;
+                Some(BoundaryCaught(message.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::label::{Label, LabelData};
+    use crate::metadata::NoEvent;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[derive(Clone, Debug)]
+    struct Fragile {
+        broken: bool,
+    }
+
+    impl Element for Fragile {
+        type Event = NoEvent;
+        type ComponentState = NoState;
+        type AggregateChildrenState = ();
+        type BuildOutput = LabelData;
+
+        fn build(self, _prev_state: ()) -> (LabelData, ()) {
+            if self.broken {
+                panic!("Fragile is broken");
+            }
+            Label::new("Working").build(())
+        }
+    }
+
+    fn boundary(broken: bool) -> ErrorBoundary<Fragile, Label> {
+        ErrorBoundary::new(Fragile { broken }, |message| {
+            Label::new(format!("Error: {}", message))
+        })
+    }
+
+    #[test]
+    fn new_error_boundary() {
+        let (boundary_data, state) = boundary(false).build(Default::default());
+        assert_eq!(
+            boundary_data,
+            ErrorBoundaryData::Child(LabelData::new("Working"))
+        );
+        assert_eq!(state.0, None);
+
+        let (boundary_data, state) = boundary(true).build(state);
+        assert_eq!(
+            boundary_data,
+            ErrorBoundaryData::Fallback(LabelData::new("Error: Fragile is broken"))
+        );
+        assert_eq!(state.0, Some((String::from("Fragile is broken"), false)));
+    }
+
+    #[test]
+    fn error_boundary_fallback() {
+        let (event_sender, event_receiver) = channel();
+        let boundary = |broken| EventLogger::new(event_sender.clone(), boundary(broken));
+
+        Harness::run_test_window(boundary(true), |harness| {
+            assert!(harness.find_by_name("Error: Fragile is broken").is_some());
+            assert!(harness.find_by_name("Working").is_none());
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(BoundaryCaught(String::from("Fragile is broken")))
+            );
+
+            // The child is built again, but the event is only emitted once
+            harness.update_root_element(boundary(true));
+            assert!(harness.find_by_name("Working").is_none());
+            assert!(harness.find_by_name("Error: Fragile is broken").is_some());
+            assert!(event_receiver.try_recv().is_err());
+
+            harness.update_root_element(boundary(false));
+            assert!(harness.find_by_name("Working").is_some());
+            assert!(harness.find_by_name("Error: Fragile is broken").is_none());
+        });
+    }
+}
@@ -78,6 +78,15 @@ impl<Child: VirtualDom> VirtualDom for EventLoggerData<Child> {
         self.child.reconcile(&prev_value.child, widget_seq, ctx);
     }
 
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.event_queue = other.event_queue;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
     #[instrument(
         name = "EventLogger",
         skip(self, comp_ctx, children_state, widget_seq, cx)
@@ -0,0 +1,236 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{FileButtonWidget, SingleFileButtonWidget};
+
+use druid::{FileDialogOptions, FileSpec};
+use std::path::PathBuf;
+use tracing::{instrument, trace};
+
+/// A button which opens the platform's file dialog when clicked.
+///
+/// ## Events
+///
+/// Emits [FileChosen] events when a file is chosen in the dialog. No event is emitted if the
+/// dialog is cancelled.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileButton {
+    pub text: String,
+    pub filters: Vec<FileSpec>,
+    pub directory: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileButtonData {
+    pub text: String,
+    pub filters: Vec<FileSpec>,
+    pub directory: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a file is chosen in the dialog opened by a [FileButton].
+///
+/// Holds the path of the chosen file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileChosen(pub PathBuf);
+
+//
+// --- IMPLS
+
+impl FileButton {
+    /// Build a button with the given label.
+    ///
+    /// Use the [.on_choose](FileButton::on_choose) method to provide a closure to be called when a file is chosen.
+    pub fn new(text: impl Into<String>) -> Self {
+        FileButton {
+            text: text.into(),
+            filters: Vec::new(),
+            directory: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Only let the user choose files of the given types.
+    pub fn filters(self, filters: Vec<FileSpec>) -> Self {
+        FileButton { filters, ..self }
+    }
+
+    /// Set whether the dialog picks directories instead of files.
+    pub fn directory(self, directory: bool) -> Self {
+        FileButton { directory, ..self }
+    }
+
+    /// Change the way the button's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        FileButton {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The button created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same button is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        FileButton {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a file is chosen.
+    ///
+    /// The closure is given the path of the file.
+    pub fn on_choose<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, PathBuf) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, event: FileChosen| {
+            callback(state, event.0)
+        })
+    }
+}
+
+impl FileButtonData {
+    fn dialog_options(&self) -> FileDialogOptions {
+        let mut options = FileDialogOptions::new();
+        if !self.filters.is_empty() {
+            options = options.allowed_types(self.filters.clone());
+        }
+        if self.directory {
+            options = options.select_directories();
+        }
+        options
+    }
+}
+
+impl Element for FileButton {
+    type Event = FileChosen;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = FileButtonData;
+
+    #[instrument(name = "FileButton", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (FileButtonData, ()) {
+        (
+            FileButtonData {
+                text: self.text,
+                filters: self.filters,
+                directory: self.directory,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for FileButtonData {
+    type Event = FileChosen;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleFileButtonWidget;
+
+    #[instrument(name = "FileButton", skip(self))]
+    fn init_tree(&self) -> SingleFileButtonWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleFileButtonWidget::new(
+            FileButtonWidget::new(self.text.clone(), self.dialog_options(), id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "FileButton", skip(self, prev_value, widget, _ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleFileButtonWidget,
+        _ctx: &mut ReconcileCtx,
+    ) {
+        // TODO - Reconcile text (druid's Button can't change its label after creation)
+        if self.filters != prev_value.filters || self.directory != prev_value.directory {
+            widget.widget_mut().options = self.dialog_options();
+        }
+        widget.flex = self.flex;
+    }
+
+    #[instrument(name = "FileButton", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleFileButtonWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<FileChosen> {
+        let id = widget.widget().id();
+        if let Some(Action::FileChosen(path)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed chosen file: {:?}", path);
+            Some(FileChosen(path))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_file_button() {
+        let filters = vec![FileSpec::new("Text file", &["txt"])];
+        let button = FileButton::new("Open...").filters(filters.clone());
+        let (button_data, ()) = button.build(());
+
+        assert_eq!(
+            button_data,
+            FileButtonData {
+                text: String::from("Open..."),
+                filters,
+                directory: false,
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn file_button_choose() {
+        let (event_sender, event_receiver) = channel();
+        let button = EventLogger::new(event_sender, FileButton::new("Open..."));
+
+        Harness::run_test_window(button, |harness| {
+            // Cancelling the dialog emits nothing
+            harness.stub_file_dialog(None);
+            harness.click_on("Open...");
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            harness.stub_file_dialog(Some(PathBuf::from("/tmp/notes.txt")));
+            harness.click_on("Open...");
+            assert_eq!(
+                event_receiver.try_recv().ok(),
+                Some(FileChosen(PathBuf::from("/tmp/notes.txt")))
+            );
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
+}
@@ -7,7 +7,7 @@ use crate::glue::GlobalEventCx;
 use crate::metadata::{NoEvent, NoState};
 use crate::widgets::{Container, FlexWidget, SingleWidget};
 
-use druid::KeyOrValue;
+use druid::{EventCtx, KeyOrValue, Rect};
 
 use tracing::instrument;
 
@@ -19,6 +19,7 @@ pub struct Flex<Child: Element> {
     pub flex: FlexParams,
     pub flex_container: FlexContainerParams,
     pub container_style: ContainerStyle,
+    pub spacing: f64,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -28,6 +29,7 @@ pub struct FlexData<Child: VirtualDom> {
     pub flex: FlexParams,
     pub flex_container: FlexContainerParams,
     pub container_style: ContainerStyle,
+    pub spacing: f64,
 }
 
 // ----
@@ -51,6 +53,7 @@ impl<Child: Element> Flex<Child> {
                 border: None,
                 corner_radius: KeyOrValue::Concrete(0.0),
             },
+            spacing: 0.0,
         }
     }
 
@@ -74,6 +77,44 @@ impl<Child: Element> Flex<Child> {
             ..self
         }
     }
+
+    /// Set the space between adjacent children, along the main axis.
+    pub fn with_spacing(self, spacing: f64) -> Self {
+        Flex { spacing, ..self }
+    }
+
+    /// Set how children are aligned on the cross (or minor) axis.
+    pub fn with_cross_axis_alignment(self, cross_alignment: CrossAxisAlignment) -> Self {
+        Flex {
+            flex_container: FlexContainerParams {
+                cross_alignment,
+                ..self.flex_container
+            },
+            ..self
+        }
+    }
+
+    /// Set how children are arranged on the main axis.
+    pub fn with_main_axis_alignment(self, main_alignment: MainAxisAlignment) -> Self {
+        Flex {
+            flex_container: FlexContainerParams {
+                main_alignment,
+                ..self.flex_container
+            },
+            ..self
+        }
+    }
+
+    /// Set whether the container takes all the available space on its main axis.
+    pub fn with_fill_major_axis(self, fill_major_axis: bool) -> Self {
+        Flex {
+            flex_container: FlexContainerParams {
+                fill_major_axis,
+                ..self.flex_container
+            },
+            ..self
+        }
+    }
 }
 
 impl<Child: VirtualDom> FlexData<Child> {
@@ -83,6 +124,7 @@ impl<Child: VirtualDom> FlexData<Child> {
         flex: FlexParams,
         flex_container: FlexContainerParams,
         container_style: ContainerStyle,
+        spacing: f64,
     ) -> Self {
         FlexData {
             axis,
@@ -90,6 +132,7 @@ impl<Child: VirtualDom> FlexData<Child> {
             flex,
             flex_container,
             container_style,
+            spacing,
         }
     }
 }
@@ -115,6 +158,7 @@ impl<Child: Element> Element for Flex<Child> {
                 self.flex,
                 self.flex_container,
                 self.container_style,
+                self.spacing,
             ),
             children_state,
         )
@@ -132,6 +176,7 @@ impl<Child: VirtualDom> VirtualDom for FlexData<Child> {
         let flex = FlexWidget {
             direction: self.axis,
             flex_params: self.flex_container,
+            spacing: self.spacing,
             children_seq: self.child.init_tree(),
         };
         let mut container = Container::new(flex);
@@ -155,7 +200,23 @@ impl<Child: VirtualDom> VirtualDom for FlexData<Child> {
         widget_seq: &mut Self::TargetWidgetSeq,
         ctx: &mut ReconcileCtx,
     ) {
-        // TODO - Reconcile style params
+        // TODO - Reconcile container style
+        if self.axis != prev_value.axis
+            || self.flex_container != prev_value.flex_container
+            || self.spacing != prev_value.spacing
+        {
+            let flex_widget = widget_seq.pod.widget_mut().child_mut();
+            flex_widget.direction = self.axis;
+            flex_widget.flex_params = self.flex_container;
+            flex_widget.spacing = self.spacing;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+        widget_seq.flex = self.flex;
+
         self.child.reconcile(
             &prev_value.child,
             &mut widget_seq.pod.widget_mut().child_mut().children_seq,
@@ -163,6 +224,28 @@ impl<Child: VirtualDom> VirtualDom for FlexData<Child> {
         );
     }
 
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.axis = other.axis;
+        self.flex = other.flex;
+        self.flex_container = other.flex_container;
+        self.container_style = other.container_style;
+        self.spacing = other.spacing;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        // Children are laid out in the flex widget; we ignore the container's border
+        let origin = widget_seq.pod.layout_rect().origin().to_vec2();
+        let children_seq = &mut widget_seq.pod.widget_mut().child_mut().children_seq;
+        self.child
+            .find_key_rect(key, children_seq)
+            .map(|rect| rect + origin)
+    }
+
     #[instrument(name = "Flex", skip(self, comp_ctx, children_state, widget_seq, cx))]
     fn process_event(
         &self,
@@ -316,6 +399,128 @@ mod tests {
         });
     }
 
+    #[test]
+    fn flex_container_params() {
+        let row = Row!(Label::new("Hello"))
+            .with_cross_axis_alignment(CrossAxisAlignment::Start)
+            .with_main_axis_alignment(MainAxisAlignment::SpaceBetween)
+            .with_fill_major_axis(true);
+        let (row_data, _) = row.build(Default::default());
+
+        assert_eq!(
+            row_data.flex_container,
+            FlexContainerParams {
+                cross_alignment: CrossAxisAlignment::Start,
+                main_alignment: MainAxisAlignment::SpaceBetween,
+                fill_major_axis: true,
+            }
+        );
+    }
+
+    #[test]
+    fn main_axis_alignment_widget() {
+        use crate::elements::Button;
+        use crate::glue::WidgetId;
+        use crate::test_harness::Harness;
+
+        let button_id = WidgetId::reserved(1);
+        let make_row = |main_alignment| {
+            Row!(Button::new("Hello").with_reserved_id(button_id))
+                .with_main_axis_alignment(main_alignment)
+                .with_fill_major_axis(true)
+        };
+
+        Harness::run_test_window(make_row(MainAxisAlignment::End), |harness| {
+            let button_rect = harness.get_state(button_id).layout_rect();
+            assert!(button_rect.x0 > 0.0);
+
+            harness.update_root_element(make_row(MainAxisAlignment::Start));
+            let button_rect = harness.get_state(button_id).layout_rect();
+            assert_eq!(button_rect.x0, 0.0);
+        });
+    }
+
+    #[test]
+    fn spacing_widget() {
+        use crate::elements::{Button, ElementList};
+        use crate::glue::WidgetId;
+        use crate::test_harness::Harness;
+
+        let button_ids = [
+            WidgetId::reserved(1),
+            WidgetId::reserved(2),
+            WidgetId::reserved(3),
+        ];
+        let make_row = |count: usize| {
+            let keys = (0..count).map(|i| i.to_string());
+            let buttons = button_ids[0..count]
+                .iter()
+                .map(|id| Button::new("Hello").with_reserved_id(*id));
+            Flex::new(
+                Axis::Horizontal,
+                ElementList::from_keys_elems(keys, buttons),
+            )
+            .with_spacing(8.0)
+        };
+
+        Harness::run_test_window(make_row(0), |harness| {
+            for &count in &[1, 3, 2] {
+                harness.update_root_element(make_row(count));
+
+                let rects: Vec<_> = button_ids[0..count]
+                    .iter()
+                    .map(|id| harness.get_state(*id).layout_rect())
+                    .collect();
+
+                // No spacing before the first child
+                assert_eq!(rects[0].x0, 0.0);
+                for pair in rects.windows(2) {
+                    // Child sizes are rounded up, so the gap can be slightly bigger
+                    let gap = pair[1].x0 - pair[0].x1;
+                    assert!(gap >= 8.0 && gap < 9.0, "unexpected gap: {}", gap);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn find_label_in_column() {
+        use crate::elements::SizedBox;
+        use crate::test_harness::Harness;
+
+        let column = Column!(
+            Label::new("First"),
+            SizedBox::empty(10.0, 50.0),
+            Label::new("Second"),
+            // Pushes the last label below the bottom of the 400px test window
+            SizedBox::empty(10.0, 500.0),
+            Label::new("Offscreen"),
+        );
+
+        Harness::run_test_window(column, |harness| {
+            let first = harness.find_by_name("First").unwrap();
+            let second = harness.find_by_name("Second").unwrap();
+
+            assert_eq!(first.layout_rect().y0, 0.0);
+            assert_eq!(second.layout_rect().y0, first.layout_rect().y1 + 50.0);
+            assert!(first.is_visible());
+            assert!(second.is_visible());
+
+            let offscreen = harness.find_by_name("Offscreen").unwrap();
+            assert!(offscreen.layout_rect().y0 > 400.0);
+            assert!(!offscreen.is_visible());
+
+            assert!(harness.find_by_name("Third").is_none());
+
+            let names: Vec<_> = harness
+                .find_all(|widget| widget.is_visible())
+                .iter()
+                .map(|widget| widget.name().to_string())
+                .collect();
+            assert_eq!(names, vec!["First", "Second"]);
+        });
+    }
+
     // TODO
     // - Test that layout is calculated properly
 }
@@ -0,0 +1,201 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{FlexedWidget, SingleFlexedWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which gives its child a flex factor in a [Flex](crate::elements::Flex) container.
+///
+/// The space left over by the other children of the container is shared between flex
+/// children, in proportion to their flex factor. The child may still be smaller than the
+/// space it's given.
+///
+/// Usually created with [`ElementExt::flex`](crate::ElementExt::flex).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Flexed<Child: Element> {
+    pub child: Child,
+    pub flex: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FlexedData<Child: VirtualDom> {
+    pub child: Child,
+    pub flex: f64,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Flexed<Child> {
+    pub fn new(child: Child, flex: f64) -> Self {
+        Flexed { child, flex }
+    }
+}
+
+impl<Child: VirtualDom> FlexedData<Child> {
+    fn flex_params(&self) -> FlexParams {
+        FlexParams {
+            flex: Some(self.flex),
+            alignment: None,
+        }
+    }
+}
+
+impl<Child: Element> Element for Flexed<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = FlexedData<Child::BuildOutput>;
+
+    #[instrument(name = "Flexed", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            FlexedData {
+                child,
+                flex: self.flex,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for FlexedData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleFlexedWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Flexed", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleFlexedWidget::new(
+            FlexedWidget::new(self.child.init_tree(), WidgetId::next()),
+            self.flex_params(),
+        )
+    }
+
+    #[instrument(name = "Flexed", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.flex != prev_value.flex {
+            widget_seq.flex = self.flex_params();
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.flex = other.flex;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Flexed", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Flexed", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::label::{Label, LabelData};
+    use crate::elements::SizedBox;
+    use crate::test_harness::Harness;
+    use crate::Row;
+    use test_env_log::test;
+
+    #[test]
+    fn new_flexed() {
+        let (flexed_data, _) = Label::new("Hello").flex(2.0).build(Default::default());
+
+        assert_eq!(
+            flexed_data,
+            FlexedData {
+                child: LabelData::new("Hello"),
+                flex: 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn flexed_layout() {
+        let fixed_id = WidgetId::reserved(1);
+        let small_id = WidgetId::reserved(2);
+        let large_id = WidgetId::reserved(3);
+
+        // Boxes with an infinite width take all the space their flex factor gives them.
+        // The test window is 400px wide, so flex children share 300px.
+        let row = |large_flex| {
+            Row!(
+                SizedBox::empty(100.0, 10.0).with_reserved_id(fixed_id),
+                SizedBox::empty(f64::INFINITY, 10.0)
+                    .with_reserved_id(small_id)
+                    .flex(1.0),
+                SizedBox::empty(f64::INFINITY, 10.0)
+                    .with_reserved_id(large_id)
+                    .flex(large_flex),
+            )
+        };
+
+        Harness::run_test_window(row(2.0), |harness| {
+            assert_eq!(harness.get_state(fixed_id).layout_rect().width(), 100.0);
+            assert_eq!(harness.get_state(small_id).layout_rect().width(), 100.0);
+            assert_eq!(harness.get_state(large_id).layout_rect().width(), 200.0);
+
+            harness.update_root_element(row(5.0));
+            assert_eq!(harness.get_state(fixed_id).layout_rect().width(), 100.0);
+            assert_eq!(harness.get_state(small_id).layout_rect().width(), 50.0);
+            assert_eq!(harness.get_state(large_id).layout_rect().width(), 250.0);
+        });
+    }
+}
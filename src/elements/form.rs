@@ -0,0 +1,329 @@
+use crate::ctx::{with_form_validation, ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::internals::ButtonData;
+use crate::elements::Button;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{FormWidget, SingleFormWidget};
+
+use tracing::{instrument, trace};
+
+/// Wrapper which groups the inputs of a form, and emits [FormSubmitted] when the form is
+/// submitted.
+///
+/// The form is submitted when Enter is pressed in any of its fields, or when its submit button
+/// is clicked, if it has one (see [`with_submit_button`](Form::with_submit_button)). The
+/// button is displayed below the fields.
+///
+/// Submission is suppressed while a [ValidatedTextBox](crate::elements::ValidatedTextBox) in
+/// the form holds invalid content. Like the validated text boxes themselves, the form checks
+/// the content the boxes are built with, so their text should be bound to the component's
+/// state. Components inside the form are called every time it's built, even if their props are
+/// unchanged.
+///
+/// ## Events
+///
+/// Emits [FormSubmitted] events. The events of the fields should be handled inside the form,
+/// eg with [`ElementExt::on`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Form<Child: Element> {
+    pub child: Child,
+    pub submit_button: Option<Button>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormData<Child: VirtualDom> {
+    pub child: Child,
+    pub submit_button: Option<ButtonData>,
+    pub valid: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [Form] is submitted with valid content.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FormSubmitted;
+
+//
+// --- IMPLS
+
+impl<Child: Element> Form<Child> {
+    pub fn new(child: Child) -> Self {
+        Form {
+            child,
+            submit_button: None,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Display `button` below the fields, and submit the form when it's clicked.
+    pub fn with_submit_button(self, button: Button) -> Self {
+        Form {
+            submit_button: Some(button),
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Form {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this form is submitted.
+    pub fn on_submit<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, FormSubmitted) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+impl<Child: Element> Element for Form<Child> {
+    type Event = FormSubmitted;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = FormData<Child::BuildOutput>;
+
+    #[instrument(name = "Form", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let child = self.child;
+        let ((child, state), valid) = with_form_validation(|| child.build(prev_state));
+        let submit_button = self.submit_button.map(|button| button.build(()).0);
+        (
+            FormData {
+                child,
+                submit_button,
+                valid,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for FormData<Child> {
+    type Event = FormSubmitted;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleFormWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Form", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleFormWidget::new(
+            FormWidget::new(self.child.init_tree(), self.submit_button.init_tree(), id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Form", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let widget = widget_seq.widget_mut();
+        self.child
+            .reconcile(&prev_value.child, &mut widget.children, ctx);
+        self.submit_button
+            .reconcile(&prev_value.submit_button, &mut widget.submit_button, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.submit_button = other.submit_button;
+        self.valid = other.valid;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Form", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Form", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<FormSubmitted> {
+        // FIXME - Rework event dispatching
+        let widget = widget_seq.widget_mut();
+        let clicked = match (&self.submit_button, &mut widget.submit_button) {
+            (Some(button), Some(button_widget)) => button
+                .process_local_event(&mut (), button_widget, cx)
+                .is_some(),
+            _ => false,
+        };
+        let entered = matches!(
+            cx.app_data.dequeue_action(widget.id()),
+            Some(Action::Submitted)
+        );
+        if !clicked && !entered {
+            return None;
+        }
+
+        if self.valid {
+            trace!("Processed form submission");
+            Some(FormSubmitted)
+        } else {
+            trace!("Form has invalid content, submission suppressed");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate as panoramix;
+    use crate::elements::internals::LabelData;
+    use crate::elements::{ErrorBoundary, Label, ValidatedChange, ValidatedTextBox};
+    use crate::test_harness::Harness;
+    use crate::{CompCtx, NoEvent, Tuple};
+    use test_env_log::test;
+
+    fn validate_email(text: &str) -> Result<(), String> {
+        if text.contains('@') {
+            Ok(())
+        } else {
+            Err(String::from("Not an email address"))
+        }
+    }
+
+    fn email_form(email: &str) -> Form<ValidatedTextBox> {
+        let field = ValidatedTextBox::new(email, validate_email);
+        Form::new(field).with_submit_button(Button::new("Send"))
+    }
+
+    #[crate::component]
+    fn EmailField(_ctx: &CompCtx, email: String) -> impl Element<Event = ValidatedChange> {
+        ValidatedTextBox::new(email, validate_email)
+    }
+
+    fn email_field_form(email: &str) -> Form<impl Element> {
+        Form::new(EmailField::new(email.to_string())).with_submit_button(Button::new("Send"))
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Broken;
+
+    impl Element for Broken {
+        type Event = NoEvent;
+        type ComponentState = NoState;
+        type AggregateChildrenState = ();
+        type BuildOutput = LabelData;
+
+        fn build(self, _prev_state: ()) -> (LabelData, ()) {
+            panic!("Broken field");
+        }
+    }
+
+    #[test]
+    fn new_form() {
+        let (form_data, _) = email_form("ada@example.com").build(false);
+        assert!(form_data.valid);
+        assert_eq!(form_data.submit_button.unwrap().text, "Send");
+
+        let (form_data, _) = email_form("ada").build(false);
+        assert!(!form_data.valid);
+    }
+
+    #[test]
+    fn form_submit() {
+        Harness::run_test_window(email_form("ada@example.com"), |harness| {
+            // Enter in a field submits the form
+            harness.type_text("ada@example.com", "\n");
+            assert_eq!(
+                harness.take_emitted_events::<FormSubmitted>(),
+                vec![FormSubmitted]
+            );
+
+            harness.click_on("Send");
+            assert_eq!(
+                harness.take_emitted_events::<FormSubmitted>(),
+                vec![FormSubmitted]
+            );
+        });
+    }
+
+    #[test]
+    fn invalid_form_submit() {
+        Harness::run_test_window(email_form("ada"), |harness| {
+            harness.type_text("ada", "\n");
+            assert_eq!(harness.take_emitted_events::<FormSubmitted>(), vec![]);
+
+            harness.click_on("Send");
+            assert_eq!(harness.take_emitted_events::<FormSubmitted>(), vec![]);
+
+            harness.update_root_element(email_form("ada@example.com"));
+            harness.click_on("Send");
+            assert_eq!(
+                harness.take_emitted_events::<FormSubmitted>(),
+                vec![FormSubmitted]
+            );
+        });
+    }
+
+    #[test]
+    fn invalid_field_in_unchanged_component() {
+        Harness::run_test_window(email_field_form("ada"), |harness| {
+            // The component is built again with the same props
+            harness.update_root_element(email_field_form("ada"));
+            harness.click_on("Send");
+            assert_eq!(harness.take_emitted_events::<FormSubmitted>(), vec![]);
+
+            harness.update_root_element(email_field_form("ada@example.com"));
+            harness.click_on("Send");
+            assert_eq!(
+                harness.take_emitted_events::<FormSubmitted>(),
+                vec![FormSubmitted]
+            );
+        });
+    }
+
+    #[test]
+    fn invalid_field_after_caught_panic() {
+        let inner_form = ErrorBoundary::new(Form::new(Broken), |message| Label::new(message));
+        let form = Form::new(Tuple!(
+            inner_form,
+            ValidatedTextBox::new("ada", validate_email)
+        ));
+
+        let (form_data, _) = form.build(Default::default());
+        assert!(!form_data.valid);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::GlobalEventCx;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{GridWidget, SingleWidget};
+
+use druid::EventCtx;
+
+use tracing::instrument;
+
+/// A container which arranges its children in rows of equal-width columns.
+///
+/// Children flow left-to-right, top-to-bottom; the last row may have fewer children than
+/// the others. A child can take up several columns with
+/// [`ElementExt::span`](crate::ElementExt::span).
+///
+/// The grid takes all the width it's given, and shares it between its columns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<Child: Element> {
+    pub columns: usize,
+    pub child: Child,
+    pub flex: FlexParams,
+    pub spacing: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridData<Child: VirtualDom> {
+    pub columns: usize,
+    pub child: Child,
+    pub flex: FlexParams,
+    pub spacing: f64,
+}
+
+// ----
+
+impl<Child: Element> Grid<Child> {
+    /// Build a grid with the given number of columns.
+    ///
+    /// `child` is usually a tuple or a list of elements, each of which fills one cell.
+    pub fn new(columns: usize, child: Child) -> Self {
+        Grid {
+            columns,
+            child,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            spacing: 0.0,
+        }
+    }
+
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Grid {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// Set the space between adjacent columns and adjacent rows.
+    pub fn with_spacing(self, spacing: f64) -> Self {
+        Grid { spacing, ..self }
+    }
+}
+
+// ----
+
+impl<Child: Element> Element for Grid<Child> {
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = GridData<Child::BuildOutput>;
+
+    #[instrument(name = "Grid", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, children_state) = self.child.build(prev_state);
+        (
+            GridData {
+                columns: self.columns,
+                child: element,
+                flex: self.flex,
+                spacing: self.spacing,
+            },
+            children_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for GridData<Child> {
+    type Event = NoEvent;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleWidget<GridWidget<Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Grid", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let grid = GridWidget {
+            columns: self.columns,
+            spacing: self.spacing,
+            children_seq: self.child.init_tree(),
+        };
+        SingleWidget::new(grid, self.flex)
+    }
+
+    #[instrument(name = "Grid", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.columns != prev_value.columns || self.spacing != prev_value.spacing {
+            let grid_widget = widget_seq.widget_mut();
+            grid_widget.columns = self.columns;
+            grid_widget.spacing = self.spacing;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+        widget_seq.flex = self.flex;
+
+        // Children keep their widgets; added or removed cells only shift their neighbors.
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children_seq,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.columns = other.columns;
+        self.flex = other.flex;
+        self.spacing = other.spacing;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Grid", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children_seq,
+            cx,
+        )
+    }
+}
+
+// ----
+
+/// Builds a grid of up to 12 Elements, with the given number of columns.
+///
+/// Returns [`Grid`].
+///
+/// ## Events
+///
+/// Returned element doesn't emit events.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::{Grid, ElementExt};
+/// # use panoramix::elements::{Label, TextBox};
+/// let form = Grid!(2;
+///     Label::new("Name"),
+///     TextBox::new(""),
+///     Label::new("Every field is required").span(2),
+/// );
+/// ```
+#[macro_export]
+macro_rules! Grid {
+    ( $columns:expr; $($arg:expr),* $(,)?) => {
+        $crate::elements::Grid::new(
+            $columns,
+            $crate::Tuple!($($arg,)*)
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{ElementList, Label, SizedBox};
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    #[test]
+    fn new_grid() {
+        let grid = Grid!(3; Label::new("Hello"), Label::new("World")).with_spacing(4.0);
+        let (grid_data, _) = grid.build(Default::default());
+
+        assert_eq!(grid_data.columns, 3);
+        assert_eq!(grid_data.spacing, 4.0);
+    }
+
+    #[test]
+    fn grid_with_span() {
+        let ids: Vec<_> = (1..=5).map(WidgetId::reserved).collect();
+
+        // The test window is 400px wide, so each column is 200px wide.
+        let grid = Grid!(2;
+            SizedBox::empty(50.0, 10.0).with_reserved_id(ids[0]),
+            SizedBox::empty(50.0, 20.0).with_reserved_id(ids[1]),
+            SizedBox::empty(f64::INFINITY, 10.0)
+                .with_reserved_id(ids[2])
+                .span(2),
+            SizedBox::empty(f64::INFINITY, 10.0).with_reserved_id(ids[3]),
+            SizedBox::empty(50.0, 10.0).with_reserved_id(ids[4]),
+            // Uneven final row
+            Label::new("Last"),
+        );
+
+        Harness::run_test_window(grid, |harness| {
+            let mut rect = |id| harness.get_state(id).layout_rect();
+
+            assert_eq!((rect(ids[0]).x0, rect(ids[0]).y0), (0.0, 0.0));
+            assert_eq!((rect(ids[1]).x0, rect(ids[1]).y0), (200.0, 0.0));
+
+            // The spanning cell is laid out inside its Span wrapper
+            assert_eq!(rect(ids[2]).width(), 400.0);
+
+            assert_eq!(rect(ids[3]).width(), 200.0);
+            assert_eq!((rect(ids[3]).x0, rect(ids[3]).y0), (0.0, 30.0));
+            assert_eq!((rect(ids[4]).x0, rect(ids[4]).y0), (200.0, 30.0));
+        });
+    }
+
+    #[test]
+    fn grid_add_remove_children() {
+        let ids: Vec<_> = (1..=3).map(WidgetId::reserved).collect();
+        let make_grid = |count: usize| {
+            let keys = ids[0..count].iter().map(|id| format!("{:?}", id));
+            let cells = ids[0..count]
+                .iter()
+                .map(|id| SizedBox::empty(50.0, 10.0).with_reserved_id(*id));
+            Grid::new(2, ElementList::from_keys_elems(keys, cells))
+        };
+
+        Harness::run_test_window(make_grid(1), |harness| {
+            harness.update_root_element(make_grid(3));
+            assert_eq!(harness.get_state(ids[1]).layout_rect().origin().x, 200.0);
+            assert_eq!(harness.get_state(ids[2]).layout_rect().origin().y, 10.0);
+
+            harness.update_root_element(make_grid(2));
+            assert_eq!(harness.get_state(ids[0]).layout_rect().origin().x, 0.0);
+            assert_eq!(harness.get_state(ids[1]).layout_rect().origin().x, 200.0);
+            assert!(harness.try_get_state(ids[2]).is_none());
+        });
+    }
+}
@@ -0,0 +1,295 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::internals::{ParsedSvg, SvgData};
+use crate::elements::{ButtonClick, Svg, Tooltip};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{IconButtonWidget, SingleIconButtonWidget, SingleSvgWidget};
+
+use druid::widget::FillStrat;
+use druid::EventCtx;
+use tracing::{instrument, trace};
+
+const DEFAULT_ICON_SIZE: f64 = 16.0;
+// Same as druid's default text color
+const ICON_COLOR: &str = "#f0f0ea";
+
+/// The icons which can be displayed by an [IconButton].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Icon {
+    Close,
+    Add,
+    Remove,
+    Menu,
+    ChevronLeft,
+    ChevronRight,
+    ChevronUp,
+    ChevronDown,
+}
+
+/// A button displaying an icon instead of a text.
+///
+/// The clickable area extends a few pixels past the icon, so that small icons are still easy
+/// to hit. Useful for toolbars.
+///
+/// ## Events
+///
+/// Emits [ButtonClick] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IconButton {
+    pub icon: Icon,
+    pub size: f64,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IconButtonData {
+    pub icon: Icon,
+    pub svg: SvgData,
+    pub size: f64,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl Icon {
+    /// Every available icon.
+    pub const ALL: [Icon; 8] = [
+        Icon::Close,
+        Icon::Add,
+        Icon::Remove,
+        Icon::Menu,
+        Icon::ChevronLeft,
+        Icon::ChevronRight,
+        Icon::ChevronUp,
+        Icon::ChevronDown,
+    ];
+
+    // The path of the icon, drawn with a thick stroke on a 24x24 grid
+    fn path(self) -> &'static str {
+        match self {
+            Icon::Close => "M6 6 L18 18 M18 6 L6 18",
+            Icon::Add => "M12 5 V19 M5 12 H19",
+            Icon::Remove => "M5 12 H19",
+            Icon::Menu => "M4 6 H20 M4 12 H20 M4 18 H20",
+            Icon::ChevronLeft => "M15 6 L9 12 L15 18",
+            Icon::ChevronRight => "M9 6 L15 12 L9 18",
+            Icon::ChevronUp => "M6 15 L12 9 L18 15",
+            Icon::ChevronDown => "M6 9 L12 15 L18 9",
+        }
+    }
+
+    /// The SVG markup of the icon.
+    pub fn svg_markup(self) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="24" viewBox="0 0 24 24"><path d="{}" fill="none" stroke="{}" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"/></svg>"#,
+            self.path(),
+            ICON_COLOR
+        )
+    }
+}
+
+impl IconButton {
+    /// Build a button displaying the given icon.
+    ///
+    /// Use the [.on_click](IconButton::on_click) method to provide a closure to be called when the button is clicked.
+    pub fn new(icon: Icon) -> Self {
+        IconButton {
+            icon,
+            size: DEFAULT_ICON_SIZE,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the size of the icon. The button itself is a bit larger.
+    pub fn with_size(self, size: f64) -> Self {
+        IconButton { size, ..self }
+    }
+
+    /// Show a text next to the mouse cursor while it hovers the button.
+    ///
+    /// Equivalent to [`ElementExt::tooltip`](crate::ElementExt::tooltip); this must be the
+    /// last method called on the button.
+    pub fn with_tooltip(self, text: impl Into<String>) -> Tooltip<Self> {
+        Tooltip::new(self, text)
+    }
+
+    /// Change the way the button's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        IconButton {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The button created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same button is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        IconButton {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this button is clicked.
+    pub fn on_click<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, ButtonClick) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+impl Element for IconButton {
+    type Event = ButtonClick;
+
+    type ComponentState = NoState;
+    // The icon is only parsed again when it changes
+    type AggregateChildrenState = ParsedSvg;
+    type BuildOutput = IconButtonData;
+
+    #[instrument(name = "IconButton", skip(self, prev_state))]
+    fn build(self, prev_state: ParsedSvg) -> (IconButtonData, ParsedSvg) {
+        let (svg, state) = Svg::from_str(&self.icon.svg_markup())
+            .fit(FillStrat::Contain)
+            .build(prev_state);
+        (
+            IconButtonData {
+                icon: self.icon,
+                svg,
+                size: self.size,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl VirtualDom for IconButtonData {
+    type Event = ButtonClick;
+    type AggregateChildrenState = ParsedSvg;
+
+    type TargetWidgetSeq = SingleIconButtonWidget<SingleSvgWidget>;
+
+    #[instrument(name = "IconButton", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleIconButtonWidget::new(
+            IconButtonWidget::new(
+                self.svg.init_tree(),
+                self.size,
+                format!("{:?}", self.icon),
+                id,
+            ),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "IconButton", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.svg
+            .reconcile(&prev_value.svg, &mut widget.widget_mut().icon, ctx);
+        if self.icon != prev_value.icon {
+            widget.widget_mut().set_label(format!("{:?}", self.icon));
+        }
+        if self.size != prev_value.size {
+            let size = self.size;
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut IconButtonWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_icon_size(size, ctx);
+                },
+            );
+        }
+        widget.flex = self.flex;
+    }
+
+    #[instrument(name = "IconButton", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<ButtonClick> {
+        let id = widget.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            trace!("Processed icon button click");
+            Some(ButtonClick)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_icon_button() {
+        let button = IconButton::new(Icon::Close).with_size(24.0);
+        let (button_data, _) = button.build(Default::default());
+
+        assert_eq!(button_data.icon, Icon::Close);
+        assert_eq!(button_data.size, 24.0);
+        assert!(button_data.svg.svg.is_some());
+    }
+
+    #[test]
+    fn all_icons_parse() {
+        for icon in Icon::ALL.iter() {
+            let (svg_data, _) = Svg::from_str(&icon.svg_markup()).build(Default::default());
+            assert!(svg_data.svg.is_some(), "{:?} could not be parsed", icon);
+        }
+    }
+
+    #[test]
+    fn icon_button_click() {
+        let (event_sender, event_receiver) = channel();
+        let button_id = WidgetId::reserved(1);
+        let button = |icon| {
+            EventLogger::new(
+                event_sender.clone(),
+                IconButton::new(icon)
+                    .with_size(16.0)
+                    .with_reserved_id(button_id),
+            )
+        };
+
+        Harness::run_test_window(button(Icon::Close), |harness| {
+            // The hit area is larger than the icon
+            let rect = harness.get_state(button_id).layout_rect();
+            assert!(rect.width() > 16.0 && rect.height() > 16.0);
+
+            harness.click_on("Close");
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+
+            harness.update_root_element(button(Icon::Menu));
+            assert_eq!(harness.get_debug_state(button_id).main_value, "Menu");
+            harness.click_on("Menu");
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+        });
+    }
+}
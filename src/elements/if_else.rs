@@ -0,0 +1,254 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::glue::GlobalEventCx;
+use crate::metadata::NoState;
+
+use either::{Either, Left, Right};
+use tracing::{debug_span, info, instrument};
+use tracing_unwrap::OptionExt;
+
+/// Element which displays one of two branches, depending on a condition.
+///
+/// Both branches are constructed, but only the active one is built. When the condition changes,
+/// the widgets of the previous branch are destroyed and the widgets of the new branch are
+/// created. Both branches must have the same event type.
+///
+/// ## Events
+///
+/// Emits the events of the active branch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct If<Then: Element, Else: Element<Event = Then::Event>> {
+    pub condition: bool,
+    pub then_branch: Then,
+    pub else_branch: Else,
+}
+
+/// Virtual DOM node of [`If`], holding the branch that was built.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IfData<Then: VirtualDom, Else: VirtualDom<Event = Then::Event>> {
+    Then(Then),
+    Else(Else),
+}
+
+//
+// --- IMPLS
+
+impl<Then: Element, Else: Element<Event = Then::Event>> If<Then, Else> {
+    pub fn new(condition: bool, then_branch: Then, else_branch: Else) -> Self {
+        If {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+}
+
+impl<Then: Element, Else: Element<Event = Then::Event>> Element for If<Then, Else> {
+    type Event = Then::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState =
+        Option<Either<Then::AggregateChildrenState, Else::AggregateChildrenState>>;
+    type BuildOutput = IfData<Then::BuildOutput, Else::BuildOutput>;
+
+    #[instrument(name = "If", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        if self.condition {
+            let prev_state = prev_state.map_or(None, |ps| ps.left()).unwrap_or_default();
+            let (output, state) = self.then_branch.build(prev_state);
+            (IfData::Then(output), Some(Left(state)))
+        } else {
+            let prev_state = prev_state.map_or(None, |ps| ps.right()).unwrap_or_default();
+            let (output, state) = self.else_branch.build(prev_state);
+            (IfData::Else(output), Some(Right(state)))
+        }
+    }
+}
+
+impl<Then: VirtualDom, Else: VirtualDom<Event = Then::Event>> VirtualDom for IfData<Then, Else> {
+    type Event = Then::Event;
+    type AggregateChildrenState =
+        Option<Either<Then::AggregateChildrenState, Else::AggregateChildrenState>>;
+    type TargetWidgetSeq = Either<Then::TargetWidgetSeq, Else::TargetWidgetSeq>;
+
+    #[instrument(name = "If", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        match self {
+            IfData::Then(child) => Left(child.init_tree()),
+            IfData::Else(child) => Right(child.init_tree()),
+        }
+    }
+
+    #[instrument(name = "If", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        match (self, prev_value) {
+            (IfData::Then(child), IfData::Then(prev_child)) => {
+                let widget_seq = widget_seq.as_mut().left().expect_or_log(
+                    "The previous value of this element was Then. Expected Left widget.",
+                );
+                child.reconcile(prev_child, widget_seq, ctx);
+                ctx.stats.updated += 1;
+            }
+            (IfData::Else(child), IfData::Else(prev_child)) => {
+                let widget_seq = widget_seq.as_mut().right().expect_or_log(
+                    "The previous value of this element was Else. Expected Right widget.",
+                );
+                child.reconcile(prev_child, widget_seq, ctx);
+                ctx.stats.updated += 1;
+            }
+
+            (IfData::Then(child), IfData::Else(prev_child)) => {
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
+                debug_span!("init_tree").in_scope(|| {
+                    info!("switching to then branch");
+                    *widget_seq = Left(child.init_tree());
+                });
+            }
+            (IfData::Else(child), IfData::Then(prev_child)) => {
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
+                debug_span!("init_tree").in_scope(|| {
+                    info!("switching to else branch");
+                    *widget_seq = Right(child.init_tree());
+                });
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        match (self, other) {
+            (IfData::Then(child), IfData::Then(other_child)) => child.update_value(other_child),
+            (IfData::Else(child), IfData::Else(other_child)) => child.update_value(other_child),
+            (this, other) => *this = other,
+        }
+    }
+
+    fn unmount(&self) {
+        match self {
+            IfData::Then(child) => child.unmount(),
+            IfData::Else(child) => child.unmount(),
+        }
+    }
+
+    #[instrument(name = "If", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let children_state = children_state.as_mut().unwrap_or_log().as_mut();
+        match self {
+            IfData::Then(child) => child.process_event(
+                comp_ctx,
+                children_state.left().unwrap_or_log(),
+                widget_seq.as_mut().left().unwrap_or_log(),
+                cx,
+            ),
+            IfData::Else(child) => child.process_event(
+                comp_ctx,
+                children_state.right().unwrap_or_log(),
+                widget_seq.as_mut().right().unwrap_or_log(),
+                cx,
+            ),
+        }
+    }
+
+    #[instrument(name = "If", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let children_state = children_state.as_mut()?.as_mut();
+        match self {
+            IfData::Then(child) => {
+                child.process_local_event(children_state.left()?, widget_seq.as_mut().left()?, cx)
+            }
+            IfData::Else(child) => {
+                child.process_local_event(children_state.right()?, widget_seq.as_mut().right()?, cx)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::button::{Button, ButtonClick, ButtonData};
+    use crate::elements::event_logger::EventLogger;
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn button_if(condition: bool) -> If<Button, Button> {
+        If::new(
+            condition,
+            Button::new("Then").with_reserved_id(WidgetId::reserved(1)),
+            Button::new("Else").with_reserved_id(WidgetId::reserved(2)),
+        )
+    }
+
+    #[test]
+    fn new_if() {
+        let (if_data, state) = button_if(true).build(None);
+        assert!(matches!(&if_data, IfData::Then(ButtonData { text, .. }) if text == "Then"));
+        assert!(matches!(state, Some(Left(_))));
+
+        let (if_data, state) = button_if(false).build(state);
+        assert!(matches!(&if_data, IfData::Else(ButtonData { text, .. }) if text == "Else"));
+        assert!(matches!(state, Some(Right(_))));
+    }
+
+    #[test]
+    fn if_widget() {
+        let then_id = WidgetId::reserved(1);
+        let else_id = WidgetId::reserved(2);
+
+        Harness::run_test_window(button_if(true), |harness| {
+            assert!(harness.try_get_state(then_id).is_some());
+            assert!(harness.try_get_state(else_id).is_none());
+
+            // Same branch; widgets are reconciled in place
+            harness.update_root_element(button_if(true));
+            assert!(harness.try_get_state(then_id).is_some());
+            assert!(harness.try_get_state(else_id).is_none());
+
+            harness.update_root_element(button_if(false));
+            assert!(harness.try_get_state(then_id).is_none());
+            assert!(harness.try_get_state(else_id).is_some());
+
+            harness.update_root_element(button_if(true));
+            assert!(harness.try_get_state(then_id).is_some());
+            assert!(harness.try_get_state(else_id).is_none());
+        });
+    }
+
+    #[test]
+    fn if_events() {
+        let (event_sender, event_receiver) = channel();
+        let if_element = |condition| EventLogger::new(event_sender.clone(), button_if(condition));
+
+        Harness::run_test_window(if_element(false), |harness| {
+            harness.mouse_click_on(WidgetId::reserved(2));
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+
+            harness.update_root_element(if_element(true));
+            harness.mouse_click_on(WidgetId::reserved(1));
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+        });
+    }
+}
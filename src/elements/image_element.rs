@@ -0,0 +1,262 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::WidgetId;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{ImageWidget, SingleImageWidget};
+
+use derivative::Derivative;
+use druid::widget::FillStrat;
+use druid::{EventCtx, ImageBuf};
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{instrument, trace, warn};
+
+pub use image::ImageFormat;
+
+/// Displays an image, loaded from encoded bytes or from a file.
+///
+/// The image is decoded when the element is built, and only decoded again when its source
+/// changes. If the image can't be decoded, a placeholder is displayed instead.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub source: ImageSource,
+    pub fill_mode: FillStrat,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Where an [Image] gets its data from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageSource {
+    /// Encoded image data, eg the content of a PNG file.
+    Bytes(Arc<[u8]>, ImageFormat),
+    /// Path to an image file. The format is guessed from the file's content.
+    Path(PathBuf),
+}
+
+#[derive(Derivative, Clone)]
+#[derivative(Debug, PartialEq)]
+pub struct ImageData {
+    pub source: ImageSource,
+    /// The decoded image, or `None` if it couldn't be decoded.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub image: Option<ImageBuf>,
+    pub fill_mode: FillStrat,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// The last decoded image, kept between builds so that it isn't decoded again.
+#[derive(Derivative, Clone, Default)]
+#[derivative(Debug, PartialEq)]
+pub struct DecodedImage {
+    source: Option<ImageSource>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    image: Option<ImageBuf>,
+}
+
+//
+// --- IMPLS
+
+impl Image {
+    /// Build an image from encoded data, eg the content of a PNG file.
+    pub fn from_bytes(bytes: &[u8], format: ImageFormat) -> Self {
+        Image::new(ImageSource::Bytes(bytes.into(), format))
+    }
+
+    /// Build an image from a file.
+    ///
+    /// The file is read when the element is built.
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        Image::new(ImageSource::Path(path.into()))
+    }
+
+    fn new(source: ImageSource) -> Self {
+        Image {
+            source,
+            fill_mode: FillStrat::Contain,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the image is scaled to fit its widget.
+    pub fn fill_mode(self, fill_mode: FillStrat) -> Self {
+        Image { fill_mode, ..self }
+    }
+
+    /// Change the way the image's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Image {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The image created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same image is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Image {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl ImageSource {
+    fn decode(&self) -> Result<ImageBuf, Box<dyn Error>> {
+        match self {
+            ImageSource::Bytes(bytes, format) => {
+                let image = image::load_from_memory_with_format(bytes, *format)?;
+                Ok(ImageBuf::from_dynamic_image(image))
+            }
+            ImageSource::Path(path) => ImageBuf::from_file(path),
+        }
+    }
+}
+
+impl Element for Image {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = DecodedImage;
+    type BuildOutput = ImageData;
+
+    #[instrument(name = "Image", skip(self, prev_state))]
+    fn build(self, prev_state: DecodedImage) -> (ImageData, DecodedImage) {
+        let image = if prev_state.source.as_ref() == Some(&self.source) {
+            prev_state.image
+        } else {
+            trace!("Decoding image");
+            match self.source.decode() {
+                Ok(image) => Some(image),
+                Err(error) => {
+                    warn!("Could not load image {:?}: {}", self.source, error);
+                    None
+                }
+            }
+        };
+
+        (
+            ImageData {
+                source: self.source.clone(),
+                image: image.clone(),
+                fill_mode: self.fill_mode,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            DecodedImage {
+                source: Some(self.source),
+                image,
+            },
+        )
+    }
+}
+
+impl VirtualDom for ImageData {
+    type Event = NoEvent;
+    type AggregateChildrenState = DecodedImage;
+
+    type TargetWidgetSeq = SingleImageWidget;
+
+    #[instrument(name = "Image", skip(self))]
+    fn init_tree(&self) -> SingleImageWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleImageWidget::new(
+            ImageWidget::new(self.image.clone(), self.fill_mode, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Image", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut SingleImageWidget, ctx: &mut ReconcileCtx) {
+        if self.source != prev_value.source {
+            let image = self.image.clone();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ImageWidget, ctx: &mut EventCtx| {
+                    widget.set_image(image.clone(), ctx);
+                },
+            );
+        }
+        if self.fill_mode != prev_value.fill_mode {
+            let fill_mode = self.fill_mode;
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ImageWidget, ctx: &mut EventCtx| {
+                    widget.set_fill_mode(fill_mode, ctx);
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    // A 3x2 red PNG image
+    const PNG_DATA: &[u8] = &[
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x08, 0x06, 0x00, 0x00, 0x00, 0x9d,
+        0x74, 0x66, 0x1a, 0x00, 0x00, 0x00, 0x11, 0x49, 0x44, 0x41, 0x54, 0x78, 0xda, 0x63, 0xf8,
+        0xcf, 0xc0, 0xf0, 0x1f, 0x86, 0x19, 0x90, 0x39, 0x00, 0x9b, 0x7e, 0x0b, 0xf5, 0x0f, 0x5f,
+        0x26, 0x22, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    #[test]
+    fn new_image() {
+        let image = Image::from_bytes(PNG_DATA, ImageFormat::Png).fill_mode(FillStrat::Fill);
+        let (image_data, state) = image.build(Default::default());
+
+        assert_eq!(image_data.fill_mode, FillStrat::Fill);
+        let decoded = image_data.image.unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (3, 2));
+        assert_eq!(
+            state.source,
+            Some(ImageSource::Bytes(PNG_DATA.into(), ImageFormat::Png))
+        );
+    }
+
+    #[test]
+    fn invalid_image() {
+        let image = Image::from_bytes(&[1, 2, 3], ImageFormat::Png);
+        let (image_data, _) = image.build(Default::default());
+        assert!(image_data.image.is_none());
+
+        let image = Image::from_path("this/file/does/not/exist.png");
+        let (image_data, _) = image.build(Default::default());
+        assert!(image_data.image.is_none());
+    }
+
+    #[test]
+    fn image_widget() {
+        let image_id = WidgetId::reserved(1);
+        let image = Image::from_bytes(PNG_DATA, ImageFormat::Png).with_reserved_id(image_id);
+
+        Harness::run_test_window(image, |harness| {
+            assert_eq!(harness.get_debug_state(image_id).main_value, "3x2");
+
+            let placeholder = Image::from_bytes(&[1, 2, 3], ImageFormat::Png);
+            harness.update_root_element(placeholder.with_reserved_id(image_id));
+            assert_eq!(
+                harness.get_debug_state(image_id).main_value,
+                "Image could not be loaded"
+            );
+        });
+    }
+}
@@ -0,0 +1,224 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::glue::GlobalEventCx;
+use crate::metadata::NoState;
+use crate::widget_sequence::sequence_layout_rect;
+
+use druid::Rect;
+use tracing::instrument;
+
+/// Wrapper which gives its child an identity within a [Tuple](crate::Tuple),
+/// [Row](crate::Row) or [Column](crate::Column).
+///
+/// The children of a group are usually matched with the previous children at the same
+/// position. Keyed children are first matched with the previous child which had the same key
+/// and the same type, so that their local state and widgets follow them when the group's
+/// children are reordered. Children which aren't matched by key are matched by position.
+///
+/// Usually created with [`ElementExt::key`](crate::ElementExt::key).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Keyed<Child: Element> {
+    pub key: String,
+    pub child: Child,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeyedData<Child: VirtualDom> {
+    pub key: String,
+    pub child: Child,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Keyed<Child> {
+    pub fn new(key: impl Into<String>, child: Child) -> Self {
+        Keyed {
+            key: key.into(),
+            child,
+        }
+    }
+}
+
+impl<Child: Element> Element for Keyed<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The key is kept with the child's state, so that the state can follow the key
+    type AggregateChildrenState = (Option<String>, Child::AggregateChildrenState);
+    type BuildOutput = KeyedData<Child::BuildOutput>;
+
+    #[instrument(name = "Keyed", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (mut child, state) = self.child.build(prev_state.1);
+        child.set_debug_key(&self.key);
+        (
+            KeyedData {
+                key: self.key.clone(),
+                child,
+            },
+            (Some(self.key), state),
+        )
+    }
+
+    fn element_key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+
+    fn get_state_key(state: &Self::AggregateChildrenState) -> Option<&str> {
+        state.0.as_deref()
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for KeyedData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = (Option<String>, Child::AggregateChildrenState);
+    type TargetWidgetSeq = Child::TargetWidgetSeq;
+
+    #[instrument(name = "Keyed", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.child.init_tree()
+    }
+
+    #[instrument(name = "Keyed", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.child.reconcile(&prev_value.child, widget_seq, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.key = other.key;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    fn key(&self) -> Option<&str> {
+        Some(&self.key)
+    }
+
+    fn find_key_rect(&self, key: &str, widget_seq: &mut Self::TargetWidgetSeq) -> Option<Rect> {
+        if self.key == key {
+            sequence_layout_rect(widget_seq)
+        } else {
+            self.child.find_key_rect(key, widget_seq)
+        }
+    }
+
+    #[instrument(name = "Keyed", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child
+            .process_event(comp_ctx, &mut children_state.1, widget_seq, cx);
+    }
+
+    #[instrument(name = "Keyed", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(&mut children_state.1, widget_seq, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::Label;
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use crate::{Column, Tuple};
+    use test_env_log::test;
+
+    #[test]
+    fn state_follows_key() {
+        let tuple = Tuple!(
+            MockComponent::new().key("a"),
+            MockComponent::new().key("b"),
+            MockComponent::new(),
+        );
+        let (_, mut state) = tuple.build(Default::default());
+        (state.0).1 = MockState::new("state-a");
+        (state.1).1 = MockState::new("state-b");
+        state.2 = MockState::new("unkeyed");
+
+        let tuple = Tuple!(
+            MockComponent::new().key("b"),
+            MockComponent::new().key("a"),
+            MockComponent::new(),
+        );
+        let (_, state) = tuple.build(state);
+        assert_eq!(state.0, (Some("b".to_string()), MockState::new("state-b")));
+        assert_eq!(state.1, (Some("a".to_string()), MockState::new("state-a")));
+        assert_eq!(state.2, MockState::new("unkeyed"));
+    }
+
+    #[test]
+    fn key_matches_same_type_only() {
+        let tuple = |keys: [&str; 3]| {
+            Tuple!(
+                MockComponent::new().key(keys[0]),
+                Label::new("Hello").key(keys[1]),
+                MockComponent::new().key(keys[2]),
+            )
+        };
+        let (_, mut state) = tuple(["a", "b", "c"]).build(Default::default());
+        (state.0).1 = MockState::new("state-a");
+        (state.2).1 = MockState::new("state-c");
+
+        // The label's key moves to the first slot, but the first slot can't take the label's
+        // state, and the label's slot can't take the state of "a": both fall back to position
+        let (_, state) = tuple(["b", "a", "c"]).build(state);
+        assert_eq!(state.0, (Some("b".to_string()), MockState::new("state-a")));
+        assert_eq!(state.2, (Some("c".to_string()), MockState::new("state-c")));
+    }
+
+    #[test]
+    fn widgets_follow_key() {
+        let a_id = WidgetId::reserved(1);
+        let b_id = WidgetId::reserved(2);
+        let column = |a_first: bool| {
+            let a = MockComponent::new().with_reserved_id(a_id).key("a");
+            let b = MockComponent::new().with_reserved_id(b_id).key("b");
+            if a_first {
+                Column!(a, b)
+            } else {
+                Column!(b, a)
+            }
+        };
+
+        Harness::run_test_window(column(true), |harness| {
+            let a_rect = harness.get_state(a_id).layout_rect();
+            let b_rect = harness.get_state(b_id).layout_rect();
+            assert!(a_rect.y0 < b_rect.y0);
+
+            // Each widget moves with its key, so a is now below b
+            harness.update_root_element(column(false));
+            let a_rect = harness.get_state(a_id).layout_rect();
+            let b_rect = harness.get_state(b_id).layout_rect();
+            assert!(b_rect.y0 < a_rect.y0);
+        });
+    }
+}
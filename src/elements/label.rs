@@ -6,6 +6,7 @@ use crate::metadata::{NoEvent, NoState};
 use crate::widgets::SingleWidget;
 
 use druid::widget as druid_w;
+use druid::{theme, Color, KeyOrValue};
 
 use tracing::instrument;
 
@@ -18,12 +19,16 @@ use tracing::instrument;
 pub struct Label {
     pub text: String,
     pub flex: FlexParams,
+    pub text_color: Option<Color>,
+    pub font_size: Option<f64>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct LabelData {
     pub text: String,
     pub flex: FlexParams,
+    pub text_color: Option<Color>,
+    pub font_size: Option<f64>,
 }
 
 //
@@ -38,6 +43,28 @@ impl Label {
                 flex: None,
                 alignment: None,
             },
+            text_color: None,
+            font_size: None,
+        }
+    }
+
+    /// Set the color of the text.
+    ///
+    /// By default, the label uses the text color of the theme.
+    pub fn with_text_color(self, text_color: Color) -> Self {
+        Label {
+            text_color: Some(text_color),
+            ..self
+        }
+    }
+
+    /// Set the font size of the text.
+    ///
+    /// By default, the label uses the text size of the theme.
+    pub fn with_font_size(self, font_size: f64) -> Self {
+        Label {
+            font_size: Some(font_size),
+            ..self
         }
     }
 
@@ -58,8 +85,23 @@ impl LabelData {
                 flex: None,
                 alignment: None,
             },
+            text_color: None,
+            font_size: None,
         }
     }
+
+    // Unset values are reset to the theme's defaults
+    fn apply_style(&self, label: &mut druid_w::Label<DruidAppData>) {
+        let text_color = self
+            .text_color
+            .clone()
+            .map_or_else(|| theme::LABEL_COLOR.into(), KeyOrValue::from);
+        let font_size = self
+            .font_size
+            .map_or_else(|| theme::TEXT_SIZE_NORMAL.into(), KeyOrValue::from);
+        label.set_text_color(text_color);
+        label.set_text_size(font_size);
+    }
 }
 
 impl Element for Label {
@@ -74,6 +116,8 @@ impl Element for Label {
             LabelData {
                 text: self.text,
                 flex: self.flex,
+                text_color: self.text_color,
+                font_size: self.font_size,
             },
             (),
         )
@@ -87,7 +131,13 @@ impl VirtualDom for LabelData {
 
     #[instrument(name = "Label", skip(self))]
     fn init_tree(&self) -> Self::TargetWidgetSeq {
-        let label = druid_w::Label::new(self.text.clone());
+        let mut label = druid_w::Label::new(self.text.clone());
+        if let Some(text_color) = self.text_color.clone() {
+            label.set_text_color(text_color);
+        }
+        if let Some(font_size) = self.font_size {
+            label.set_text_size(font_size);
+        }
         SingleWidget::new(label, self.flex)
     }
 
@@ -98,8 +148,16 @@ impl VirtualDom for LabelData {
         widget: &mut Self::TargetWidgetSeq,
         ctx: &mut ReconcileCtx,
     ) {
+        let mut changed = false;
         if self.text != prev_value.text {
             widget.pod.widget_mut().set_text(self.text.clone());
+            changed = true;
+        }
+        if self.text_color != prev_value.text_color || self.font_size != prev_value.font_size {
+            self.apply_style(widget.pod.widget_mut());
+            changed = true;
+        }
+        if changed {
             widget.request_druid_update(ctx.event_ctx);
         }
     }
@@ -139,4 +197,40 @@ mod tests {
             assert_debug_snapshot!(label_state_2);
         });
     }
+    #[test]
+    fn label_style() {
+        let label = Label::new("Hello")
+            .with_text_color(Color::rgb8(255, 0, 0))
+            .with_font_size(24.0);
+        let (label_data, ()) = label.build(());
+
+        assert_eq!(
+            label_data,
+            LabelData {
+                text_color: Some(Color::rgb8(255, 0, 0)),
+                font_size: Some(24.0),
+                ..LabelData::new("Hello")
+            }
+        );
+    }
+
+    #[test]
+    fn label_text_update() {
+        use crate::test_harness::Harness;
+
+        let label_text = |harness: &Harness<'_, '_, Label>| {
+            let root_state = format!("{:?}", harness.get_root_debug_state());
+            (root_state.contains("Hello"), root_state.contains("World"))
+        };
+
+        Harness::run_test_window(Label::new("Hello"), |harness| {
+            assert_eq!(label_text(harness), (true, false));
+
+            harness.update_root_element(Label::new("World"));
+            assert_eq!(label_text(harness), (false, true));
+
+            harness.update_root_element(Label::new("World").with_font_size(24.0));
+            assert_eq!(label_text(harness), (false, true));
+        });
+    }
 }
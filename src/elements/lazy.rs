@@ -0,0 +1,265 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{LazyWidget, SingleLazyWidget};
+
+use druid::{EventCtx, Size};
+use tracing::{debug_span, info, instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// Wrapper which only builds its child once it has been visible.
+///
+/// Until the wrapper is laid out somewhere inside the window, eg because a scroll area around
+/// it has been scrolled far enough, the child isn't built, and an empty placeholder of size
+/// `size_hint` is displayed instead. Once built, the child stays built, even if it's scrolled
+/// out of view again.
+///
+/// The size hint should be close to the size of the child, so that the content around the
+/// wrapper doesn't jump when the child replaces the placeholder.
+///
+/// Clipping by scroll areas is ignored: an element hidden by a scroll area, but inside the
+/// window, counts as visible.
+///
+/// ## Events
+///
+/// Emits the events of the child once it's built.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lazy<Child: Element> {
+    pub child: Child,
+    pub size_hint: Size,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LazyData<Child: VirtualDom> {
+    pub child: Option<Child>,
+    pub size_hint: Size,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Lazy<Child> {
+    pub fn new(child: Child, size_hint: Size) -> Self {
+        Lazy {
+            child,
+            size_hint,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Lazy {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Lazy<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // Whether the wrapper has been visible, and the child's state
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type BuildOutput = LazyData<Child::BuildOutput>;
+
+    #[instrument(name = "Lazy", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (is_revealed, prev_child_state) = prev_state;
+        let (child, child_state) = if is_revealed {
+            let (child, child_state) = self.child.build(prev_child_state);
+            (Some(child), child_state)
+        } else {
+            (None, prev_child_state)
+        };
+        (
+            LazyData {
+                child,
+                size_hint: self.size_hint,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (is_revealed, child_state),
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for LazyData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type TargetWidgetSeq = SingleLazyWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Lazy", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let child = self.child.as_ref().map(|child| child.init_tree());
+        let is_revealed = child.is_some();
+        SingleLazyWidget::new(
+            LazyWidget::new(child, self.size_hint, is_revealed, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Lazy", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let lazy_widget = widget_seq.widget_mut();
+        let mut child_changed = false;
+        match (&self.child, &prev_value.child) {
+            (Some(child), Some(prev_child)) => {
+                let child_widget = lazy_widget.child.as_mut().expect_or_log(
+                    "The previous value of this element had a child. Expected child widget.",
+                );
+                child.reconcile(prev_child, child_widget, ctx);
+                ctx.stats.updated += 1;
+            }
+            (Some(child), None) => {
+                child_changed = true;
+                ctx.stats.created += 1;
+                debug_span!("init_tree").in_scope(|| {
+                    info!("child became visible");
+                    lazy_widget.child = Some(child.init_tree());
+                });
+            }
+            (None, Some(prev_child)) => {
+                child_changed = true;
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                lazy_widget.child = None;
+            }
+            (None, None) => {}
+        }
+
+        let size_changed = self.size_hint != prev_value.size_hint;
+        lazy_widget.size_hint = self.size_hint;
+
+        if child_changed || size_changed {
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |_widget: &mut LazyWidget<_>, ctx: &mut EventCtx| {
+                    if child_changed {
+                        ctx.children_changed();
+                    }
+                    ctx.request_layout();
+                },
+            );
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        match (&mut self.child, other.child) {
+            (Some(child), Some(other_child)) => child.update_value(other_child),
+            (child, other_child) => *child = other_child,
+        }
+        self.size_hint = other.size_hint;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        if let Some(child) = &self.child {
+            child.unmount();
+        }
+    }
+
+    #[instrument(name = "Lazy", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        if let (Some(child), Some(child_widget)) = (&self.child, &mut widget_seq.widget_mut().child)
+        {
+            child.process_event(comp_ctx, &mut children_state.1, child_widget, cx);
+        }
+    }
+
+    #[instrument(name = "Lazy", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::BecameVisible) = cx.app_data.dequeue_action(id) {
+            // The child is built the next time the element is built
+            trace!("Lazy element became visible");
+            children_state.0 = true;
+        }
+
+        let child = self.child.as_ref()?;
+        let child_widget = widget_seq.widget_mut().child.as_mut()?;
+        child.process_local_event(&mut children_state.1, child_widget, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{Label, Skeleton};
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use test_env_log::test;
+
+    #[test]
+    fn new_lazy() {
+        let lazy = Lazy::new(Label::new("Hello"), Size::new(100.0, 20.0));
+
+        let (lazy_data, state) = lazy.clone().build(Default::default());
+        assert!(lazy_data.child.is_none());
+        assert!(!state.0);
+
+        let (lazy_data, _) = lazy.build((true, state.1));
+        assert_eq!(lazy_data.child.unwrap().text, "Hello");
+    }
+
+    #[test]
+    fn lazy_widget() {
+        let lazy_id = WidgetId::reserved(1);
+        let scroll_id = WidgetId::reserved(2);
+        let column = Column!(
+            Skeleton::new(100.0, 1000.0),
+            Lazy::new(Label::new("Lazy content"), Size::new(100.0, 40.0)).with_reserved_id(lazy_id),
+        )
+        .scroll_vertical()
+        .with_reserved_id(scroll_id);
+
+        Harness::run_test_window(column, |harness| {
+            // Below the fold, only the placeholder is displayed
+            assert!(harness.find_by_name("Lazy content").is_none());
+            let rect = harness.get_state(lazy_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(100.0, 40.0));
+            let values = harness.get_debug_state(lazy_id).other_values;
+            assert_eq!(values["is_revealed"], "false");
+
+            harness.mouse_move_to(scroll_id);
+            harness.mouse_wheel((0.0, 1000.0));
+            assert!(harness.find_by_name("Lazy content").is_some());
+
+            // The child stays built once it has been visible
+            harness.mouse_wheel((0.0, -1000.0));
+            assert!(harness.find_by_name("Lazy content").is_some());
+        });
+    }
+}
@@ -0,0 +1,294 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{LinkWidget, SingleLinkWidget};
+
+use druid::Color;
+use tracing::{instrument, trace};
+
+/// Color of links which don't set their own.
+const LINK_COLOR: Color = Color::rgb8(0x5c, 0xa6, 0xf2);
+
+/// Underlined text which opens a URL in the system browser when clicked.
+///
+/// The URL is opened once the click has been processed, unless a callback registered with
+/// [.on_click](Link::on_click) handles it first.
+///
+/// ## Events
+///
+/// Emits [LinkClicked] events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Link {
+    pub text: String,
+    pub url: String,
+    pub text_color: Option<Color>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkData {
+    pub text: String,
+    pub url: String,
+    pub text_color: Option<Color>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [Link] is clicked, holding the link's URL.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkClicked(pub String);
+
+//
+// --- IMPLS
+
+impl Link {
+    /// Build a link with the given text, opening `url` when clicked.
+    pub fn new(text: impl Into<String>, url: impl Into<String>) -> Self {
+        Link {
+            text: text.into(),
+            url: url.into(),
+            text_color: None,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the color of the text and its underline.
+    ///
+    /// By default, links are blue.
+    pub fn with_text_color(self, text_color: Color) -> Self {
+        Link {
+            text_color: Some(text_color),
+            ..self
+        }
+    }
+
+    /// Change the way the link's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Link {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Link {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this link is clicked.
+    ///
+    /// If the closure returns true, the click is considered handled, and the URL isn't opened.
+    pub fn on_click<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, LinkClicked) -> bool + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, event: LinkClicked| {
+            if callback(state, event) {
+                trace!("Link click handled, not opening URL");
+                crate::open_url::cancel_url();
+            }
+        })
+    }
+}
+
+impl LinkData {
+    fn text_color(&self) -> Color {
+        self.text_color.clone().unwrap_or(LINK_COLOR)
+    }
+}
+
+impl Element for Link {
+    type Event = LinkClicked;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = LinkData;
+
+    #[instrument(name = "Link", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (LinkData, ()) {
+        (
+            LinkData {
+                text: self.text,
+                url: self.url,
+                text_color: self.text_color,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for LinkData {
+    type Event = LinkClicked;
+    type AggregateChildrenState = ();
+    type TargetWidgetSeq = SingleLinkWidget;
+
+    #[instrument(name = "Link", skip(self))]
+    fn init_tree(&self) -> SingleLinkWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let link = LinkWidget::new(self.text.clone(), self.url.clone(), self.text_color(), id);
+        SingleLinkWidget::new(link, self.flex)
+    }
+
+    #[instrument(name = "Link", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut SingleLinkWidget, ctx: &mut ReconcileCtx) {
+        widget.flex = self.flex;
+        widget.widget_mut().url = self.url.clone();
+
+        let mut changed = false;
+        if self.text != prev_value.text {
+            widget.widget_mut().set_text(self.text.clone());
+            changed = true;
+        }
+        if self.text_color != prev_value.text_color {
+            widget.widget_mut().set_text_color(self.text_color());
+            changed = true;
+        }
+        if changed {
+            widget.request_druid_update(ctx.event_ctx);
+        }
+    }
+
+    #[instrument(name = "Link", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleLinkWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<LinkClicked> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            trace!("Processed link click: {}", self.url);
+            crate::open_url::queue_url(self.url.clone());
+            Some(LinkClicked(self.url.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::open_url::set_url_opener;
+    use crate::test_harness::Harness;
+    use crate::NoEvent;
+    use std::cell::RefCell;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    thread_local! {
+        static OPENED_URLS: RefCell<Vec<String>> = Default::default();
+    }
+
+    // Replaces the system browser in tests
+    fn record_url(url: &str) {
+        OPENED_URLS.with(|urls| urls.borrow_mut().push(url.to_string()));
+    }
+
+    fn take_opened_urls() -> Vec<String> {
+        OPENED_URLS.with(|urls| urls.take())
+    }
+
+    #[test]
+    fn new_link() {
+        let link = Link::new("docs", "https://example.com");
+        let (link_data, ()) = link.build(());
+
+        assert_eq!(
+            link_data,
+            LinkData {
+                text: String::from("docs"),
+                url: String::from("https://example.com"),
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn link_widget() {
+        let link = |text: &str, text_color: Color| {
+            Link::new(text, "https://example.com").with_text_color(text_color)
+        };
+
+        Harness::run_test_window(link("docs", Color::RED), |harness| {
+            let link_state = harness.find_by_name("docs").unwrap().debug_state();
+            assert_eq!(link_state.display_name, "Link");
+            assert_eq!(link_state.other_values["url"], "https://example.com");
+
+            harness.update_root_element(link("guide", Color::BLACK));
+            assert!(harness.find_by_name("docs").is_none());
+            assert!(harness.find_by_name("guide").is_some());
+        });
+    }
+
+    #[test]
+    fn link_click() {
+        set_url_opener(record_url);
+        let link_id = WidgetId::reserved(1);
+        let link = Link::new("docs", "https://example.com").with_reserved_id(link_id);
+
+        Harness::run_test_window(link, |harness| {
+            harness.mouse_click_on(link_id);
+
+            assert_eq!(
+                harness.take_emitted_events::<LinkClicked>(),
+                vec![LinkClicked("https://example.com".to_string())]
+            );
+            assert_eq!(take_opened_urls(), vec!["https://example.com".to_string()]);
+        });
+    }
+
+    #[test]
+    fn link_click_handled() {
+        set_url_opener(record_url);
+        let (event_sender, event_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let link_id = WidgetId::reserved(1);
+        let link = |handled: bool| {
+            let event_sender = event_sender.clone();
+            Link::new("docs", "https://example.com")
+                .with_reserved_id(link_id)
+                .on_click(md, move |_state, event| {
+                    event_sender.send(event).unwrap();
+                    handled
+                })
+        };
+
+        Harness::run_test_window(link(true), |harness| {
+            harness.mouse_click_on(link_id);
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(LinkClicked("https://example.com".to_string()))
+            );
+            assert_eq!(take_opened_urls(), Vec::<String>::new());
+
+            // Returning false lets the link open its URL
+            harness.update_root_element(link(false));
+            harness.mouse_click_on(link_id);
+            assert!(event_receiver.try_recv().is_ok());
+            assert_eq!(take_opened_urls(), vec!["https://example.com".to_string()]);
+        });
+    }
+}
@@ -0,0 +1,238 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::glue::GlobalEventCx;
+use crate::metadata::NoState;
+
+use std::fmt::Debug;
+use tracing::{instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// Wrapper which only rebuilds its child when a set of dependencies changes.
+///
+/// Usually created with [`ElementExt::memoize`](crate::ElementExt::memoize).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Memoize<Deps: Clone + Debug + PartialEq + 'static, Child: Element> {
+    pub child: Child,
+    pub deps: Deps,
+}
+
+/// Virtual DOM node of [`Memoize`].
+///
+/// If the dependencies were unchanged when it was built, `child` is `None`, and
+/// [`update_value`](VirtualDom::update_value) keeps the previous child.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoizeData<Deps: Clone + Debug + PartialEq + 'static, Child: VirtualDom> {
+    pub child: Option<Child>,
+    pub deps: Deps,
+}
+
+// ----
+
+impl<Deps: Clone + Debug + PartialEq + 'static, Child: Element> Memoize<Deps, Child> {
+    pub fn new(child: Child, deps: Deps) -> Self {
+        Memoize { child, deps }
+    }
+}
+
+impl<Deps: Clone + Debug + PartialEq + 'static, Child: Element> Element for Memoize<Deps, Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = (Option<Deps>, Child::AggregateChildrenState);
+    type BuildOutput = MemoizeData<Deps, Child::BuildOutput>;
+
+    #[instrument(name = "Memoize", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (prev_deps, children_prev_state) = prev_state;
+
+        if prev_deps.as_ref() == Some(&self.deps) {
+            trace!("Dependencies unchanged, skipping build");
+            return (
+                MemoizeData {
+                    child: None,
+                    deps: self.deps,
+                },
+                (prev_deps, children_prev_state),
+            );
+        }
+
+        let (child, children_state) = self.child.build(children_prev_state);
+        (
+            MemoizeData {
+                child: Some(child),
+                deps: self.deps.clone(),
+            },
+            (Some(self.deps), children_state),
+        )
+    }
+}
+
+impl<Deps: Clone + Debug + PartialEq + 'static, Child: VirtualDom> VirtualDom
+    for MemoizeData<Deps, Child>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (Option<Deps>, Child::AggregateChildrenState);
+    type TargetWidgetSeq = Child::TargetWidgetSeq;
+
+    fn update_value(&mut self, other: Self) {
+        match (&mut self.child, other.child) {
+            (Some(child), Some(other_child)) => child.update_value(other_child),
+            (this_child, Some(other_child)) => *this_child = Some(other_child),
+            // Dependencies were unchanged; keep the previous child
+            (_, None) => {}
+        }
+        self.deps = other.deps;
+    }
+
+    #[instrument(name = "Memoize", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.child
+            .as_ref()
+            .expect_or_log("Memoize element was never built")
+            .init_tree()
+    }
+
+    #[instrument(name = "Memoize", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        match (&self.child, &prev_value.child) {
+            (Some(child), Some(prev_child)) => {
+                child.reconcile(prev_child, widget_seq, ctx);
+            }
+            (Some(child), None) => {
+                // This only happens if a parent element doesn't forward update_value
+                trace!("Previous child missing, recreating widgets");
+                *widget_seq = child.init_tree();
+            }
+            (None, _) => {
+                trace!("Dependencies unchanged, skipping reconcile");
+            }
+        }
+    }
+
+    fn unmount(&self) {
+        if let Some(child) = &self.child {
+            child.unmount();
+        }
+    }
+
+    #[instrument(name = "Memoize", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        if let Some(child) = &self.child {
+            child.process_event(comp_ctx, &mut children_state.1, widget_seq, cx);
+        }
+    }
+
+    #[instrument(name = "Memoize", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .as_ref()?
+            .process_local_event(&mut children_state.1, widget_seq, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::label::{Label, LabelData};
+    use crate::metadata::NoEvent;
+    use crate::test_harness::Harness;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use test_env_log::test;
+
+    #[derive(Clone, Debug)]
+    struct BuildCounter(Rc<Cell<u32>>);
+
+    impl Element for BuildCounter {
+        type Event = NoEvent;
+        type ComponentState = NoState;
+        type AggregateChildrenState = ();
+        type BuildOutput = LabelData;
+
+        fn build(self, _prev_state: ()) -> (LabelData, ()) {
+            self.0.set(self.0.get() + 1);
+            Label::new("Hello").build(())
+        }
+    }
+
+    #[test]
+    fn memoize_skips_build() {
+        let build_count = Rc::new(Cell::new(0));
+        let memoized = |deps: i32| BuildCounter(build_count.clone()).memoize(deps);
+
+        let (memoize_data, state) = memoized(1).build(Default::default());
+        assert_eq!(build_count.get(), 1);
+        assert_eq!(memoize_data.child, Some(LabelData::new("Hello")));
+
+        let (memoize_data, state) = memoized(1).build(state);
+        assert_eq!(build_count.get(), 1);
+        assert_eq!(memoize_data.child, None);
+
+        let (memoize_data, _state) = memoized(2).build(state);
+        assert_eq!(build_count.get(), 2);
+        assert_eq!(memoize_data.child, Some(LabelData::new("Hello")));
+    }
+
+    #[test]
+    fn update_value() {
+        let mut memoize_data = MemoizeData {
+            child: Some(LabelData::new("Hello")),
+            deps: 1,
+        };
+
+        memoize_data.update_value(MemoizeData {
+            child: None,
+            deps: 1,
+        });
+        assert_eq!(memoize_data.child, Some(LabelData::new("Hello")));
+
+        memoize_data.update_value(MemoizeData {
+            child: Some(LabelData::new("World")),
+            deps: 2,
+        });
+        assert_eq!(memoize_data.child, Some(LabelData::new("World")));
+        assert_eq!(memoize_data.deps, 2);
+    }
+
+    #[test]
+    fn memoize_widget() {
+        let label = Label::new("Hello").memoize(1);
+
+        Harness::run_test_window(label, |harness| {
+            let root_state = format!("{:?}", harness.get_root_debug_state());
+            assert!(root_state.contains("Hello"));
+
+            // Deps are unchanged, so the new label is ignored
+            harness.update_root_element(Label::new("World").memoize(1));
+            let root_state = format!("{:?}", harness.get_root_debug_state());
+            assert!(root_state.contains("Hello"));
+
+            harness.update_root_element(Label::new("World").memoize(2));
+            let root_state = format!("{:?}", harness.get_root_debug_state());
+            assert!(root_state.contains("World"));
+        });
+    }
+}
@@ -0,0 +1,230 @@
+use crate::app_menu::{MenuItemSpec, MenuSpec, MENU_ITEM_SELECTED};
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::MenuItem;
+use crate::glue::GlobalEventCx;
+use crate::metadata::NoState;
+use crate::widgets::EmptySequence;
+
+use std::fmt::Debug;
+use tracing::{instrument, trace};
+
+/// A menu of the application menu; see [`MenuBar`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Menu<Event> {
+    pub title: String,
+    pub items: Vec<MenuItem<Event>>,
+}
+
+/// The application menu of the window.
+///
+/// The menu bar doesn't create a widget; it replaces the window's native menu with its menus.
+/// When the element is rebuilt with different menus (eg when an item is enabled or disabled),
+/// the native menu is rebuilt too.
+///
+/// There should be at most one menu bar in the element tree.
+///
+/// ## Events
+///
+/// Emits the `event` of the [`MenuItem`] selected in the menu, or whose keyboard accelerator
+/// was pressed. Use [`bubble_up`](crate::ElementExt::bubble_up) to emit it from the component.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::elements::{Menu, MenuBar, MenuItem};
+/// # use druid::RawMods;
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Action {
+///     Save,
+///     Quit,
+/// }
+///
+/// let menu_bar = MenuBar::new(vec![Menu::new(
+///     "File",
+///     vec![
+///         MenuItem::new("Save", Action::Save).with_hotkey(RawMods::Ctrl, "s"),
+///         MenuItem::new("Quit", Action::Quit),
+///     ],
+/// )]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuBar<Event> {
+    pub menus: Vec<Menu<Event>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuBarData<Event> {
+    pub menus: Vec<Menu<Event>>,
+}
+
+//
+// --- IMPLS
+
+impl<Event> Menu<Event> {
+    pub fn new(title: impl Into<String>, items: Vec<MenuItem<Event>>) -> Self {
+        Menu {
+            title: title.into(),
+            items,
+        }
+    }
+}
+
+impl<Event> MenuBar<Event> {
+    pub fn new(menus: Vec<Menu<Event>>) -> Self {
+        MenuBar { menus }
+    }
+}
+
+impl<Event> MenuBarData<Event> {
+    fn menu_specs(&self) -> Vec<MenuSpec> {
+        self.menus
+            .iter()
+            .map(|menu| MenuSpec {
+                title: menu.title.clone(),
+                items: menu
+                    .items
+                    .iter()
+                    .map(|item| MenuItemSpec {
+                        text: item.text.clone(),
+                        enabled: item.enabled,
+                        hotkey: item.hotkey.clone(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl<Event: Clone + Debug + 'static> Element for MenuBar<Event> {
+    type Event = Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = MenuBarData<Event>;
+
+    #[instrument(name = "MenuBar", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (MenuBarData<Event>, ()) {
+        (MenuBarData { menus: self.menus }, ())
+    }
+}
+
+impl<Event: Clone + Debug + 'static> VirtualDom for MenuBarData<Event> {
+    type Event = Event;
+    type AggregateChildrenState = ();
+    type TargetWidgetSeq = EmptySequence;
+
+    #[instrument(name = "MenuBar", skip(self))]
+    fn init_tree(&self) -> EmptySequence {
+        crate::app_menu::set_menu_bar(self.menu_specs());
+        EmptySequence
+    }
+
+    #[instrument(name = "MenuBar", skip(self, prev_value, _widget_seq, _ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        _widget_seq: &mut EmptySequence,
+        _ctx: &mut ReconcileCtx,
+    ) {
+        let menu_specs = self.menu_specs();
+        if menu_specs != prev_value.menu_specs() {
+            crate::app_menu::set_menu_bar(menu_specs);
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        *self = other;
+    }
+
+    fn unmount(&self) {
+        crate::app_menu::set_menu_bar(Vec::new());
+    }
+
+    #[instrument(name = "MenuBar", skip(self, _children_state, _widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut (),
+        _widget_seq: &mut EmptySequence,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Event> {
+        let (menu_index, item_index) = *cx.command.as_ref()?.get(MENU_ITEM_SELECTED)?;
+        // The command is consumed, so that the item's event is only emitted once
+        cx.command = None;
+
+        let item = self.menus.get(menu_index)?.items.get(item_index)?;
+        if !item.enabled {
+            return None;
+        }
+        trace!("Processing selection of menu item {:?}", item.text);
+        Some(item.event.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::ComponentOutput;
+    use crate::metadata::Metadata;
+    use crate::test_harness::Harness;
+    use druid::RawMods;
+    use test_env_log::test;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Action {
+        Save,
+        Quit,
+    }
+
+    #[test]
+    fn new_menu_bar() {
+        let menu_bar = MenuBar::new(vec![Menu::new(
+            "File",
+            vec![MenuItem::new("Quit", Action::Quit).with_hotkey(RawMods::Ctrl, "q")],
+        )]);
+        let (menu_bar_data, _) = menu_bar.build(());
+
+        assert_eq!(
+            menu_bar_data.menu_specs(),
+            vec![MenuSpec {
+                title: "File".to_string(),
+                items: vec![MenuItemSpec {
+                    text: "Quit".to_string(),
+                    enabled: true,
+                    hotkey: Some((RawMods::Ctrl, "q".to_string())),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn menu_bar_select() {
+        let md: Metadata<Action, ()> = Default::default();
+        let tree = |can_save: bool| {
+            let save = MenuItem::new("Save", Action::Save);
+            let save = if can_save { save } else { save.disabled() };
+            ComponentOutput::new(
+                md,
+                MenuBar::new(vec![Menu::new(
+                    "File",
+                    vec![save, MenuItem::new("Quit", Action::Quit)],
+                )])
+                .bubble_up(md),
+            )
+        };
+
+        Harness::run_test_window(tree(false), |harness| {
+            // Disabled items can't be selected
+            harness.select_menu_item("File", "Save");
+            assert_eq!(harness.take_emitted_events::<Action>(), vec![]);
+
+            harness.select_menu_item("File", "Quit");
+            assert_eq!(harness.take_emitted_events::<Action>(), vec![Action::Quit]);
+
+            // The item is enabled when the menu bar is rebuilt
+            harness.update_root_element(tree(true));
+            harness.select_menu_item("File", "Save");
+            assert_eq!(harness.take_emitted_events::<Action>(), vec![Action::Save]);
+        });
+    }
+}
@@ -1,16 +1,76 @@
 //! GUI elements that can be built in a component.
 
+mod accordion;
+mod aligned;
 mod any_element;
+mod badge;
 mod button;
+mod canvas;
 mod checkbox;
 mod clickable;
+mod color_picker;
 mod component;
+mod container;
+mod context_menu;
+mod debounce;
+mod divider;
+mod drag_drop;
 mod element_list;
 mod element_option;
 mod empty;
+mod error_boundary;
+mod file_button;
 mod flex_element;
+mod flexed;
+mod form;
+mod grid;
+mod icon_button;
+mod if_else;
+mod image_element;
+mod keyed;
 mod label;
+mod lazy;
+mod link;
+mod memoize;
+mod menu_bar;
+mod modal;
+mod number_box;
+mod opacity;
+mod pagination;
+mod popover;
+mod portal;
+mod progress_bar;
+mod provider;
+mod radio_group;
+mod raw_widget;
+mod responsive;
+mod scroll;
+mod segmented_control;
+mod sized_box;
+mod skeleton;
+mod slider;
+mod spacer;
+mod span;
+mod split_pane;
+mod stack;
+mod stepper;
+mod suspense;
+mod svg;
+mod switch;
+mod tab_index;
+mod table;
+mod tabs;
+mod textarea;
 mod textbox;
+mod toast;
+mod tooltip;
+mod transition;
+mod tree_view;
+mod validated_textbox;
+mod virtual_list;
+mod visibility;
+mod with_id;
+mod wrap;
 
 pub mod element_tuple;
 mod event_logger;
@@ -19,34 +79,168 @@ mod with_event;
 
 mod compute_diff;
 
+pub use accordion::{Accordion, SectionToggled};
+pub use aligned::Aligned;
 pub use any_element::ElementBox;
+pub use badge::Badge;
 pub use button::{Button, ButtonClick};
-pub use checkbox::{Checkbox, Toggled};
+pub use canvas::{Canvas, CanvasMouse};
+pub use checkbox::{Checkbox, CheckboxToggled};
 #[doc(hidden)]
 pub use clickable::{ClickEvent, Clickable};
+pub use color_picker::{ColorChanged, ColorPicker};
 pub use component::{Component, ComponentOutput};
+pub use container::Container;
+pub use context_menu::MenuItem;
+pub use debounce::Debounce;
+pub use divider::Divider;
 pub use element_list::ElementList;
 pub use element_option::*;
 pub use empty::EmptyElement;
+pub use error_boundary::{BoundaryCaught, ErrorBoundary};
+pub use file_button::{FileButton, FileChosen};
 pub use flex_element::Flex;
+pub use flexed::Flexed;
+pub use form::{Form, FormSubmitted};
+pub use grid::Grid;
+pub use icon_button::{Icon, IconButton};
+pub use if_else::If;
+pub use image_element::{Image, ImageFormat, ImageSource};
+pub use keyed::Keyed;
 pub use label::Label;
-pub use textbox::{TextBox, TextChanged};
+pub use lazy::Lazy;
+pub use link::{Link, LinkClicked};
+pub use memoize::Memoize;
+pub use menu_bar::{Menu, MenuBar};
+pub use modal::{Modal, ModalDismissed};
+pub use number_box::{NumberBox, NumberChanged};
+pub use opacity::Opacity;
+pub use pagination::{PageSelected, Pagination};
+pub use popover::{Placement, Popover, PopoverDismissed};
+pub use portal::Portal;
+pub use progress_bar::ProgressBar;
+pub use provider::Provider;
+pub use radio_group::{RadioGroup, RadioSelected};
+pub use raw_widget::RawWidget;
+pub use responsive::Responsive;
+pub use scroll::{Scroll, ScrollDirection};
+pub use segmented_control::{SegmentSelected, SegmentedControl};
+pub use sized_box::SizedBox;
+pub use skeleton::{Skeleton, SkeletonShape};
+pub use slider::{Slider, SliderMoved};
+pub use spacer::Spacer;
+pub use span::Span;
+pub use split_pane::{SplitMoved, SplitPane};
+pub use stack::Stack;
+pub use stepper::{Stepper, StepperChanged, StepperValue};
+pub use suspense::Suspense;
+pub use svg::{Svg, SvgSource};
+pub use switch::{Switch, SwitchToggled};
+pub use tab_index::TabIndex;
+pub use table::{Column, Row, Table};
+pub use tabs::{TabSelected, Tabs};
+pub use textarea::TextArea;
+pub use textbox::{BoundTextBox, TextBox, TextChanged};
+pub use toast::{Toast, ToastDismissed};
+pub use tooltip::Tooltip;
+pub use transition::{Transition, TransitionProgress};
+pub use tree_view::{NodeId, TreeNode, TreeView, TreeViewEvent};
+pub use validated_textbox::{ValidatedChange, ValidatedTextBox};
+pub use virtual_list::VirtualList;
+pub use visibility::Visibility;
+pub use with_event::{DoubleClick, KeyDown, LongPress, DOUBLE_CLICK_INTERVAL};
+pub use with_id::WithId;
+pub use wrap::Wrap;
 
 // TODO - doc
 pub mod internals {
+    pub use super::accordion::AccordionData;
+    pub use super::aligned::AlignedData;
     pub use super::any_element::VirtualDomBox;
+    pub use super::badge::BadgeData;
     pub use super::button::ButtonData;
+    pub use super::canvas::CanvasData;
     pub use super::checkbox::CheckboxData;
     pub use super::clickable::ClickableData;
-    pub use super::component::{ComponentHolder, ComponentOutputData};
+    pub use super::color_picker::ColorPickerData;
+    pub use super::component::{
+        ComponentHolder, ComponentHolderData, ComponentOutputData, SharedChild,
+    };
+    pub use super::container::ContainerData;
+    pub use super::context_menu::{ContextMenu, ContextMenuData};
+    pub use super::debounce::DebounceData;
+    pub use super::divider::DividerData;
+    pub use super::drag_drop::{Draggable, DraggableData, DropTarget, DropTargetData};
     pub use super::element_list::ElementListData;
     pub use super::element_tuple::ElementTupleData;
     pub use super::empty::EmptyElementData;
+    pub use super::error_boundary::ErrorBoundaryData;
+    pub use super::file_button::FileButtonData;
     pub use super::flex_element::FlexData;
+    pub use super::flexed::FlexedData;
+    pub use super::form::FormData;
+    pub use super::grid::GridData;
+    pub use super::icon_button::IconButtonData;
+    pub use super::if_else::IfData;
+    pub use super::image_element::{DecodedImage, ImageData};
+    pub use super::keyed::KeyedData;
     pub use super::label::LabelData;
+    pub use super::lazy::LazyData;
+    pub use super::link::LinkData;
+    pub use super::memoize::MemoizeData;
+    pub use super::menu_bar::MenuBarData;
+    pub use super::modal::ModalData;
+    pub use super::number_box::NumberBoxData;
+    pub use super::opacity::OpacityData;
+    pub use super::pagination::PaginationData;
+    pub use super::popover::PopoverData;
+    pub use super::portal::PortalData;
+    pub use super::progress_bar::ProgressBarData;
+    pub use super::radio_group::RadioGroupData;
+    pub use super::raw_widget::RawWidgetData;
+    pub use super::responsive::ResponsiveData;
+    pub use super::scroll::ScrollData;
+    pub use super::segmented_control::SegmentedControlData;
+    pub use super::sized_box::SizedBoxData;
+    pub use super::skeleton::SkeletonData;
+    pub use super::slider::SliderData;
+    pub use super::spacer::SpacerData;
+    pub use super::span::SpanData;
+    pub use super::split_pane::SplitPaneData;
+    pub use super::stack::StackData;
+    pub use super::stepper::StepperData;
+    pub use super::suspense::SuspenseData;
+    pub use super::svg::{ParsedSvg, SvgData};
+    pub use super::switch::SwitchData;
+    pub use super::tab_index::TabIndexData;
+    pub use super::table::TableData;
+    pub use super::tabs::TabsData;
+    pub use super::textarea::TextAreaData;
     pub use super::textbox::TextBoxData;
+    pub use super::toast::ToastData;
+    pub use super::tooltip::TooltipData;
+    pub use super::transition::TransitionData;
+    pub use super::tree_view::{TreeRowData, TreeViewData};
+    pub use super::validated_textbox::ValidatedTextBoxData;
+    pub use super::virtual_list::VirtualListData;
+    pub use super::visibility::VisibilityData;
     pub use super::with_event::WithEventTarget;
-    pub use super::with_event::{ParentEvent, WithBubbleEvent, WithCallbackEvent, WithMapEvent};
+    pub use super::with_event::{ClickableArea, ClickableAreaTarget};
+    pub use super::with_event::{
+        ParentEvent, WithBubbleEvent, WithCallbackEvent, WithFlatMapEvent, WithMapEvent,
+    };
+    pub use super::with_event::{WithAnyEvent, WithAnyEventTarget};
+    pub use super::with_event::{WithDoubleClickEvent, WithDoubleClickEventTarget};
+    pub use super::with_event::{WithFocusEvent, WithFocusEventTarget};
+    pub use super::with_event::{WithHoverEvent, WithHoverEventTarget};
+    pub use super::with_event::{WithKeyEvent, WithKeyEventTarget};
+    pub use super::with_event::{WithLongPressEvent, WithLongPressEventTarget};
+    pub use super::with_event::{WithMappedState, WithMappedStateTarget};
+    pub use super::with_event::{WithMouseMoveEvent, WithMouseMoveEventTarget};
+    pub use super::with_event::{WithPasteEvent, WithPasteEventTarget};
+    pub use super::with_event::{WithRightClickEvent, WithRightClickEventTarget};
+    pub use super::with_id::WithIdData;
+    pub use super::wrap::WrapData;
 
     // TODO - move to test_harness?
     pub use super::event_logger::{EventLogger, EventLoggerData};
@@ -0,0 +1,293 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{ModalWidget, SingleModalWidget};
+
+use tracing::{debug_span, info, instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// A dialog displayed over a dimmed backdrop.
+///
+/// While `open` is true, the child is centered over a backdrop which catches all mouse
+/// events, so that the elements below it can't be clicked. While `open` is false, the modal
+/// displays nothing, and the child isn't built; opening the modal again builds the child
+/// from scratch.
+///
+/// The backdrop fills the space it's given. The modal is usually the last element of a
+/// [`Stack`](crate::elements::Stack), so that it covers the rest of the stack.
+///
+/// ## Events
+///
+/// Emits [ModalDismissed] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Modal<Child: Element> {
+    pub open: bool,
+    pub child: Child,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModalData<Child: VirtualDom> {
+    pub child: Option<Child>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when the backdrop of a [Modal] is clicked.
+///
+/// Clicks within the dialog don't dismiss it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModalDismissed;
+
+//
+// --- IMPLS
+
+impl<Child: Element> Modal<Child> {
+    /// Build a modal showing `child` if `open` is true.
+    ///
+    /// Use the [.on_dismiss](Modal::on_dismiss) method to provide a closure to be called when the backdrop is clicked.
+    pub fn new(open: bool, child: Child) -> Self {
+        Modal {
+            open,
+            child,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Modal {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when the backdrop is clicked.
+    ///
+    /// The modal isn't closed automatically; the closure usually sets the flag passed as `open`
+    /// to false.
+    pub fn on_dismiss<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, _: ModalDismissed| {
+            callback(state)
+        })
+    }
+}
+
+impl<Child: VirtualDom> ModalData<Child> {
+    fn init_overlay(
+        child: &Child,
+        id: Option<WidgetId>,
+    ) -> SingleModalWidget<Child::TargetWidgetSeq> {
+        let id = id.unwrap_or_else(WidgetId::next);
+        SingleModalWidget::new(
+            ModalWidget::new(child.init_tree(), id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+}
+
+impl<Child: Element> Element for Modal<Child> {
+    type Event = ModalDismissed;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = Option<Child::AggregateChildrenState>;
+    type BuildOutput = ModalData<Child::BuildOutput>;
+
+    #[instrument(name = "Modal", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        // The child's state is dropped while the modal is closed
+        let (child, state) = if self.open {
+            let (child, state) = self.child.build(prev_state.unwrap_or_default());
+            (Some(child), Some(state))
+        } else {
+            (None, None)
+        };
+        (
+            ModalData {
+                child,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for ModalData<Child> {
+    type Event = ModalDismissed;
+    type AggregateChildrenState = Option<Child::AggregateChildrenState>;
+    type TargetWidgetSeq = Option<SingleModalWidget<Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Modal", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.child
+            .as_ref()
+            .map(|child| Self::init_overlay(child, self.reserved_widget_id))
+    }
+
+    #[instrument(name = "Modal", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        match (&self.child, &prev_value.child) {
+            (Some(child), Some(prev_child)) => {
+                let widget = widget_seq
+                    .as_mut()
+                    .expect_or_log("The modal was open. Expected a modal widget.");
+                child.reconcile(prev_child, &mut widget.widget_mut().children, ctx);
+            }
+            (Some(child), None) => {
+                debug_span!("init_tree").in_scope(|| {
+                    info!("opening modal");
+                    *widget_seq = Some(Self::init_overlay(child, self.reserved_widget_id));
+                });
+            }
+            (None, Some(prev_child)) => {
+                info!("closing modal");
+                prev_child.unmount();
+                *widget_seq = None;
+            }
+            (None, None) => {}
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        match (&mut self.child, other.child) {
+            (Some(child), Some(other_child)) => child.update_value(other_child),
+            (child, other_child) => *child = other_child,
+        }
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        if let Some(child) = &self.child {
+            child.unmount();
+        }
+    }
+
+    #[instrument(name = "Modal", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        if let (Some(child), Some(child_state), Some(widget)) =
+            (&self.child, children_state, widget_seq)
+        {
+            child.process_event(comp_ctx, child_state, &mut widget.widget_mut().children, cx);
+        }
+    }
+
+    #[instrument(name = "Modal", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<ModalDismissed> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.as_ref()?.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            trace!("Processed modal dismissal");
+            Some(ModalDismissed)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::{Button, ButtonClick};
+    use crate::test_harness::Harness;
+    use crate::Stack;
+    use druid::{MouseButton, Vec2};
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_modal() {
+        let (modal_data, state) = Modal::new(false, Button::new("OK")).build(None);
+        assert!(modal_data.child.is_none());
+        assert!(state.is_none());
+
+        let (modal_data, state) = Modal::new(true, Button::new("OK")).build(state);
+        assert!(modal_data.child.is_some());
+        assert!(state.is_some());
+    }
+
+    #[test]
+    fn modal_dismiss() {
+        let (behind_sender, behind_receiver) = channel();
+        let (content_sender, content_receiver) = channel();
+        let (modal_sender, modal_receiver) = channel();
+        let behind_id = WidgetId::reserved(1);
+        let content_id = WidgetId::reserved(2);
+
+        let screen = |open| {
+            Stack!(
+                EventLogger::new(
+                    behind_sender.clone(),
+                    Button::new("Behind").with_reserved_id(behind_id),
+                )
+                .fix_size(200.0, 100.0),
+                EventLogger::new(
+                    modal_sender.clone(),
+                    Modal::new(
+                        open,
+                        EventLogger::new(
+                            content_sender.clone(),
+                            Button::new("OK").with_reserved_id(content_id),
+                        ),
+                    ),
+                ),
+            )
+        };
+
+        Harness::run_test_window(screen(false), |harness| {
+            assert!(harness.try_get_state(content_id).is_none());
+            harness.mouse_click_on(behind_id);
+            assert_eq!(behind_receiver.try_recv(), Ok(ButtonClick));
+
+            harness.update_root_element(screen(true));
+            assert!(harness.try_get_state(content_id).is_some());
+
+            // Clicks within the dialog don't dismiss it
+            harness.click_on("OK");
+            assert_eq!(content_receiver.try_recv(), Ok(ButtonClick));
+            assert!(modal_receiver.try_recv().is_err());
+
+            // The backdrop covers the button behind it
+            let behind_rect = harness.find_by_name("Behind").unwrap().layout_rect();
+            harness.mouse_move(behind_rect.origin() + Vec2::new(5.0, 5.0));
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(modal_receiver.try_recv(), Ok(ModalDismissed));
+            assert!(behind_receiver.try_recv().is_err());
+
+            harness.update_root_element(screen(false));
+            assert!(harness.try_get_state(content_id).is_none());
+        });
+    }
+}
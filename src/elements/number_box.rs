@@ -0,0 +1,302 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleValidatedTextBoxWidget, ValidatedTextBoxWidget};
+
+use druid::EventCtx;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use tracing::{instrument, trace};
+
+const PARSE_ERROR: &str = "Not a number";
+
+/// A text-editing box holding a number, or any value that can be parsed from text.
+///
+/// The box emits the parsed value each time its content is edited into valid text. While the
+/// content doesn't parse, eg while the user is typing `-` before a negative number, no event
+/// is emitted, and an error message is displayed below the box.
+///
+/// Like [TextBox](crate::elements::TextBox), the value should be bound to the component's
+/// state. The text typed by the user is kept as long as the value it parses to is the one
+/// the box is built with, so that `1.50` isn't reformatted to `1.5` mid-edit. When the value
+/// is changed from elsewhere, the new value is formatted into the box.
+///
+/// ## Events
+///
+/// Emits [NumberChanged] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberBox<T> {
+    pub value: T,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberBoxData<T> {
+    /// The last valid value.
+    pub value: T,
+    /// The text displayed in the box, which may not parse.
+    pub text: String,
+    pub error: Option<String>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [NumberBox] is edited into valid text.
+///
+/// Holds the parsed value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NumberChanged<T>(pub T);
+
+//
+// --- IMPLS
+
+impl<T: FromStr + Display + Clone + Debug + PartialEq + 'static> NumberBox<T> {
+    /// Build a box displaying `value`.
+    ///
+    /// Use the [.on_change](NumberBox::on_change) method to provide a closure to be called when the box is edited.
+    pub fn new(value: T) -> Self {
+        NumberBox {
+            value,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the box's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        NumberBox {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The text box created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same text box is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        NumberBox {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called with the new value when this box is edited.
+    pub fn on_change<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, T) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: NumberChanged<T>| callback(state, event.0),
+        )
+    }
+}
+
+impl<T: FromStr + Display + Clone + Debug + PartialEq + 'static> Element for NumberBox<T> {
+    type Event = NumberChanged<T>;
+
+    type ComponentState = NoState;
+    // The text typed by the user, and the formatted value it was typed for
+    type AggregateChildrenState = Option<(String, String)>;
+    type BuildOutput = NumberBoxData<T>;
+
+    #[instrument(name = "NumberBox", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Option<(String, String)>,
+    ) -> (NumberBoxData<T>, Option<(String, String)>) {
+        let formatted = self.value.to_string();
+        // If the value was changed from elsewhere, the typed text is replaced
+        let state = prev_state.filter(|(_, typed_value)| *typed_value == formatted);
+        let text = match &state {
+            Some((text, _)) => text.clone(),
+            None => formatted,
+        };
+        let error = match text.trim().parse::<T>() {
+            Ok(_) => None,
+            Err(_) => Some(String::from(PARSE_ERROR)),
+        };
+        (
+            NumberBoxData {
+                value: self.value,
+                text,
+                error,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<T: FromStr + Display + Clone + Debug + PartialEq + 'static> VirtualDom for NumberBoxData<T> {
+    type Event = NumberChanged<T>;
+    type AggregateChildrenState = Option<(String, String)>;
+    type TargetWidgetSeq = SingleValidatedTextBoxWidget;
+
+    #[instrument(name = "NumberBox", skip(self))]
+    fn init_tree(&self) -> SingleValidatedTextBoxWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let textbox = ValidatedTextBoxWidget::new(self.text.clone(), self.error.clone(), id);
+        SingleValidatedTextBoxWidget::new(textbox, self.flex)
+    }
+
+    #[instrument(name = "NumberBox", skip(self, _prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        _prev_value: &Self,
+        widget: &mut SingleValidatedTextBoxWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+
+        // The widget's text is only replaced when it was reformatted, so edits don't reset
+        // the cursor
+        if self.text != widget.widget().text() {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ValidatedTextBoxWidget, ctx: &mut EventCtx| {
+                    widget.set_text(self.text.clone(), ctx);
+                },
+            );
+        }
+
+        if self.error != widget.widget().error {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ValidatedTextBoxWidget, ctx: &mut EventCtx| {
+                    widget.set_error(self.error.clone(), ctx);
+                },
+            );
+        }
+    }
+
+    #[instrument(name = "NumberBox", skip(self, children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Option<(String, String)>,
+        widget: &mut SingleValidatedTextBoxWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<NumberChanged<T>> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        let text = match cx.app_data.dequeue_action(id) {
+            Some(Action::TextChanged(text)) => text,
+            _ => return None,
+        };
+
+        match text.trim().parse::<T>() {
+            Ok(value) => {
+                trace!("Parsed number {:?} from {:?}", value, text);
+                *children_state = Some((text, value.to_string()));
+                Some(NumberChanged(value))
+            }
+            Err(_) => {
+                trace!("Text {:?} isn't a valid number", text);
+                *children_state = Some((text, self.value.to_string()));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    #[test]
+    fn new_number_box() {
+        let (number_data, state) = NumberBox::new(42).build(None);
+        assert_eq!(number_data.value, 42);
+        assert_eq!(number_data.text, "42");
+        assert_eq!(number_data.error, None);
+        assert_eq!(state, None);
+
+        // Text typed for the current value is kept
+        let typed = Some((String::from("1.50"), String::from("1.5")));
+        let (number_data, _) = NumberBox::new(1.5).build(typed.clone());
+        assert_eq!(number_data.text, "1.50");
+
+        // A value changed from elsewhere replaces it
+        let (number_data, state) = NumberBox::new(2.0).build(typed);
+        assert_eq!(number_data.text, "2");
+        assert_eq!(state, None);
+
+        let invalid = Some((String::from("abc"), String::from("2")));
+        let (number_data, _) = NumberBox::new(2).build(invalid);
+        assert_eq!(number_data.value, 2);
+        assert_eq!(number_data.error, Some(String::from(PARSE_ERROR)));
+    }
+
+    #[test]
+    fn number_box_valid_input() {
+        Harness::run_test_window(NumberBox::new(1), |harness| {
+            harness.type_text("1", "2");
+            assert_eq!(
+                harness.take_emitted_events::<NumberChanged<i32>>(),
+                vec![NumberChanged(12)]
+            );
+
+            harness.update_root_element(NumberBox::new(12));
+            assert!(harness.find_by_name("12").is_some());
+            assert!(harness.find_by_name(PARSE_ERROR).is_none());
+        });
+    }
+
+    #[test]
+    fn number_box_invalid_input() {
+        Harness::run_test_window(NumberBox::new(12), |harness| {
+            harness.type_text("12", "x");
+            assert_eq!(harness.take_emitted_events::<NumberChanged<i32>>(), vec![]);
+            assert!(harness.find_by_name(PARSE_ERROR).is_some());
+
+            // Rebuilding with the same value keeps the invalid text
+            harness.update_root_element(NumberBox::new(12));
+            assert!(harness.find_by_name("12x").is_some());
+
+            // A value changed from elsewhere is formatted into the box
+            harness.update_root_element(NumberBox::new(7));
+            assert!(harness.find_by_name("7").is_some());
+            assert!(harness.find_by_name(PARSE_ERROR).is_none());
+        });
+    }
+
+    #[test]
+    fn number_box_partial_input() {
+        Harness::run_test_window(NumberBox::new(0), |harness| {
+            harness.type_text("0", "\u{8}-");
+            assert_eq!(harness.take_emitted_events::<NumberChanged<i32>>(), vec![]);
+            assert!(harness.find_by_name("-").is_some());
+
+            harness.type_text("-", "4");
+            assert_eq!(
+                harness.take_emitted_events::<NumberChanged<i32>>(),
+                vec![NumberChanged(-4)]
+            );
+            harness.update_root_element(NumberBox::new(-4));
+            assert!(harness.find_by_name("-4").is_some());
+            assert!(harness.find_by_name(PARSE_ERROR).is_none());
+        });
+
+        // Text that parses to the current value isn't reformatted
+        Harness::run_test_window(NumberBox::new(1.5), |harness| {
+            harness.type_text("1.5", "0");
+            assert_eq!(
+                harness.take_emitted_events::<NumberChanged<f64>>(),
+                vec![NumberChanged(1.5)]
+            );
+            assert!(harness.find_by_name("1.50").is_some());
+        });
+    }
+}
@@ -0,0 +1,240 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{OpacityWidget, SingleOpacityWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which paints the child partially transparent.
+///
+/// The opacity ranges from `0.0` (invisible) to `1.0` (fully opaque). An invisible child still
+/// takes up space in the layout, and can still be clicked, unless the wrapper is made
+/// non-interactive with [`.interactive(false)`](Opacity::interactive). Driving the opacity
+/// from local state is an easy way to fade an element in or out.
+///
+/// Usually created with [`ElementExt::opacity`](crate::ElementExt::opacity).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opacity<Child: Element> {
+    pub child: Child,
+    pub opacity: f64,
+    pub interactive: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpacityData<Child: VirtualDom> {
+    pub child: Child,
+    pub opacity: f64,
+    pub interactive: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Opacity<Child> {
+    /// Wrap `child`, painting it with the given opacity.
+    ///
+    /// The opacity is clamped to `0.0..=1.0`.
+    pub fn new(child: Child, opacity: f64) -> Self {
+        Opacity {
+            child,
+            opacity: opacity.max(0.0).min(1.0),
+            interactive: true,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set whether the child gets mouse events.
+    ///
+    /// Non-interactive children can't be clicked or hovered, which is usually what you want
+    /// for an element faded out of view.
+    pub fn interactive(self, interactive: bool) -> Self {
+        Opacity {
+            interactive,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Opacity {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Opacity<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = OpacityData<Child::BuildOutput>;
+
+    #[instrument(name = "Opacity", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            OpacityData {
+                child,
+                opacity: self.opacity,
+                interactive: self.interactive,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for OpacityData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleOpacityWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Opacity", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleOpacityWidget::new(
+            OpacityWidget::new(self.child.init_tree(), self.opacity, self.interactive, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Opacity", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget_seq.widget_mut().interactive = self.interactive;
+        if self.opacity != prev_value.opacity {
+            let opacity = self.opacity;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut OpacityWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_opacity(opacity, ctx);
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.opacity = other.opacity;
+        self.interactive = other.interactive;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Opacity", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Opacity", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{Button, ButtonClick, Label};
+    use crate::test_harness::Harness;
+    use insta::assert_debug_snapshot;
+    use test_env_log::test;
+
+    #[test]
+    fn new_opacity() {
+        let (opacity_data, ()) = Label::new("Hello").opacity(1.5).build(());
+        assert_eq!(opacity_data.opacity, 1.0);
+        assert!(opacity_data.interactive);
+
+        let (opacity_data, ()) = Label::new("Hello")
+            .opacity(-1.0)
+            .interactive(false)
+            .build(());
+        assert_eq!(opacity_data.opacity, 0.0);
+        assert!(!opacity_data.interactive);
+    }
+
+    #[test]
+    fn opacity_widget() {
+        Harness::run_test_window(Label::new("Hello").opacity(0.5), |harness| {
+            let opacity_state = harness.get_root_debug_state();
+            assert_debug_snapshot!(opacity_state);
+
+            // Invisible children still take up space
+            harness.update_root_element(Label::new("Hello").opacity(0.0));
+            let opacity_state_2 = harness.get_root_debug_state();
+            assert_debug_snapshot!(opacity_state_2);
+            assert!(harness.find_by_name("Hello").is_some());
+        });
+    }
+
+    #[test]
+    fn opacity_interactive() {
+        let button_id = WidgetId::reserved(1);
+        let button = |interactive: bool| {
+            Button::new("Hello")
+                .with_reserved_id(button_id)
+                .opacity(0.0)
+                .interactive(interactive)
+        };
+
+        Harness::run_test_window(button(true), |harness| {
+            harness.mouse_click_on(button_id);
+            assert_eq!(harness.take_emitted_events::<ButtonClick>().len(), 1);
+
+            harness.update_root_element(button(false));
+            harness.mouse_click_on(button_id);
+            assert_eq!(harness.take_emitted_events::<ButtonClick>().len(), 0);
+        });
+    }
+}
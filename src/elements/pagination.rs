@@ -0,0 +1,359 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::internals::{ButtonData, ElementListData, FlexData};
+use crate::elements::{Button, ButtonClick, ElementList, Flex};
+use crate::flex::{Axis, FlexParams};
+use crate::glue::GlobalEventCx;
+use crate::metadata::{Metadata, NoState};
+
+use tracing::{instrument, trace};
+
+/// Above this number of pages, some pages are replaced with ellipses.
+const MAX_VISIBLE_PAGES: usize = 7;
+
+const FIRST_KEY: &str = "first";
+const PREV_KEY: &str = "prev";
+const NEXT_KEY: &str = "next";
+const LAST_KEY: &str = "last";
+
+/// A row of buttons to pick a page among a range of pages.
+///
+/// Pages are numbered from 1. The row starts with "first" and "previous" buttons, and ends
+/// with "next" and "last" buttons; they're disabled on the first and last page respectively.
+///
+/// When there are more than 7 pages, the first page, the last page and the pages around the
+/// current one are displayed, and the pages in between are replaced with an ellipsis. The
+/// row always holds the same number of buttons, so it doesn't change size when the current
+/// page moves.
+///
+/// Like [Stepper](crate::elements::Stepper), the pagination doesn't store its current page:
+/// it's usually bound to the component's local state with
+/// [.on_page_selected](Pagination::on_page_selected).
+///
+/// ## Events
+///
+/// Emits [PageSelected] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pagination {
+    pub current: usize,
+    pub total_pages: usize,
+    pub flex: FlexParams,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaginationData {
+    pub current: usize,
+    pub total_pages: usize,
+    pub row: FlexData<ElementListData<ButtonData>>,
+}
+
+/// Event emitted when a button of a [Pagination] is pressed.
+///
+/// Holds the selected page, starting at 1.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageSelected(pub usize);
+
+//
+// --- IMPLS
+
+impl Pagination {
+    /// Build a pagination showing `current` among `total_pages` pages.
+    ///
+    /// `current` is clamped between 1 and `total_pages`. Use the
+    /// [.on_page_selected](Pagination::on_page_selected) method to provide a closure to be
+    /// called when a page is picked.
+    pub fn new(current: usize, total_pages: usize) -> Self {
+        let total_pages = total_pages.max(1);
+        Pagination {
+            current: current.max(1).min(total_pages),
+            total_pages,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        }
+    }
+
+    /// Change the way the row's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Pagination {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called with the selected page when a button is pressed.
+    pub fn on_page_selected<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, usize) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: PageSelected| callback(state, event.0),
+        )
+    }
+}
+
+/// The pages to display between the navigation buttons, with `None` for ellipses.
+fn visible_pages(current: usize, total_pages: usize) -> Vec<Option<usize>> {
+    if total_pages <= MAX_VISIBLE_PAGES {
+        return (1..=total_pages).map(Some).collect();
+    }
+
+    // Pages on either side of an ellipsis, so that the row has a constant length
+    let edge = MAX_VISIBLE_PAGES - 2;
+    if current < edge {
+        (1..=edge)
+            .map(Some)
+            .chain(vec![None, Some(total_pages)])
+            .collect()
+    } else if current > total_pages + 1 - edge {
+        vec![Some(1), None]
+            .into_iter()
+            .chain((total_pages + 1 - edge..=total_pages).map(Some))
+            .collect()
+    } else {
+        vec![
+            Some(1),
+            None,
+            Some(current - 1),
+            Some(current),
+            Some(current + 1),
+            None,
+            Some(total_pages),
+        ]
+    }
+}
+
+impl PaginationData {
+    // Page buttons are keyed by page, so that their label never changes
+    fn target_page(&self, key: &str) -> Option<usize> {
+        match key {
+            FIRST_KEY => Some(1),
+            PREV_KEY => Some(self.current - 1),
+            NEXT_KEY => Some(self.current + 1),
+            LAST_KEY => Some(self.total_pages),
+            key => key.strip_prefix("page-")?.parse().ok(),
+        }
+    }
+}
+
+impl Element for Pagination {
+    type Event = PageSelected;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = PaginationData;
+
+    #[instrument(name = "Pagination", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (PaginationData, ()) {
+        let (current, total_pages) = (self.current, self.total_pages);
+        let is_first = current == 1;
+        let is_last = current == total_pages;
+
+        let mut buttons = vec![
+            (FIRST_KEY.to_string(), Button::new("«").disabled(is_first)),
+            (PREV_KEY.to_string(), Button::new("‹").disabled(is_first)),
+        ];
+        // There's at most one ellipsis on each side of the current page
+        let mut ellipsis_key = "ellipsis-start";
+        for page in visible_pages(current, total_pages) {
+            let button = match page {
+                Some(page) => {
+                    if page == current {
+                        ellipsis_key = "ellipsis-end";
+                    }
+                    (
+                        format!("page-{}", page),
+                        Button::new(page.to_string()).disabled(page == current),
+                    )
+                }
+                None => (ellipsis_key.to_string(), Button::new("…").disabled(true)),
+            };
+            buttons.push(button);
+        }
+        buttons.push((NEXT_KEY.to_string(), Button::new("›").disabled(is_last)));
+        buttons.push((LAST_KEY.to_string(), Button::new("»").disabled(is_last)));
+
+        let row = Flex::new(Axis::Horizontal, ElementList::from_pairs(buttons))
+            .with_flex_params(self.flex);
+        let (row, _) = row.build(Default::default());
+        (
+            PaginationData {
+                current,
+                total_pages,
+                row,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for PaginationData {
+    type Event = PageSelected;
+    type AggregateChildrenState = ();
+    type TargetWidgetSeq = <FlexData<ElementListData<ButtonData>> as VirtualDom>::TargetWidgetSeq;
+
+    #[instrument(name = "Pagination", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        self.row.init_tree()
+    }
+
+    #[instrument(name = "Pagination", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.row.reconcile(&prev_value.row, widget_seq, ctx);
+    }
+
+    #[instrument(name = "Pagination", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut (),
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<PageSelected> {
+        let buttons = &mut widget_seq.pod.widget_mut().child_mut().children_seq;
+        let mut selected = None;
+        for ((key, button), widget) in self.row.child.children.iter().zip(&mut buttons.children) {
+            // Disabled buttons don't emit clicks
+            if let Some(ButtonClick) = button.process_local_event(&mut (), widget, cx) {
+                selected = self.target_page(key);
+            }
+        }
+        let page = selected?;
+        trace!("Processed page selection: {}", page);
+        Some(PageSelected(page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    fn is_disabled(harness: &mut Harness<'_, '_, Pagination>, name: &str) -> bool {
+        let values = harness
+            .find_by_name(name)
+            .unwrap()
+            .debug_state()
+            .other_values;
+        values.get("disabled").map(String::as_str) == Some("true")
+    }
+
+    #[test]
+    fn pagination_pages() {
+        let pages = |current, total_pages| -> Vec<String> {
+            visible_pages(current, total_pages)
+                .into_iter()
+                .map(|page| page.map_or("…".to_string(), |page| page.to_string()))
+                .collect()
+        };
+
+        assert_eq!(pages(2, 5), ["1", "2", "3", "4", "5"]);
+        assert_eq!(pages(1, 7), ["1", "2", "3", "4", "5", "6", "7"]);
+        assert_eq!(pages(1, 20), ["1", "2", "3", "4", "5", "…", "20"]);
+        assert_eq!(pages(4, 20), ["1", "2", "3", "4", "5", "…", "20"]);
+        assert_eq!(pages(5, 20), ["1", "…", "4", "5", "6", "…", "20"]);
+        assert_eq!(pages(16, 20), ["1", "…", "15", "16", "17", "…", "20"]);
+        assert_eq!(pages(17, 20), ["1", "…", "16", "17", "18", "19", "20"]);
+        assert_eq!(pages(20, 20), ["1", "…", "16", "17", "18", "19", "20"]);
+    }
+
+    #[test]
+    fn new_pagination() {
+        let (pagination_data, ()) = Pagination::new(12, 3).build(());
+        assert_eq!(pagination_data.current, 3);
+        assert_eq!(pagination_data.total_pages, 3);
+
+        let keys: Vec<_> = pagination_data
+            .row
+            .child
+            .children
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            ["first", "prev", "page-1", "page-2", "page-3", "next", "last"]
+        );
+
+        let (pagination_data, ()) = Pagination::new(10, 20).build(());
+        let keys: Vec<_> = pagination_data
+            .row
+            .child
+            .children
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "first",
+                "prev",
+                "page-1",
+                "ellipsis-start",
+                "page-9",
+                "page-10",
+                "page-11",
+                "ellipsis-end",
+                "page-20",
+                "next",
+                "last"
+            ]
+        );
+    }
+
+    #[test]
+    fn pagination_widget() {
+        Harness::run_test_window(Pagination::new(1, 20), |harness| {
+            // First and previous are disabled on the first page
+            assert!(is_disabled(harness, "«"));
+            assert!(is_disabled(harness, "‹"));
+            assert!(!is_disabled(harness, "›"));
+            assert!(is_disabled(harness, "1"));
+            assert!(harness.find_by_name("6").is_none());
+
+            harness.click_on("«");
+            assert_eq!(harness.take_emitted_events::<PageSelected>(), vec![]);
+            harness.click_on("3");
+            assert_eq!(
+                harness.take_emitted_events::<PageSelected>(),
+                vec![PageSelected(3)]
+            );
+            harness.click_on("›");
+            assert_eq!(
+                harness.take_emitted_events::<PageSelected>(),
+                vec![PageSelected(2)]
+            );
+
+            harness.update_root_element(Pagination::new(10, 20));
+            assert!(!is_disabled(harness, "«"));
+            assert!(is_disabled(harness, "10"));
+            assert!(harness.find_by_name("9").is_some());
+            assert!(harness.find_by_name("3").is_none());
+            harness.click_on("11");
+            harness.click_on("‹");
+            assert_eq!(
+                harness.take_emitted_events::<PageSelected>(),
+                vec![PageSelected(11), PageSelected(9)]
+            );
+
+            // Next and last are disabled on the last page
+            harness.update_root_element(Pagination::new(20, 20));
+            assert!(is_disabled(harness, "»"));
+            assert!(is_disabled(harness, "›"));
+            harness.click_on("»");
+            harness.click_on("«");
+            assert_eq!(
+                harness.take_emitted_events::<PageSelected>(),
+                vec![PageSelected(1)]
+            );
+        });
+    }
+}
@@ -0,0 +1,382 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{PopoverWidget, SinglePopoverWidget};
+
+use druid::EventCtx;
+use tracing::{debug_span, info, instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// Element which displays its content next to an anchor, over the surrounding elements.
+///
+/// The anchor is displayed inline, like any other child. While `open` is true, the content
+/// floats next to the anchor, below it by default (see [`placement`](Popover::placement)).
+/// If the content doesn't fit in the window on that side, it's displayed on the opposite side
+/// instead, and it's slid along the anchor to stay within the window.
+///
+/// While `open` is false, the content isn't built; opening the popover again builds the
+/// content from scratch.
+///
+/// ## Events
+///
+/// Emits [PopoverDismissed] events. Events of the anchor and of the content are only passed
+/// to their callbacks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Popover<Anchor: Element, Content: Element> {
+    pub open: bool,
+    pub anchor: Anchor,
+    pub content: Content,
+    pub placement: Placement,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopoverData<Anchor: VirtualDom, Content: VirtualDom> {
+    pub anchor: Anchor,
+    pub content: Option<Content>,
+    pub placement: Placement,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Where the content of a [Popover] is placed, relative to its anchor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Placement {
+    Below,
+    Above,
+    Left,
+    Right,
+}
+
+/// Event emitted when the mouse is pressed outside of an open [Popover].
+///
+/// Presses on the anchor or on the content don't dismiss it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PopoverDismissed;
+
+//
+// --- IMPLS
+
+impl Default for Placement {
+    fn default() -> Self {
+        Placement::Below
+    }
+}
+
+impl<Anchor: Element, Content: Element> Popover<Anchor, Content> {
+    /// Build a popover showing `content` next to `anchor` if `open` is true.
+    ///
+    /// Use the [.on_dismiss](Popover::on_dismiss) method to provide a closure to be called when the mouse is pressed outside of the popover.
+    pub fn new(open: bool, anchor: Anchor, content: Content) -> Self {
+        Popover {
+            open,
+            anchor,
+            content,
+            placement: Placement::default(),
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the side of the anchor the content is displayed on.
+    pub fn placement(self, placement: Placement) -> Self {
+        Popover { placement, ..self }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Popover {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when the mouse is pressed outside of the popover.
+    ///
+    /// The popover isn't closed automatically; the closure usually sets the flag passed as
+    /// `open` to false.
+    pub fn on_dismiss<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, _: PopoverDismissed| callback(state),
+        )
+    }
+}
+
+impl<Anchor: Element, Content: Element> Element for Popover<Anchor, Content> {
+    type Event = PopoverDismissed;
+
+    type ComponentState = NoState;
+    // The content's state is dropped while the popover is closed
+    type AggregateChildrenState = (
+        Anchor::AggregateChildrenState,
+        Option<Content::AggregateChildrenState>,
+    );
+    type BuildOutput = PopoverData<Anchor::BuildOutput, Content::BuildOutput>;
+
+    #[instrument(name = "Popover", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (anchor_prev_state, content_prev_state) = prev_state;
+        let (anchor, anchor_state) = self.anchor.build(anchor_prev_state);
+        let (content, content_state) = if self.open {
+            let (content, state) = self.content.build(content_prev_state.unwrap_or_default());
+            (Some(content), Some(state))
+        } else {
+            (None, None)
+        };
+        (
+            PopoverData {
+                anchor,
+                content,
+                placement: self.placement,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (anchor_state, content_state),
+        )
+    }
+}
+
+impl<Anchor: VirtualDom, Content: VirtualDom> VirtualDom for PopoverData<Anchor, Content> {
+    type Event = PopoverDismissed;
+    type AggregateChildrenState = (
+        Anchor::AggregateChildrenState,
+        Option<Content::AggregateChildrenState>,
+    );
+    type TargetWidgetSeq = SinglePopoverWidget<Anchor::TargetWidgetSeq, Content::TargetWidgetSeq>;
+
+    #[instrument(name = "Popover", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SinglePopoverWidget::new(
+            PopoverWidget::new(
+                self.anchor.init_tree(),
+                self.content.as_ref().map(|content| content.init_tree()),
+                self.placement,
+                id,
+            ),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Popover", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.anchor
+            .reconcile(&prev_value.anchor, &mut widget_seq.widget_mut().anchor, ctx);
+
+        match (&self.content, &prev_value.content) {
+            (Some(content), Some(prev_content)) => {
+                let content_seq = widget_seq
+                    .widget_mut()
+                    .content
+                    .as_mut()
+                    .expect_or_log("The popover was open. Expected content widgets.");
+                content.reconcile(prev_content, content_seq, ctx);
+            }
+            (Some(content), None) => {
+                // with_event_context takes a FnMut, so the widgets are moved out of an Option
+                let mut content_seq = debug_span!("init_tree").in_scope(|| {
+                    info!("opening popover");
+                    Some(content.init_tree())
+                });
+                widget_seq.pod.with_event_context(
+                    ctx.event_ctx,
+                    |widget: &mut PopoverWidget<_, _>, ctx: &mut EventCtx| {
+                        widget.set_content(content_seq.take(), ctx);
+                    },
+                );
+            }
+            (None, Some(prev_content)) => {
+                info!("closing popover");
+                prev_content.unmount();
+                widget_seq.pod.with_event_context(
+                    ctx.event_ctx,
+                    |widget: &mut PopoverWidget<_, _>, ctx: &mut EventCtx| {
+                        widget.set_content(None, ctx);
+                    },
+                );
+            }
+            (None, None) => {}
+        }
+        if self.placement != prev_value.placement {
+            let placement = self.placement;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut PopoverWidget<_, _>, ctx: &mut EventCtx| {
+                    widget.set_placement(placement, ctx);
+                },
+            );
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.anchor.update_value(other.anchor);
+        match (&mut self.content, other.content) {
+            (Some(content), Some(other_content)) => content.update_value(other_content),
+            (content, other_content) => *content = other_content,
+        }
+        self.placement = other.placement;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.anchor.unmount();
+        if let Some(content) = &self.content {
+            content.unmount();
+        }
+    }
+
+    #[instrument(name = "Popover", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let (anchor_state, content_state) = children_state;
+        let widget = widget_seq.widget_mut();
+        self.anchor
+            .process_event(comp_ctx, anchor_state, &mut widget.anchor, cx);
+        if let (Some(content), Some(content_state), Some(content_seq)) =
+            (&self.content, content_state, &mut widget.content)
+        {
+            content.process_event(comp_ctx, content_state, content_seq, cx);
+        }
+    }
+
+    #[instrument(name = "Popover", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<PopoverDismissed> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            trace!("Processed popover dismissal");
+            Some(PopoverDismissed)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::{Button, ButtonClick, Label};
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_popover() {
+        let popover = |open| Popover::new(open, Label::new("Anchor"), Button::new("Content"));
+
+        let (popover_data, state) = popover(false).build(Default::default());
+        assert!(popover_data.content.is_none());
+        assert!(state.1.is_none());
+        assert_eq!(popover_data.placement, Placement::Below);
+
+        let (popover_data, state) = popover(true).placement(Placement::Right).build(state);
+        assert!(popover_data.content.is_some());
+        assert!(state.1.is_some());
+        assert_eq!(popover_data.placement, Placement::Right);
+    }
+
+    #[test]
+    fn popover_dismiss() {
+        let (content_sender, content_receiver) = channel();
+        let (popover_sender, popover_receiver) = channel();
+        let popover_id = WidgetId::reserved(1);
+
+        let screen = |open| {
+            Column!(
+                Label::new("Top"),
+                EventLogger::new(
+                    popover_sender.clone(),
+                    Popover::new(
+                        open,
+                        Label::new("Anchor"),
+                        EventLogger::new(content_sender.clone(), Button::new("Content")),
+                    )
+                    .with_reserved_id(popover_id),
+                ),
+                Label::new("Bottom"),
+            )
+        };
+
+        Harness::run_test_window(screen(false), |harness| {
+            assert!(harness.find_by_name("Content").is_none());
+
+            harness.update_root_element(screen(true));
+            let anchor_rect = harness.find_by_name("Anchor").unwrap().layout_rect();
+            let content_rect = harness.find_by_name("Content").unwrap().layout_rect();
+            assert!(content_rect.y0 >= anchor_rect.y1);
+            assert_eq!(content_rect.x0, anchor_rect.x0);
+            assert_eq!(
+                harness.get_debug_state(popover_id).other_values["placement"],
+                "Below"
+            );
+
+            // The content floats over the following elements, instead of pushing them down
+            let bottom_rect = harness.find_by_name("Bottom").unwrap().layout_rect();
+            assert_eq!(bottom_rect.y0, anchor_rect.y1);
+
+            // Clicks within the popover don't dismiss it
+            harness.click_on("Content");
+            assert_eq!(content_receiver.try_recv(), Ok(ButtonClick));
+            assert!(popover_receiver.try_recv().is_err());
+
+            harness.click_on("Top");
+            assert_eq!(popover_receiver.try_recv(), Ok(PopoverDismissed));
+
+            harness.update_root_element(screen(false));
+            assert!(harness.find_by_name("Content").is_none());
+        });
+    }
+
+    #[test]
+    fn popover_flip() {
+        let popover_id = WidgetId::reserved(1);
+
+        // The anchor is too close to the bottom of the window to display the content below it
+        let screen = Column!(
+            Label::new("Spacer").fix_height(370.0),
+            Popover::new(true, Label::new("Anchor"), Label::new("Content"))
+                .with_reserved_id(popover_id),
+        );
+
+        Harness::run_test_window(screen, |harness| {
+            let anchor_rect = harness.find_by_name("Anchor").unwrap().layout_rect();
+            let content_rect = harness.find_by_name("Content").unwrap().layout_rect();
+            assert!(content_rect.y1 <= anchor_rect.y0);
+            assert_eq!(
+                harness.get_debug_state(popover_id).other_values["placement"],
+                "Above"
+            );
+        });
+    }
+}
@@ -0,0 +1,205 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{PortalWidget, SinglePortalWidget};
+
+use tracing::instrument;
+
+/// Wrapper which displays its child at the root of the window instead of inline.
+///
+/// The child is laid out within the whole window, at its top-left corner, and painted over
+/// everything else, so it isn't clipped by scroll areas or other containers around the portal.
+/// In its place, the portal leaves an empty widget of minimal size.
+///
+/// The child is still part of the portal's element tree: it gets its state from the
+/// surrounding components, and its events are handled by them as usual. Its widgets are
+/// removed from the window when the portal is.
+///
+/// Keys below a portal aren't found by scrolling methods, since the child isn't laid out in
+/// the portal's coordinate space.
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Portal<Child: Element> {
+    pub child: Child,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortalData<Child: VirtualDom> {
+    pub child: Child,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Portal<Child> {
+    pub fn new(child: Child) -> Self {
+        Portal {
+            child,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The placeholder widget created by this element always has the same id. If two widgets
+    /// are created with the same id (for instance, because the same widget is returned twice
+    /// because of a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Portal {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+// The children's widgets are shared with the root widget, so they can't borrow anything
+impl<Child: Element> Element for Portal<Child>
+where
+    <Child::BuildOutput as VirtualDom>::TargetWidgetSeq: 'static,
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = PortalData<Child::BuildOutput>;
+
+    #[instrument(name = "Portal", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            PortalData {
+                child,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for PortalData<Child>
+where
+    Child::TargetWidgetSeq: 'static,
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SinglePortalWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Portal", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SinglePortalWidget::new(
+            PortalWidget::new(self.child.init_tree(), id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Portal", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        // The children are direct children of the root widget, whose context we have
+        let children = widget_seq.widget().children.clone();
+        let mut children = children.borrow_mut();
+        self.child.reconcile(&prev_value.child, &mut children, ctx);
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Portal", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let children = widget_seq.widget().children.clone();
+        let mut children = children.borrow_mut();
+        self.child
+            .process_event(comp_ctx, children_state, &mut children, cx);
+    }
+
+    #[instrument(name = "Portal", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let children = widget_seq.widget().children.clone();
+        let mut children = children.borrow_mut();
+        self.child
+            .process_local_event(children_state, &mut children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{Button, ButtonClick, Label};
+    use crate::metadata::Metadata;
+    use crate::test_harness::Harness;
+    use crate::{Column, NoEvent};
+    use druid::{Point, Size};
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn portal_widget() {
+        let portal_id = WidgetId::reserved(1);
+        let (click_sender, click_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let column = |open: bool| {
+            let click_sender = click_sender.clone();
+            Column!(
+                Label::new("Header"),
+                open.then(|| {
+                    Portal::new(Button::new("Open"))
+                        .with_reserved_id(portal_id)
+                        .on(md, move |_, _: ButtonClick| click_sender.send(()).unwrap())
+                }),
+            )
+        };
+
+        Harness::run_test_window(column(true), |harness| {
+            // The placeholder is empty, and the child is at the top-left of the window
+            let rect = harness.get_state(portal_id).layout_rect();
+            assert_eq!(rect.size(), Size::ZERO);
+            let button = harness.find_by_name("Open").unwrap();
+            assert_eq!(button.layout_rect().origin(), Point::ZERO);
+            assert_eq!(harness.get_root_debug_state().children.len(), 2);
+
+            // The handler around the portal gets the child's events
+            harness.click_on("Open");
+            assert_eq!(click_receiver.try_recv(), Ok(()));
+
+            harness.update_root_element(column(false));
+            assert!(harness.try_get_state(portal_id).is_none());
+            assert!(harness.find_by_name("Open").is_none());
+            assert_eq!(harness.get_root_debug_state().children.len(), 1);
+        });
+    }
+}
@@ -0,0 +1,172 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::WidgetId;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{ProgressBarWidget, SingleProgressBarWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// A bar showing the progress of a task.
+///
+/// The bar is either filled up to a given fraction, or, if the progress of the task is
+/// unknown, [indeterminate](ProgressBar::indeterminate).
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgressBar {
+    pub progress: Option<f64>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProgressBarData {
+    pub progress: Option<f64>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl ProgressBar {
+    /// Build a progress bar filled up to `progress`, between 0.0 and 1.0.
+    ///
+    /// Values outside that range are clamped; NaN is treated as 0.0.
+    pub fn new(progress: f64) -> Self {
+        let progress = if progress.is_nan() {
+            0.0
+        } else {
+            progress.max(0.0).min(1.0)
+        };
+        ProgressBar {
+            progress: Some(progress),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Build a progress bar for a task whose progress is unknown.
+    pub fn indeterminate() -> Self {
+        ProgressBar {
+            progress: None,
+            ..ProgressBar::new(0.0)
+        }
+    }
+
+    /// Change the way the progress bar's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        ProgressBar {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The progress bar created by this element always has the same id. If two widgets are
+    /// created with the same id (for instance, because the same progress bar is returned twice
+    /// because of a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        ProgressBar {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl Element for ProgressBar {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = ProgressBarData;
+
+    #[instrument(name = "ProgressBar", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (ProgressBarData, ()) {
+        (
+            ProgressBarData {
+                progress: self.progress,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for ProgressBarData {
+    type Event = NoEvent;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleProgressBarWidget;
+
+    #[instrument(name = "ProgressBar", skip(self))]
+    fn init_tree(&self) -> SingleProgressBarWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleProgressBarWidget::new(ProgressBarWidget::new(self.progress, id), self.flex)
+    }
+
+    #[instrument(name = "ProgressBar", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleProgressBarWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+        if self.progress != prev_value.progress {
+            widget.widget_mut().progress = self.progress;
+            widget.request_druid_update(ctx.event_ctx);
+            widget.widget_mut().request_druid_update(ctx.event_ctx);
+            // The indeterminate segment isn't painted by the druid widget, so changing modes
+            // doesn't always trigger a repaint by itself.
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_paint();
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use insta::assert_debug_snapshot;
+    use test_env_log::test;
+
+    #[test]
+    fn new_progress_bar() {
+        let (progress_bar_data, ()) = ProgressBar::new(0.25).build(());
+        assert_eq!(progress_bar_data.progress, Some(0.25));
+
+        assert_eq!(ProgressBar::new(1.5).progress, Some(1.0));
+        assert_eq!(ProgressBar::new(-0.5).progress, Some(0.0));
+        assert_eq!(ProgressBar::new(f64::NAN).progress, Some(0.0));
+        assert_eq!(ProgressBar::indeterminate().progress, None);
+    }
+
+    #[test]
+    fn progress_bar_widget() {
+        let progress_bar = ProgressBar::new(1.5);
+
+        Harness::run_test_window(progress_bar, |harness| {
+            let progress_bar_state = harness.get_root_debug_state();
+            assert_debug_snapshot!(progress_bar_state);
+
+            harness.update_root_element(ProgressBar::indeterminate());
+
+            let progress_bar_state_2 = harness.get_root_debug_state();
+            assert_debug_snapshot!(progress_bar_state_2);
+        });
+    }
+}
@@ -0,0 +1,205 @@
+use crate::ctx::with_provided_context;
+use crate::element_tree::Element;
+use crate::metadata::NoState;
+
+use derivative::Derivative;
+use tracing::instrument;
+
+/// Element which makes a value available to all the components inside its child.
+///
+/// Components read the value with [`CompCtx::use_context`](crate::CompCtx::use_context), or
+/// derive a smaller value from it with [`CompCtx::use_selector`](crate::CompCtx::use_selector). This
+/// lets you pass values like a theme down the tree without adding them to the props of every
+/// component along the way. If providers of the same type are nested, the innermost one
+/// shadows the others.
+///
+/// The value is read when components are built; a component whose build is skipped (eg
+/// because it's inside a [`Memoize`](crate::elements::Memoize)) won't see a changed value.
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct Provider<T: Clone + 'static, Child: Element> {
+    #[derivative(Debug = "ignore")]
+    pub value: T,
+    pub child: Child,
+}
+
+//
+// --- IMPLS
+
+impl<T: Clone + 'static, Child: Element> Provider<T, Child> {
+    pub fn new(value: T, child: Child) -> Self {
+        Provider { value, child }
+    }
+}
+
+impl<T: Clone + 'static, Child: Element> Element for Provider<T, Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = Child::BuildOutput;
+
+    #[instrument(name = "Provider", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let child = self.child;
+        with_provided_context(self.value, move || child.build(prev_state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(dead_code)]
+
+    use super::*;
+    use crate as panoramix;
+    use crate::elements::internals::{ComponentHolder, ComponentHolderData, LabelData};
+    use crate::elements::Label;
+    use crate::Tuple;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use test_env_log::test;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Theme(&'static str);
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct ThemedLabel;
+
+    impl ThemedLabel {
+        fn new() -> ComponentHolder<Self, Label, fn(&panoramix::CompCtx, ()) -> Label> {
+            ComponentHolder::new(ThemedLabel::render, ())
+        }
+
+        fn render(ctx: &panoramix::CompCtx, _props: ()) -> Label {
+            match ctx.use_context::<Theme>() {
+                Some(Theme(name)) => Label::new(name),
+                None => Label::new("no theme"),
+            }
+        }
+    }
+
+    impl panoramix::elements::component::Component for ThemedLabel {
+        type Props = ();
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = panoramix::NoState;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &ThemedLabel::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "ThemedLabel"
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Settings {
+        user: &'static str,
+        volume: u32,
+    }
+
+    // Counts calls to the component function; all copies of a counter are equal
+    #[derive(Debug, Default, Clone)]
+    struct CallCount(Rc<Cell<u32>>);
+
+    impl PartialEq for CallCount {
+        fn eq(&self, other: &Self) -> bool {
+            Rc::ptr_eq(&self.0, &other.0)
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct UserLabel;
+
+    impl UserLabel {
+        fn render(ctx: &panoramix::CompCtx, calls: CallCount) -> Label {
+            calls.0.set(calls.0.get() + 1);
+            let user = ctx.use_selector(|settings: &Settings| settings.user);
+            Label::new(user.unwrap_or("nobody"))
+        }
+    }
+
+    impl panoramix::elements::component::Component for UserLabel {
+        type Props = CallCount;
+        type LocalEvent = panoramix::NoEvent;
+        type LocalState = panoramix::NoState;
+
+        fn new(props: Self::Props) -> panoramix::elements::ElementBox<panoramix::NoEvent> {
+            panoramix::elements::ElementBox::new(ComponentHolder::<Self, _, _>::new(
+                &UserLabel::render,
+                props,
+            ))
+        }
+
+        fn name() -> &'static str {
+            "UserLabel"
+        }
+    }
+
+    #[test]
+    fn no_provider() {
+        let (label_data, _) = ThemedLabel::new().build(Default::default());
+        assert_eq!(label_data.child().text, "no theme");
+    }
+
+    #[test]
+    fn nested_providers() {
+        let tree = Provider::new(
+            Theme("outer"),
+            Tuple!(
+                ThemedLabel::new(),
+                Provider::new(Theme("inner"), ThemedLabel::new()),
+                ThemedLabel::new(),
+            ),
+        );
+        let (tree_data, _) = tree.build(Default::default());
+
+        assert_eq!(tree_data.0.child().text, "outer");
+        // The closest provider shadows the outer one...
+        assert_eq!(tree_data.1.child().text, "inner");
+        // ...but only for its own child
+        assert_eq!(tree_data.2.child().text, "outer");
+
+        // Values are removed once the provider is built
+        let (label_data, _) = ThemedLabel::new().build(Default::default());
+        assert_eq!(label_data.child().text, "no theme");
+    }
+
+    #[test]
+    fn selector_skips_irrelevant_changes() {
+        let calls = CallCount::default();
+        let tree = |user, volume| {
+            Provider::new(
+                Settings { user, volume },
+                ComponentHolder::<UserLabel, _, _>::new(&UserLabel::render, calls.clone()),
+            )
+        };
+        let text = |data: ComponentHolderData<UserLabel, LabelData>| data.child().text.clone();
+
+        let (data, state) = tree("alice", 1).build(Default::default());
+        assert_eq!(calls.0.get(), 1);
+        assert_eq!(text(data), "alice");
+
+        // The selected field didn't change; the component isn't called again
+        let (data, state) = tree("alice", 2).build(state);
+        assert_eq!(calls.0.get(), 1);
+        assert_eq!(text(data), "alice");
+
+        let (data, state) = tree("bob", 2).build(state);
+        assert_eq!(calls.0.get(), 2);
+        assert_eq!(text(data), "bob");
+
+        let (data, _state) = tree("bob", 3).build(state);
+        assert_eq!(calls.0.get(), 2);
+        assert_eq!(text(data), "bob");
+    }
+}
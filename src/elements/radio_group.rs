@@ -0,0 +1,274 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{RadioGroupWidget, SingleRadioGroupWidget};
+
+use druid::EventCtx;
+use std::fmt::Debug;
+use tracing::{instrument, trace};
+
+/// A column of radio buttons, of which at most one is selected.
+///
+/// Each option is a `(value, label)` pair. Options are compared with `PartialEq`, so the
+/// selection is preserved if options are reordered.
+///
+/// ## Events
+///
+/// Emits [RadioSelected] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadioGroup<T: Clone + Debug + PartialEq + 'static> {
+    pub options: Vec<(T, String)>,
+    pub selected: Option<T>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadioGroupData<T: Clone + Debug + PartialEq + 'static> {
+    pub options: Vec<(T, String)>,
+    pub selected: Option<T>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when an option of a [RadioGroup] is picked.
+///
+/// Holds the value of the picked option.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RadioSelected<T>(pub T);
+
+//
+// --- IMPLS
+
+impl<T: Clone + Debug + PartialEq + 'static> RadioGroup<T> {
+    /// Build a radio group from a list of `(value, label)` pairs, with no option selected.
+    ///
+    /// Use the [.on_select](RadioGroup::on_select) method to provide a closure to be called when an option is picked.
+    pub fn new(options: Vec<(T, String)>) -> Self {
+        RadioGroup {
+            options,
+            selected: None,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the currently selected value.
+    ///
+    /// If no option matches the given value, no radio button is checked.
+    pub fn with_selected(self, selected: T) -> Self {
+        RadioGroup {
+            selected: Some(selected),
+            ..self
+        }
+    }
+
+    /// Change the way the group's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        RadioGroup {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The group created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same group is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        RadioGroup {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when an option is picked.
+    ///
+    /// The closure is given the value of the picked option.
+    pub fn on_select<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, T) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: RadioSelected<T>| callback(state, event.0),
+        )
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> RadioGroupData<T> {
+    fn labels(&self) -> Vec<String> {
+        self.options
+            .iter()
+            .map(|(_, label)| label.clone())
+            .collect()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.options.iter().position(|(value, _)| value == selected)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> Element for RadioGroup<T> {
+    type Event = RadioSelected<T>;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = RadioGroupData<T>;
+
+    #[instrument(name = "RadioGroup", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (RadioGroupData<T>, ()) {
+        (
+            RadioGroupData {
+                options: self.options,
+                selected: self.selected,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> VirtualDom for RadioGroupData<T> {
+    type Event = RadioSelected<T>;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleRadioGroupWidget;
+
+    #[instrument(name = "RadioGroup", skip(self))]
+    fn init_tree(&self) -> SingleRadioGroupWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleRadioGroupWidget::new(
+            RadioGroupWidget::new(self.labels(), self.selected_index(), id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "RadioGroup", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleRadioGroupWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.options != prev_value.options {
+            let labels = self.labels();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut RadioGroupWidget, ctx: &mut EventCtx| {
+                    widget.set_labels(labels.clone(), ctx);
+                },
+            );
+        }
+
+        // Options are compared by value, so reordering the options only changes the index
+        let selected_index = self.selected_index();
+        if selected_index != widget.widget().selected_index {
+            widget.widget_mut().selected_index = selected_index;
+            widget.request_druid_update(ctx.event_ctx);
+            widget.widget_mut().request_druid_update(ctx.event_ctx);
+        }
+    }
+
+    #[instrument(name = "RadioGroup", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleRadioGroupWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<RadioSelected<T>> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            let index = widget.widget().selected_index?;
+            let (value, _label) = self.options.get(index)?;
+            if self.selected.as_ref() == Some(value) {
+                return None;
+            }
+            trace!("Processed radio selection: {:?}", value);
+            Some(RadioSelected(value.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use druid::{MouseButton, Point};
+    use insta::assert_debug_snapshot;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn three_options() -> RadioGroup<i32> {
+        RadioGroup::new(vec![
+            (1, String::from("One")),
+            (2, String::from("Two")),
+            (3, String::from("Three")),
+        ])
+    }
+
+    #[test]
+    fn new_radio_group() {
+        let radio_group = three_options().with_selected(2);
+        let (radio_group_data, ()) = radio_group.clone().build(());
+
+        assert_debug_snapshot!(radio_group);
+        assert_debug_snapshot!(radio_group_data);
+
+        assert_eq!(radio_group_data.selected_index(), Some(1));
+    }
+
+    #[test]
+    fn reordered_options() {
+        let (radio_group_data, ()) = three_options().with_selected(2).build(());
+        let mut reordered_group = three_options().with_selected(2);
+        reordered_group.options.reverse();
+        let (reordered_data, ()) = reordered_group.build(());
+
+        assert_eq!(radio_group_data.selected_index(), Some(1));
+        assert_eq!(reordered_data.selected_index(), Some(1));
+
+        let (unknown_data, ()) = three_options().with_selected(42).build(());
+        assert_eq!(unknown_data.selected_index(), None);
+    }
+
+    #[test]
+    fn radio_select() {
+        let (event_sender, event_receiver) = channel();
+        let radio_id = WidgetId::reserved(1);
+        let radio_group = EventLogger::new(
+            event_sender,
+            three_options().with_selected(1).with_reserved_id(radio_id),
+        );
+
+        Harness::run_test_window(radio_group, |harness| {
+            let rect = harness.get_state(radio_id).layout_rect();
+            let first_option = Point::new(rect.x0 + 5.0, rect.y0 + 5.0);
+            let last_option = Point::new(rect.x0 + 5.0, rect.y1 - 5.0);
+
+            // Picking the option that's already selected doesn't emit an event
+            harness.mouse_move(first_option);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            harness.mouse_move(last_option);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(event_receiver.try_recv(), Ok(RadioSelected(3)));
+        });
+    }
+}
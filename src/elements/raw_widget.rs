@@ -0,0 +1,163 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::flex::FlexParams;
+use crate::glue::DruidAppData;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::SingleWidget;
+
+use derivative::Derivative;
+use druid::Widget;
+use std::rc::Rc;
+use tracing::instrument;
+
+/// A druid widget, used as-is in the element tree.
+///
+/// This is an escape hatch for widgets which Panoramix doesn't wrap yet. Since druid widgets
+/// can't be cloned or compared, the element holds a function creating the widget. The
+/// function is only called when the element is first added to the GUI; after that, the
+/// widget is kept as long as the element stays at the same place in the tree, even if the
+/// element is built again with a different function.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct RawWidget<W: Widget<DruidAppData> + 'static> {
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub factory: Rc<dyn Fn() -> W>,
+    pub flex: FlexParams,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct RawWidgetData<W: Widget<DruidAppData> + 'static> {
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub factory: Rc<dyn Fn() -> W>,
+    pub flex: FlexParams,
+}
+
+//
+// --- IMPLS
+
+impl<W: Widget<DruidAppData> + 'static> RawWidget<W> {
+    /// Build an element from a function creating the druid widget.
+    pub fn new(factory: impl Fn() -> W + 'static) -> Self {
+        RawWidget {
+            factory: Rc::new(factory),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        }
+    }
+
+    /// Change the way the widget's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        RawWidget {
+            flex: flex_params,
+            ..self
+        }
+    }
+}
+
+impl<W: Widget<DruidAppData> + 'static> Element for RawWidget<W> {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = RawWidgetData<W>;
+
+    #[instrument(name = "RawWidget", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (RawWidgetData<W>, ()) {
+        (
+            RawWidgetData {
+                factory: self.factory,
+                flex: self.flex,
+            },
+            (),
+        )
+    }
+}
+
+impl<W: Widget<DruidAppData> + 'static> VirtualDom for RawWidgetData<W> {
+    type Event = NoEvent;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleWidget<W>;
+
+    #[instrument(name = "RawWidget", skip(self))]
+    fn init_tree(&self) -> SingleWidget<W> {
+        SingleWidget::new((self.factory)(), self.flex)
+    }
+
+    // The widget is never rebuilt; it handles its own updates.
+    #[instrument(name = "RawWidget", skip(self, _prev_value, widget, _ctx))]
+    fn reconcile(&self, _prev_value: &Self, widget: &mut SingleWidget<W>, _ctx: &mut ReconcileCtx) {
+        widget.flex = self.flex;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Label;
+    use crate::glue::DebugState;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::{
+        BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+        RenderContext, Size, UpdateCtx,
+    };
+    use test_env_log::test;
+
+    // A colored rectangle, written directly against druid
+    struct Swatch;
+
+    impl Widget<DruidAppData> for Swatch {
+        fn event(&mut self, _: &mut EventCtx, _: &Event, _: &mut DruidAppData, _: &Env) {}
+
+        fn lifecycle(&mut self, _: &mut LifeCycleCtx, _: &LifeCycle, _: &DruidAppData, _: &Env) {}
+
+        fn update(&mut self, _: &mut UpdateCtx, _: &DruidAppData, _: &DruidAppData, _: &Env) {}
+
+        fn layout(
+            &mut self,
+            _: &mut LayoutCtx,
+            bc: &BoxConstraints,
+            _: &DruidAppData,
+            _: &Env,
+        ) -> Size {
+            bc.constrain(Size::new(30.0, 20.0))
+        }
+
+        fn paint(&mut self, ctx: &mut PaintCtx, _: &DruidAppData, _: &Env) {
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &Color::RED);
+        }
+
+        fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+            DebugState {
+                display_name: "Swatch".to_string(),
+                main_value: "swatch".to_string(),
+                ..Default::default()
+            }
+        }
+    }
+
+    #[test]
+    fn raw_widget() {
+        let column = Column!(Label::new("Above"), RawWidget::new(|| Swatch));
+
+        Harness::run_test_window(column, |harness| {
+            let swatch = harness.find_by_name("swatch").unwrap();
+            assert_eq!(swatch.debug_state().display_name, "Swatch");
+            assert_eq!(swatch.layout_rect().size(), Size::new(30.0, 20.0));
+
+            // The widget is kept when the element is rebuilt
+            harness.update_root_element(Column!(Label::new("Above"), RawWidget::new(|| Swatch)));
+            assert!(harness.find_by_name("swatch").is_some());
+        });
+    }
+}
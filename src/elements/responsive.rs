@@ -0,0 +1,288 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::Branch;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{ResponsiveWidget, SingleResponsiveWidget};
+
+use druid::EventCtx;
+use either::Either;
+use tracing::{instrument, trace};
+
+/// Default margin past the breakpoint before switching layouts, in pixels.
+const DEFAULT_HYSTERESIS: f64 = 16.0;
+
+/// Element which displays one of two children, depending on the width available to it.
+///
+/// The `narrow` child is displayed while the available width is below `breakpoint`, and the
+/// `wide` child once it's above. Typically, both children hold the same content, laid out in a
+/// [`Column`](crate::Column) and a [`Row`](crate::Row) respectively. Like with
+/// [`If`](crate::elements::If), the widgets of the previous child are replaced when the layout
+/// switches, and both children must have the same event type.
+///
+/// The width is only known once the element has been laid out, so the `narrow` child is
+/// displayed until then. To keep the layout from flickering when the width stays close to the
+/// breakpoint, the width must go past the breakpoint by a margin before the layout switches
+/// back and forth; see [`with_hysteresis`](Responsive::with_hysteresis). Changes of width which
+/// don't switch the layout don't rebuild the children.
+///
+/// ## Events
+///
+/// Emits the events of the displayed child.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Responsive<Narrow: Element, Wide: Element<Event = Narrow::Event>> {
+    pub breakpoint: f64,
+    pub hysteresis: f64,
+    pub narrow: Narrow,
+    pub wide: Wide,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResponsiveData<Narrow: VirtualDom, Wide: VirtualDom<Event = Narrow::Event>> {
+    pub breakpoint: f64,
+    pub hysteresis: f64,
+    pub is_wide: Option<bool>,
+    pub child: Branch<Narrow, Wide>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Narrow: Element, Wide: Element<Event = Narrow::Event>> Responsive<Narrow, Wide> {
+    /// Display `narrow` below `breakpoint`, and `wide` above it.
+    pub fn new(breakpoint: f64, narrow: Narrow, wide: Wide) -> Self {
+        Responsive {
+            breakpoint,
+            hysteresis: DEFAULT_HYSTERESIS,
+            narrow,
+            wide,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set how far past the breakpoint the width must go before the layout switches.
+    ///
+    /// With a hysteresis of zero, the layout switches as soon as the width crosses the
+    /// breakpoint.
+    pub fn with_hysteresis(self, hysteresis: f64) -> Self {
+        Responsive {
+            hysteresis: hysteresis.max(0.0),
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Responsive {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Narrow: Element, Wide: Element<Event = Narrow::Event>> Element for Responsive<Narrow, Wide> {
+    type Event = Narrow::Event;
+    type ComponentState = NoState;
+    // Whether the width was past the breakpoint at the last layout, and the displayed child's
+    // state
+    type AggregateChildrenState = (
+        Option<bool>,
+        Option<Either<Narrow::AggregateChildrenState, Wide::AggregateChildrenState>>,
+    );
+    type BuildOutput = ResponsiveData<Narrow::BuildOutput, Wide::BuildOutput>;
+
+    #[instrument(name = "Responsive", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (is_wide, prev_child_state) = prev_state;
+        let child = if is_wide == Some(true) {
+            Branch::right(self.wide)
+        } else {
+            Branch::left(self.narrow)
+        };
+        let (child, child_state) = child.build(prev_child_state);
+        (
+            ResponsiveData {
+                breakpoint: self.breakpoint,
+                hysteresis: self.hysteresis,
+                is_wide,
+                child,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (is_wide, child_state),
+        )
+    }
+}
+
+impl<Narrow: VirtualDom, Wide: VirtualDom<Event = Narrow::Event>> VirtualDom
+    for ResponsiveData<Narrow, Wide>
+{
+    type Event = Narrow::Event;
+    type AggregateChildrenState = (
+        Option<bool>,
+        Option<Either<Narrow::AggregateChildrenState, Wide::AggregateChildrenState>>,
+    );
+    type TargetWidgetSeq =
+        SingleResponsiveWidget<Either<Narrow::TargetWidgetSeq, Wide::TargetWidgetSeq>>;
+
+    #[instrument(name = "Responsive", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleResponsiveWidget::new(
+            ResponsiveWidget::new(
+                self.child.init_tree(),
+                self.breakpoint,
+                self.hysteresis,
+                self.is_wide,
+                id,
+            ),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Responsive", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        // The widget keeps its own measurement of the width, which the state catches up with
+        if self.breakpoint != prev_value.breakpoint || self.hysteresis != prev_value.hysteresis {
+            let (breakpoint, hysteresis) = (self.breakpoint, self.hysteresis);
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ResponsiveWidget<_>, ctx: &mut EventCtx| {
+                    widget.breakpoint = breakpoint;
+                    widget.hysteresis = hysteresis;
+                    ctx.request_layout();
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.breakpoint = other.breakpoint;
+        self.hysteresis = other.hysteresis;
+        self.is_wide = other.is_wide;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "Responsive",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Responsive", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::BreakpointCrossed(is_wide)) = cx.app_data.dequeue_action(id) {
+            // The other child is built the next time the element is built
+            trace!("Breakpoint crossed: is_wide={}", is_wide);
+            children_state.0 = Some(is_wide);
+        }
+
+        self.child.process_local_event(
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use druid::Size;
+    use test_env_log::test;
+
+    #[test]
+    fn new_responsive() {
+        let responsive = Responsive::new(600.0, Label::new("Narrow"), Label::new("Wide"));
+        assert_eq!(responsive.hysteresis, DEFAULT_HYSTERESIS);
+        assert_eq!(responsive.clone().with_hysteresis(-1.0).hysteresis, 0.0);
+
+        // The narrow child is built until the width is known
+        let (responsive_data, state) = responsive.clone().build(Default::default());
+        assert_eq!(responsive_data.child.0.left().unwrap().text, "Narrow");
+        assert_eq!(state.0, None);
+
+        let (responsive_data, _) = responsive.build((Some(true), state.1));
+        assert_eq!(responsive_data.child.0.right().unwrap().text, "Wide");
+    }
+
+    #[test]
+    fn responsive_widget() {
+        let responsive_id = WidgetId::reserved(1);
+        let responsive = Responsive::new(600.0, Label::new("Narrow"), Label::new("Wide"))
+            .with_hysteresis(20.0)
+            .with_reserved_id(responsive_id);
+
+        Harness::run_test_window(responsive, |harness| {
+            harness.resize_window(Size::new(400.0, 300.0));
+            assert!(harness.find_by_name("Narrow").is_some());
+
+            // Crossing the breakpoint by less than the hysteresis doesn't switch the layout
+            harness.resize_window(Size::new(610.0, 300.0));
+            assert!(harness.find_by_name("Narrow").is_some());
+
+            harness.resize_window(Size::new(640.0, 300.0));
+            assert!(harness.find_by_name("Wide").is_some());
+            assert!(harness.find_by_name("Narrow").is_none());
+
+            // Going back requires the same margin on the other side
+            harness.resize_window(Size::new(590.0, 300.0));
+            assert!(harness.find_by_name("Wide").is_some());
+
+            harness.resize_window(Size::new(560.0, 300.0));
+            assert!(harness.find_by_name("Narrow").is_some());
+            let values = harness.get_debug_state(responsive_id).other_values;
+            assert_eq!(values["is_wide"], "Some(false)");
+        });
+    }
+}
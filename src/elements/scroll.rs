@@ -0,0 +1,367 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{ScrollWidget, SingleScrollWidget};
+
+use druid::EventCtx;
+use tracing::{instrument, trace};
+
+/// Container which lets its child be larger than the available space, and scrolls over it.
+///
+/// Usually created with [`ElementExt::scroll`](crate::ElementExt::scroll),
+/// [`ElementExt::scroll_vertical`](crate::ElementExt::scroll_vertical) or
+/// [`ElementExt::scroll_horizontal`](crate::ElementExt::scroll_horizontal).
+///
+/// The scroll position is kept as long as the container stays in the element tree, even if
+/// its content changes.
+///
+/// The container can also be scrolled programmatically to one of its descendants with
+/// [`with_scroll_to`](Scroll::with_scroll_to).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scroll<Child: Element> {
+    pub child: Child,
+    pub direction: ScrollDirection,
+    pub scroll_to: Option<String>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScrollData<Child: VirtualDom> {
+    pub child: Child,
+    pub direction: ScrollDirection,
+    pub scroll_to: Option<String>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// The axes along which a [Scroll] container can scroll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+    /// Scroll along both axes.
+    All,
+    /// Only scroll vertically; the content is constrained to the container's width.
+    Vertical,
+    /// Only scroll horizontally; the content is constrained to the container's height.
+    Horizontal,
+}
+
+//
+// --- IMPLS
+
+impl Default for ScrollDirection {
+    fn default() -> Self {
+        ScrollDirection::All
+    }
+}
+
+impl<Child: Element> Scroll<Child> {
+    /// Wrap the child in a container scrolling along both axes.
+    pub fn new(child: Child) -> Self {
+        Scroll {
+            child,
+            direction: ScrollDirection::All,
+            scroll_to: None,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Restrict scrolling to the given axes.
+    pub fn with_direction(self, direction: ScrollDirection) -> Self {
+        Scroll { direction, ..self }
+    }
+
+    /// Scroll the minimal distance to show the descendant with the given key.
+    ///
+    /// The container is scrolled once each time the key changes; after that, it can be
+    /// scrolled away as usual. Keys are given with [`ElementExt::key`](crate::ElementExt::key),
+    /// or by an [`ElementList`](crate::elements::ElementList). If no descendant with that key
+    /// is currently built and laid out, the key is ignored until the container is built again.
+    pub fn with_scroll_to(self, key: Option<String>) -> Self {
+        Scroll {
+            scroll_to: key,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Scroll {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Scroll<Child> {
+    type Event = Child::Event;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = ScrollData<Child::BuildOutput>;
+
+    #[instrument(name = "Scroll", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, child_state) = self.child.build(prev_state);
+        (
+            ScrollData {
+                child,
+                direction: self.direction,
+                scroll_to: self.scroll_to,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            child_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for ScrollData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleScrollWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Scroll", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleScrollWidget::new(
+            ScrollWidget::new(self.child.init_tree(), self.direction, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Scroll", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        // Druid's Scroll can't change its direction after it's created
+        if self.direction != prev_value.direction {
+            trace!("Scroll direction changed, recreating widgets");
+            *widget_seq = self.init_tree();
+            return;
+        }
+
+        // The scroll widget is kept as-is, so the scroll position is preserved
+        self.child.reconcile(
+            &prev_value.child,
+            widget_seq.widget_mut().children_mut(),
+            ctx,
+        );
+
+        // The last target we scrolled to is kept in the widget, so that we only scroll once
+        // per target
+        let key = match &self.scroll_to {
+            Some(key) => key,
+            None => {
+                widget_seq.widget_mut().scroll_target = None;
+                return;
+            }
+        };
+        if widget_seq.widget().scroll_target.as_ref() == Some(key) {
+            return;
+        }
+        match self
+            .child
+            .find_key_rect(key, widget_seq.widget_mut().children_mut())
+        {
+            // Widgets which were just created haven't been laid out yet
+            Some(rect) if rect.area() > 0.0 => {
+                let key = key.clone();
+                widget_seq.pod.with_event_context(
+                    ctx.event_ctx,
+                    |widget: &mut ScrollWidget<_>, ctx: &mut EventCtx| {
+                        widget.scroll_to(key.clone(), rect, ctx);
+                    },
+                );
+            }
+            _ => {
+                trace!("Scroll target {:?} not found, not scrolling", key);
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.direction = other.direction;
+        self.scroll_to = other.scroll_to;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Scroll", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            widget_seq.widget_mut().children_mut(),
+            cx,
+        );
+    }
+
+    #[instrument(name = "Scroll", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, widget_seq.widget_mut().children_mut(), cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::button::{Button, ButtonClick};
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::label::{Label, LabelData};
+    use crate::elements::ElementList;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::Vec2;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_scroll() {
+        let (scroll_data, ()) = Label::new("Hello").scroll().build(());
+        assert_eq!(
+            scroll_data,
+            ScrollData {
+                child: LabelData::new("Hello"),
+                direction: ScrollDirection::All,
+                scroll_to: None,
+                reserved_widget_id: None,
+            }
+        );
+
+        let (vertical_data, ()) = Label::new("Hello").scroll_vertical().build(());
+        assert_eq!(vertical_data.direction, ScrollDirection::Vertical);
+
+        let (horizontal_data, ()) = Label::new("Hello").scroll_horizontal().build(());
+        assert_eq!(horizontal_data.direction, ScrollDirection::Horizontal);
+    }
+
+    #[test]
+    fn scroll_widget() {
+        let scroll_id = WidgetId::reserved(1);
+        let scroll = |text: &str| {
+            Label::new(text)
+                .fix_size(100.0, 1000.0)
+                .scroll_vertical()
+                .with_reserved_id(scroll_id)
+        };
+
+        Harness::run_test_window(scroll("Hello"), |harness| {
+            let scroll_state = harness.get_debug_state(scroll_id);
+            assert_eq!(scroll_state.display_name, "Scroll");
+            assert_eq!(scroll_state.main_value, format!("{:?}", Vec2::ZERO));
+
+            harness.mouse_move_to(scroll_id);
+            harness.mouse_wheel((0.0, 100.0));
+            let scroll_state = harness.get_debug_state(scroll_id);
+            assert_eq!(
+                scroll_state.main_value,
+                format!("{:?}", Vec2::new(0.0, 100.0))
+            );
+
+            // The content changes, but the scroll position is kept
+            harness.update_root_element(scroll("World"));
+            let scroll_state = harness.get_debug_state(scroll_id);
+            assert_eq!(
+                scroll_state.main_value,
+                format!("{:?}", Vec2::new(0.0, 100.0))
+            );
+            assert!(format!("{:?}", scroll_state).contains("World"));
+        });
+    }
+
+    #[test]
+    fn scroll_to_key() {
+        let scroll_id = WidgetId::reserved(1);
+        let scroll = |target: Option<&str>| {
+            let items = (0..20).map(|i| {
+                (
+                    format!("item-{}", i),
+                    Label::new(format!("Item {}", i)).fix_height(50.0),
+                )
+            });
+            Column!(ElementList::from_pairs(items))
+                .scroll_vertical()
+                .with_scroll_to(target.map(String::from))
+                .with_reserved_id(scroll_id)
+                .fix_height(100.0)
+        };
+        let offset = |harness: &mut Harness<'_, '_, _>| {
+            harness.get_debug_state(scroll_id).main_value.clone()
+        };
+
+        Harness::run_test_window(scroll(None), |harness| {
+            assert_eq!(offset(harness), format!("{:?}", Vec2::ZERO));
+
+            // Keys which aren't built are ignored
+            harness.update_root_element(scroll(Some("item-99")));
+            assert_eq!(offset(harness), format!("{:?}", Vec2::ZERO));
+
+            // Item 10 spans from 500 to 550; the container scrolls just enough to show it
+            harness.update_root_element(scroll(Some("item-10")));
+            assert_eq!(offset(harness), format!("{:?}", Vec2::new(0.0, 450.0)));
+
+            // The same target doesn't scroll the container again
+            harness.mouse_move_to(scroll_id);
+            harness.mouse_wheel((0.0, -100.0));
+            harness.update_root_element(scroll(Some("item-10")));
+            assert_eq!(offset(harness), format!("{:?}", Vec2::new(0.0, 350.0)));
+
+            harness.update_root_element(scroll(Some("item-0")));
+            assert_eq!(offset(harness), format!("{:?}", Vec2::ZERO));
+        });
+    }
+
+    #[test]
+    fn scroll_events() {
+        let (event_sender, event_receiver) = channel();
+        let scroll_id = WidgetId::reserved(1);
+        let scroll = EventLogger::new(
+            event_sender,
+            Button::new("Hello").scroll().with_reserved_id(scroll_id),
+        );
+
+        Harness::run_test_window(scroll, |harness| {
+            let root_state = format!("{:?}", harness.get_root_debug_state());
+            assert!(root_state.contains("Scroll"));
+
+            // The button's layout rect is relative to the scroll container, so we click on the
+            // container instead
+            harness.mouse_click_on(scroll_id);
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+        });
+    }
+}
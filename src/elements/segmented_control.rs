@@ -0,0 +1,304 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SegmentedControlWidget, SingleSegmentedControlWidget};
+
+use druid::EventCtx;
+use std::fmt::Debug;
+use tracing::{instrument, trace};
+
+/// A row of adjacent buttons, of which at most one is selected.
+///
+/// Each segment is a `(value, label)` pair. Like [RadioGroup](crate::elements::RadioGroup),
+/// segments are compared with `PartialEq`, so the selection is preserved if segments are
+/// reordered; a segmented control is better suited to small sets of options.
+///
+/// Once the control has focus, the left and right arrow keys select the neighboring segments.
+///
+/// ## Events
+///
+/// Emits [SegmentSelected] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentedControl<T: Clone + Debug + PartialEq + 'static> {
+    pub segments: Vec<(T, String)>,
+    pub selected: Option<T>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SegmentedControlData<T: Clone + Debug + PartialEq + 'static> {
+    pub segments: Vec<(T, String)>,
+    pub selected: Option<T>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a segment of a [SegmentedControl] is picked.
+///
+/// Holds the value of the picked segment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentSelected<T>(pub T);
+
+//
+// --- IMPLS
+
+impl<T: Clone + Debug + PartialEq + 'static> SegmentedControl<T> {
+    /// Build a segmented control from a list of `(value, label)` pairs, with no segment selected.
+    ///
+    /// Use the [.on_select](SegmentedControl::on_select) method to provide a closure to be called when a segment is picked.
+    pub fn new(segments: Vec<(T, String)>) -> Self {
+        SegmentedControl {
+            segments,
+            selected: None,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the currently selected value.
+    ///
+    /// If no segment matches the given value, no segment is highlighted.
+    pub fn with_selected(self, selected: T) -> Self {
+        SegmentedControl {
+            selected: Some(selected),
+            ..self
+        }
+    }
+
+    /// Change the way the control's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        SegmentedControl {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The control created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same control is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        SegmentedControl {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a segment is picked.
+    ///
+    /// The closure is given the value of the picked segment.
+    pub fn on_select<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, T) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: SegmentSelected<T>| callback(state, event.0),
+        )
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> SegmentedControlData<T> {
+    fn labels(&self) -> Vec<String> {
+        self.segments
+            .iter()
+            .map(|(_, label)| label.clone())
+            .collect()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let selected = self.selected.as_ref()?;
+        self.segments
+            .iter()
+            .position(|(value, _)| value == selected)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> Element for SegmentedControl<T> {
+    type Event = SegmentSelected<T>;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = SegmentedControlData<T>;
+
+    #[instrument(name = "SegmentedControl", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (SegmentedControlData<T>, ()) {
+        (
+            SegmentedControlData {
+                segments: self.segments,
+                selected: self.selected,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl<T: Clone + Debug + PartialEq + 'static> VirtualDom for SegmentedControlData<T> {
+    type Event = SegmentSelected<T>;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleSegmentedControlWidget;
+
+    #[instrument(name = "SegmentedControl", skip(self))]
+    fn init_tree(&self) -> SingleSegmentedControlWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSegmentedControlWidget::new(
+            SegmentedControlWidget::new(self.labels(), self.selected_index(), id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "SegmentedControl", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleSegmentedControlWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+        let labels = self.labels();
+        if labels != prev_value.labels() {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SegmentedControlWidget, ctx: &mut EventCtx| {
+                    widget.set_labels(labels.clone(), ctx);
+                },
+            );
+        }
+
+        // Segments are compared by value, so reordering the segments only changes the index
+        let selected_index = self.selected_index();
+        if selected_index != widget.widget().selected_index {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SegmentedControlWidget, ctx: &mut EventCtx| {
+                    widget.set_selected_index(selected_index, ctx);
+                },
+            );
+        }
+    }
+
+    #[instrument(name = "SegmentedControl", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleSegmentedControlWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<SegmentSelected<T>> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        if let Some(Action::Selected(index)) = cx.app_data.dequeue_action(id) {
+            let (value, _label) = self.segments.get(index)?;
+            if self.selected.as_ref() == Some(value) {
+                return None;
+            }
+            trace!("Processed segment selection: {:?}", value);
+            Some(SegmentSelected(value.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use druid::KbKey;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn three_segments() -> SegmentedControl<i32> {
+        SegmentedControl::new(vec![
+            (1, String::from("One")),
+            (2, String::from("Two")),
+            (3, String::from("Three")),
+        ])
+    }
+
+    #[test]
+    fn new_segmented_control() {
+        let (control_data, ()) = three_segments().with_selected(2).build(());
+        assert_eq!(control_data.labels(), vec!["One", "Two", "Three"]);
+        assert_eq!(control_data.selected_index(), Some(1));
+
+        let (unknown_data, ()) = three_segments().with_selected(42).build(());
+        assert_eq!(unknown_data.selected_index(), None);
+    }
+
+    #[test]
+    fn segment_select() {
+        let (event_sender, event_receiver) = channel();
+        let control = |selected| {
+            EventLogger::new(
+                event_sender.clone(),
+                three_segments()
+                    .with_selected(selected)
+                    .with_reserved_id(WidgetId::reserved(1)),
+            )
+        };
+        let is_highlighted = |harness: &mut Harness<'_, '_, _>, label: &str| {
+            assert!(harness.find_by_name(label).is_some());
+            let control_state = harness.get_debug_state(WidgetId::reserved(1));
+            control_state.other_values.get("selected") == Some(&label.to_string())
+        };
+
+        Harness::run_test_window(control(1), |harness| {
+            assert!(is_highlighted(harness, "One"));
+
+            // Picking the segment that's already selected doesn't emit an event
+            harness.click_on("One");
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            // The caller decides whether to change the selection
+            harness.click_on("Three");
+            assert_eq!(event_receiver.try_recv(), Ok(SegmentSelected(3)));
+            assert!(is_highlighted(harness, "One"));
+
+            harness.update_root_element(control(3));
+            assert!(is_highlighted(harness, "Three"));
+            assert!(!is_highlighted(harness, "One"));
+        });
+    }
+
+    #[test]
+    fn segment_arrow_keys() {
+        let (event_sender, event_receiver) = channel();
+        let control = |selected| {
+            EventLogger::new(
+                event_sender.clone(),
+                three_segments()
+                    .with_selected(selected)
+                    .with_reserved_id(WidgetId::reserved(1)),
+            )
+        };
+
+        Harness::run_test_window(control(2), |harness| {
+            // Clicking a segment gives focus to the control
+            harness.click_on("Two");
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            harness.press_key(KbKey::ArrowRight);
+            assert_eq!(event_receiver.try_recv(), Ok(SegmentSelected(3)));
+
+            harness.press_key(KbKey::ArrowLeft);
+            assert_eq!(event_receiver.try_recv(), Ok(SegmentSelected(1)));
+
+            // Arrows don't wrap around
+            harness.update_root_element(control(3));
+            harness.press_key(KbKey::ArrowRight);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
+}
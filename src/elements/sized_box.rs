@@ -0,0 +1,280 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::EmptyElement;
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleSizedBoxWidget, SizedBoxWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which forces its child to have a given width and/or height.
+///
+/// Usually created with [`ElementExt::fix_width`](crate::ElementExt::fix_width),
+/// [`ElementExt::fix_height`](crate::ElementExt::fix_height) or
+/// [`ElementExt::fix_size`](crate::ElementExt::fix_size).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SizedBox<Child: Element> {
+    pub child: Child,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SizedBoxData<Child: VirtualDom> {
+    pub child: Child,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> SizedBox<Child> {
+    /// Wrap the child, without constraining its size.
+    pub fn new(child: Child) -> Self {
+        SizedBox {
+            child,
+            width: None,
+            height: None,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the width of the box.
+    pub fn with_width(self, width: f64) -> Self {
+        SizedBox {
+            width: Some(width),
+            ..self
+        }
+    }
+
+    /// Set the height of the box.
+    pub fn with_height(self, height: f64) -> Self {
+        SizedBox {
+            height: Some(height),
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        SizedBox {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl SizedBox<EmptyElement> {
+    /// Build a blank area of the given size.
+    ///
+    /// Useful as a spacer between other elements.
+    pub fn empty(width: f64, height: f64) -> Self {
+        SizedBox::new(EmptyElement::new())
+            .with_width(width)
+            .with_height(height)
+    }
+}
+
+impl<Child: Element> Element for SizedBox<Child> {
+    type Event = Child::Event;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = SizedBoxData<Child::BuildOutput>;
+
+    #[instrument(name = "SizedBox", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, child_state) = self.child.build(prev_state);
+        (
+            SizedBoxData {
+                child,
+                width: self.width,
+                height: self.height,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            child_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for SizedBoxData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleSizedBoxWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "SizedBox", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSizedBoxWidget::new(
+            SizedBoxWidget::new(self.child.init_tree(), self.width, self.height, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "SizedBox", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.width != prev_value.width || self.height != prev_value.height {
+            let (width, height) = (self.width, self.height);
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SizedBoxWidget<Child::TargetWidgetSeq>, ctx: &mut EventCtx| {
+                    widget.set_size(width, height, ctx);
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.width = other.width;
+        self.height = other.height;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "SizedBox",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "SizedBox", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::button::{Button, ButtonClick};
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::label::{Label, LabelData};
+    use crate::test_harness::Harness;
+    use druid::Size;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_sized_box() {
+        let sized_box = Label::new("Hello").fix_size(100.0, 40.0);
+        let (sized_box_data, ()) = sized_box.build(());
+
+        assert_eq!(
+            sized_box_data,
+            SizedBoxData {
+                child: LabelData::new("Hello"),
+                width: Some(100.0),
+                height: Some(40.0),
+                reserved_widget_id: None,
+            }
+        );
+
+        let (width_data, ()) = Label::new("Hello").fix_width(100.0).build(());
+        assert_eq!((width_data.width, width_data.height), (Some(100.0), None));
+
+        let (height_data, ()) = Label::new("Hello").fix_height(40.0).build(());
+        assert_eq!((height_data.width, height_data.height), (None, Some(40.0)));
+    }
+
+    #[test]
+    fn sized_box_widget() {
+        let box_id = WidgetId::reserved(1);
+        let sized_box = |width: f64, height: f64| {
+            Label::new("Hello")
+                .fix_size(width, height)
+                .with_reserved_id(box_id)
+        };
+
+        Harness::run_test_window(sized_box(100.0, 40.0), |harness| {
+            let rect = harness.get_state(box_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(100.0, 40.0));
+
+            harness.update_root_element(sized_box(60.0, 30.0));
+            let rect = harness.get_state(box_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(60.0, 30.0));
+        });
+    }
+
+    #[test]
+    fn empty_sized_box() {
+        let box_id = WidgetId::reserved(1);
+        let strut = SizedBox::empty(50.0, 20.0).with_reserved_id(box_id);
+
+        Harness::run_test_window(strut, |harness| {
+            let rect = harness.get_state(box_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(50.0, 20.0));
+        });
+    }
+
+    #[test]
+    fn sized_box_events() {
+        let (event_sender, event_receiver) = channel();
+        let box_id = WidgetId::reserved(1);
+        let sized_box = EventLogger::new(
+            event_sender,
+            Button::new("Hello")
+                .fix_size(100.0, 40.0)
+                .with_reserved_id(box_id),
+        );
+
+        Harness::run_test_window(sized_box, |harness| {
+            // The button's layout rect is relative to the box, so we click on the box instead
+            harness.mouse_click_on(box_id);
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+        });
+    }
+}
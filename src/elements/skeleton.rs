@@ -0,0 +1,182 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::WidgetId;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleSkeletonWidget, SkeletonWidget};
+
+use druid::{EventCtx, Size};
+use tracing::instrument;
+
+/// The outline of a [Skeleton].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkeletonShape {
+    /// A rectangle with rounded corners.
+    Rectangle,
+    /// A circle fitting in the element.
+    Circle,
+}
+
+/// A placeholder with a shimmer sweeping over it, displayed while data is loading.
+///
+/// The placeholder has a fixed size, which should be close to the size of the content it
+/// stands for. See also [`Suspense`](crate::elements::Suspense).
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Skeleton {
+    pub shape: SkeletonShape,
+    pub size: Size,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkeletonData {
+    pub shape: SkeletonShape,
+    pub size: Size,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl Skeleton {
+    /// Build a rectangular placeholder, eg for a line of text.
+    pub fn new(width: f64, height: f64) -> Self {
+        Skeleton {
+            shape: SkeletonShape::Rectangle,
+            size: Size::new(width, height),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Build a round placeholder, eg for an avatar.
+    pub fn circle(diameter: f64) -> Self {
+        Skeleton {
+            shape: SkeletonShape::Circle,
+            ..Skeleton::new(diameter, diameter)
+        }
+    }
+
+    /// Change the way the placeholder's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Skeleton {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The placeholder created by this element always has the same id. If two widgets are
+    /// created with the same id (for instance, because the same placeholder is returned twice
+    /// because of a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Skeleton {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl Element for Skeleton {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = SkeletonData;
+
+    #[instrument(name = "Skeleton", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (SkeletonData, ()) {
+        (
+            SkeletonData {
+                shape: self.shape,
+                size: self.size,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for SkeletonData {
+    type Event = NoEvent;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleSkeletonWidget;
+
+    #[instrument(name = "Skeleton", skip(self))]
+    fn init_tree(&self) -> SingleSkeletonWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSkeletonWidget::new(SkeletonWidget::new(self.shape, self.size, id), self.flex)
+    }
+
+    #[instrument(name = "Skeleton", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleSkeletonWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+        if self.shape != prev_value.shape || self.size != prev_value.size {
+            let (shape, size) = (self.shape, self.size);
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SkeletonWidget, ctx: &mut EventCtx| {
+                    widget.set_shape(shape, size, ctx);
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use std::time::Duration;
+    use test_env_log::test;
+
+    #[test]
+    fn new_skeleton() {
+        let (skeleton_data, ()) = Skeleton::new(120.0, 16.0).build(());
+        assert_eq!(skeleton_data.shape, SkeletonShape::Rectangle);
+        assert_eq!(skeleton_data.size, Size::new(120.0, 16.0));
+
+        let (skeleton_data, ()) = Skeleton::circle(32.0).build(());
+        assert_eq!(skeleton_data.shape, SkeletonShape::Circle);
+        assert_eq!(skeleton_data.size, Size::new(32.0, 32.0));
+    }
+
+    #[test]
+    fn skeleton_widget() {
+        let skeleton_id = WidgetId::reserved(1);
+        let with_id = |skeleton: Skeleton| skeleton.with_reserved_id(skeleton_id);
+
+        Harness::run_test_window(with_id(Skeleton::new(120.0, 16.0)), |harness| {
+            let rect = harness.get_state(skeleton_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(120.0, 16.0));
+
+            // The shimmer doesn't change the layout
+            harness.animate_frame(Duration::from_millis(100));
+            let rect = harness.get_state(skeleton_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(120.0, 16.0));
+
+            harness.update_root_element(with_id(Skeleton::circle(32.0)));
+            let rect = harness.get_state(skeleton_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(32.0, 32.0));
+            let values = harness.get_debug_state(skeleton_id).other_values;
+            assert_eq!(values["shape"], "Circle");
+        });
+    }
+}
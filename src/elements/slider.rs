@@ -0,0 +1,365 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleSliderWidget, SliderWidget};
+
+use tracing::{instrument, trace};
+
+/// A slider, to pick a number in a range.
+///
+/// ## Events
+///
+/// Emits [SliderMoved] events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Slider {
+    pub min: f64,
+    pub max: f64,
+    pub step: Option<f64>,
+    pub value: f64,
+    pub release_only: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SliderData {
+    pub min: f64,
+    pub max: f64,
+    pub step: Option<f64>,
+    pub value: f64,
+    pub release_only: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [Slider] is moved.
+///
+/// Holds the new value of the slider.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SliderMoved(pub f64);
+
+//
+// --- IMPLS
+
+impl Slider {
+    /// Build a slider covering the range from `min` to `max`.
+    ///
+    /// The slider starts at `min`. Use the [.on_move](Slider::on_move) method to provide a closure to be called when the slider is moved.
+    pub fn new(min: f64, max: f64) -> Self {
+        Slider {
+            min,
+            max,
+            step: None,
+            value: min,
+            release_only: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the current value of the slider.
+    pub fn with_value(self, value: f64) -> Self {
+        Slider { value, ..self }
+    }
+
+    /// Round reported values to a multiple of `step` (starting from `min`).
+    pub fn with_step(self, step: f64) -> Self {
+        Slider {
+            step: Some(step),
+            ..self
+        }
+    }
+
+    /// Only emit an event when the user releases the slider.
+    ///
+    /// By default, the slider emits an event every time the value changes while it's dragged.
+    pub fn on_release_only(self) -> Self {
+        Slider {
+            release_only: true,
+            ..self
+        }
+    }
+
+    /// Change the way the slider's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Slider {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The slider created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same slider is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Slider {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this slider is moved.
+    ///
+    /// The closure is given the new value of the slider.
+    pub fn on_move<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, f64) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, event: SliderMoved| {
+            callback(state, event.0)
+        })
+    }
+}
+
+impl SliderData {
+    fn clamp_value(&self, value: f64) -> f64 {
+        let value = match self.step {
+            Some(step) if step > 0.0 => self.min + ((value - self.min) / step).round() * step,
+            _ => value,
+        };
+        value.max(self.min).min(self.max)
+    }
+}
+
+impl Element for Slider {
+    type Event = SliderMoved;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = SliderData;
+
+    #[instrument(name = "Slider", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (SliderData, ()) {
+        (
+            SliderData {
+                min: self.min,
+                max: self.max,
+                step: self.step,
+                value: self.value,
+                release_only: self.release_only,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for SliderData {
+    type Event = SliderMoved;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleSliderWidget;
+
+    #[instrument(name = "Slider", skip(self))]
+    fn init_tree(&self) -> SingleSliderWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSliderWidget::new(
+            SliderWidget::new(self.min, self.max, self.value, self.release_only, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Slider", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleSliderWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        // Druid's Slider can't change its range after it's created
+        if self.min != prev_value.min || self.max != prev_value.max || self.step != prev_value.step
+        {
+            trace!("Slider range changed, recreating widget");
+            *widget = self.init_tree();
+            return;
+        }
+
+        widget.widget_mut().release_only = self.release_only;
+        if self.value != prev_value.value {
+            widget.widget_mut().value = self.value;
+            widget.widget_mut().request_druid_update(ctx.event_ctx);
+        }
+    }
+
+    #[instrument(name = "Slider", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleSliderWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<SliderMoved> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        if let Some(Action::ValueChanged(value)) = cx.app_data.dequeue_action(id) {
+            let value = self.clamp_value(value);
+            trace!("Processed slider move: {}", value);
+            Some(SliderMoved(value))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use druid::{MouseButton, Vec2};
+    use insta::assert_debug_snapshot;
+    use std::sync::mpsc::{channel, Receiver};
+    use test_env_log::test;
+
+    #[test]
+    fn new_slider() {
+        let slider = Slider::new(0.0, 100.0).with_step(10.0);
+        let (slider_data, ()) = slider.clone().build(());
+
+        assert_debug_snapshot!(slider);
+        assert_debug_snapshot!(slider_data);
+
+        assert_eq!(
+            slider_data,
+            SliderData {
+                min: 0.0,
+                max: 100.0,
+                step: Some(10.0),
+                value: 0.0,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn clamp_value() {
+        let (slider_data, ()) = Slider::new(0.0, 100.0).build(());
+        assert_eq!(slider_data.clamp_value(42.5), 42.5);
+        assert_eq!(slider_data.clamp_value(-3.0), 0.0);
+        assert_eq!(slider_data.clamp_value(120.0), 100.0);
+
+        let (slider_data, ()) = Slider::new(0.0, 100.0).with_step(10.0).build(());
+        assert_eq!(slider_data.clamp_value(42.5), 40.0);
+        assert_eq!(slider_data.clamp_value(97.0), 100.0);
+    }
+
+    #[test]
+    fn slider_drag() {
+        let (event_sender, event_receiver) = channel();
+        let slider_id = WidgetId::reserved(1);
+        let slider = EventLogger::new(
+            event_sender,
+            Slider::new(0.0, 100.0).with_reserved_id(slider_id),
+        );
+
+        Harness::run_test_window(slider, |harness| {
+            harness.mouse_move_to(slider_id);
+            harness.mouse_button_press(MouseButton::Left);
+            let press_events: Vec<_> = event_receiver.try_iter().collect();
+
+            let drag_pos = harness.mouse_state.pos + Vec2::new(10.0, 0.0);
+            harness.mouse_move(drag_pos);
+            let drag_events: Vec<_> = event_receiver.try_iter().collect();
+
+            harness.mouse_button_release(MouseButton::Left);
+
+            assert_eq!(press_events.len(), 1);
+            assert_eq!(drag_events.len(), 1);
+            for SliderMoved(value) in press_events.iter().chain(drag_events.iter()) {
+                assert!((0.0..=100.0).contains(value));
+            }
+        });
+    }
+
+    #[test]
+    fn slider_drag_release_only() {
+        let (event_sender, event_receiver) = channel();
+        let slider_id = WidgetId::reserved(1);
+        let slider = EventLogger::new(
+            event_sender,
+            Slider::new(0.0, 100.0)
+                .on_release_only()
+                .with_reserved_id(slider_id),
+        );
+
+        Harness::run_test_window(slider, |harness| {
+            harness.mouse_move_to(slider_id);
+            harness.mouse_button_press(MouseButton::Left);
+            let drag_pos = harness.mouse_state.pos + Vec2::new(10.0, 0.0);
+            harness.mouse_move(drag_pos);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            harness.mouse_button_release(MouseButton::Left);
+            let release_events: Vec<_> = event_receiver.try_iter().collect();
+
+            assert_eq!(release_events.len(), 1);
+            assert!((0.0..=100.0).contains(&release_events[0].0));
+        });
+    }
+
+    // Press the slider at its center, then release it, and return the events emitted by each
+    fn press_and_release(
+        harness: &mut Harness<'_, '_, EventLogger<Slider>>,
+        slider_id: WidgetId,
+        event_receiver: &Receiver<SliderMoved>,
+    ) -> (Vec<SliderMoved>, Vec<SliderMoved>) {
+        harness.mouse_move_to(slider_id);
+        harness.mouse_button_press(MouseButton::Left);
+        let press_events = event_receiver.try_iter().collect();
+        harness.mouse_button_release(MouseButton::Left);
+        let release_events = event_receiver.try_iter().collect();
+        (press_events, release_events)
+    }
+
+    #[test]
+    fn slider_modes() {
+        let (event_sender, event_receiver) = channel();
+        let slider_id = WidgetId::reserved(1);
+        let slider = |release_only| {
+            let slider = Slider::new(0.0, 100.0)
+                .with_step(10.0)
+                .with_reserved_id(slider_id);
+            let slider = if release_only {
+                slider.on_release_only()
+            } else {
+                slider
+            };
+            EventLogger::new(event_sender.clone(), slider)
+        };
+
+        Harness::run_test_window(slider(false), |harness| {
+            assert_debug_snapshot!(press_and_release(harness, slider_id, &event_receiver));
+        });
+        Harness::run_test_window(slider(true), |harness| {
+            assert_debug_snapshot!(press_and_release(harness, slider_id, &event_receiver));
+        });
+    }
+
+    #[test]
+    fn slider_range_change() {
+        let (event_sender, event_receiver) = channel();
+        let slider_id = WidgetId::reserved(1);
+        let slider = |max| {
+            EventLogger::new(
+                event_sender.clone(),
+                Slider::new(0.0, max)
+                    .with_step(5.0)
+                    .with_reserved_id(slider_id),
+            )
+        };
+
+        Harness::run_test_window(slider(100.0), |harness| {
+            harness.update_root_element(slider(50.0));
+            let (press_events, _) = press_and_release(harness, slider_id, &event_receiver);
+            assert_eq!(press_events, vec![SliderMoved(25.0)]);
+        });
+    }
+}
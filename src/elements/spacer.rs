@@ -0,0 +1,126 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::SpacerWidget;
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Empty element which takes up space in a [Flex](crate::elements::Flex) container.
+///
+/// The space left over by the other children of the container is shared between spacers and
+/// flex children, in proportion to their flex factor.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spacer {
+    pub flex: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpacerData {
+    pub flex: f64,
+}
+
+//
+// --- IMPLS
+
+impl Spacer {
+    /// Build a spacer with a flex factor of 1.
+    pub fn new() -> Self {
+        Spacer { flex: 1.0 }
+    }
+
+    /// Build a spacer with the given flex factor.
+    pub fn flex(flex: f64) -> Self {
+        Spacer { flex }
+    }
+}
+
+impl SpacerData {
+    fn flex_params(&self) -> FlexParams {
+        FlexParams {
+            flex: Some(self.flex),
+            alignment: None,
+        }
+    }
+}
+
+impl Element for Spacer {
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = SpacerData;
+
+    #[instrument(name = "Spacer", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (SpacerData, ()) {
+        (SpacerData { flex: self.flex }, ())
+    }
+}
+
+impl VirtualDom for SpacerData {
+    type Event = NoEvent;
+    type AggregateChildrenState = ();
+    type TargetWidgetSeq = SpacerWidget;
+
+    #[instrument(name = "Spacer", skip(self))]
+    fn init_tree(&self) -> SpacerWidget {
+        SpacerWidget::new(self.flex_params())
+    }
+
+    #[instrument(name = "Spacer", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut SpacerWidget, ctx: &mut ReconcileCtx) {
+        if self.flex != prev_value.flex {
+            widget.flex = self.flex_params();
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::SizedBox;
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use crate::Row;
+    use test_env_log::test;
+
+    #[test]
+    fn new_spacer() {
+        let (spacer_data, ()) = Spacer::new().build(());
+        assert_eq!(spacer_data, SpacerData { flex: 1.0 });
+
+        let (spacer_data, ()) = Spacer::flex(3.0).build(());
+        assert_eq!(spacer_data, SpacerData { flex: 3.0 });
+    }
+
+    #[test]
+    fn spacer_layout() {
+        let first_id = WidgetId::reserved(1);
+        let second_id = WidgetId::reserved(2);
+        let third_id = WidgetId::reserved(3);
+
+        // The test window is 400px wide; the boxes take 200px, and the spacers share the rest
+        let row = Row!(
+            SizedBox::empty(100.0, 10.0).with_reserved_id(first_id),
+            Spacer::new(),
+            SizedBox::empty(50.0, 10.0).with_reserved_id(second_id),
+            Spacer::flex(3.0),
+            SizedBox::empty(50.0, 10.0).with_reserved_id(third_id),
+        );
+
+        Harness::run_test_window(row, |harness| {
+            assert_eq!(harness.get_state(first_id).layout_rect().x0, 0.0);
+            assert_eq!(harness.get_state(second_id).layout_rect().x0, 150.0);
+            assert_eq!(harness.get_state(third_id).layout_rect().x0, 350.0);
+        });
+    }
+}
@@ -0,0 +1,160 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleSpanWidget, SpanWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which makes its child take up several columns of a [Grid](crate::elements::Grid).
+///
+/// If the child doesn't fit in what's left of the current row, it's moved to the next row.
+/// Spans wider than the grid are clamped to its number of columns.
+///
+/// Usually created with [`ElementExt::span`](crate::ElementExt::span).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Span<Child: Element> {
+    pub child: Child,
+    pub span: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpanData<Child: VirtualDom> {
+    pub child: Child,
+    pub span: usize,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Span<Child> {
+    pub fn new(child: Child, span: usize) -> Self {
+        Span { child, span }
+    }
+}
+
+impl<Child: Element> Element for Span<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = SpanData<Child::BuildOutput>;
+
+    #[instrument(name = "Span", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            SpanData {
+                child,
+                span: self.span,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for SpanData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleSpanWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Span", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleSpanWidget::new(
+            SpanWidget::new(self.child.init_tree(), WidgetId::next()),
+            self.span,
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Span", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.span != prev_value.span {
+            widget_seq.span = self.span;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.span = other.span;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Span", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Span", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::label::{Label, LabelData};
+    use test_env_log::test;
+
+    #[test]
+    fn new_span() {
+        let (span_data, _) = Label::new("Hello").span(2).build(Default::default());
+
+        assert_eq!(
+            span_data,
+            SpanData {
+                child: LabelData::new("Hello"),
+                span: 2,
+            }
+        );
+    }
+}
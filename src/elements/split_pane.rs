@@ -0,0 +1,355 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::{Axis, FlexParams};
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleSplitPaneWidget, SplitPaneWidget};
+
+use druid::EventCtx;
+use tracing::{instrument, trace};
+
+/// Element which displays two panes next to each other, separated by a bar which can be
+/// dragged to resize them.
+///
+/// The split pane takes all the space it's given; the panes are given all of its height (for
+/// a horizontal split pane) or all of its width (for a vertical one). The split is the
+/// fraction of the space given to the first pane, and is clamped so that each pane keeps its
+/// minimum size (see [`with_min_sizes`](SplitPane::with_min_sizes)) if possible.
+///
+/// ## Events
+///
+/// Emits [SplitMoved] events. Events of the panes are only passed to their callbacks.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitPane<First: Element, Second: Element> {
+    pub first: First,
+    pub second: Second,
+    pub axis: Axis,
+    pub split: f64,
+    pub min_sizes: (f64, f64),
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SplitPaneData<First: VirtualDom, Second: VirtualDom> {
+    pub first: First,
+    pub second: Second,
+    pub axis: Axis,
+    pub split: f64,
+    pub min_sizes: (f64, f64),
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when the bar of a [SplitPane] is dragged.
+///
+/// Holds the new split, ie the fraction of the space given to the first pane.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SplitMoved(pub f64);
+
+//
+// --- IMPLS
+
+impl<First: Element, Second: Element> SplitPane<First, Second> {
+    /// Build a split pane with `first` on the left and `second` on the right, each taking
+    /// half of the space.
+    ///
+    /// Use the [.on_move](SplitPane::on_move) method to provide a closure to be called when the bar is dragged.
+    pub fn horizontal(first: First, second: Second) -> Self {
+        SplitPane::new(Axis::Horizontal, first, second)
+    }
+
+    /// Build a split pane with `first` at the top and `second` at the bottom, each taking
+    /// half of the space.
+    ///
+    /// Use the [.on_move](SplitPane::on_move) method to provide a closure to be called when the bar is dragged.
+    pub fn vertical(first: First, second: Second) -> Self {
+        SplitPane::new(Axis::Vertical, first, second)
+    }
+
+    fn new(axis: Axis, first: First, second: Second) -> Self {
+        SplitPane {
+            first,
+            second,
+            axis,
+            split: 0.5,
+            min_sizes: (0.0, 0.0),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the fraction of the space given to the first pane, from 0.0 to 1.0.
+    pub fn with_split(self, split: f64) -> Self {
+        SplitPane {
+            split: split.max(0.0).min(1.0),
+            ..self
+        }
+    }
+
+    /// Set the minimum size of each pane along the split axis, in pixels.
+    pub fn with_min_sizes(self, first: f64, second: f64) -> Self {
+        SplitPane {
+            min_sizes: (first, second),
+            ..self
+        }
+    }
+
+    /// Change the way the split pane's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        SplitPane {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        SplitPane {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when the bar is dragged.
+    ///
+    /// The closure is given the new split. The bar stays where it was dragged even if the
+    /// split passed to [`with_split`](SplitPane::with_split) isn't updated.
+    pub fn on_move<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, f64) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, event: SplitMoved| {
+            callback(state, event.0)
+        })
+    }
+}
+
+impl<First: Element, Second: Element> Element for SplitPane<First, Second> {
+    type Event = SplitMoved;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = (
+        First::AggregateChildrenState,
+        Second::AggregateChildrenState,
+    );
+    type BuildOutput = SplitPaneData<First::BuildOutput, Second::BuildOutput>;
+
+    #[instrument(name = "SplitPane", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (first_prev_state, second_prev_state) = prev_state;
+        let (first, first_state) = self.first.build(first_prev_state);
+        let (second, second_state) = self.second.build(second_prev_state);
+        (
+            SplitPaneData {
+                first,
+                second,
+                axis: self.axis,
+                split: self.split,
+                min_sizes: self.min_sizes,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (first_state, second_state),
+        )
+    }
+}
+
+impl<First: VirtualDom, Second: VirtualDom> VirtualDom for SplitPaneData<First, Second> {
+    type Event = SplitMoved;
+    type AggregateChildrenState = (
+        First::AggregateChildrenState,
+        Second::AggregateChildrenState,
+    );
+    type TargetWidgetSeq = SingleSplitPaneWidget<First::TargetWidgetSeq, Second::TargetWidgetSeq>;
+
+    #[instrument(name = "SplitPane", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSplitPaneWidget::new(
+            SplitPaneWidget::new(
+                self.first.init_tree(),
+                self.second.init_tree(),
+                self.axis,
+                self.split,
+                self.min_sizes,
+                id,
+            ),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "SplitPane", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let widget = widget_seq.widget_mut();
+        self.first
+            .reconcile(&prev_value.first, &mut widget.first, ctx);
+        self.second
+            .reconcile(&prev_value.second, &mut widget.second, ctx);
+
+        if self.split != prev_value.split {
+            let split = self.split;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SplitPaneWidget<_, _>, ctx: &mut EventCtx| {
+                    widget.set_split(split, ctx);
+                },
+            );
+        }
+        if self.axis != prev_value.axis {
+            let axis = self.axis;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SplitPaneWidget<_, _>, ctx: &mut EventCtx| {
+                    widget.set_axis(axis, ctx);
+                },
+            );
+        }
+        if self.min_sizes != prev_value.min_sizes {
+            let min_sizes = self.min_sizes;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SplitPaneWidget<_, _>, ctx: &mut EventCtx| {
+                    widget.set_min_sizes(min_sizes, ctx);
+                },
+            );
+        }
+        widget_seq.flex = self.flex;
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.first.update_value(other.first);
+        self.second.update_value(other.second);
+        self.axis = other.axis;
+        self.split = other.split;
+        self.min_sizes = other.min_sizes;
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.first.unmount();
+        self.second.unmount();
+    }
+
+    #[instrument(
+        name = "SplitPane",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let (first_state, second_state) = children_state;
+        let widget = widget_seq.widget_mut();
+        self.first
+            .process_event(comp_ctx, first_state, &mut widget.first, cx);
+        self.second
+            .process_event(comp_ctx, second_state, &mut widget.second, cx);
+    }
+
+    #[instrument(name = "SplitPane", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<SplitMoved> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::ValueChanged(split)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed split move: {}", split);
+            Some(SplitMoved(split))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use druid::{MouseButton, Point};
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_split_pane() {
+        let split_pane = SplitPane::horizontal(Label::new("Left"), Label::new("Right"))
+            .with_split(0.3)
+            .with_min_sizes(20.0, 40.0);
+        let (split_pane_data, _) = split_pane.build(Default::default());
+
+        assert_eq!(split_pane_data.axis, Axis::Horizontal);
+        assert_eq!(split_pane_data.split, 0.3);
+        assert_eq!(split_pane_data.min_sizes, (20.0, 40.0));
+
+        let split_pane =
+            SplitPane::vertical(Label::new("Top"), Label::new("Bottom")).with_split(1.5);
+        assert_eq!(split_pane.axis, Axis::Vertical);
+        assert_eq!(split_pane.split, 1.0);
+    }
+
+    #[test]
+    fn split_pane_drag() {
+        let (event_sender, event_receiver) = channel();
+        let split_pane = EventLogger::new(
+            event_sender,
+            SplitPane::horizontal(Label::new("Left"), Label::new("Right"))
+                .with_split(0.25)
+                .with_min_sizes(50.0, 100.0),
+        );
+
+        Harness::run_test_window(split_pane, |harness| {
+            let left_rect = harness.find_by_name("Left").unwrap().layout_rect();
+            let right_rect = harness.find_by_name("Right").unwrap().layout_rect();
+            let available = left_rect.width() + right_rect.width();
+            assert_eq!(left_rect.width() / available, 0.25);
+
+            // The bar can be grabbed slightly outside of its painted area
+            let y = left_rect.center().y;
+            harness.mouse_move(Point::new(left_rect.x1 - 2.0, y));
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(Point::new(left_rect.x1 + 38.0, y));
+            harness.mouse_button_release(MouseButton::Left);
+
+            let SplitMoved(split) = event_receiver.try_iter().last().unwrap();
+            assert!((split - (left_rect.width() + 40.0) / available).abs() < 1e-9);
+            let left_rect = harness.find_by_name("Left").unwrap().layout_rect();
+            assert!((left_rect.width() - split * available).abs() < 1e-9);
+
+            // Dragging past the minimum size of the second pane stops at that size
+            harness.mouse_move(Point::new(left_rect.x1 + 1.0, y));
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(Point::new(right_rect.x1, y));
+            harness.mouse_button_release(MouseButton::Left);
+
+            let SplitMoved(split) = event_receiver.try_iter().last().unwrap();
+            assert!((split - (available - 100.0) / available).abs() < 1e-9);
+            let right_rect = harness.find_by_name("Right").unwrap().layout_rect();
+            assert!((right_rect.width() - 100.0).abs() < 1e-9);
+        });
+    }
+}
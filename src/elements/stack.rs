@@ -0,0 +1,233 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::GlobalEventCx;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleWidget, StackWidget};
+
+use druid::{EventCtx, UnitPoint};
+
+use tracing::instrument;
+
+/// A container which layers its children on top of each other.
+///
+/// Children are drawn in the order they're declared, so the last child is on top. The stack
+/// is as big as its biggest child; smaller children are placed according to the stack's
+/// alignment, or their own if they're wrapped with [`ElementExt::align`](crate::ElementExt::align).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stack<Child: Element> {
+    pub child: Child,
+    pub alignment: UnitPoint,
+    pub flex: FlexParams,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackData<Child: VirtualDom> {
+    pub child: Child,
+    pub alignment: UnitPoint,
+    pub flex: FlexParams,
+}
+
+// ----
+
+impl<Child: Element> Stack<Child> {
+    /// Build a stack; `child` is usually a tuple or a list of elements.
+    pub fn new(child: Child) -> Self {
+        Stack {
+            child,
+            alignment: UnitPoint::TOP_LEFT,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        }
+    }
+
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Stack {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// Set where children are placed by default, within the stack's bounds.
+    pub fn with_alignment(self, alignment: UnitPoint) -> Self {
+        Stack { alignment, ..self }
+    }
+}
+
+// ----
+
+impl<Child: Element> Element for Stack<Child> {
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = StackData<Child::BuildOutput>;
+
+    #[instrument(name = "Stack", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, children_state) = self.child.build(prev_state);
+        (
+            StackData {
+                child: element,
+                alignment: self.alignment,
+                flex: self.flex,
+            },
+            children_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for StackData<Child> {
+    type Event = NoEvent;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleWidget<StackWidget<Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Stack", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let stack = StackWidget {
+            alignment: self.alignment,
+            children_seq: self.child.init_tree(),
+        };
+        SingleWidget::new(stack, self.flex)
+    }
+
+    #[instrument(name = "Stack", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.alignment != prev_value.alignment {
+            widget_seq.widget_mut().alignment = self.alignment;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+        widget_seq.flex = self.flex;
+
+        // Children are reconciled in place, so their z-order follows declaration order.
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children_seq,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.alignment = other.alignment;
+        self.flex = other.flex;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Stack", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children_seq,
+            cx,
+        )
+    }
+}
+
+// ----
+
+/// Builds a stack of up to 12 Elements, drawn on top of each other.
+///
+/// Returns [`Stack`].
+///
+/// ## Events
+///
+/// Returned element doesn't emit events.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::{ElementExt, Stack};
+/// # use panoramix::elements::{Button, Label};
+/// # use druid::UnitPoint;
+/// let button_with_badge = Stack!(
+///     Button::new("Inbox"),
+///     Label::new("3").align(UnitPoint::TOP_RIGHT),
+/// );
+/// ```
+#[macro_export]
+macro_rules! Stack {
+    ( $($arg:expr),* $(,)?) => {
+        $crate::elements::Stack::new(
+            $crate::Tuple!($($arg,)*)
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{Label, SizedBox};
+    use crate::flex::ContainerStyle;
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use crate::Row;
+    use druid::{Color, KeyOrValue};
+    use test_env_log::test;
+
+    #[test]
+    fn new_stack() {
+        let stack =
+            Stack!(Label::new("Hello"), Label::new("World")).with_alignment(UnitPoint::CENTER);
+        let (stack_data, _) = stack.build(Default::default());
+
+        assert_eq!(stack_data.alignment, UnitPoint::CENTER);
+    }
+
+    #[test]
+    fn stack_overlay() {
+        let background_id = WidgetId::reserved(1);
+        let centered_id = WidgetId::reserved(2);
+        let corner_id = WidgetId::reserved(3);
+
+        let background = Row!(SizedBox::empty(200.0, 100.0).with_reserved_id(background_id))
+            .with_container_style(ContainerStyle {
+                background: Some(KeyOrValue::Concrete(Color::rgb8(0x40, 0x80, 0xc0))),
+                border: None,
+                corner_radius: KeyOrValue::Concrete(0.0),
+            });
+        let stack = Stack!(
+            background,
+            SizedBox::empty(50.0, 20.0).with_reserved_id(centered_id),
+            SizedBox::empty(20.0, 10.0)
+                .with_reserved_id(corner_id)
+                .align(UnitPoint::BOTTOM_RIGHT),
+        )
+        .with_alignment(UnitPoint::CENTER);
+
+        Harness::run_test_window(stack, |harness| {
+            let background_rect = harness.get_state(background_id).layout_rect();
+            assert_eq!(background_rect.size(), (200.0, 100.0).into());
+
+            // The box is drawn over the background, centered within the stack
+            let centered_rect = harness.get_state(centered_id).layout_rect();
+            assert_eq!(centered_rect.origin(), (75.0, 40.0).into());
+
+            assert!(harness.try_get_state(corner_id).is_some());
+        });
+    }
+}
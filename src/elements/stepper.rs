@@ -0,0 +1,385 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleStepperWidget, StepperWidget};
+
+use druid::EventCtx;
+use std::fmt::{Debug, Display};
+use tracing::{instrument, trace};
+
+/// A number between "-" and "+" buttons, to pick a value in a range.
+///
+/// Each button press moves the value by a fixed step, without leaving the range; a button is
+/// disabled when the value is at the matching bound. The stepper doesn't store its value:
+/// like [Slider](crate::elements::Slider), it's usually bound to the component's local
+/// state with [.on_change](Stepper::on_change).
+///
+/// ## Events
+///
+/// Emits [StepperChanged] events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stepper<T: StepperValue> {
+    pub value: T,
+    pub min: T,
+    pub max: T,
+    pub step: T,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepperData<T: StepperValue> {
+    pub value: T,
+    pub min: T,
+    pub max: T,
+    pub step: T,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when one of the buttons of a [Stepper] is pressed.
+///
+/// Holds the new value of the stepper.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepperChanged<T>(pub T);
+
+/// Numbers which can be picked with a [Stepper].
+///
+/// Implemented for all primitive integer and float types.
+pub trait StepperValue: Copy + PartialOrd + Debug + Display + 'static {
+    /// Add `step`, saturating at the type's bounds.
+    fn add_step(self, step: Self) -> Self;
+
+    /// Subtract `step`, saturating at the type's bounds.
+    fn sub_step(self, step: Self) -> Self;
+}
+
+macro_rules! impl_stepper_value_int {
+    ($($ty:ty),*) => {
+        $(
+            impl StepperValue for $ty {
+                fn add_step(self, step: Self) -> Self {
+                    self.saturating_add(step)
+                }
+
+                fn sub_step(self, step: Self) -> Self {
+                    self.saturating_sub(step)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_stepper_value_float {
+    ($($ty:ty),*) => {
+        $(
+            impl StepperValue for $ty {
+                fn add_step(self, step: Self) -> Self {
+                    self + step
+                }
+
+                fn sub_step(self, step: Self) -> Self {
+                    self - step
+                }
+            }
+        )*
+    };
+}
+
+impl_stepper_value_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_stepper_value_float!(f32, f64);
+
+//
+// --- IMPLS
+
+impl<T: StepperValue> Stepper<T> {
+    /// Build a stepper showing `value`, moving by `step` between `min` and `max`.
+    ///
+    /// `value` is clamped to the range. Use the [.on_change](Stepper::on_change) method to
+    /// provide a closure to be called when one of the buttons is pressed.
+    pub fn new(value: T, min: T, max: T, step: T) -> Self {
+        Stepper {
+            value: clamp(value, min, max),
+            min,
+            max,
+            step,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the stepper's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Stepper {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The stepper created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same stepper is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Stepper {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when one of the buttons is pressed.
+    ///
+    /// The closure is given the new value of the stepper.
+    pub fn on_change<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, T) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: StepperChanged<T>| callback(state, event.0),
+        )
+    }
+}
+
+fn clamp<T: StepperValue>(value: T, min: T, max: T) -> T {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
+
+impl<T: StepperValue> StepperData<T> {
+    fn can_decrement(&self) -> bool {
+        self.value > self.min
+    }
+
+    fn can_increment(&self) -> bool {
+        self.value < self.max
+    }
+}
+
+impl<T: StepperValue> Element for Stepper<T> {
+    type Event = StepperChanged<T>;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = StepperData<T>;
+
+    #[instrument(name = "Stepper", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (StepperData<T>, ()) {
+        (
+            StepperData {
+                value: self.value,
+                min: self.min,
+                max: self.max,
+                step: self.step,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl<T: StepperValue> VirtualDom for StepperData<T> {
+    type Event = StepperChanged<T>;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleStepperWidget;
+
+    #[instrument(name = "Stepper", skip(self))]
+    fn init_tree(&self) -> SingleStepperWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleStepperWidget::new(
+            StepperWidget::new(
+                self.value.to_string(),
+                self.can_decrement(),
+                self.can_increment(),
+                id,
+            ),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Stepper", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleStepperWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+        if self.value != prev_value.value {
+            let text = self.value.to_string();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut StepperWidget, ctx: &mut EventCtx| {
+                    widget.set_text(text.clone(), ctx);
+                },
+            );
+        }
+        let (can_decrement, can_increment) = (self.can_decrement(), self.can_increment());
+        if (can_decrement, can_increment)
+            != (prev_value.can_decrement(), prev_value.can_increment())
+        {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut StepperWidget, ctx: &mut EventCtx| {
+                    widget.set_enabled(can_decrement, can_increment, ctx);
+                },
+            );
+        }
+    }
+
+    #[instrument(name = "Stepper", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleStepperWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<StepperChanged<T>> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        let new_value = match cx.app_data.dequeue_action(id) {
+            Some(Action::Incremented) => self.value.add_step(self.step),
+            Some(Action::Decremented) => self.value.sub_step(self.step),
+            _ => return None,
+        };
+        let new_value = clamp(new_value, self.min, self.max);
+        if new_value != self.value {
+            trace!("Processed stepper change: {}", new_value);
+            Some(StepperChanged(new_value))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use druid::{MouseButton, Vec2};
+    use std::any::Any;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    // Click near the left or right edge of the stepper, where its buttons are.
+    fn click_stepper_button<RootElem: Element + Any>(
+        harness: &mut Harness<'_, '_, RootElem>,
+        id: WidgetId,
+        increment: bool,
+    ) {
+        let width = harness.get_state(id).layout_rect().width();
+        let offset = width / 2.0 - 5.0;
+        harness.mouse_move_to(id);
+        let offset = if increment { offset } else { -offset };
+        harness.mouse_move(harness.mouse_state.pos + Vec2::new(offset, 0.0));
+        harness.mouse_button_press(MouseButton::Left);
+        harness.mouse_button_release(MouseButton::Left);
+    }
+
+    #[test]
+    fn new_stepper() {
+        let (stepper_data, ()) = Stepper::new(42, 0, 10, 1).build(());
+        assert_eq!(
+            stepper_data,
+            StepperData {
+                value: 10,
+                min: 0,
+                max: 10,
+                step: 1,
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                reserved_widget_id: None,
+            }
+        );
+
+        assert_eq!(Stepper::new(-3.5, 0.0, 1.0, 0.1).value, 0.0);
+    }
+
+    #[test]
+    fn stepper_int_bounds() {
+        let (event_sender, event_receiver) = channel();
+        let stepper_id = WidgetId::reserved(1);
+        let stepper = |value: i32| {
+            EventLogger::new(
+                event_sender.clone(),
+                Stepper::new(value, 0, 10, 4).with_reserved_id(stepper_id),
+            )
+        };
+
+        Harness::run_test_window(stepper(8), |harness| {
+            // The new value is clamped to the max
+            click_stepper_button(harness, stepper_id, true);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(10)));
+
+            harness.update_root_element(stepper(10));
+            let values = harness.get_debug_state(stepper_id);
+            assert_eq!(values.main_value, "10");
+            assert_eq!(values.other_values["can_increment"], "false");
+
+            // The increment button is disabled at the max
+            click_stepper_button(harness, stepper_id, true);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            click_stepper_button(harness, stepper_id, false);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(6)));
+
+            // The new value is clamped to the min
+            harness.update_root_element(stepper(2));
+            click_stepper_button(harness, stepper_id, false);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(0)));
+
+            // The decrement button is disabled at the min
+            harness.update_root_element(stepper(0));
+            assert_eq!(
+                harness.get_debug_state(stepper_id).other_values["can_decrement"],
+                "false"
+            );
+            click_stepper_button(harness, stepper_id, false);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
+
+    #[test]
+    fn stepper_float_bounds() {
+        let (event_sender, event_receiver) = channel();
+        let stepper_id = WidgetId::reserved(1);
+        let stepper = |value: f64| {
+            EventLogger::new(
+                event_sender.clone(),
+                Stepper::new(value, 0.0, 1.0, 0.75).with_reserved_id(stepper_id),
+            )
+        };
+
+        Harness::run_test_window(stepper(0.5), |harness| {
+            click_stepper_button(harness, stepper_id, true);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(1.0)));
+
+            click_stepper_button(harness, stepper_id, false);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(0.0)));
+
+            harness.update_root_element(stepper(1.0));
+            assert_eq!(harness.get_debug_state(stepper_id).main_value, "1");
+            click_stepper_button(harness, stepper_id, true);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            click_stepper_button(harness, stepper_id, false);
+            assert_eq!(event_receiver.try_recv(), Ok(StepperChanged(0.25)));
+        });
+    }
+}
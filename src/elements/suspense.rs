@@ -0,0 +1,263 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::internals::SkeletonData;
+use crate::elements::Skeleton;
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleSkeletonWidget, SingleSuspenseWidget, SuspenseWidget};
+
+use druid::EventCtx;
+use either::{Either, Left, Right};
+use tracing::{debug_span, info, instrument};
+use tracing_unwrap::OptionExt;
+
+const DEFAULT_SKELETON_WIDTH: f64 = 120.0;
+const DEFAULT_SKELETON_HEIGHT: f64 = 16.0;
+
+/// Wrapper which displays a [Skeleton] until the child is ready.
+///
+/// When `ready` becomes true, the skeleton is replaced with the child in the same update, and
+/// the child fades in. The child isn't built while it's not ready, but its state is kept.
+///
+/// To keep the rest of the layout from moving when the child appears, give the skeleton
+/// roughly the child's size with [`with_skeleton`](Suspense::with_skeleton).
+///
+/// ## Events
+///
+/// Emits the events of the child once it's ready.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suspense<Child: Element> {
+    pub ready: bool,
+    pub child: Child,
+    pub skeleton: Skeleton,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuspenseData<Child: VirtualDom> {
+    pub content: Either<SkeletonData, Child>,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Suspense<Child> {
+    pub fn new(ready: bool, child: Child) -> Self {
+        Suspense {
+            ready,
+            child,
+            skeleton: Skeleton::new(DEFAULT_SKELETON_WIDTH, DEFAULT_SKELETON_HEIGHT),
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Replace the placeholder displayed while the child isn't ready.
+    pub fn with_skeleton(self, skeleton: Skeleton) -> Self {
+        Suspense { skeleton, ..self }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Suspense {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Suspense<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = SuspenseData<Child::BuildOutput>;
+
+    #[instrument(name = "Suspense", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (content, state) = if self.ready {
+            let (child, state) = self.child.build(prev_state);
+            (Right(child), state)
+        } else {
+            let (skeleton, ()) = self.skeleton.build(());
+            (Left(skeleton), prev_state)
+        };
+        (
+            SuspenseData {
+                content,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for SuspenseData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq =
+        SingleSuspenseWidget<Either<SingleSkeletonWidget, Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Suspense", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let children = match &self.content {
+            Left(skeleton) => Left(skeleton.init_tree()),
+            Right(child) => Right(child.init_tree()),
+        };
+        SingleSuspenseWidget::new(
+            SuspenseWidget::new(children, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Suspense", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let children = &mut widget_seq.widget_mut().children;
+        match (&self.content, &prev_value.content) {
+            (Left(skeleton), Left(prev_skeleton)) => {
+                let children = children.as_mut().left().expect_or_log(
+                    "The previous value of this element was a skeleton. Expected Left widget.",
+                );
+                skeleton.reconcile(prev_skeleton, children, ctx);
+                ctx.stats.updated += 1;
+            }
+            (Right(child), Right(prev_child)) => {
+                let children = children.as_mut().right().expect_or_log(
+                    "The previous value of this element was the child. Expected Right widget.",
+                );
+                child.reconcile(prev_child, children, ctx);
+                ctx.stats.updated += 1;
+            }
+
+            (Right(child), Left(_)) => {
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
+                debug_span!("init_tree").in_scope(|| {
+                    info!("child is ready");
+                    *children = Right(child.init_tree());
+                });
+                widget_seq.pod.with_event_context(
+                    ctx.event_ctx,
+                    |widget: &mut SuspenseWidget<_>, ctx: &mut EventCtx| {
+                        widget.fade_in(ctx);
+                    },
+                );
+            }
+            (Left(skeleton), Right(prev_child)) => {
+                prev_child.unmount();
+                ctx.stats.removed += 1;
+                ctx.stats.created += 1;
+                debug_span!("init_tree").in_scope(|| {
+                    info!("child is loading again");
+                    *children = Left(skeleton.init_tree());
+                });
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        match (&mut self.content, other.content) {
+            (Right(child), Right(other_child)) => child.update_value(other_child),
+            (content, other_content) => *content = other_content,
+        }
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        if let Right(child) = &self.content {
+            child.unmount();
+        }
+    }
+
+    #[instrument(
+        name = "Suspense",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        if let Right(child) = &self.content {
+            let children = widget_seq
+                .widget_mut()
+                .children
+                .as_mut()
+                .right()
+                .unwrap_or_log();
+            child.process_event(comp_ctx, children_state, children, cx);
+        }
+    }
+
+    #[instrument(name = "Suspense", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let child = self.content.as_ref().right()?;
+        let children = widget_seq.widget_mut().children.as_mut().right()?;
+        child.process_local_event(children_state, children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use druid::Size;
+    use std::time::Duration;
+    use test_env_log::test;
+
+    #[test]
+    fn suspense_widget() {
+        let suspense_id = WidgetId::reserved(1);
+        let skeleton_id = WidgetId::reserved(2);
+        let suspense = |ready| {
+            Suspense::new(ready, Label::new("Loaded"))
+                .with_skeleton(Skeleton::new(80.0, 20.0).with_reserved_id(skeleton_id))
+                .with_reserved_id(suspense_id)
+        };
+
+        Harness::run_test_window(suspense(false), |harness| {
+            let rect = harness.get_state(skeleton_id).layout_rect();
+            assert_eq!(rect.size(), Size::new(80.0, 20.0));
+            assert!(harness.find_by_name("Loaded").is_none());
+
+            // The child replaces the skeleton in a single update, and fades in
+            harness.update_root_element(suspense(true));
+            assert!(harness.try_get_state(skeleton_id).is_none());
+            assert!(harness.find_by_name("Loaded").is_some());
+            let values = harness.get_debug_state(suspense_id).other_values;
+            assert_eq!(values["opacity"], "0.00");
+
+            harness.animate_frame(Duration::from_millis(200));
+            let values = harness.get_debug_state(suspense_id).other_values;
+            assert_eq!(values["opacity"], "1.00");
+
+            harness.update_root_element(suspense(false));
+            assert!(harness.try_get_state(skeleton_id).is_some());
+            assert!(harness.find_by_name("Loaded").is_none());
+        });
+    }
+}
@@ -0,0 +1,258 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::WidgetId;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleSvgWidget, SvgWidget};
+
+use derivative::Derivative;
+use druid::widget::{FillStrat, SvgData as DruidSvgData};
+use druid::EventCtx;
+use std::error::Error;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{instrument, trace, warn};
+
+/// Displays an SVG document, loaded from markup or from a file.
+///
+/// The document is parsed when the element is built, and only parsed again when its source
+/// changes. If the document can't be parsed, a placeholder is displayed instead.
+///
+/// Unlike an [`Image`](crate::elements::Image), the document stays crisp at any scale, which
+/// makes it a good fit for icons.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Svg {
+    pub source: SvgSource,
+    pub fill_mode: FillStrat,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Where an [Svg] gets its document from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgSource {
+    /// SVG markup.
+    Markup(Arc<str>),
+    /// Path to an SVG file.
+    Path(PathBuf),
+}
+
+#[derive(Derivative, Clone)]
+#[derivative(Debug, PartialEq)]
+pub struct SvgData {
+    pub source: SvgSource,
+    /// The parsed document, or `None` if it couldn't be parsed.
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    pub svg: Option<DruidSvgData>,
+    pub fill_mode: FillStrat,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// The last parsed document, kept between builds so that it isn't parsed again.
+#[derive(Derivative, Clone, Default)]
+#[derivative(Debug, PartialEq)]
+pub struct ParsedSvg {
+    source: Option<SvgSource>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    svg: Option<DruidSvgData>,
+}
+
+//
+// --- IMPLS
+
+impl Svg {
+    /// Build an SVG element from markup.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(markup: &str) -> Self {
+        Svg::new(SvgSource::Markup(markup.into()))
+    }
+
+    /// Build an SVG element from a file.
+    ///
+    /// The file is read when the element is built.
+    pub fn from_file(path: impl Into<PathBuf>) -> Self {
+        Svg::new(SvgSource::Path(path.into()))
+    }
+
+    fn new(source: SvgSource) -> Self {
+        Svg {
+            source,
+            fill_mode: FillStrat::Contain,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the document is scaled to fit its widget.
+    pub fn fit(self, fill_mode: FillStrat) -> Self {
+        Svg { fill_mode, ..self }
+    }
+
+    /// Change the way the element's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Svg {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same element is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Svg {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl SvgSource {
+    fn parse(&self) -> Result<DruidSvgData, Box<dyn Error>> {
+        match self {
+            SvgSource::Markup(markup) => markup.parse(),
+            SvgSource::Path(path) => std::fs::read_to_string(path)?.parse(),
+        }
+    }
+}
+
+impl Element for Svg {
+    type Event = NoEvent;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ParsedSvg;
+    type BuildOutput = SvgData;
+
+    #[instrument(name = "Svg", skip(self, prev_state))]
+    fn build(self, prev_state: ParsedSvg) -> (SvgData, ParsedSvg) {
+        let svg = if prev_state.source.as_ref() == Some(&self.source) {
+            prev_state.svg
+        } else {
+            trace!("Parsing SVG");
+            match self.source.parse() {
+                Ok(svg) => Some(svg),
+                Err(error) => {
+                    warn!("Could not load SVG {:?}: {}", self.source, error);
+                    None
+                }
+            }
+        };
+
+        (
+            SvgData {
+                source: self.source.clone(),
+                svg: svg.clone(),
+                fill_mode: self.fill_mode,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            ParsedSvg {
+                source: Some(self.source),
+                svg,
+            },
+        )
+    }
+}
+
+impl VirtualDom for SvgData {
+    type Event = NoEvent;
+    type AggregateChildrenState = ParsedSvg;
+
+    type TargetWidgetSeq = SingleSvgWidget;
+
+    #[instrument(name = "Svg", skip(self))]
+    fn init_tree(&self) -> SingleSvgWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSvgWidget::new(
+            SvgWidget::new(self.svg.clone(), self.fill_mode, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Svg", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut SingleSvgWidget, ctx: &mut ReconcileCtx) {
+        if self.source != prev_value.source {
+            let svg = self.svg.clone();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SvgWidget, ctx: &mut EventCtx| {
+                    widget.set_svg(svg.clone(), ctx);
+                },
+            );
+        }
+        if self.fill_mode != prev_value.fill_mode {
+            let fill_mode = self.fill_mode;
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SvgWidget, ctx: &mut EventCtx| {
+                    widget.set_fill_mode(fill_mode, ctx);
+                },
+            );
+        }
+        widget.flex = self.flex;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    // A 24x16 red rectangle
+    const SVG_MARKUP: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="16" viewBox="0 0 24 16">
+        <rect width="24" height="16" fill="red"/>
+    </svg>"#;
+
+    #[test]
+    fn new_svg() {
+        let svg = Svg::from_str(SVG_MARKUP).fit(FillStrat::Fill);
+        let (svg_data, state) = svg.build(Default::default());
+
+        assert_eq!(svg_data.fill_mode, FillStrat::Fill);
+        assert!(svg_data.svg.is_some());
+        assert_eq!(state.source, Some(SvgSource::Markup(SVG_MARKUP.into())));
+    }
+
+    #[test]
+    fn invalid_svg() {
+        let svg = Svg::from_str("<svg");
+        let (svg_data, _) = svg.build(Default::default());
+        assert!(svg_data.svg.is_none());
+
+        let svg = Svg::from_file("this/file/does/not/exist.svg");
+        let (svg_data, _) = svg.build(Default::default());
+        assert!(svg_data.svg.is_none());
+    }
+
+    #[test]
+    fn svg_widget() {
+        let svg_id = WidgetId::reserved(1);
+        let svg = Svg::from_str(SVG_MARKUP).with_reserved_id(svg_id);
+
+        Harness::run_test_window(svg, |harness| {
+            assert_eq!(harness.get_debug_state(svg_id).main_value, "24x16");
+            assert_eq!(
+                harness.get_state(svg_id).layout_rect().size(),
+                druid::Size::new(24.0, 16.0)
+            );
+
+            let placeholder = Svg::from_str("<svg");
+            harness.update_root_element(placeholder.with_reserved_id(svg_id));
+            assert_eq!(
+                harness.get_debug_state(svg_id).main_value,
+                "SVG could not be loaded"
+            );
+        });
+    }
+}
@@ -0,0 +1,276 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleSwitchWidget, SwitchWidget};
+
+use druid::EventCtx;
+use tracing::{instrument, trace};
+
+/// An on/off toggle switch, with an optional text label.
+///
+/// Like [Checkbox](crate::elements::Checkbox), the state of the switch is usually bound to a
+/// value in the component's local state, with [.with_on](Switch::with_on) to read it and
+/// [.on_toggle](Switch::on_toggle) to write it back.
+///
+/// ## Events
+///
+/// Emits [SwitchToggled] events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Switch {
+    pub label: Option<String>,
+    pub on: bool,
+    pub disabled: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwitchData {
+    pub label: Option<String>,
+    pub on: bool,
+    pub disabled: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [Switch] is toggled.
+///
+/// Holds the new state of the switch.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SwitchToggled(pub bool);
+
+//
+// --- IMPLS
+
+impl Switch {
+    /// Build a switch in the "off" position, with no label.
+    ///
+    /// Use the [.on_toggle](Switch::on_toggle) method to provide a closure to be called when the switch is toggled.
+    pub fn new() -> Self {
+        Switch {
+            label: None,
+            on: false,
+            disabled: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Display a text label to the left of the switch.
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Switch {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// Set whether the switch is in the "on" position.
+    pub fn with_on(self, on: bool) -> Self {
+        Switch { on, ..self }
+    }
+
+    /// Set whether the switch is disabled.
+    ///
+    /// A disabled switch is greyed out, and doesn't emit events.
+    pub fn disabled(self, disabled: bool) -> Self {
+        Switch { disabled, ..self }
+    }
+
+    /// Change the way the switch's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Switch {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The switch created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same switch is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Switch {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this switch is toggled.
+    ///
+    /// The closure is given the new state of the switch.
+    pub fn on_toggle<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, bool) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: SwitchToggled| callback(state, event.0),
+        )
+    }
+}
+
+impl Element for Switch {
+    type Event = SwitchToggled;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = ();
+    type BuildOutput = SwitchData;
+
+    #[instrument(name = "Switch", skip(self, _prev_state))]
+    fn build(self, _prev_state: ()) -> (SwitchData, ()) {
+        (
+            SwitchData {
+                label: self.label,
+                on: self.on,
+                disabled: self.disabled,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (),
+        )
+    }
+}
+
+impl VirtualDom for SwitchData {
+    type Event = SwitchToggled;
+    type AggregateChildrenState = ();
+
+    type TargetWidgetSeq = SingleSwitchWidget;
+
+    #[instrument(name = "Switch", skip(self))]
+    fn init_tree(&self) -> SingleSwitchWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleSwitchWidget::new(
+            SwitchWidget::new(self.label.clone(), self.on, self.disabled, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Switch", skip(self, prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget: &mut SingleSwitchWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.label != prev_value.label {
+            let label = self.label.clone();
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SwitchWidget, ctx: &mut EventCtx| {
+                    widget.set_label(label.clone(), ctx);
+                },
+            );
+        }
+        if self.disabled != prev_value.disabled {
+            let disabled = self.disabled;
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut SwitchWidget, ctx: &mut EventCtx| {
+                    widget.set_disabled(disabled, ctx);
+                },
+            );
+        }
+        if self.on != prev_value.on {
+            widget.widget_mut().value = self.on;
+            widget.request_druid_update(ctx.event_ctx);
+            widget.widget_mut().request_druid_update(ctx.event_ctx);
+        }
+    }
+
+    #[instrument(name = "Switch", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut SingleSwitchWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<SwitchToggled> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            if self.disabled {
+                return None;
+            }
+            let on = widget.widget().value;
+            trace!("Processed switch toggle: {}", on);
+            Some(SwitchToggled(on))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_switch() {
+        let switch = Switch::new().with_label("Dark mode").with_on(true);
+        let (switch_data, ()) = switch.build(());
+
+        assert_eq!(
+            switch_data,
+            SwitchData {
+                label: Some(String::from("Dark mode")),
+                on: true,
+                disabled: false,
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn switch_widget() {
+        let switch_id = WidgetId::reserved(1);
+
+        Harness::run_test_window(Switch::new().with_reserved_id(switch_id), |harness| {
+            assert_eq!(harness.get_debug_state(switch_id).main_value, "false");
+
+            harness.update_root_element(Switch::new().with_on(true).with_reserved_id(switch_id));
+            assert_eq!(harness.get_debug_state(switch_id).main_value, "true");
+        });
+    }
+
+    #[test]
+    fn switch_toggle() {
+        let (event_sender, event_receiver) = channel();
+        let switch_id = WidgetId::reserved(1);
+        let switch = |disabled: bool| {
+            EventLogger::new(
+                event_sender.clone(),
+                Switch::new().disabled(disabled).with_reserved_id(switch_id),
+            )
+        };
+
+        Harness::run_test_window(switch(false), |harness| {
+            harness.mouse_click_on(switch_id);
+            assert_eq!(event_receiver.try_recv(), Ok(SwitchToggled(true)));
+
+            // Disabled switches don't emit events
+            harness.update_root_element(switch(true));
+            harness.mouse_click_on(switch_id);
+            assert_eq!(event_receiver.try_recv().ok(), None);
+
+            // The switch wasn't bound to a local state, so it stayed in the "on" position
+            harness.update_root_element(switch(false));
+            harness.mouse_click_on(switch_id);
+            assert_eq!(event_receiver.try_recv(), Ok(SwitchToggled(false)));
+        });
+    }
+}
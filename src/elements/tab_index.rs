@@ -0,0 +1,206 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleTabIndexWidget, TabIndexWidget};
+
+use tracing::instrument;
+
+/// Wrapper which sets the position of the child in the focus order.
+///
+/// When Tab is pressed, the focus moves through elements with a tab index first, from the
+/// lowest index to the highest, then through the other elements in tree order. Elements with
+/// the same index are visited in tree order.
+///
+/// Usually created with [`ElementExt::tab_index`](crate::ElementExt::tab_index).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabIndex<Child: Element> {
+    pub child: Child,
+    pub index: u32,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabIndexData<Child: VirtualDom> {
+    pub child: Child,
+    pub index: u32,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> TabIndex<Child> {
+    pub fn new(child: Child, index: u32) -> Self {
+        TabIndex {
+            child,
+            index,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        TabIndex {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for TabIndex<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = TabIndexData<Child::BuildOutput>;
+
+    #[instrument(name = "TabIndex", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            TabIndexData {
+                child,
+                index: self.index,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TabIndexData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleTabIndexWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "TabIndex", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleTabIndexWidget::new(
+            TabIndexWidget::new(self.child.init_tree(), self.index, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "TabIndex", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.index != prev_value.index {
+            widget_seq.widget_mut().set_index(self.index);
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.index = other.index;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "TabIndex",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "TabIndex", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::TextBox;
+    use crate::metadata::Metadata;
+    use crate::test_harness::Harness;
+    use crate::{Column, NoEvent};
+    use druid::KbKey;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn tab_index_order() {
+        let (focus_sender, focus_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let text_box = |name: &'static str| {
+            let focus_sender = focus_sender.clone();
+            TextBox::new(name).on_focus_change(md, move |_state, has_focus| {
+                if has_focus {
+                    focus_sender.send(name).unwrap();
+                }
+            })
+        };
+        let first_id = WidgetId::reserved(1);
+        let column = Column!(
+            text_box("a").tab_index(1).with_reserved_id(first_id),
+            text_box("b"),
+            text_box("c").tab_index(0),
+        );
+
+        Harness::run_test_window(column, |harness| {
+            let values = harness.get_debug_state(first_id).other_values;
+            assert_eq!(values["tab_index"], "1");
+
+            harness.click_on("c");
+            assert_eq!(focus_receiver.try_recv(), Ok("c"));
+
+            // Indexed elements first, then tree order
+            harness.press_key(KbKey::Tab);
+            assert_eq!(focus_receiver.try_recv(), Ok("a"));
+            harness.press_key(KbKey::Tab);
+            assert_eq!(focus_receiver.try_recv(), Ok("b"));
+            harness.press_key(KbKey::Tab);
+            assert_eq!(focus_receiver.try_recv(), Ok("c"));
+        });
+    }
+}
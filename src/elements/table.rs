@@ -0,0 +1,489 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleTableWidget, TableWidget};
+
+use druid::EventCtx;
+use std::collections::HashMap;
+use tracing::{debug_span, info, instrument};
+
+/// The width of a [Table] column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Column {
+    /// The column is always the given width.
+    Fixed(f64),
+    /// The column takes a share of the width left by fixed columns, proportional to its
+    /// flex factor.
+    Flex(f64),
+}
+
+/// A row of a [Table], with a unique key.
+///
+/// Keys work the same way as in [`ElementList`](crate::elements::ElementList), except that
+/// rows which are moved around keep their widgets too.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row<Child: Element> {
+    pub key: String,
+    pub cells: Vec<Child>,
+}
+
+/// Rows of cells laid out in aligned columns, under a header row.
+///
+/// The table has one column per header. Rows with fewer cells than there are columns leave
+/// their last columns empty; cells beyond the last column aren't built.
+///
+/// Rows are matched by key when the table is updated, so that sorting or reordering rows
+/// reuses their widgets and their state.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table<Child: Element> {
+    pub headers: Vec<String>,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Row<Child>>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableData<Child: VirtualDom> {
+    pub headers: Vec<String>,
+    pub columns: Vec<Column>,
+    pub rows: Vec<(String, Vec<Child>)>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl Column {
+    pub fn fixed(width: f64) -> Self {
+        Column::Fixed(width)
+    }
+
+    pub fn flex(flex: f64) -> Self {
+        Column::Flex(flex)
+    }
+}
+
+impl<Child: Element> Row<Child> {
+    pub fn new(key: impl Into<String>, cells: Vec<Child>) -> Self {
+        Row {
+            key: key.into(),
+            cells,
+        }
+    }
+}
+
+impl<Child: Element> Table<Child> {
+    /// Build a table with the given headers and rows.
+    ///
+    /// All columns are [`Column::flex(1.0)`](Column::flex) by default.
+    pub fn new(headers: Vec<impl Into<String>>, rows: Vec<Row<Child>>) -> Self {
+        let headers: Vec<String> = headers.into_iter().map(Into::into).collect();
+        Table {
+            columns: vec![Column::flex(1.0); headers.len()],
+            headers,
+            rows,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the width of each column.
+    ///
+    /// Columns missing from `columns` keep their previous width; extra items are ignored.
+    pub fn with_columns(self, columns: Vec<Column>) -> Self {
+        let mut new_columns = self.columns;
+        for (column, new_column) in new_columns.iter_mut().zip(columns) {
+            *column = new_column;
+        }
+        Table {
+            columns: new_columns,
+            ..self
+        }
+    }
+
+    /// Change the way the table's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Table {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Table {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+// For each new row, the index of the previous row with the same key, if any.
+//
+// Each previous row is matched at most once, so that a duplicated key gets a new row.
+fn match_rows<T1, T2>(prev_rows: &[(String, T1)], rows: &[(String, T2)]) -> Vec<Option<usize>> {
+    let mut prev_indices = HashMap::new();
+    for (index, (key, _)) in prev_rows.iter().enumerate() {
+        prev_indices.entry(key.as_str()).or_insert(index);
+    }
+    rows.iter()
+        .map(|(key, _)| prev_indices.remove(key.as_str()))
+        .collect()
+}
+
+impl<Child: Element> Element for Table<Child> {
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Vec<(String, Vec<Child::AggregateChildrenState>)>;
+    type BuildOutput = TableData<Child::BuildOutput>;
+
+    #[instrument(name = "Table", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let column_count = self.headers.len();
+        let keys: Vec<_> = self.rows.iter().map(|row| (row.key.clone(), ())).collect();
+        let row_matches = match_rows(&prev_state, &keys);
+        let mut prev_state: Vec<_> = prev_state
+            .into_iter()
+            .map(|(_key, cells_state)| Some(cells_state))
+            .collect();
+
+        let (rows, rows_state): (Vec<_>, Vec<_>) = self
+            .rows
+            .into_iter()
+            .zip(row_matches)
+            .map(|(row, prev_index)| {
+                let mut prev_cells_state = prev_index
+                    .and_then(|prev_index| prev_state[prev_index].take())
+                    .unwrap_or_default()
+                    .into_iter();
+                let (cells, cells_state): (Vec<_>, Vec<_>) = row
+                    .cells
+                    .into_iter()
+                    .take(column_count)
+                    .map(|cell| cell.build(prev_cells_state.next().unwrap_or_default()))
+                    .unzip();
+                ((row.key.clone(), cells), (row.key, cells_state))
+            })
+            .unzip();
+
+        (
+            TableData {
+                headers: self.headers,
+                columns: self.columns,
+                rows,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            rows_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TableData<Child> {
+    type Event = NoEvent;
+    type AggregateChildrenState = Vec<(String, Vec<Child::AggregateChildrenState>)>;
+    type TargetWidgetSeq = SingleTableWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Table", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let rows = self
+            .rows
+            .iter()
+            .map(|(_key, cells)| cells.iter().map(|cell| cell.init_tree()).collect())
+            .collect();
+        SingleTableWidget::new(
+            TableWidget::new(self.headers.clone(), self.columns.clone(), rows, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Table", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let row_matches = match_rows(&prev_value.rows, &self.rows);
+        widget_seq.flex = self.flex;
+        let table_widget = widget_seq.widget_mut();
+
+        let mut prev_row_widgets: Vec<_> = std::mem::take(&mut table_widget.rows)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut layout_changed = row_matches
+            .iter()
+            .enumerate()
+            .any(|(index, prev_index)| *prev_index != Some(index))
+            || self.rows.len() != prev_value.rows.len();
+
+        table_widget.rows = self
+            .rows
+            .iter()
+            .zip(row_matches)
+            .map(|((_key, cells), prev_index)| {
+                let prev_row = prev_index.and_then(|prev_index| {
+                    let prev_widgets = prev_row_widgets[prev_index].take()?;
+                    Some((&prev_value.rows[prev_index].1, prev_widgets))
+                });
+                let (prev_cells, mut cell_widgets) = match prev_row {
+                    Some(prev_row) => prev_row,
+                    None => {
+                        return debug_span!("init_tree").in_scope(|| {
+                            info!("creating row");
+                            cells.iter().map(|cell| cell.init_tree()).collect()
+                        });
+                    }
+                };
+
+                for prev_cell in prev_cells.iter().skip(cells.len()) {
+                    prev_cell.unmount();
+                }
+                cell_widgets.truncate(cells.len());
+                for (index, cell) in cells.iter().enumerate() {
+                    match (prev_cells.get(index), cell_widgets.get_mut(index)) {
+                        (Some(prev_cell), Some(cell_widget)) => {
+                            cell.reconcile(prev_cell, cell_widget, ctx);
+                        }
+                        _ => {
+                            layout_changed = true;
+                            cell_widgets.push(cell.init_tree());
+                        }
+                    }
+                }
+                cell_widgets
+            })
+            .collect();
+
+        // Rows which weren't matched have been removed
+        for ((_key, prev_cells), prev_widgets) in prev_value.rows.iter().zip(prev_row_widgets) {
+            if prev_widgets.is_some() {
+                for prev_cell in prev_cells {
+                    prev_cell.unmount();
+                }
+            }
+        }
+
+        if self.headers != prev_value.headers {
+            let headers = self.headers.clone();
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut TableWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_headers(headers.clone(), ctx);
+                },
+            );
+            layout_changed = true;
+        }
+        if self.columns != prev_value.columns {
+            widget_seq.widget_mut().columns = self.columns.clone();
+            layout_changed = true;
+        }
+        if layout_changed {
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.children_changed();
+                    ctx.request_layout();
+                });
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        let row_matches = match_rows(&self.rows, &other.rows);
+        let mut prev_rows: Vec<_> = std::mem::take(&mut self.rows)
+            .into_iter()
+            .map(|(_key, cells)| Some(cells))
+            .collect();
+        self.rows = other
+            .rows
+            .into_iter()
+            .zip(row_matches)
+            .map(|((key, cells), prev_index)| {
+                let prev_cells = prev_index.and_then(|prev_index| prev_rows[prev_index].take());
+                match prev_cells {
+                    Some(mut prev_cells) => {
+                        prev_cells.truncate(cells.len());
+                        for (index, cell) in cells.into_iter().enumerate() {
+                            match prev_cells.get_mut(index) {
+                                Some(prev_cell) => prev_cell.update_value(cell),
+                                None => prev_cells.push(cell),
+                            }
+                        }
+                        (key, prev_cells)
+                    }
+                    None => (key, cells),
+                }
+            })
+            .collect();
+        self.headers = other.headers;
+        self.columns = other.columns;
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        for (_key, cells) in &self.rows {
+            for cell in cells {
+                cell.unmount();
+            }
+        }
+    }
+
+    #[instrument(name = "Table", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        for (((_key, cells), (_state_key, cells_state)), cell_widgets) in self
+            .rows
+            .iter()
+            .zip(children_state.iter_mut())
+            .zip(widget_seq.widget_mut().rows.iter_mut())
+        {
+            for ((cell, cell_state), cell_widget) in cells
+                .iter()
+                .zip(cells_state.iter_mut())
+                .zip(cell_widgets.iter_mut())
+            {
+                cell.process_event(comp_ctx, cell_state, cell_widget, cx);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::SizedBox;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    fn mock_table(keys: &[&str]) -> Table<MockComponent> {
+        let rows = keys
+            .iter()
+            .map(|key| Row::new(*key, vec![MockComponent::new(), MockComponent::new()]))
+            .collect();
+        Table::new(vec!["Name", "Value"], rows)
+    }
+
+    #[test]
+    fn new_table() {
+        let table = Table::new(
+            vec!["Name", "Value"],
+            vec![
+                Row::new("a", vec![MockComponent::new(), MockComponent::new()]),
+                Row::new("b", vec![MockComponent::new()]),
+                Row::new("c", vec![MockComponent::new(); 3]),
+            ],
+        )
+        .with_columns(vec![Column::fixed(100.0)]);
+        let (table_data, state) = table.build(Default::default());
+
+        assert_eq!(
+            table_data.columns,
+            vec![Column::fixed(100.0), Column::flex(1.0)]
+        );
+        // Missing cells are left empty, extra cells are dropped
+        let cell_counts: Vec<_> = table_data
+            .rows
+            .iter()
+            .map(|(_, cells)| cells.len())
+            .collect();
+        assert_eq!(cell_counts, vec![2, 1, 2]);
+        assert_eq!(state.len(), 3);
+    }
+
+    #[test]
+    fn table_rows_keep_state() {
+        let (_, mut state) = mock_table(&["a", "b", "c"]).build(Default::default());
+        state[0].1[1] = MockState::new("a-value");
+        state[2].1[0] = MockState::new("c-name");
+
+        // Reordered and removed rows
+        let (_, state) = mock_table(&["c", "a"]).build(state);
+        assert_eq!(state[0].0, "c");
+        assert_eq!(state[0].1[0], MockState::new("c-name"));
+        assert_eq!(state[1].1[1], MockState::new("a-value"));
+
+        // Added rows
+        let (_, state) = mock_table(&["c", "d", "a"]).build(state);
+        assert_eq!(state[1].1[0], MockState::default());
+        assert_eq!(state[2].1[1], MockState::new("a-value"));
+    }
+
+    #[test]
+    fn reordered_rows_are_matched() {
+        let rows = |keys: &[&str]| -> Vec<(String, ())> {
+            keys.iter().map(|key| (key.to_string(), ())).collect()
+        };
+
+        assert_eq!(
+            match_rows(&rows(&["a", "b", "c"]), &rows(&["c", "a", "b"])),
+            vec![Some(2), Some(0), Some(1)],
+        );
+        assert_eq!(
+            match_rows(&rows(&["a", "b", "c"]), &rows(&["d", "c", "a"])),
+            vec![None, Some(2), Some(0)],
+        );
+        // Duplicated keys don't share the same row
+        assert_eq!(
+            match_rows(&rows(&["a", "b"]), &rows(&["a", "a"])),
+            vec![Some(0), None],
+        );
+    }
+
+    #[test]
+    fn table_widget() {
+        let ids: Vec<_> = (1..=4).map(WidgetId::reserved).collect();
+        let table = |keys: &[usize]| {
+            let rows = keys
+                .iter()
+                .map(|key| {
+                    Row::new(
+                        key.to_string(),
+                        vec![SizedBox::empty(50.0, 20.0).with_reserved_id(ids[*key])],
+                    )
+                })
+                .collect();
+            Table::new(vec!["Name", "Value"], rows).with_columns(vec![Column::fixed(100.0)])
+        };
+
+        Harness::run_test_window(table(&[0, 1, 2]), |harness| {
+            let header_rect = harness.find_by_name("Value").unwrap().layout_rect();
+            let first_y = harness.get_state(ids[0]).layout_rect().y0;
+            assert!(first_y >= header_rect.y1);
+            // The second column starts after the fixed first column
+            assert!(header_rect.x0 >= 100.0);
+
+            harness.update_root_element(table(&[2, 0, 3]));
+            assert_eq!(harness.get_state(ids[2]).layout_rect().y0, first_y);
+            assert!(harness.get_state(ids[0]).layout_rect().y0 > first_y);
+            assert!(harness.get_state(ids[3]).layout_rect().y0 > first_y);
+            assert!(harness.try_get_state(ids[1]).is_none());
+        });
+    }
+}
@@ -0,0 +1,421 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleTabsWidget, TabsWidget};
+
+use druid::EventCtx;
+use tracing::{debug_span, info, instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// A row of tab headers, above the body of the active tab.
+///
+/// Tabs are given as `(title, body)` pairs; all bodies must have the same type, so tabs
+/// with different contents usually hold an [`ElementBox`](crate::elements::ElementBox).
+/// The active tab is chosen by the caller: clicking a header doesn't switch tabs by itself,
+/// it only emits an event.
+///
+/// By default, only the body of the active tab is built. Switching to another tab destroys
+/// the widgets and local state of the previous body, and switching back builds it from
+/// scratch. With [`keep_alive`](Tabs::keep_alive), all bodies are built and kept, and only
+/// the active one is displayed.
+///
+/// ## Events
+///
+/// Emits [TabSelected] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tabs<Child: Element> {
+    pub tabs: Vec<(String, Child)>,
+    pub active: usize,
+    pub keep_alive: bool,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabsData<Child: VirtualDom> {
+    pub titles: Vec<String>,
+    pub bodies: Vec<Option<Child>>,
+    pub active: usize,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when the header of a [Tabs] element is clicked.
+///
+/// Holds the index of the clicked tab.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TabSelected(pub usize);
+
+//
+// --- IMPLS
+
+impl<Child: Element> Tabs<Child> {
+    /// Build tabs from a list of `(title, body)` pairs, with the tab at index `active` displayed.
+    ///
+    /// If `active` is out of bounds, no body is displayed.
+    ///
+    /// Use the [.on_select](Tabs::on_select) method to provide a closure to be called when a header is clicked.
+    pub fn new(tabs: Vec<(String, Child)>, active: usize) -> Self {
+        Tabs {
+            tabs,
+            active,
+            keep_alive: false,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Build and keep the bodies of all tabs, so that they keep their local state when
+    /// they're not displayed.
+    pub fn keep_alive(self) -> Self {
+        Tabs {
+            keep_alive: true,
+            ..self
+        }
+    }
+
+    /// Change the way the element's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Tabs {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Tabs {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a header is clicked.
+    ///
+    /// The closure is given the index of the clicked tab.
+    pub fn on_select<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, usize) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, event: TabSelected| {
+            callback(state, event.0)
+        })
+    }
+}
+
+impl<Child: Element> Element for Tabs<Child> {
+    type Event = TabSelected;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState = Vec<Option<Child::AggregateChildrenState>>;
+    type BuildOutput = TabsData<Child::BuildOutput>;
+
+    #[instrument(name = "Tabs", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let mut prev_state = prev_state;
+        prev_state.resize_with(self.tabs.len(), Default::default);
+
+        let active = self.active;
+        let keep_alive = self.keep_alive;
+        let mut titles = Vec::with_capacity(self.tabs.len());
+        let mut bodies = Vec::with_capacity(self.tabs.len());
+        let mut new_state = Vec::with_capacity(self.tabs.len());
+        for (index, ((title, child), child_prev_state)) in
+            self.tabs.into_iter().zip(prev_state).enumerate()
+        {
+            titles.push(title);
+            // Inactive bodies are dropped along with their state, unless they're kept alive
+            if index == active || keep_alive {
+                let (body, child_state) = child.build(child_prev_state.unwrap_or_default());
+                bodies.push(Some(body));
+                new_state.push(Some(child_state));
+            } else {
+                bodies.push(None);
+                new_state.push(None);
+            }
+        }
+
+        (
+            TabsData {
+                titles,
+                bodies,
+                active,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            new_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TabsData<Child> {
+    type Event = TabSelected;
+    type AggregateChildrenState = Vec<Option<Child::AggregateChildrenState>>;
+    type TargetWidgetSeq = SingleTabsWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Tabs", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let bodies = self
+            .bodies
+            .iter()
+            .map(|body| body.as_ref().map(|body| body.init_tree()))
+            .collect();
+        SingleTabsWidget::new(
+            TabsWidget::new(self.titles.clone(), self.active, bodies, id),
+            self.flex,
+        )
+    }
+
+    #[instrument(name = "Tabs", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.titles != prev_value.titles {
+            let titles = self.titles.clone();
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut TabsWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_titles(titles.clone(), ctx);
+                },
+            );
+        }
+        if self.active != prev_value.active {
+            let active = self.active;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut TabsWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_active(active, ctx);
+                },
+            );
+        }
+        widget_seq.flex = self.flex;
+
+        let widget_bodies = &mut widget_seq.widget_mut().bodies;
+        for (index, prev_body) in prev_value.bodies.iter().enumerate() {
+            if let (Some(prev_body), None) =
+                (prev_body, self.bodies.get(index).and_then(Option::as_ref))
+            {
+                prev_body.unmount();
+                widget_bodies[index] = None;
+            }
+        }
+        widget_bodies.resize_with(self.bodies.len(), || None);
+
+        for (index, body) in self.bodies.iter().enumerate() {
+            let body = match body {
+                Some(body) => body,
+                None => continue,
+            };
+            match prev_value.bodies.get(index).and_then(Option::as_ref) {
+                Some(prev_body) => {
+                    let body_widget_seq = widget_bodies[index].as_mut().expect_or_log(
+                        "The previous value of this tab had a body. Expected a body widget.",
+                    );
+                    body.reconcile(prev_body, body_widget_seq, ctx);
+                }
+                None => {
+                    debug_span!("init_tree").in_scope(|| {
+                        info!("creating body of tab {}", index);
+                        widget_bodies[index] = Some(body.init_tree());
+                    });
+                }
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        let mut prev_bodies = std::mem::take(&mut self.bodies).into_iter();
+        self.bodies = other
+            .bodies
+            .into_iter()
+            .map(|body| match (prev_bodies.next().flatten(), body) {
+                (Some(mut prev_body), Some(body)) => {
+                    prev_body.update_value(body);
+                    Some(prev_body)
+                }
+                (_, body) => body,
+            })
+            .collect();
+        self.titles = other.titles;
+        self.active = other.active;
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        for body in self.bodies.iter().flatten() {
+            body.unmount();
+        }
+    }
+
+    #[instrument(name = "Tabs", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        for ((body, body_state), body_widget_seq) in self
+            .bodies
+            .iter()
+            .zip(children_state)
+            .zip(widget_seq.widget_mut().bodies.iter_mut())
+        {
+            if let (Some(body), Some(body_state), Some(body_widget_seq)) =
+                (body, body_state, body_widget_seq)
+            {
+                body.process_event(comp_ctx, body_state, body_widget_seq, cx);
+            }
+        }
+    }
+
+    #[instrument(name = "Tabs", skip(self, _children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<TabSelected> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::Selected(index)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed tab selection: {}", index);
+            Some(TabSelected(index))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::Button;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn mock_tabs(active: usize) -> Tabs<MockComponent> {
+        Tabs::new(
+            vec![
+                (String::from("First"), MockComponent::new()),
+                (String::from("Second"), MockComponent::new()),
+            ],
+            active,
+        )
+    }
+
+    fn button_tabs(active: usize) -> Tabs<Button> {
+        Tabs::new(
+            vec![
+                (
+                    String::from("First"),
+                    Button::new("Body 1").with_reserved_id(WidgetId::reserved(1)),
+                ),
+                (
+                    String::from("Second"),
+                    Button::new("Body 2").with_reserved_id(WidgetId::reserved(2)),
+                ),
+            ],
+            active,
+        )
+    }
+
+    #[test]
+    fn new_tabs() {
+        let (tabs_data, state) = mock_tabs(0).build(Default::default());
+        assert_eq!(tabs_data.titles, vec!["First", "Second"]);
+        assert!(tabs_data.bodies[0].is_some());
+        assert!(tabs_data.bodies[1].is_none());
+        assert_eq!(state, vec![Some(MockState::default()), None]);
+    }
+
+    #[test]
+    fn tabs_state_reset() {
+        let changed_state = vec![Some(MockState::new("changed")), None];
+
+        // Switching away and back builds the first body from scratch
+        let (_, state) = mock_tabs(1).build(changed_state.clone());
+        assert_eq!(state, vec![None, Some(MockState::default())]);
+        let (_, state) = mock_tabs(0).build(state);
+        assert_eq!(state, vec![Some(MockState::default()), None]);
+
+        // Kept alive, the first body keeps its state
+        let (tabs_data, state) = mock_tabs(1).keep_alive().build(changed_state);
+        assert!(tabs_data.bodies.iter().all(Option::is_some));
+        assert_eq!(
+            state,
+            vec![Some(MockState::new("changed")), Some(MockState::default())]
+        );
+        let (_, state) = mock_tabs(0).keep_alive().build(state);
+        assert_eq!(state[0], Some(MockState::new("changed")));
+    }
+
+    #[test]
+    fn tabs_widget() {
+        let (event_sender, event_receiver) = channel();
+        let tabs = |active| EventLogger::new(event_sender.clone(), button_tabs(active));
+
+        Harness::run_test_window(tabs(0), |harness| {
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_some());
+            assert!(harness.try_get_state(WidgetId::reserved(2)).is_none());
+
+            // The caller decides whether to switch tabs
+            harness.click_on("Second");
+            assert_eq!(event_receiver.try_recv(), Ok(TabSelected(1)));
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_some());
+
+            harness.update_root_element(tabs(1));
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_none());
+            assert!(harness.try_get_state(WidgetId::reserved(2)).is_some());
+            assert!(harness.find_by_name("Body 2").is_some());
+
+            // Same tab; the body is reconciled in place
+            harness.update_root_element(tabs(1));
+            assert!(harness.try_get_state(WidgetId::reserved(2)).is_some());
+
+            harness.click_on("First");
+            assert_eq!(event_receiver.try_recv(), Ok(TabSelected(0)));
+            harness.update_root_element(tabs(0));
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_some());
+            assert!(harness.try_get_state(WidgetId::reserved(2)).is_none());
+        });
+    }
+
+    #[test]
+    fn tabs_keep_alive_widget() {
+        let tabs = |active| button_tabs(active).keep_alive();
+
+        Harness::run_test_window(tabs(0), |harness| {
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_some());
+            assert!(harness.try_get_state(WidgetId::reserved(2)).is_some());
+            assert!(harness.find_by_name("Body 1").is_some());
+            assert!(harness.find_by_name("Body 2").is_none());
+
+            harness.update_root_element(tabs(1));
+            assert!(harness.try_get_state(WidgetId::reserved(1)).is_some());
+            assert!(harness.find_by_name("Body 1").is_none());
+            assert!(harness.find_by_name("Body 2").is_some());
+        });
+    }
+}
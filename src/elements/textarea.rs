@@ -0,0 +1,265 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::TextChanged;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::TextAreaWidget;
+
+use druid::EventCtx;
+use tracing::{instrument, trace};
+
+/// A multi-line text-editing area.
+///
+/// ## Events
+///
+/// Emits [TextChanged] events.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextArea {
+    pub text: String,
+    pub placeholder: String,
+    pub rows: Option<usize>,
+    pub flex: FlexParams,
+    pub autofocus: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextAreaData {
+    pub text: String,
+    pub placeholder: String,
+    pub rows: Option<usize>,
+    pub flex: FlexParams,
+    pub request_focus: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl TextArea {
+    /// Build an empty text area.
+    ///
+    /// Use the [.on_text_changed](TextArea::on_text_changed) method to provide a closure to be called when the area is edited.
+    pub fn new() -> Self {
+        TextArea {
+            text: String::new(),
+            placeholder: String::new(),
+            rows: None,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            autofocus: false,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Set the content of the area.
+    pub fn with_text(self, text: impl Into<String>) -> Self {
+        TextArea {
+            text: text.into(),
+            ..self
+        }
+    }
+
+    /// Set the text displayed when the area is empty.
+    pub fn with_placeholder(self, placeholder: impl Into<String>) -> Self {
+        TextArea {
+            placeholder: placeholder.into(),
+            ..self
+        }
+    }
+
+    /// Make the area at least tall enough to display the given number of lines.
+    pub fn rows(self, rows: usize) -> Self {
+        TextArea {
+            rows: Some(rows),
+            ..self
+        }
+    }
+
+    /// Give focus to the area the first time it's built.
+    ///
+    /// If several elements with autofocus are built at the same time, the first one in the
+    /// tree gets focus. Building the area again doesn't take focus back.
+    pub fn autofocus(self) -> Self {
+        TextArea {
+            autofocus: true,
+            ..self
+        }
+    }
+
+    /// Change the way the area's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        TextArea {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The text area created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same text area is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        TextArea {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this area is edited.
+    pub fn on_text_changed<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, TextChanged) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+impl Element for TextArea {
+    type Event = TextChanged;
+
+    type ComponentState = NoState;
+    // Whether the area has been built with autofocus before
+    type AggregateChildrenState = bool;
+    type BuildOutput = TextAreaData;
+
+    #[instrument(name = "TextArea", skip(self, prev_state))]
+    fn build(self, prev_state: bool) -> (TextAreaData, bool) {
+        let request_focus = self.autofocus && !prev_state && crate::autofocus::claim_autofocus();
+        (
+            TextAreaData {
+                text: self.text,
+                placeholder: self.placeholder,
+                rows: self.rows,
+                flex: self.flex,
+                request_focus,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            prev_state || self.autofocus,
+        )
+    }
+}
+
+impl VirtualDom for TextAreaData {
+    type Event = TextChanged;
+    type AggregateChildrenState = bool;
+
+    type TargetWidgetSeq = TextAreaWidget;
+
+    #[instrument(name = "TextArea", skip(self))]
+    fn init_tree(&self) -> TextAreaWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        // The widget isn't part of the GUI yet; the root widget focuses it once it is
+        if self.request_focus {
+            crate::autofocus::queue_focus(id);
+        }
+        TextAreaWidget::new(
+            self.text.clone(),
+            self.placeholder.clone(),
+            self.rows,
+            self.flex,
+            id,
+        )
+    }
+
+    #[instrument(name = "TextArea", skip(self, prev_value, widget, ctx))]
+    fn reconcile(&self, prev_value: &Self, widget: &mut TextAreaWidget, ctx: &mut ReconcileCtx) {
+        if self.placeholder != prev_value.placeholder {
+            widget.set_placeholder(self.placeholder.clone(), ctx.event_ctx);
+        }
+        if self.rows != prev_value.rows {
+            widget.rows = self.rows;
+            ctx.event_ctx.request_layout();
+        }
+        // If the widget already holds this text (eg because the user just typed it), leave it
+        // alone, so that the cursor and selection are preserved.
+        if self.text != widget.text {
+            widget.text = self.text.clone();
+            widget.request_druid_update(ctx.event_ctx);
+        }
+        if self.request_focus {
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_focus();
+                });
+        }
+    }
+
+    #[instrument(name = "TextArea", skip(self, _children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        _children_state: &mut Self::AggregateChildrenState,
+        widget: &mut TextAreaWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<TextChanged> {
+        // FIXME - Rework event dispatching
+        let id = widget.id();
+        if let Some(Action::TextChanged(new_content)) = cx.app_data.dequeue_action(id) {
+            trace!("Processed text change");
+            Some(TextChanged { new_content })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn new_textarea() {
+        let textarea = TextArea::new()
+            .with_text("Hello")
+            .with_placeholder("Write something")
+            .rows(3);
+        let (textarea_data, _) = textarea.build(false);
+
+        assert_eq!(
+            textarea_data,
+            TextAreaData {
+                text: String::from("Hello"),
+                placeholder: String::from("Write something"),
+                rows: Some(3),
+                flex: FlexParams {
+                    flex: None,
+                    alignment: None,
+                },
+                request_focus: false,
+                reserved_widget_id: None,
+            }
+        );
+    }
+
+    #[test]
+    fn textarea_keydown() {
+        let (event_sender, event_receiver) = channel();
+        let textarea_id = WidgetId::reserved(42);
+        let textarea = EventLogger::new(
+            event_sender,
+            TextArea::new().rows(3).with_reserved_id(textarea_id),
+        );
+
+        Harness::run_test_window(textarea, |harness| {
+            harness.mouse_click_on(textarea_id);
+            harness.keyboard_key("a");
+            harness.keyboard_key("b");
+
+            // TODO - Because we don't mock IME events, the emitted events don't
+            // have the right text
+            assert!(matches!(event_receiver.try_recv(), Ok(TextChanged { .. })));
+            assert!(matches!(event_receiver.try_recv(), Ok(TextChanged { .. })));
+            assert!(event_receiver.try_recv().is_err());
+        });
+    }
+}
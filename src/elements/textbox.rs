@@ -1,10 +1,13 @@
-use crate::ctx::ReconcileCtx;
+use crate::ctx::{read_building_state, ReconcileCtx};
 use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::with_event::{format_typename, WithCallbackEvent, WithMappedState};
 use crate::flex::FlexParams;
 use crate::glue::{Action, GlobalEventCx, WidgetId};
 use crate::metadata::{Metadata, NoState};
 use crate::widgets::TextBoxWidget;
 
+use derivative::Derivative;
+use druid::EventCtx;
 use tracing::{instrument, trace};
 
 // TODO - Handle the anti-pattern where the user does something like
@@ -22,6 +25,7 @@ use tracing::{instrument, trace};
 pub struct TextBox {
     pub text: String,
     pub flex: FlexParams,
+    pub autofocus: bool,
     pub reserved_widget_id: Option<WidgetId>,
 }
 
@@ -29,9 +33,40 @@ pub struct TextBox {
 pub struct TextBoxData {
     pub text: String,
     pub flex: FlexParams,
+    pub request_focus: bool,
     pub reserved_widget_id: Option<WidgetId>,
 }
 
+/// A [TextBox] whose content is a field of the component's local state.
+///
+/// Created with [`TextBox::bound`].
+///
+/// ## Events
+///
+/// Emits [TextChanged] events, after writing the new content to the field.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Get: Clone, GetMut: Clone"), Debug(bound = ""))]
+pub struct BoundTextBox<ComponentEvent, ComponentState, Get, GetMut> {
+    pub text_box: TextBox,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub get: Get,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub get_mut: GetMut,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+// Writes the new content of a bound text box to its field
+type WriteText = fn(&mut String, TextChanged);
+
+type BoundTextBoxInner<ComponentEvent, ComponentState, GetMut> = WithMappedState<
+    ComponentEvent,
+    ComponentState,
+    String,
+    WithCallbackEvent<ComponentEvent, String, TextChanged, TextBox, WriteText>,
+    GetMut,
+>;
+
 /// Event emitted when text is entered or edited in a [TextBox].
 ///
 /// Holds the new content of the box
@@ -54,10 +89,22 @@ impl TextBox {
                 flex: None,
                 alignment: None,
             },
+            autofocus: false,
             reserved_widget_id: None,
         }
     }
 
+    /// Give focus to the box the first time it's built.
+    ///
+    /// If several elements with autofocus are built at the same time, the first one in the
+    /// tree gets focus. Building the box again doesn't take focus back.
+    pub fn autofocus(self) -> Self {
+        TextBox {
+            autofocus: true,
+            ..self
+        }
+    }
+
     /// Change the way the box's size is calculated
     pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
         TextBox {
@@ -78,6 +125,32 @@ impl TextBox {
         }
     }
 
+    /// Build a text box whose content is a field of the component's local state.
+    ///
+    /// `get` and `get_mut` return the field. The box displays the field's value when it's
+    /// built, and writes to it when it's edited, so that changes made elsewhere to the field
+    /// are displayed too. The box's content is only replaced when the field holds a
+    /// different value, so edits don't reset the cursor.
+    ///
+    /// `md` must be the metadata of the component whose state holds the field.
+    pub fn bound<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Get: Fn(&ComponentState) -> &String + Clone + 'static,
+        GetMut: Fn(&mut ComponentState) -> &mut String + Clone + 'static,
+    >(
+        md: Metadata<ComponentEvent, ComponentState>,
+        get: Get,
+        get_mut: GetMut,
+    ) -> BoundTextBox<ComponentEvent, ComponentState, Get, GetMut> {
+        BoundTextBox {
+            text_box: TextBox::new(""),
+            get,
+            get_mut,
+            _metadata: md,
+        }
+    }
+
     /// Provide a closure to be called when this box is edited.
     pub fn on_text_changed<ComponentEvent: 'static, ComponentState: 'static>(
         self,
@@ -92,39 +165,106 @@ impl Element for TextBox {
     type Event = TextChanged;
 
     type ComponentState = NoState;
-    type AggregateChildrenState = ();
+    // Whether the box has been built with autofocus before
+    type AggregateChildrenState = bool;
     type BuildOutput = TextBoxData;
 
-    #[instrument(name = "TextBox", skip(self, _prev_state))]
-    fn build(self, _prev_state: ()) -> (TextBoxData, ()) {
+    #[instrument(name = "TextBox", skip(self, prev_state))]
+    fn build(self, prev_state: bool) -> (TextBoxData, bool) {
+        let request_focus = self.autofocus && !prev_state && crate::autofocus::claim_autofocus();
         (
             TextBoxData {
                 text: self.text,
                 flex: self.flex,
+                request_focus,
                 reserved_widget_id: self.reserved_widget_id,
             },
-            (),
+            prev_state || self.autofocus,
         )
     }
 }
 
+impl<ComponentEvent, ComponentState, Get, GetMut>
+    BoundTextBox<ComponentEvent, ComponentState, Get, GetMut>
+{
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        BoundTextBox {
+            text_box: self.text_box.with_reserved_id(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Get: Fn(&ComponentState) -> &String + Clone + 'static,
+        GetMut: Fn(&mut ComponentState) -> &mut String + Clone + 'static,
+    > Element for BoundTextBox<ComponentEvent, ComponentState, Get, GetMut>
+{
+    type Event = TextChanged;
+
+    type ComponentState = NoState;
+    type AggregateChildrenState =
+        <BoundTextBoxInner<ComponentEvent, ComponentState, GetMut> as Element>::AggregateChildrenState;
+    type BuildOutput =
+        <BoundTextBoxInner<ComponentEvent, ComponentState, GetMut> as Element>::BuildOutput;
+
+    #[instrument(name = "BoundTextBox", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let get = self.get;
+        let text =
+            read_building_state(|state: &ComponentState| get(state).clone()).unwrap_or_default();
+        let write_text: WriteText = |text, event| *text = event.new_content;
+        let text_box: BoundTextBoxInner<ComponentEvent, ComponentState, GetMut> = TextBox {
+            text,
+            ..self.text_box
+        }
+        .on(self._metadata.scoped(), write_text)
+        .map_state(self._metadata, self.get_mut);
+        text_box.build(prev_state)
+    }
+}
+
 impl VirtualDom for TextBoxData {
     type Event = TextChanged;
-    type AggregateChildrenState = ();
+    type AggregateChildrenState = bool;
 
     type TargetWidgetSeq = TextBoxWidget;
 
     #[instrument(name = "TextBox", skip(self))]
     fn init_tree(&self) -> TextBoxWidget {
         let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        // The widget isn't part of the GUI yet; the root widget focuses it once it is
+        if self.request_focus {
+            crate::autofocus::queue_focus(id);
+        }
         TextBoxWidget::new(self.text.clone(), self.flex, id)
     }
 
     #[instrument(name = "TextBox", skip(self, _prev_value, widget, ctx))]
     fn reconcile(&self, _prev_value: &Self, widget: &mut TextBoxWidget, ctx: &mut ReconcileCtx) {
-        widget.text = self.text.clone();
-        // TODO - check diff with previous value
-        widget.request_druid_update(ctx.event_ctx);
+        // The widget's text is edited directly; replacing it with the same value would reset
+        // the cursor
+        if widget.text != self.text {
+            widget.text = self.text.clone();
+            widget.request_druid_update(ctx.event_ctx);
+        }
+        if self.request_focus {
+            widget
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_focus();
+                });
+        }
     }
 
     #[instrument(name = "TextBox", skip(self, _children_state, widget, cx))]
@@ -157,7 +297,7 @@ mod tests {
     #[test]
     fn new_textbox() {
         let textbox = TextBox::new("Hello");
-        let (textbox_data, ()) = textbox.clone().build(());
+        let (textbox_data, _) = textbox.clone().build(false);
 
         assert_debug_snapshot!(textbox);
         assert_debug_snapshot!(textbox_data);
@@ -191,6 +331,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn textbox_autofocus() {
+        use crate::Column;
+        use druid::KbKey;
+
+        let form = || {
+            Column!(
+                TextBox::new("name"),
+                TextBox::new("email").autofocus(),
+                TextBox::new("phone").autofocus(),
+            )
+        };
+
+        Harness::run_test_window(form(), |harness| {
+            // Only the first text box with autofocus gets focus
+            harness.press_key(KbKey::Character("!".to_string()));
+            assert!(harness.find_by_name("email!").is_some());
+            assert!(harness.find_by_name("phone").is_some());
+
+            // Rebuilding the form doesn't take focus back
+            harness.click_on("name");
+            harness.update_root_element(form());
+            harness.press_key(KbKey::Character("?".to_string()));
+            assert!(harness.find_by_name("name?").is_some());
+        });
+    }
+
     #[test]
     fn textbox_keydown() {
         use crate::elements::event_logger::EventLogger;
@@ -218,4 +385,82 @@ mod tests {
             // TODO - test data persistence, somehow?
         });
     }
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Profile {
+        name: String,
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Hash)]
+    struct ProfileForm;
+
+    const TEXTBOX_ID: WidgetId = WidgetId::reserved(1);
+
+    impl ProfileForm {
+        fn render(
+            ctx: &crate::CompCtx,
+            _props: (),
+        ) -> impl Element<Event = crate::NoEvent, ComponentState = Profile> {
+            use crate::elements::{Button, ButtonClick, ComponentOutput, Label};
+            use crate::Column;
+
+            let md = ctx.use_metadata::<crate::NoEvent, Profile>();
+            let profile = ctx.get_local_state(md);
+            ComponentOutput::new(
+                md,
+                Column!(
+                    TextBox::bound(
+                        md,
+                        |profile: &Profile| &profile.name,
+                        |profile: &mut Profile| &mut profile.name,
+                    )
+                    .with_reserved_id(TEXTBOX_ID),
+                    Label::new(format!("Name: {}", profile.name)),
+                    Button::new("Reset").on(md, |profile: &mut Profile, _: ButtonClick| {
+                        profile.name = String::from("Bob");
+                    }),
+                ),
+            )
+        }
+    }
+
+    impl crate::elements::Component for ProfileForm {
+        type Props = ();
+        type LocalEvent = crate::NoEvent;
+        type LocalState = Profile;
+
+        fn new(props: Self::Props) -> crate::elements::ElementBox<crate::NoEvent> {
+            crate::elements::ElementBox::new(crate::elements::internals::ComponentHolder::<
+                Self,
+                _,
+                _,
+            >::new(&ProfileForm::render, props))
+        }
+
+        fn name() -> &'static str {
+            "ProfileForm"
+        }
+    }
+
+    #[test]
+    fn bound_textbox() {
+        use druid::KbKey;
+
+        Harness::run_test_window(ProfileForm::new(()), |harness| {
+            assert!(harness.find_by_name("Name: ").is_some());
+
+            // Edits are written to the field
+            harness.mouse_click_on(TEXTBOX_ID);
+            for c in ["A", "d", "a"] {
+                harness.press_key(KbKey::Character(c.to_string()));
+            }
+            assert!(harness.find_by_name("Ada").is_some());
+            assert!(harness.find_by_name("Name: Ada").is_some());
+
+            // Changes to the field are displayed in the box
+            harness.click_on("Reset");
+            assert!(harness.find_by_name("Bob").is_some());
+            assert!(harness.find_by_name("Name: Bob").is_some());
+        });
+    }
 }
@@ -0,0 +1,305 @@
+use crate::ctx::ReconcileCtx;
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleToastWidget, ToastWidget};
+
+use druid::EventCtx;
+use std::time::{Duration, Instant};
+use tracing::{debug_span, info, instrument, trace};
+
+/// A transient message, which disappears by itself after a delay.
+///
+/// While `show` is true, the message is displayed until `duration` has elapsed; it then
+/// disappears, and a [ToastDismissed] event is emitted. Showing a different text restarts
+/// the delay; setting `show` to false hides the toast immediately, without emitting an event.
+///
+/// The toast is laid out like any other element. To display it over other elements, put it
+/// in a [`Stack`](crate::elements::Stack), with eg
+/// [`ElementExt::align`](crate::ElementExt::align).
+///
+/// ## Events
+///
+/// Emits [ToastDismissed] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub show: bool,
+    pub text: String,
+    pub duration: Duration,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Virtual DOM node of [`Toast`].
+///
+/// `deadline` is the time at which the toast disappears. It's `None` if the toast isn't
+/// displayed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToastData {
+    pub text: String,
+    pub deadline: Option<Instant>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [Toast] disappears after its delay.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToastDismissed;
+
+//
+// --- IMPLS
+
+impl Toast {
+    /// Build a toast showing `text` for `duration`, if `show` is true.
+    ///
+    /// Use the [.on_dismiss](Toast::on_dismiss) method to provide a closure to be called when the toast disappears.
+    pub fn new(show: bool, text: impl Into<String>, duration: Duration) -> Self {
+        Toast {
+            show,
+            text: text.into(),
+            duration,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the toast's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Toast {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Toast {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when the toast disappears.
+    ///
+    /// The closure usually sets the flag passed as `show` to false.
+    pub fn on_dismiss<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, move |state: &mut ComponentState, _: ToastDismissed| {
+            callback(state)
+        })
+    }
+}
+
+impl ToastData {
+    fn init_widget(&self, deadline: Instant) -> SingleToastWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleToastWidget::new(ToastWidget::new(self.text.clone(), deadline, id), self.flex)
+    }
+}
+
+impl Element for Toast {
+    type Event = ToastDismissed;
+
+    type ComponentState = NoState;
+    // The text being shown, and the time at which it disappears; once it has disappeared,
+    // the deadline is `None` until the text changes or the toast is shown again.
+    type AggregateChildrenState = Option<(String, Option<Instant>)>;
+    type BuildOutput = ToastData;
+
+    #[instrument(name = "Toast", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (ToastData, Self::AggregateChildrenState) {
+        let state = if self.show {
+            match prev_state {
+                Some((prev_text, deadline)) if prev_text == self.text => {
+                    Some((prev_text, deadline))
+                }
+                _ => Some((self.text.clone(), Some(Instant::now() + self.duration))),
+            }
+        } else {
+            None
+        };
+        let deadline = state.as_ref().and_then(|(_, deadline)| *deadline);
+
+        (
+            ToastData {
+                text: self.text,
+                deadline,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl VirtualDom for ToastData {
+    type Event = ToastDismissed;
+    type AggregateChildrenState = Option<(String, Option<Instant>)>;
+    type TargetWidgetSeq = Option<SingleToastWidget>;
+
+    #[instrument(name = "Toast", skip(self))]
+    fn init_tree(&self) -> Option<SingleToastWidget> {
+        self.deadline.map(|deadline| self.init_widget(deadline))
+    }
+
+    #[instrument(name = "Toast", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Option<SingleToastWidget>,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let deadline = match self.deadline {
+            Some(deadline) => deadline,
+            None => {
+                // Dropping the widget drops its timer
+                *widget_seq = None;
+                return;
+            }
+        };
+
+        match (prev_value.deadline, widget_seq) {
+            (Some(prev_deadline), Some(widget)) => {
+                if self.text != prev_value.text {
+                    let text = self.text.clone();
+                    widget.pod.with_event_context(
+                        ctx.event_ctx,
+                        |widget: &mut ToastWidget, ctx: &mut EventCtx| {
+                            widget.set_text(text.clone(), ctx);
+                        },
+                    );
+                }
+                if deadline != prev_deadline {
+                    trace!("Restarting timer: {:?}", deadline);
+                    widget.pod.with_event_context(
+                        ctx.event_ctx,
+                        |widget: &mut ToastWidget, ctx: &mut EventCtx| {
+                            widget.restart_timer(deadline, ctx);
+                        },
+                    );
+                }
+                widget.flex = self.flex;
+            }
+            (_, widget_seq) => {
+                debug_span!("init_tree").in_scope(|| {
+                    info!("showing toast");
+                    *widget_seq = Some(self.init_widget(deadline));
+                });
+            }
+        }
+    }
+
+    #[instrument(name = "Toast", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Option<SingleToastWidget>,
+        cx: &mut GlobalEventCx,
+    ) -> Option<ToastDismissed> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.as_ref()?.widget().id();
+        if let Some(Action::TimerFired) = cx.app_data.dequeue_action(id) {
+            trace!("Toast delay elapsed");
+            // The widget is removed the next time the element is built
+            if let Some((_text, deadline)) = children_state {
+                *deadline = None;
+            }
+            Some(ToastDismissed)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    const DURATION: Duration = Duration::from_millis(1000);
+
+    #[test]
+    fn new_toast() {
+        let (toast_data, state) = Toast::new(false, "Saved", DURATION).build(None);
+        assert_eq!(toast_data.deadline, None);
+        assert_eq!(state, None);
+
+        let (toast_data, state) = Toast::new(true, "Saved", DURATION).build(state);
+        let deadline = toast_data.deadline.unwrap();
+        assert_eq!(state, Some((String::from("Saved"), Some(deadline))));
+
+        // Same text; the deadline is kept
+        let (toast_data, state) = Toast::new(true, "Saved", DURATION).build(state);
+        assert_eq!(toast_data.deadline, Some(deadline));
+
+        // New text; the delay restarts
+        let (toast_data, _) = Toast::new(true, "Saved again", DURATION).build(state);
+        assert_ne!(toast_data.deadline, Some(deadline));
+    }
+
+    #[test]
+    fn toast_dismiss() {
+        let (event_sender, event_receiver) = channel();
+        let toast_id = WidgetId::reserved(1);
+        let toast = |show| {
+            EventLogger::new(
+                event_sender.clone(),
+                Toast::new(show, "Saved", DURATION).with_reserved_id(toast_id),
+            )
+        };
+
+        Harness::run_test_window(toast(true), |harness| {
+            assert!(harness.find_by_name("Saved").is_some());
+
+            harness.move_timers_forward(Duration::from_millis(500));
+            assert_eq!(event_receiver.try_recv().ok(), None);
+            assert!(harness.try_get_state(toast_id).is_some());
+
+            harness.move_timers_forward(Duration::from_millis(600));
+            assert_eq!(event_receiver.try_recv(), Ok(ToastDismissed));
+            assert!(harness.try_get_state(toast_id).is_none());
+
+            // Still dismissed, until the toast is hidden and shown again
+            harness.update_root_element(toast(true));
+            assert!(harness.try_get_state(toast_id).is_none());
+        });
+    }
+
+    #[test]
+    fn toast_hidden_before_deadline() {
+        let (event_sender, event_receiver) = channel();
+        let toast_id = WidgetId::reserved(1);
+        let toast = |show| {
+            EventLogger::new(
+                event_sender.clone(),
+                Toast::new(show, "Saved", DURATION).with_reserved_id(toast_id),
+            )
+        };
+
+        Harness::run_test_window(toast(true), |harness| {
+            harness.update_root_element(toast(false));
+            assert!(harness.try_get_state(toast_id).is_none());
+
+            // The timer of the removed toast is ignored
+            harness.move_timers_forward(Duration::from_millis(1100));
+            assert_eq!(event_receiver.try_recv().ok(), None);
+        });
+    }
+}
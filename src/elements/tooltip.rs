@@ -0,0 +1,195 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleTooltipWidget, TooltipWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which shows a text next to the mouse cursor while it hovers the child.
+///
+/// Usually created with [`ElementExt::tooltip`](crate::ElementExt::tooltip).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tooltip<Child: Element> {
+    pub child: Child,
+    pub text: String,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TooltipData<Child: VirtualDom> {
+    pub child: Child,
+    pub text: String,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Tooltip<Child> {
+    pub fn new(child: Child, text: impl Into<String>) -> Self {
+        Tooltip {
+            child,
+            text: text.into(),
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Tooltip {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Tooltip<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = TooltipData<Child::BuildOutput>;
+
+    #[instrument(name = "Tooltip", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            TooltipData {
+                child,
+                text: self.text,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TooltipData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleTooltipWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Tooltip", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleTooltipWidget::new(
+            TooltipWidget::new(self.child.init_tree(), self.text.clone(), id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Tooltip", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.text != prev_value.text {
+            let text = self.text.clone();
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut TooltipWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_text(text.clone(), ctx);
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.text = other.text;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Tooltip", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Tooltip", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::{Button, ButtonClick};
+    use crate::test_harness::Harness;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn tooltip_button() {
+        let (event_sender, event_receiver) = channel();
+        let tooltip_id = WidgetId::reserved(1);
+        let button = |tooltip: &str| {
+            EventLogger::new(
+                event_sender.clone(),
+                Button::new("Save")
+                    .tooltip(tooltip)
+                    .with_reserved_id(tooltip_id),
+            )
+        };
+
+        Harness::run_test_window(button("Save the file"), |harness| {
+            let values = harness.get_debug_state(tooltip_id).other_values;
+            assert_eq!(values["tooltip"], "Save the file");
+
+            // The button still gets clicks
+            harness.click_on("Save");
+            assert_eq!(event_receiver.try_recv(), Ok(ButtonClick));
+
+            harness.update_root_element(button("Save the file to disk"));
+            let values = harness.get_debug_state(tooltip_id).other_values;
+            assert_eq!(values["tooltip"], "Save the file to disk");
+        });
+    }
+}
@@ -0,0 +1,334 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleTransitionWidget, TransitionWidget};
+
+use druid::{EventCtx, Vec2};
+use std::time::Duration;
+use tracing::{instrument, trace};
+use tracing_unwrap::OptionExt;
+
+/// Wrapper which fades its child in or out when its `visible` flag changes.
+///
+/// The child is faded over the given duration, and optionally slid from an offset (see
+/// [`with_offset`](Transition::with_offset)). Once it's faded out, the child is removed from
+/// the tree, and its local state is dropped. If the flag flips back while the child is still
+/// fading, the animation reverses from where it was, instead of starting over.
+///
+/// The first time the wrapper is built, the child is shown or hidden right away.
+///
+/// Usually created with [`ElementExt::transition`](crate::ElementExt::transition).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transition<Child: Element> {
+    pub child: Child,
+    pub visible: bool,
+    pub duration: Duration,
+    pub offset: Vec2,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransitionData<Child: VirtualDom> {
+    pub child: Option<Child>,
+    pub progress: TransitionProgress,
+    pub duration: Duration,
+    pub offset: Vec2,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// The state of the animation of a [Transition].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TransitionProgress {
+    /// From `0.0` (hidden) to `1.0` (fully visible).
+    pub progress: f64,
+    /// Whether the child is being shown or hidden.
+    pub visible: bool,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Transition<Child> {
+    pub fn new(child: Child, visible: bool, duration: Duration) -> Self {
+        Transition {
+            child,
+            visible,
+            duration,
+            offset: Vec2::ZERO,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Slide the child from `offset` to its place while it's shown, and back while it's hidden.
+    pub fn with_offset(self, offset: impl Into<Vec2>) -> Self {
+        Transition {
+            offset: offset.into(),
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Transition {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl TransitionProgress {
+    /// Move the progress towards its target, by the fraction of `duration` that `interval`
+    /// represents.
+    fn advance(self, interval: Duration, duration: Duration) -> Self {
+        let step = if duration.as_nanos() == 0 {
+            1.0
+        } else {
+            interval.as_nanos() as f64 / duration.as_nanos() as f64
+        };
+        let progress = if self.visible {
+            (self.progress + step).min(1.0)
+        } else {
+            (self.progress - step).max(0.0)
+        };
+        TransitionProgress { progress, ..self }
+    }
+}
+
+impl<Child: Element> Element for Transition<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The animation state, and the state of the child while it's in the tree
+    type AggregateChildrenState = (
+        Option<TransitionProgress>,
+        Option<Child::AggregateChildrenState>,
+    );
+    type BuildOutput = TransitionData<Child::BuildOutput>;
+
+    #[instrument(name = "Transition", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (prev_progress, child_prev_state) = prev_state;
+        let progress = match prev_progress {
+            Some(prev_progress) => TransitionProgress {
+                progress: prev_progress.progress,
+                visible: self.visible,
+            },
+            None => TransitionProgress {
+                progress: if self.visible { 1.0 } else { 0.0 },
+                visible: self.visible,
+            },
+        };
+
+        // The child is kept until it's done fading out
+        let (child, child_state) = if progress.visible || progress.progress > 0.0 {
+            let (child, child_state) = self.child.build(child_prev_state.unwrap_or_default());
+            (Some(child), Some(child_state))
+        } else {
+            (None, None)
+        };
+
+        (
+            TransitionData {
+                child,
+                progress,
+                duration: self.duration,
+                offset: self.offset,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (Some(progress), child_state),
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TransitionData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = (
+        Option<TransitionProgress>,
+        Option<Child::AggregateChildrenState>,
+    );
+    type TargetWidgetSeq = SingleTransitionWidget<Option<Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Transition", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleTransitionWidget::new(
+            TransitionWidget::new(
+                self.child.init_tree(),
+                self.progress.progress,
+                self.progress.visible,
+                self.offset,
+                id,
+            ),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Transition", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+
+        widget_seq.widget_mut().offset = self.offset;
+        if self.progress != prev_value.progress {
+            let progress = self.progress;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut TransitionWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_progress(progress.progress, progress.visible, ctx);
+                },
+            );
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.progress = other.progress;
+        self.duration = other.duration;
+        self.offset = other.offset;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "Transition",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        let (progress, child_state) = children_state;
+
+        if let Some(child) = &self.child {
+            child.process_event(
+                comp_ctx,
+                child_state.as_mut().unwrap_or_log(),
+                widget_seq.widget_mut().children.as_mut().unwrap_or_log(),
+                cx,
+            );
+        }
+
+        // The new progress is picked up by reconcile, which requests the next frame
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::AnimFrame(interval)) = cx.app_data.dequeue_action(id) {
+            if let Some(progress) = progress {
+                *progress = progress.advance(Duration::from_nanos(interval), self.duration);
+                trace!("Transition progress: {:?}", progress);
+            }
+        }
+    }
+
+    #[instrument(name = "Transition", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        let child = self.child.as_ref()?;
+        child.process_local_event(
+            children_state.1.as_mut().unwrap_or_log(),
+            widget_seq.widget_mut().children.as_mut().unwrap_or_log(),
+            cx,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    #[test]
+    fn new_transition() {
+        let transition = Label::new("Hello").transition(false, Duration::from_millis(100));
+        let (transition_data, state) = transition.build(Default::default());
+
+        // Hidden children aren't built in the first place
+        assert_eq!(transition_data.child, None);
+        assert_eq!(
+            state.0,
+            Some(TransitionProgress {
+                progress: 0.0,
+                visible: false,
+            })
+        );
+    }
+
+    #[test]
+    fn transition_frames() {
+        let transition_id = WidgetId::reserved(1);
+        let label = |visible: bool| {
+            Label::new("Hello")
+                .transition(visible, Duration::from_millis(100))
+                .with_offset((0.0, 20.0))
+                .with_reserved_id(transition_id)
+        };
+        let opacity = |harness: &mut Harness<'_, '_, _>| {
+            harness.get_debug_state(transition_id).other_values["opacity"].clone()
+        };
+        let frame = Duration::from_millis(25);
+
+        Harness::run_test_window(label(true), |harness| {
+            assert_eq!(opacity(harness), "1.00");
+
+            harness.update_root_element(label(false));
+            harness.animate_frame(frame);
+            assert_eq!(opacity(harness), "0.75");
+            harness.animate_frame(frame);
+            assert_eq!(opacity(harness), "0.50");
+            assert!(harness.find_by_name("Hello").is_some());
+
+            // Toggling back reverses from the current opacity
+            harness.update_root_element(label(true));
+            assert_eq!(opacity(harness), "0.50");
+            harness.animate_frame(frame);
+            assert_eq!(opacity(harness), "0.75");
+
+            // Once faded out, the child is removed
+            harness.update_root_element(label(false));
+            for _ in 0..3 {
+                harness.animate_frame(frame);
+            }
+            assert_eq!(opacity(harness), "0.00");
+            assert!(harness.find_by_name("Hello").is_none());
+
+            // Extra frames don't change anything
+            harness.animate_frame(frame);
+            assert_eq!(opacity(harness), "0.00");
+        });
+    }
+}
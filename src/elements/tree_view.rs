@@ -0,0 +1,596 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::compute_diff::compute_diff;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleTreeViewWidget, TreeRowWidget, TreeViewWidget};
+
+use druid::EventCtx;
+use either::{Left, Right};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use tracing::{debug_span, info, instrument, trace};
+
+/// Identifies a node of a [TreeView].
+///
+/// Node ids must be unique within a tree. They're used as keys when the tree is updated, the
+/// same way [`ElementList`](crate::elements::ElementList) uses keys: a node keeps its expansion
+/// state, its local state and its widgets as long as its id is unchanged, even if it's moved.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub String);
+
+/// A node of a [TreeView], with the element displayed in its row, and its children.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode<Child: Element> {
+    pub id: NodeId,
+    pub content: Child,
+    pub children: Vec<TreeNode<Child>>,
+}
+
+/// A hierarchy of nodes, displayed as a column of indented rows.
+///
+/// Nodes with children have a toggle arrow, which expands or collapses them. Which nodes are
+/// expanded is stored in the tree's local state; all nodes start collapsed. Clicking a row
+/// outside of its toggle arrow emits an event, but doesn't change the selection by itself;
+/// the selected node is chosen with [`with_selected`](TreeView::with_selected).
+///
+/// Only the rows of visible nodes are built. When a node is expanded, rows are created for
+/// its children, and the other rows are kept as they are.
+///
+/// ## Events
+///
+/// Emits [TreeViewEvent] events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeView<Child: Element> {
+    pub roots: Vec<TreeNode<Child>>,
+    pub selected: Option<NodeId>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Virtual DOM node of [`TreeView`], holding the visible rows, keyed by node id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeViewData<Child: VirtualDom> {
+    pub rows: Vec<(NodeId, TreeRowData<Child>)>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// A visible node of a [`TreeViewData`].
+///
+/// `expanded` is `None` if the node has no children.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeRowData<Child: VirtualDom> {
+    pub content: Child,
+    pub depth: usize,
+    pub expanded: Option<bool>,
+    pub selected: bool,
+}
+
+/// Event emitted by a [TreeView].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TreeViewEvent {
+    /// The toggle arrow of a node was clicked. The node has already been expanded or collapsed.
+    NodeToggled(NodeId),
+    /// The row of a node was clicked.
+    NodeSelected(NodeId),
+}
+
+//
+// --- IMPLS
+
+impl NodeId {
+    pub fn new(id: impl Into<String>) -> Self {
+        NodeId(id.into())
+    }
+}
+
+impl<Child: Element> TreeNode<Child> {
+    /// Build a node without children.
+    pub fn new(id: impl Into<String>, content: Child) -> Self {
+        TreeNode {
+            id: NodeId::new(id),
+            content,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_children(self, children: Vec<TreeNode<Child>>) -> Self {
+        TreeNode { children, ..self }
+    }
+}
+
+impl<Child: Element> TreeView<Child> {
+    /// Build a tree from its top-level nodes, with no node selected.
+    ///
+    /// Use the [.on_toggle](TreeView::on_toggle) and [.on_select](TreeView::on_select) methods to provide closures to be called when nodes are clicked.
+    pub fn new(roots: Vec<TreeNode<Child>>) -> Self {
+        TreeView {
+            roots,
+            selected: None,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Highlight the row of the given node.
+    pub fn with_selected(self, selected: NodeId) -> Self {
+        TreeView {
+            selected: Some(selected),
+            ..self
+        }
+    }
+
+    /// Change the way the tree's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        TreeView {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        TreeView {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when a node is expanded or collapsed.
+    ///
+    /// The closure is given the id of the node, and whether it's now expanded.
+    pub fn on_toggle<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, NodeId) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: TreeViewEvent| {
+                if let TreeViewEvent::NodeToggled(id) = event {
+                    callback(state, id);
+                }
+            },
+        )
+    }
+
+    /// Provide a closure to be called when the row of a node is clicked.
+    ///
+    /// The closure is given the id of the node.
+    pub fn on_select<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, NodeId) + Clone + 'static,
+    ) -> impl Element {
+        self.on(
+            md,
+            move |state: &mut ComponentState, event: TreeViewEvent| {
+                if let TreeViewEvent::NodeSelected(id) = event {
+                    callback(state, id);
+                }
+            },
+        )
+    }
+}
+
+type RowState<Child> = (NodeId, <Child as Element>::AggregateChildrenState);
+
+// Appends the rows of `nodes` and of their expanded descendants, depth-first.
+fn flatten_nodes<Child: Element>(
+    nodes: Vec<TreeNode<Child>>,
+    depth: usize,
+    expanded: &BTreeSet<NodeId>,
+    selected: Option<&NodeId>,
+    prev_rows_state: &mut HashMap<NodeId, Child::AggregateChildrenState>,
+    rows: &mut Vec<(NodeId, TreeRowData<Child::BuildOutput>)>,
+    rows_state: &mut Vec<RowState<Child>>,
+) {
+    for node in nodes {
+        let is_expanded = expanded.contains(&node.id);
+        let row_prev_state = prev_rows_state.remove(&node.id).unwrap_or_default();
+        let (content, row_state) = node.content.build(row_prev_state);
+        rows.push((
+            node.id.clone(),
+            TreeRowData {
+                content,
+                depth,
+                expanded: if node.children.is_empty() {
+                    None
+                } else {
+                    Some(is_expanded)
+                },
+                selected: selected == Some(&node.id),
+            },
+        ));
+        rows_state.push((node.id, row_state));
+
+        // Collapsed subtrees aren't built
+        if is_expanded {
+            flatten_nodes(
+                node.children,
+                depth + 1,
+                expanded,
+                selected,
+                prev_rows_state,
+                rows,
+                rows_state,
+            );
+        }
+    }
+}
+
+impl<Child: Element> Element for TreeView<Child> {
+    type Event = TreeViewEvent;
+
+    type ComponentState = NoState;
+    // The expanded nodes, and the state of each visible row
+    type AggregateChildrenState = (BTreeSet<NodeId>, Vec<RowState<Child>>);
+    type BuildOutput = TreeViewData<Child::BuildOutput>;
+
+    #[instrument(name = "TreeView", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (expanded, prev_rows_state) = prev_state;
+        let mut prev_rows_state: HashMap<_, _> = prev_rows_state.into_iter().collect();
+
+        let mut rows = Vec::new();
+        let mut rows_state = Vec::new();
+        flatten_nodes(
+            self.roots,
+            0,
+            &expanded,
+            self.selected.as_ref(),
+            &mut prev_rows_state,
+            &mut rows,
+            &mut rows_state,
+        );
+
+        (
+            TreeViewData {
+                rows,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (expanded, rows_state),
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for TreeViewData<Child> {
+    type Event = TreeViewEvent;
+    type AggregateChildrenState = (
+        BTreeSet<NodeId>,
+        Vec<(NodeId, Child::AggregateChildrenState)>,
+    );
+    type TargetWidgetSeq = SingleTreeViewWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "TreeView", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let rows = self
+            .rows
+            .iter()
+            .map(|(_id, row)| {
+                TreeRowWidget::new(
+                    row.content.init_tree(),
+                    row.depth,
+                    row.expanded,
+                    row.selected,
+                )
+            })
+            .collect();
+        SingleTreeViewWidget::new(TreeViewWidget::new(rows, id), self.flex)
+    }
+
+    // Same algorithm as ElementList: rows are matched by node id, so that only the rows of
+    // inserted and removed nodes are created and destroyed.
+    #[instrument(name = "TreeView", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        let mutation = compute_diff(&prev_value.rows, &self.rows);
+        widget_seq.flex = self.flex;
+        let row_widgets = &mut widget_seq.widget_mut().rows;
+
+        let mut prev_data: Vec<_> = prev_value
+            .rows
+            .iter()
+            .zip(row_widgets.iter_mut())
+            .map(Left)
+            .collect();
+
+        let mut index_diff = 0_isize;
+        for mutation_item in &mutation.items {
+            let index = (mutation_item.index as isize + index_diff) as usize;
+            let spliced_range = index..(index + mutation_item.removed_count);
+            let new_range = index..(index + mutation_item.inserted_keys.len());
+
+            let removed_items =
+                prev_data.splice(spliced_range, self.rows[new_range].iter().map(Right));
+            for removed_item in removed_items {
+                if let Left(((_id, removed_row), _row_widget)) = removed_item {
+                    removed_row.content.unmount();
+                }
+            }
+
+            index_diff += mutation_item.inserted_keys.len() as isize;
+            index_diff -= mutation_item.removed_count as isize;
+        }
+
+        let mut rows_changed = !mutation.items.is_empty();
+        let mut rows_to_insert = VecDeque::new();
+        for ((_id, row), row_prev_data) in self.rows.iter().zip(prev_data) {
+            match row_prev_data {
+                Left(((_id, prev_row), row_widget)) => {
+                    row.content
+                        .reconcile(&prev_row.content, &mut row_widget.content, ctx);
+                    if row.depth != prev_row.depth
+                        || row.expanded != prev_row.expanded
+                        || row.selected != prev_row.selected
+                    {
+                        row_widget.depth = row.depth;
+                        row_widget.expanded = row.expanded;
+                        row_widget.selected = row.selected;
+                        rows_changed = true;
+                    }
+                }
+                Right(_) => {
+                    let row_widget = debug_span!("init_tree").in_scope(|| {
+                        info!("creating row");
+                        TreeRowWidget::new(
+                            row.content.init_tree(),
+                            row.depth,
+                            row.expanded,
+                            row.selected,
+                        )
+                    });
+                    rows_to_insert.push_back(row_widget);
+                }
+            }
+        }
+
+        let mut index_diff = 0_isize;
+        for mutation_item in &mutation.items {
+            let index = (mutation_item.index as isize + index_diff) as usize;
+            let spliced_range = index..(index + mutation_item.removed_count);
+
+            // Calling .last() runs the entire iterator, which performs the splice
+            let _ = row_widgets
+                .splice(
+                    spliced_range,
+                    rows_to_insert.drain(0..mutation_item.inserted_keys.len()),
+                )
+                .last();
+
+            index_diff += mutation_item.inserted_keys.len() as isize;
+            index_diff -= mutation_item.removed_count as isize;
+        }
+
+        if rows_changed {
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                    ctx.request_paint();
+                });
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        // TODO - O(N * M) for N the row count and M the mutation count
+        let mut prev_rows = std::mem::take(&mut self.rows);
+        self.rows = other
+            .rows
+            .into_iter()
+            .map(|(id, row)| {
+                let prev_index = prev_rows.iter().position(|(prev_id, _)| *prev_id == id);
+                match prev_index {
+                    Some(prev_index) => {
+                        let (_, mut prev_row) = prev_rows.swap_remove(prev_index);
+                        prev_row.content.update_value(row.content);
+                        prev_row.depth = row.depth;
+                        prev_row.expanded = row.expanded;
+                        prev_row.selected = row.selected;
+                        (id, prev_row)
+                    }
+                    None => (id, row),
+                }
+            })
+            .collect();
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        for (_id, row) in &self.rows {
+            row.content.unmount();
+        }
+    }
+
+    #[instrument(
+        name = "TreeView",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        for (((_id, row), (_state_id, row_state)), row_widget) in self
+            .rows
+            .iter()
+            .zip(children_state.1.iter_mut())
+            .zip(widget_seq.widget_mut().rows.iter_mut())
+        {
+            row.content
+                .process_event(comp_ctx, row_state, &mut row_widget.content, cx);
+        }
+    }
+
+    #[instrument(name = "TreeView", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<TreeViewEvent> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        match cx.app_data.dequeue_action(id) {
+            Some(Action::Toggled(index)) => {
+                let (node_id, _row) = self.rows.get(index)?;
+                // The new expansion state is picked up by the next build
+                let expanded = &mut children_state.0;
+                if !expanded.remove(node_id) {
+                    expanded.insert(node_id.clone());
+                }
+                trace!("Processed node toggle: {:?}", node_id);
+                Some(TreeViewEvent::NodeToggled(node_id.clone()))
+            }
+            Some(Action::Selected(index)) => {
+                let (node_id, _row) = self.rows.get(index)?;
+                trace!("Processed node selection: {:?}", node_id);
+                Some(TreeViewEvent::NodeSelected(node_id.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::Button;
+    use crate::test_harness::Harness;
+    use druid::{MouseButton, Point, Vec2};
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn mock_tree() -> TreeView<MockComponent> {
+        TreeView::new(vec![
+            TreeNode::new("a", MockComponent::new()).with_children(vec![
+                TreeNode::new("a1", MockComponent::new()),
+                TreeNode::new("a2", MockComponent::new())
+                    .with_children(vec![TreeNode::new("a2x", MockComponent::new())]),
+            ]),
+            TreeNode::new("b", MockComponent::new()),
+        ])
+    }
+
+    fn row_ids<Child: VirtualDom>(tree_data: &TreeViewData<Child>) -> Vec<&str> {
+        tree_data
+            .rows
+            .iter()
+            .map(|(id, _row)| id.0.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn new_tree_view() {
+        let (tree_data, state) = mock_tree().build(Default::default());
+
+        assert_eq!(row_ids(&tree_data), vec!["a", "b"]);
+        assert_eq!(tree_data.rows[0].1.expanded, Some(false));
+        assert_eq!(tree_data.rows[1].1.expanded, None);
+        assert!(state.0.is_empty());
+    }
+
+    #[test]
+    fn expand_collapse_keeps_other_rows() {
+        let (_, mut state) = mock_tree().build(Default::default());
+        state.1[1].1 = MockState::new("b-state");
+
+        state.0.insert(NodeId::new("a"));
+        let (tree_data, mut state) = mock_tree().build(state);
+        assert_eq!(row_ids(&tree_data), vec!["a", "a1", "a2", "b"]);
+        assert_eq!(tree_data.rows[2].1.depth, 1);
+        assert_eq!(state.1[3].1, MockState::new("b-state"));
+
+        // Expanding a nested node only inserts its children
+        state.1[2].1 = MockState::new("a2-state");
+        let prev_tree_data = tree_data;
+        state.0.insert(NodeId::new("a2"));
+        let (tree_data, mut state) = mock_tree().build(state);
+        assert_eq!(row_ids(&tree_data), vec!["a", "a1", "a2", "a2x", "b"]);
+        let mutation = compute_diff(&prev_tree_data.rows, &tree_data.rows);
+        assert_eq!(mutation.items.len(), 1);
+        assert_eq!(mutation.items[0].removed_count, 0);
+        assert_eq!(mutation.items[0].inserted_keys, vec![NodeId::new("a2x")]);
+        assert_eq!(state.1[2].1, MockState::new("a2-state"));
+
+        // Collapsing the root removes its subtree, but remembers which nodes were expanded
+        state.0.remove(&NodeId::new("a"));
+        let (tree_data, mut state) = mock_tree().build(state);
+        assert_eq!(row_ids(&tree_data), vec!["a", "b"]);
+        assert_eq!(state.1[1].1, MockState::new("b-state"));
+
+        state.0.insert(NodeId::new("a"));
+        let (tree_data, _) = mock_tree().build(state);
+        assert_eq!(row_ids(&tree_data), vec!["a", "a1", "a2", "a2x", "b"]);
+    }
+
+    #[test]
+    fn tree_view_widget() {
+        let (event_sender, event_receiver) = channel();
+        let tree_id = WidgetId::reserved(1);
+        let tree = EventLogger::new(
+            event_sender,
+            TreeView::new(vec![
+                TreeNode::new("a", Button::new("A"))
+                    .with_children(vec![TreeNode::new("a1", Button::new("A1"))]),
+                TreeNode::new("b", Button::new("B")),
+            ])
+            .with_reserved_id(tree_id),
+        );
+
+        Harness::run_test_window(tree, |harness| {
+            assert_eq!(harness.get_debug_state(tree_id).children.len(), 2);
+
+            // The toggle arrow is just before the content of the row
+            let row_rect = harness.find_by_name("A").unwrap().layout_rect();
+            let arrow_pos = Point::new(row_rect.x0, row_rect.center().y) - Vec2::new(8.0, 0.0);
+            harness.mouse_move(arrow_pos);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(TreeViewEvent::NodeToggled(NodeId::new("a")))
+            );
+            assert_eq!(harness.get_debug_state(tree_id).children.len(), 3);
+            assert!(harness.find_by_name("A1").is_some());
+
+            harness.click_on("B");
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(TreeViewEvent::NodeSelected(NodeId::new("b")))
+            );
+
+            harness.mouse_move(arrow_pos);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(TreeViewEvent::NodeToggled(NodeId::new("a")))
+            );
+            assert_eq!(harness.get_debug_state(tree_id).children.len(), 2);
+            assert!(harness.find_by_name("A1").is_none());
+        });
+    }
+}
@@ -0,0 +1,322 @@
+use crate::ctx::{report_validity, ReconcileCtx};
+use crate::element_tree::{Element, ElementExt, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{Metadata, NoState};
+use crate::widgets::{SingleValidatedTextBoxWidget, ValidatedTextBoxWidget};
+
+use derivative::Derivative;
+use druid::EventCtx;
+use std::rc::Rc;
+use tracing::{instrument, trace};
+
+/// A text-editing box which checks its content, and displays an error message below the box
+/// when the content is invalid.
+///
+/// The validator returns `Err` with the message to display. The text is validated each time
+/// it's edited, and when the box loses focus. The error message is only displayed once the
+/// user has edited or left the box, so that an empty form doesn't start out covered in errors.
+///
+/// Like [TextBox](crate::elements::TextBox), the text should be bound to the component's
+/// state, since validation errors are computed from the text the element is built with.
+///
+/// ## Events
+///
+/// Emits [ValidatedChange] events.
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct ValidatedTextBox {
+    pub text: String,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub validator: Rc<dyn Fn(&str) -> Result<(), String>>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone, Debug)]
+pub struct ValidatedTextBoxData {
+    pub text: String,
+    pub error: Option<String>,
+    pub show_error: bool,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub validator: Rc<dyn Fn(&str) -> Result<(), String>>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+/// Event emitted when a [ValidatedTextBox] is edited or loses focus.
+///
+/// Holds the content of the box, and whether the validator accepted it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidatedChange {
+    pub text: String,
+    pub valid: bool,
+}
+
+//
+// --- IMPLS
+
+impl ValidatedTextBox {
+    /// Build a text box with the given content, checked by `validator`.
+    ///
+    /// Use the [.on_change](ValidatedTextBox::on_change) method to provide a closure to be called when the box is edited.
+    pub fn new(
+        text: impl Into<String>,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        ValidatedTextBox {
+            text: text.into(),
+            validator: Rc::new(validator),
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+        }
+    }
+
+    /// Change the way the box's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        ValidatedTextBox {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The text box created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same text box is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        ValidatedTextBox {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+
+    /// Provide a closure to be called when this box is edited or loses focus.
+    pub fn on_change<ComponentEvent: 'static, ComponentState: 'static>(
+        self,
+        md: Metadata<ComponentEvent, ComponentState>,
+        callback: impl Fn(&mut ComponentState, ValidatedChange) + Clone + 'static,
+    ) -> impl Element {
+        self.on(md, callback)
+    }
+}
+
+impl ValidatedTextBoxData {
+    fn validate(&self, text: &str) -> bool {
+        (self.validator)(text).is_ok()
+    }
+
+    fn displayed_error(&self) -> Option<String> {
+        if self.show_error {
+            self.error.clone()
+        } else {
+            None
+        }
+    }
+}
+
+impl Element for ValidatedTextBox {
+    type Event = ValidatedChange;
+
+    type ComponentState = NoState;
+    // Whether the user has edited or left the box yet
+    type AggregateChildrenState = bool;
+    type BuildOutput = ValidatedTextBoxData;
+
+    #[instrument(name = "ValidatedTextBox", skip(self, prev_state))]
+    fn build(self, prev_state: bool) -> (ValidatedTextBoxData, bool) {
+        let error = (self.validator)(&self.text).err();
+        report_validity(error.is_none());
+        (
+            ValidatedTextBoxData {
+                text: self.text,
+                error,
+                show_error: prev_state,
+                validator: self.validator,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            prev_state,
+        )
+    }
+}
+
+impl VirtualDom for ValidatedTextBoxData {
+    type Event = ValidatedChange;
+    type AggregateChildrenState = bool;
+    type TargetWidgetSeq = SingleValidatedTextBoxWidget;
+
+    #[instrument(name = "ValidatedTextBox", skip(self))]
+    fn init_tree(&self) -> SingleValidatedTextBoxWidget {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let textbox = ValidatedTextBoxWidget::new(self.text.clone(), self.displayed_error(), id);
+        SingleValidatedTextBoxWidget::new(textbox, self.flex)
+    }
+
+    #[instrument(name = "ValidatedTextBox", skip(self, _prev_value, widget, ctx))]
+    fn reconcile(
+        &self,
+        _prev_value: &Self,
+        widget: &mut SingleValidatedTextBoxWidget,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget.flex = self.flex;
+
+        // The widget's text may have been edited since the last build
+        if self.text != widget.widget().text() {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ValidatedTextBoxWidget, ctx: &mut EventCtx| {
+                    widget.set_text(self.text.clone(), ctx);
+                },
+            );
+        }
+
+        let error = self.displayed_error();
+        if error != widget.widget().error {
+            widget.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut ValidatedTextBoxWidget, ctx: &mut EventCtx| {
+                    widget.set_error(error.clone(), ctx);
+                },
+            );
+        }
+    }
+
+    #[instrument(name = "ValidatedTextBox", skip(self, children_state, widget, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut bool,
+        widget: &mut SingleValidatedTextBoxWidget,
+        cx: &mut GlobalEventCx,
+    ) -> Option<ValidatedChange> {
+        // FIXME - Rework event dispatching
+        let id = widget.widget().id();
+        let text = match cx.app_data.dequeue_action(id) {
+            Some(Action::TextChanged(text)) => text,
+            Some(Action::FocusChanged(false)) => widget.widget().text().to_string(),
+            _ => return None,
+        };
+        *children_state = true;
+        let valid = self.validate(&text);
+        trace!("Validated text {:?}: valid={}", text, valid);
+        Some(ValidatedChange { text, valid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::event_logger::EventLogger;
+    use crate::elements::TextBox;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::KbKey;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    fn validate_email(text: &str) -> Result<(), String> {
+        if text.contains('@') {
+            Ok(())
+        } else {
+            Err(String::from("Not an email"))
+        }
+    }
+
+    #[test]
+    fn new_validated_textbox() {
+        let (valid_data, touched) =
+            ValidatedTextBox::new("me@example.com", validate_email).build(false);
+        assert_eq!(valid_data.error, None);
+        assert!(!touched);
+
+        let (invalid_data, _) = ValidatedTextBox::new("me", validate_email).build(false);
+        assert_eq!(invalid_data.error, Some(String::from("Not an email")));
+        assert_eq!(invalid_data.displayed_error(), None);
+
+        let (touched_data, _) = ValidatedTextBox::new("me", validate_email).build(true);
+        assert_eq!(
+            touched_data.displayed_error(),
+            Some(String::from("Not an email"))
+        );
+    }
+
+    #[test]
+    fn validated_textbox_input() {
+        let (event_sender, event_receiver) = channel();
+        let textbox = |text: &str| {
+            EventLogger::new(
+                event_sender.clone(),
+                ValidatedTextBox::new(text, validate_email),
+            )
+        };
+
+        Harness::run_test_window(textbox("me"), |harness| {
+            // The error isn't displayed before the box is edited
+            assert!(harness.find_by_name("Not an email").is_none());
+
+            harness.click_on("me");
+            harness.press_key(KbKey::Character("@".to_string()));
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(ValidatedChange {
+                    text: String::from("me@"),
+                    valid: true,
+                })
+            );
+            harness.update_root_element(textbox("me@"));
+            assert!(harness.find_by_name("Not an email").is_none());
+
+            harness.press_key(KbKey::Backspace);
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(ValidatedChange {
+                    text: String::from("me"),
+                    valid: false,
+                })
+            );
+            harness.update_root_element(textbox("me"));
+            let error_state = harness.find_by_name("Not an email").unwrap().debug_state();
+            assert_eq!(error_state.display_name, "ValidationError");
+
+            // Fixing the text hides the error
+            harness.update_root_element(textbox("me@example.com"));
+            assert!(harness.find_by_name("Not an email").is_none());
+        });
+    }
+
+    #[test]
+    fn validated_textbox_blur() {
+        let (event_sender, event_receiver) = channel();
+        let md: Metadata<crate::NoEvent, ()> = Default::default();
+        let form = Column!(
+            ValidatedTextBox::new("me", validate_email).on_change(md, move |_state, event| {
+                event_sender.send(event).unwrap();
+            }),
+            TextBox::new("other"),
+        );
+
+        Harness::run_test_window(form, |harness| {
+            harness.click_on("me");
+            assert_eq!(event_receiver.try_recv().ok(), None);
+            assert!(harness.find_by_name("Not an email").is_none());
+
+            // Leaving the box validates it without editing it
+            harness.click_on("other");
+            assert_eq!(
+                event_receiver.try_recv(),
+                Ok(ValidatedChange {
+                    text: String::from("me"),
+                    valid: false,
+                })
+            );
+            assert!(harness.find_by_name("Not an email").is_some());
+        });
+    }
+}
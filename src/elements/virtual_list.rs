@@ -0,0 +1,372 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::elements::with_event::format_typename;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleVirtualListWidget, VirtualListWidget};
+
+use derivative::Derivative;
+use druid::EventCtx;
+use std::collections::HashMap;
+use std::ops::Range;
+use tracing::{debug_span, info, instrument, trace};
+
+/// Number of items built before the list has been laid out.
+const INITIAL_ITEM_COUNT: usize = 20;
+
+/// A scrolling list which only builds the items it displays.
+///
+/// The list has `count` items, all `item_height` tall; the item at a given index is created
+/// by calling `builder` with that index. Only the visible items, and a few items above and
+/// below them, are built and laid out. When the list is scrolled, items which stay visible
+/// keep their widgets, and items which are scrolled out of view are dropped, along with
+/// their local state.
+///
+/// The list scrolls vertically, and fills the height it's given.
+///
+/// ## Events
+///
+/// Doesn't emit events.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct VirtualList<Child: Element, Builder: Fn(usize) -> Child + Clone + 'static> {
+    pub count: usize,
+    pub item_height: f64,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub builder: Builder,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+    #[derivative(Debug = "ignore")]
+    pub _marker: std::marker::PhantomData<Child>,
+}
+
+/// Virtual DOM node of [`VirtualList`], holding the built items and their index.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VirtualListData<Child: VirtualDom> {
+    pub count: usize,
+    pub item_height: f64,
+    pub visible_range: Range<usize>,
+    pub items: Vec<(usize, Child)>,
+    pub flex: FlexParams,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element, Builder: Fn(usize) -> Child + Clone + 'static> VirtualList<Child, Builder> {
+    /// Build a list of `count` items of height `item_height`, created by `builder`.
+    pub fn new(count: usize, item_height: f64, builder: Builder) -> Self {
+        VirtualList {
+            count,
+            item_height,
+            builder,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            reserved_widget_id: None,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Change the way the list's size is calculated
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        VirtualList {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        VirtualList {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element, Builder: Fn(usize) -> Child + Clone + 'static> Element
+    for VirtualList<Child, Builder>
+{
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    // The range of built items, once the list has been laid out, and the state of each item
+    type AggregateChildrenState = (
+        Option<Range<usize>>,
+        Vec<(usize, Child::AggregateChildrenState)>,
+    );
+    type BuildOutput = VirtualListData<Child::BuildOutput>;
+
+    #[instrument(name = "VirtualList", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (visible_range, prev_items_state) = prev_state;
+        let range = visible_range.clone().unwrap_or(0..INITIAL_ITEM_COUNT);
+        let range = range.start.min(self.count)..range.end.min(self.count);
+
+        let mut prev_items_state: HashMap<_, _> = prev_items_state.into_iter().collect();
+        let builder = &self.builder;
+        let (items, items_state): (Vec<_>, Vec<_>) = range
+            .clone()
+            .map(|index| {
+                let item_prev_state = prev_items_state.remove(&index).unwrap_or_default();
+                let (item, item_state) = builder(index).build(item_prev_state);
+                ((index, item), (index, item_state))
+            })
+            .unzip();
+
+        (
+            VirtualListData {
+                count: self.count,
+                item_height: self.item_height,
+                visible_range: range,
+                items,
+                flex: self.flex,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            (visible_range, items_state),
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for VirtualListData<Child> {
+    type Event = NoEvent;
+    type AggregateChildrenState = (
+        Option<Range<usize>>,
+        Vec<(usize, Child::AggregateChildrenState)>,
+    );
+    type TargetWidgetSeq = SingleVirtualListWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "VirtualList", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        let items = self
+            .items
+            .iter()
+            .map(|(index, item)| (*index, item.init_tree()))
+            .collect();
+        SingleVirtualListWidget::new(
+            VirtualListWidget::new(
+                self.count,
+                self.item_height,
+                items,
+                self.visible_range.clone(),
+                id,
+            ),
+            self.flex,
+        )
+    }
+
+    // Items are matched by index, so that items which stay visible keep their widgets
+    #[instrument(name = "VirtualList", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        widget_seq.flex = self.flex;
+        let list_widget = widget_seq.widget_mut();
+
+        let mut prev_items: HashMap<_, _> = prev_value
+            .items
+            .iter()
+            .zip(std::mem::take(&mut list_widget.items))
+            .map(|((index, prev_item), (_index, item_widget))| (*index, (prev_item, item_widget)))
+            .collect();
+
+        let mut items_changed = false;
+        list_widget.items = self
+            .items
+            .iter()
+            .map(|(index, item)| match prev_items.remove(index) {
+                Some((prev_item, mut item_widget)) => {
+                    item.reconcile(prev_item, &mut item_widget, ctx);
+                    ctx.stats.updated += 1;
+                    (*index, item_widget)
+                }
+                None => {
+                    items_changed = true;
+                    ctx.stats.created += 1;
+                    debug_span!("init_tree").in_scope(|| {
+                        info!("creating item {}", index);
+                        (*index, item.init_tree())
+                    })
+                }
+            })
+            .collect();
+
+        // Items which weren't matched have been scrolled out of view
+        for (index, prev_item) in &prev_value.items {
+            if prev_items.contains_key(index) {
+                items_changed = true;
+                prev_item.unmount();
+                ctx.stats.removed += 1;
+            }
+        }
+
+        let size_changed =
+            self.count != prev_value.count || self.item_height != prev_value.item_height;
+        list_widget.count = self.count;
+        list_widget.item_height = self.item_height;
+
+        if items_changed || size_changed {
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut VirtualListWidget<_>, ctx: &mut EventCtx| {
+                    if size_changed {
+                        widget.refresh_visible_range(ctx);
+                    }
+                    ctx.children_changed();
+                    ctx.request_layout();
+                },
+            );
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        let mut prev_items: HashMap<_, _> = std::mem::take(&mut self.items).into_iter().collect();
+        self.items = other
+            .items
+            .into_iter()
+            .map(|(index, item)| match prev_items.remove(&index) {
+                Some(mut prev_item) => {
+                    prev_item.update_value(item);
+                    (index, prev_item)
+                }
+                None => (index, item),
+            })
+            .collect();
+        self.count = other.count;
+        self.item_height = other.item_height;
+        self.visible_range = other.visible_range;
+        self.flex = other.flex;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        for (_index, item) in &self.items {
+            item.unmount();
+        }
+    }
+
+    #[instrument(
+        name = "VirtualList",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        for (((_index, item), (_state_index, item_state)), (_widget_index, item_widget)) in self
+            .items
+            .iter()
+            .zip(children_state.1.iter_mut())
+            .zip(widget_seq.widget_mut().items.iter_mut())
+        {
+            item.process_event(comp_ctx, item_state, item_widget, cx);
+        }
+    }
+
+    #[instrument(name = "VirtualList", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<NoEvent> {
+        // FIXME - Rework event dispatching
+        let id = widget_seq.widget().id();
+        if let Some(Action::VisibleRangeChanged(visible_range)) = cx.app_data.dequeue_action(id) {
+            // The new items are built the next time the element is built
+            trace!("Visible range changed: {:?}", visible_range);
+            children_state.0 = Some(visible_range);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::internals::{MockComponent, MockState};
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    fn indices<Child: VirtualDom>(list_data: &VirtualListData<Child>) -> Vec<usize> {
+        list_data
+            .items
+            .iter()
+            .map(|(index, _item)| *index)
+            .collect()
+    }
+
+    #[test]
+    fn new_virtual_list() {
+        let (list_data, _) =
+            VirtualList::new(1000, 20.0, |_| MockComponent::new()).build(Default::default());
+        assert_eq!(
+            indices(&list_data),
+            (0..INITIAL_ITEM_COUNT).collect::<Vec<_>>()
+        );
+
+        let (list_data, _) =
+            VirtualList::new(3, 20.0, |_| MockComponent::new()).build(Default::default());
+        assert_eq!(indices(&list_data), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn virtual_list_window() {
+        let list = VirtualList::new(1000, 20.0, |_| MockComponent::new());
+        let (_, (_, mut items_state)) = list.clone().build((Some(0..30), Vec::new()));
+        items_state[10].1 = MockState::new("item-10");
+        items_state[2].1 = MockState::new("item-2");
+
+        // Only the new window is built; items which stay visible keep their state
+        let (list_data, (_, items_state)) = list.clone().build((Some(5..35), items_state));
+        assert_eq!(indices(&list_data), (5..35).collect::<Vec<_>>());
+        assert_eq!(items_state[5], (10, MockState::new("item-10")));
+
+        // Items scrolled out of view lose their state
+        let (_, (_, items_state)) = list.build((Some(0..30), items_state));
+        assert_eq!(items_state[2], (2, MockState::default()));
+        assert_eq!(items_state[10], (10, MockState::new("item-10")));
+    }
+
+    #[test]
+    fn virtual_list_scroll() {
+        let list_id = WidgetId::reserved(1);
+        let list = VirtualList::new(1000, 20.0, |index| Label::new(format!("Item {}", index)))
+            .with_reserved_id(list_id);
+
+        Harness::run_test_window(list, |harness| {
+            assert!(harness.find_by_name("Item 0").is_some());
+            assert!(harness.find_by_name("Item 100").is_none());
+
+            // The item at the top of the viewport is 1000 / 20 = 50
+            harness.mouse_move_to(list_id);
+            harness.mouse_wheel((0.0, 1000.0));
+            assert!(harness.find_by_name("Item 50").is_some());
+            assert!(harness.find_by_name("Item 45").is_some());
+            assert!(harness.find_by_name("Item 44").is_none());
+            assert!(harness.find_by_name("Item 0").is_none());
+            assert!(harness.find_by_name("Item 999").is_none());
+
+            let built_count = harness.get_debug_state(list_id).children.len();
+            assert!(built_count < 100);
+        });
+    }
+}
@@ -0,0 +1,210 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleVisibilityWidget, VisibilityWidget};
+
+use druid::EventCtx;
+use tracing::instrument;
+
+/// Wrapper which shows or hides its child without removing it.
+///
+/// A hidden child takes no space, isn't painted, and doesn't get mouse or keyboard events.
+/// Unlike with [`If`](crate::elements::If), its widgets stay in the tree, so they keep their
+/// internal state, eg the content of a text box or the position of a scroll area, and its
+/// local state is kept too.
+///
+/// Usually created with [`ElementExt::visible`](crate::ElementExt::visible).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Visibility<Child: Element> {
+    pub child: Child,
+    pub visible: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VisibilityData<Child: VirtualDom> {
+    pub child: Child,
+    pub visible: bool,
+    pub reserved_widget_id: Option<WidgetId>,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> Visibility<Child> {
+    pub fn new(child: Child, visible: bool) -> Self {
+        Visibility {
+            child,
+            visible,
+            reserved_widget_id: None,
+        }
+    }
+
+    /// For unit tests only.
+    ///
+    /// The widget created by this element always has the same id. If two widgets are created
+    /// with the same id (for instance, because the same widget is returned twice because of
+    /// a copy-paste error), impredictable behavior may ensue.
+    pub fn with_reserved_id(self, widget_id: WidgetId) -> Self {
+        Visibility {
+            reserved_widget_id: Some(widget_id),
+            ..self
+        }
+    }
+}
+
+impl<Child: Element> Element for Visibility<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = VisibilityData<Child::BuildOutput>;
+
+    #[instrument(name = "Visibility", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (
+            VisibilityData {
+                child,
+                visible: self.visible,
+                reserved_widget_id: self.reserved_widget_id,
+            },
+            state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for VisibilityData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleVisibilityWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "Visibility", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let id = self.reserved_widget_id.unwrap_or_else(WidgetId::next);
+        SingleVisibilityWidget::new(
+            VisibilityWidget::new(self.child.init_tree(), self.visible, id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "Visibility", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.visible != prev_value.visible {
+            let visible = self.visible;
+            widget_seq.pod.with_event_context(
+                ctx.event_ctx,
+                |widget: &mut VisibilityWidget<_>, ctx: &mut EventCtx| {
+                    widget.set_visible(visible, ctx);
+                },
+            );
+        }
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.visible = other.visible;
+        self.reserved_widget_id = other.reserved_widget_id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(
+        name = "Visibility",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "Visibility", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{Label, TextBox};
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::{KbKey, Size};
+    use test_env_log::test;
+
+    #[test]
+    fn visibility_widget() {
+        let visibility_id = WidgetId::reserved(1);
+        let textbox_id = WidgetId::reserved(2);
+        let column = |visible: bool, text: &str| {
+            Column!(
+                Label::new("Header"),
+                TextBox::new(text)
+                    .with_reserved_id(textbox_id)
+                    .visible(visible)
+                    .with_reserved_id(visibility_id),
+            )
+        };
+
+        Harness::run_test_window(column(true, "Hello"), |harness| {
+            harness.type_text("Hello", "!");
+            assert!(harness.find_by_name("Hello!").is_some());
+
+            // Hidden children take no space, and can't be found or typed into
+            harness.update_root_element(column(false, "Hello!"));
+            let rect = harness.get_state(visibility_id).layout_rect();
+            assert_eq!(rect.size(), Size::ZERO);
+            assert!(harness.find_by_name("Hello!").is_none());
+            harness.press_key(KbKey::Character("?".to_string()));
+
+            // The text box is still the same widget, with its focus and cursor
+            harness.update_root_element(column(true, "Hello!"));
+            let rect = harness.get_state(visibility_id).layout_rect();
+            assert_ne!(rect.size(), Size::ZERO);
+            harness.press_key(KbKey::Character("?".to_string()));
+            assert!(harness.find_by_name("Hello!?").is_some());
+        });
+    }
+}
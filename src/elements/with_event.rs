@@ -1,29 +1,55 @@
 use crate::ctx::{ProcessEventCtx, ReconcileCtx};
 use crate::element_tree::{Element, VirtualDom};
-use crate::glue::GlobalEventCx;
+use crate::elements::ClickEvent;
+use crate::flex::FlexParams;
+use crate::glue::{Action, GlobalEventCx, WidgetId};
 use crate::metadata::{Metadata, NoState};
+use crate::widgets::{
+    ClickAreaWidget, DoubleClickWidget, FocusWidget, HoverWidget, KeyListenerWidget,
+    LongPressWidget, MouseMoveWidget, PasteWidget, RightClickWidget, SingleClickAreaWidget,
+    SingleDoubleClickWidget, SingleFocusWidget, SingleHoverWidget, SingleKeyListenerWidget,
+    SingleLongPressWidget, SingleMouseMoveWidget, SinglePasteWidget, SingleRightClickWidget,
+};
 
 use derivative::Derivative;
+use druid::{KbKey, Modifiers, Point};
+use std::any::{type_name, Any, TypeId};
+use std::time::{Duration, Instant};
 use tracing::{instrument, trace};
 
+/// Maximum delay between the two clicks of a double click.
+pub const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 // UTILITY TRAITS ---
 
-pub trait OptionOrUnit<T> {
-    fn to_option(self) -> Option<T>;
+/// Return types of event callbacks: `()` for no parent event, `Option` for at most one, and
+/// `Vec` for any number.
+pub trait CallbackReturn<T> {
+    type Events: IntoIterator<Item = T>;
+    fn into_events(self) -> Self::Events;
 }
 
-impl<T> OptionOrUnit<T> for Option<T> {
-    fn to_option(self) -> Option<T> {
+impl<T> CallbackReturn<T> for Option<T> {
+    type Events = Option<T>;
+    fn into_events(self) -> Option<T> {
         self
     }
 }
 
-impl<T> OptionOrUnit<T> for () {
-    fn to_option(self) -> Option<T> {
+impl<T> CallbackReturn<T> for () {
+    type Events = Option<T>;
+    fn into_events(self) -> Option<T> {
         None
     }
 }
 
+impl<T> CallbackReturn<T> for Vec<T> {
+    type Events = Vec<T>;
+    fn into_events(self) -> Vec<T> {
+        self
+    }
+}
+
 /// TODO - Document this
 pub trait ParentEvent<Child> {
     fn from_child_event(child: Child) -> Self;
@@ -44,7 +70,10 @@ where
     }
 }
 
-fn format_typename<T>(_value: &T, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+pub(crate) fn format_typename<T>(
+    _value: &T,
+    f: &mut std::fmt::Formatter,
+) -> Result<(), std::fmt::Error> {
     f.write_fmt(format_args!("{}", &std::any::type_name::<T>()))
 }
 
@@ -102,6 +131,32 @@ pub struct WithMapEvent<
     pub _marker: std::marker::PhantomData<EventParam>,
 }
 
+/// Maps events of child element into any number of events of parent component, using
+/// provided map function.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithFlatMapEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    EventParam,
+    EventReturn,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, EventParam) -> Vec<EventReturn>,
+> where
+    Child::Event: ParentEvent<EventParam>,
+    ComponentEvent: ParentEvent<EventReturn>,
+{
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(Debug = "ignore")]
+    pub _marker: std::marker::PhantomData<EventParam>,
+}
+
 /// Transfers events of child element to parent component.
 ///
 /// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
@@ -119,6 +174,227 @@ where
     pub _marker: std::marker::PhantomData<Event>,
 }
 
+/// Passes the raw events of child element to a callback, which may return an event of the
+/// parent component.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithAnyEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, Child::Event) -> Option<ComponentEvent>,
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Runs the callbacks of child element against part of the parent component's state.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithMappedState<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    SubState: 'static,
+    Child: Element,
+    Project: Clone + Fn(&mut ComponentState) -> &mut SubState,
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub project: Project,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(Debug = "ignore")]
+    pub _marker: std::marker::PhantomData<SubState>,
+}
+
+/// Calls callback when a key is pressed while the child element has focus.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithKeyEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, KeyDown),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback with the text in the clipboard when it's pasted while the child element has
+/// focus.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithPasteEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, String),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the mouse pointer enters or leaves the child element.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithHoverEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, bool),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the child element is clicked twice in a row.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithDoubleClickEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, DoubleClick),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the pointer is held down over the child element for a given duration.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithLongPressEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, LongPress),
+> {
+    pub element: Child,
+    pub duration: Duration,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the child element is clicked with the secondary mouse button.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithRightClickEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, Point),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback with the position of the mouse pointer when it moves over the child element.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithMouseMoveEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, Point),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the child element is clicked, without changing its appearance.
+///
+/// Clicks handled by the child's own widgets, eg an inner button, don't call the callback.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct ClickableArea<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, ClickEvent),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Calls callback when the child element gains or loses keyboard focus.
+///
+/// For internal use only. Library users should use [ElementExt](crate::ElementExt) instead.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""), Debug(bound = ""))]
+pub struct WithFocusEvent<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: Element,
+    Cb: Clone + Fn(&mut ComponentState, bool),
+> {
+    pub element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    pub callback: Cb,
+    #[derivative(Debug = "ignore")]
+    pub _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+/// Event passed to [`ElementExt::on_key_down`](crate::ElementExt::on_key_down) callbacks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyDown {
+    pub key: KbKey,
+    pub mods: Modifiers,
+}
+
+/// Event passed to [`ElementExt::on_double_click`](crate::ElementExt::on_double_click) callbacks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DoubleClick;
+
+/// Event passed to [`ElementExt::on_long_press`](crate::ElementExt::on_long_press) callbacks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LongPress;
+
 #[derive(Derivative)]
 #[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
 pub struct WithEventTarget<
@@ -126,7 +402,7 @@ pub struct WithEventTarget<
     ComponentState: 'static,
     EventParam,
     EventReturn,
-    CbReturn: OptionOrUnit<EventReturn>,
+    CbReturn: CallbackReturn<EventReturn>,
     Child: VirtualDom,
     Cb: Clone + Fn(&mut ComponentState, EventParam) -> CbReturn,
 > where
@@ -142,58 +418,228 @@ pub struct WithEventTarget<
     _marker: std::marker::PhantomData<(EventParam, EventReturn)>,
 }
 
-// ---
-
-impl<
-        ComponentEvent: 'static,
-        ComponentState: 'static,
-        EventParam: 'static,
-        Child: Element,
-        Cb: Clone + Fn(&mut ComponentState, EventParam) + 'static,
-    > Element for WithCallbackEvent<ComponentEvent, ComponentState, EventParam, Child, Cb>
-where
-    Child::Event: ParentEvent<EventParam>,
-{
-    type Event = Child::Event;
-    type ComponentState = NoState;
-    type AggregateChildrenState = Child::AggregateChildrenState;
-    type BuildOutput = WithEventTarget<
-        ComponentEvent,
-        ComponentState,
-        EventParam,
-        ComponentEvent,
-        (),
-        Child::BuildOutput,
-        Cb,
-    >;
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithAnyEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, Child::Event) -> Option<ComponentEvent>,
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
 
-    #[instrument(name = "WithEvent", skip(self, prev_state))]
-    fn build(
-        self,
-        prev_state: Self::AggregateChildrenState,
-    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
-        let (element, state) = self.element.build(prev_state);
-        (
-            WithEventTarget {
-                element,
-                callback: self.callback,
-                _metadata: Default::default(),
-                _marker: Default::default(),
-            },
-            state,
-        )
-    }
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithMappedStateTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    SubState: 'static,
+    Child: VirtualDom,
+    Project: Clone + Fn(&mut ComponentState) -> &mut SubState,
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    project: Project,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+    #[derivative(Debug = "ignore")]
+    _marker: std::marker::PhantomData<SubState>,
 }
 
-impl<
-        ComponentEvent: 'static,
-        ComponentState: 'static,
-        EventParam: 'static,
-        EventReturn: 'static,
-        Child: Element,
-        Cb: Clone + Fn(&mut ComponentState, EventParam) -> Option<EventReturn> + 'static,
-    > Element for WithMapEvent<ComponentEvent, ComponentState, EventParam, EventReturn, Child, Cb>
-where
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithKeyEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, KeyDown),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithPasteEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, String),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithHoverEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, bool),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithDoubleClickEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, DoubleClick),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithLongPressEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, LongPress),
+> {
+    element: Child,
+    duration: Duration,
+    deadline: Option<Instant>,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithRightClickEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, Point),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithMouseMoveEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, Point),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct ClickableAreaTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, ClickEvent),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Child: Clone"), Debug(bound = ""))]
+pub struct WithFocusEventTarget<
+    ComponentEvent: 'static,
+    ComponentState: 'static,
+    Child: VirtualDom,
+    Cb: Clone + Fn(&mut ComponentState, bool),
+> {
+    element: Child,
+    #[derivative(Debug(format_with = "format_typename"))]
+    callback: Cb,
+    #[derivative(Debug = "ignore")]
+    _metadata: Metadata<ComponentEvent, ComponentState>,
+}
+
+// ---
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        EventParam: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, EventParam) + 'static,
+    > Element for WithCallbackEvent<ComponentEvent, ComponentState, EventParam, Child, Cb>
+where
+    Child::Event: ParentEvent<EventParam>,
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithEventTarget<
+        ComponentEvent,
+        ComponentState,
+        EventParam,
+        ComponentEvent,
+        (),
+        Child::BuildOutput,
+        Cb,
+    >;
+
+    #[instrument(name = "WithEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+                _marker: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        EventParam: 'static,
+        EventReturn: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, EventParam) -> Option<EventReturn> + 'static,
+    > Element for WithMapEvent<ComponentEvent, ComponentState, EventParam, EventReturn, Child, Cb>
+where
     Child::Event: ParentEvent<EventParam>,
     ComponentEvent: ParentEvent<EventReturn>,
 {
@@ -228,6 +674,50 @@ where
     }
 }
 
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        EventParam: 'static,
+        EventReturn: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, EventParam) -> Vec<EventReturn> + 'static,
+    > Element
+    for WithFlatMapEvent<ComponentEvent, ComponentState, EventParam, EventReturn, Child, Cb>
+where
+    Child::Event: ParentEvent<EventParam>,
+    ComponentEvent: ParentEvent<EventReturn>,
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithEventTarget<
+        ComponentEvent,
+        ComponentState,
+        EventParam,
+        EventReturn,
+        Vec<EventReturn>,
+        Child::BuildOutput,
+        Cb,
+    >;
+
+    #[instrument(name = "WithEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+                _marker: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
 impl<ComponentEvent: 'static, ComponentState: 'static, Event: 'static, Child: Element> Element
     for WithBubbleEvent<ComponentEvent, ComponentState, Event, Child>
 where
@@ -266,12 +756,78 @@ where
     }
 }
 
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, Child::Event) -> Option<ComponentEvent> + 'static,
+    > Element for WithAnyEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithAnyEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithAnyEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithAnyEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        SubState: 'static,
+        Child: Element,
+        Project: Clone + Fn(&mut ComponentState) -> &mut SubState + 'static,
+    > Element for WithMappedState<ComponentEvent, ComponentState, SubState, Child, Project>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithMappedStateTarget<
+        ComponentEvent,
+        ComponentState,
+        SubState,
+        Child::BuildOutput,
+        Project,
+    >;
+
+    #[instrument(name = "MapState", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithMappedStateTarget {
+                element,
+                project: self.project,
+                _metadata: Default::default(),
+                _marker: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
 impl<
         ComponentEvent: 'static,
         ComponentState: 'static,
         EventParam,
         EventReturn,
-        CbReturn: OptionOrUnit<EventReturn>,
+        CbReturn: CallbackReturn<EventReturn>,
         Child: VirtualDom,
         Cb: Clone + Fn(&mut ComponentState, EventParam) -> CbReturn,
     > VirtualDom
@@ -307,6 +863,15 @@ where
         self.element.reconcile(&prev_value.element, widget_seq, ctx)
     }
 
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
     #[instrument(
         name = "WithEvent",
         skip(self, comp_ctx, children_state, widget_seq, cx)
@@ -336,16 +901,1685 @@ where
             .process_local_event(children_state, widget_seq, cx);
         if let Some(local_event) = local_event.map(ParentEvent::into_child_event).flatten() {
             trace!("Processing callback for local event");
-            let event = (self.callback)(comp_ctx.state(md), local_event)
-                .to_option()
-                .map(ComponentEvent::from_child_event);
-            if let Some(event) = event {
+            let events = (self.callback)(comp_ctx.state(md), local_event).into_events();
+            for event in events {
                 // TODO - Log event
                 trace!("Callback returned event");
-                comp_ctx.event_queue(md).push(event);
+                comp_ctx
+                    .event_queue(md)
+                    .push_back(ComponentEvent::from_child_event(event));
             }
         }
     }
 }
 
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, Child::Event) -> Option<ComponentEvent>,
+    > VirtualDom for WithAnyEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = Child::TargetWidgetSeq;
+
+    #[instrument(name = "WithAnyEvent", skip(self))]
+    fn init_tree(&self) -> Child::TargetWidgetSeq {
+        self.element.init_tree()
+    }
+
+    #[instrument(name = "WithAnyEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(&prev_value.element, widget_seq, ctx)
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithAnyEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element
+            .process_event(comp_ctx, children_state, widget_seq, cx);
+
+        // Unlike WithEventTarget, every event of the child is given to the callback, which
+        // decides whether the component emits something.
+        let md = self._metadata;
+        while let Some(local_event) =
+            self.element
+                .process_local_event(children_state, widget_seq, cx)
+        {
+            trace!("Processing callback for raw event");
+            if let Some(event) = (self.callback)(comp_ctx.state(md), local_event) {
+                trace!("Callback returned event");
+                comp_ctx.event_queue(md).push_back(event);
+            } else {
+                trace!("Callback swallowed event");
+            }
+        }
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        SubState: 'static,
+        Child: VirtualDom,
+        Project: Clone + Fn(&mut ComponentState) -> &mut SubState,
+    > VirtualDom
+    for WithMappedStateTarget<ComponentEvent, ComponentState, SubState, Child, Project>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = Child::TargetWidgetSeq;
+
+    #[instrument(name = "MapState", skip(self))]
+    fn init_tree(&self) -> Child::TargetWidgetSeq {
+        self.element.init_tree()
+    }
+
+    #[instrument(name = "MapState", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(&prev_value.element, widget_seq, ctx)
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.project = other.project;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "MapState",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        // Callbacks of the child element see the projected state, and share the event queue
+        // of the component.
+        let type_id = (*comp_ctx.state).type_id();
+        let state = comp_ctx
+            .state
+            .downcast_mut::<ComponentState>()
+            .expect(&format!(
+                "internal type error: event handler expected {:?} ({}), parent component gave {:?}",
+                TypeId::of::<ComponentState>(),
+                type_name::<ComponentState>(),
+                type_id,
+            ));
+        let mut sub_ctx = ProcessEventCtx {
+            event_queue: &mut *comp_ctx.event_queue,
+            state: (self.project)(state),
+        };
+        self.element
+            .process_event(&mut sub_ctx, children_state, widget_seq, cx);
+    }
+
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, widget_seq, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, KeyDown) + 'static,
+    > Element for WithKeyEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithKeyEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithKeyEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithKeyEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, KeyDown),
+    > VirtualDom for WithKeyEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleKeyListenerWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithKeyEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleKeyListenerWidget::new(
+            KeyListenerWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithKeyEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithKeyEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::KeyDown(key_down)) = cx.app_data.dequeue_action(id) {
+            trace!("Processing callback for key event");
+            (self.callback)(comp_ctx.state(md), key_down);
+        }
+    }
+
+    #[instrument(name = "WithKeyEvent", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, String) + 'static,
+    > Element for WithPasteEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithPasteEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithPasteEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithPasteEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, String),
+    > VirtualDom for WithPasteEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SinglePasteWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithPasteEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SinglePasteWidget::new(
+            PasteWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithPasteEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithPasteEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::Pasted(text)) = cx.app_data.dequeue_action(id) {
+            trace!("Processing callback for paste event");
+            (self.callback)(comp_ctx.state(md), text);
+        }
+    }
+
+    #[instrument(name = "WithPasteEvent", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, bool) + 'static,
+    > Element for WithHoverEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The first member is whether the pointer was over the element last time we checked
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type BuildOutput = WithHoverEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithHoverEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (is_hovered, child_prev_state) = prev_state;
+        let (element, state) = self.element.build(child_prev_state);
+        (
+            WithHoverEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            (is_hovered, state),
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, bool),
+    > VirtualDom for WithHoverEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type TargetWidgetSeq = SingleHoverWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithHoverEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleHoverWidget::new(
+            HoverWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithHoverEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithHoverEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::Hovered(is_hovered)) = cx.app_data.dequeue_action(id) {
+            if is_hovered != children_state.0 {
+                trace!("Processing callback for hover event");
+                children_state.0 = is_hovered;
+                (self.callback)(comp_ctx.state(md), is_hovered);
+            }
+        }
+    }
+
+    #[instrument(name = "WithHoverEvent", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element.process_local_event(
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, bool) + 'static,
+    > Element for WithFocusEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The first member is whether the element had focus last time we checked
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type BuildOutput = WithFocusEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithFocusEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (has_focus, child_prev_state) = prev_state;
+        let (element, state) = self.element.build(child_prev_state);
+        (
+            WithFocusEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            (has_focus, state),
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, bool),
+    > VirtualDom for WithFocusEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (bool, Child::AggregateChildrenState);
+    type TargetWidgetSeq = SingleFocusWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithFocusEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleFocusWidget::new(
+            FocusWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithFocusEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithFocusEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::FocusChanged(has_focus)) = cx.app_data.dequeue_action(id) {
+            if has_focus != children_state.0 {
+                trace!("Processing callback for focus event");
+                children_state.0 = has_focus;
+                (self.callback)(comp_ctx.state(md), has_focus);
+            }
+        }
+    }
+
+    #[instrument(name = "WithFocusEvent", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element.process_local_event(
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, DoubleClick) + 'static,
+    > Element for WithDoubleClickEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The first member is when the element was last clicked, if that click could be the first
+    // half of a double click
+    type AggregateChildrenState = (Option<Instant>, Child::AggregateChildrenState);
+    type BuildOutput =
+        WithDoubleClickEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithDoubleClickEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (last_click, child_prev_state) = prev_state;
+        let (element, state) = self.element.build(child_prev_state);
+        (
+            WithDoubleClickEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            (last_click, state),
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, DoubleClick),
+    > VirtualDom for WithDoubleClickEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (Option<Instant>, Child::AggregateChildrenState);
+    type TargetWidgetSeq = SingleDoubleClickWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithDoubleClickEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleDoubleClickWidget::new(
+            DoubleClickWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithDoubleClickEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithDoubleClickEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            let now = Instant::now();
+            match children_state.0 {
+                Some(last_click) if now - last_click <= DOUBLE_CLICK_INTERVAL => {
+                    trace!("Processing callback for double click event");
+                    // A third click starts a new double click
+                    children_state.0 = None;
+                    (self.callback)(comp_ctx.state(md), DoubleClick);
+                }
+                _ => {
+                    children_state.0 = Some(now);
+                }
+            }
+        }
+    }
+
+    #[instrument(
+        name = "WithDoubleClickEvent",
+        skip(self, children_state, widget_seq, cx)
+    )]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element.process_local_event(
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, LongPress) + 'static,
+    > Element for WithLongPressEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    // The first member is when the pending long press fires, if the pointer is held down
+    type AggregateChildrenState = (Option<Instant>, Child::AggregateChildrenState);
+    type BuildOutput =
+        WithLongPressEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithLongPressEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (deadline, child_prev_state) = prev_state;
+        let (element, state) = self.element.build(child_prev_state);
+        (
+            WithLongPressEventTarget {
+                element,
+                duration: self.duration,
+                deadline,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            (deadline, state),
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, LongPress),
+    > VirtualDom for WithLongPressEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = (Option<Instant>, Child::AggregateChildrenState);
+    type TargetWidgetSeq = SingleLongPressWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithLongPressEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleLongPressWidget::new(
+            LongPressWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithLongPressEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        );
+
+        if self.deadline != prev_value.deadline {
+            match self.deadline {
+                Some(deadline) => {
+                    let delay = deadline.saturating_duration_since(Instant::now());
+                    trace!("Starting long press timer: {:?}", delay);
+                    widget_seq.pod.with_event_context(
+                        ctx.event_ctx,
+                        |widget: &mut LongPressWidget<_>, ctx: &mut EventCtx| {
+                            widget.restart_timer(delay, ctx);
+                        },
+                    );
+                }
+                None => widget_seq.widget_mut().cancel_timer(),
+            }
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.duration = other.duration;
+        self.deadline = other.deadline;
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithLongPressEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        // The new deadline is picked up by reconcile, which starts or cancels the timer
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        match cx.app_data.dequeue_action(id) {
+            Some(Action::PressChanged(true)) => {
+                children_state.0 = Some(Instant::now() + self.duration);
+            }
+            Some(Action::PressChanged(false)) => {
+                children_state.0 = None;
+            }
+            Some(Action::TimerFired) => {
+                if children_state.0.take().is_some() {
+                    trace!("Processing callback for long press event");
+                    (self.callback)(comp_ctx.state(md), LongPress);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[instrument(
+        name = "WithLongPressEvent",
+        skip(self, children_state, widget_seq, cx)
+    )]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element.process_local_event(
+            &mut children_state.1,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, Point) + 'static,
+    > Element for WithRightClickEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput =
+        WithRightClickEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithRightClickEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithRightClickEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, Point),
+    > VirtualDom for WithRightClickEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleRightClickWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithRightClickEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleRightClickWidget::new(
+            RightClickWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithRightClickEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithRightClickEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::RightClicked(pos)) = cx.app_data.dequeue_action(id) {
+            trace!("Processing callback for right click event");
+            (self.callback)(comp_ctx.state(md), pos);
+        }
+    }
+
+    #[instrument(
+        name = "WithRightClickEvent",
+        skip(self, children_state, widget_seq, cx)
+    )]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, Point) + 'static,
+    > Element for WithMouseMoveEvent<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput =
+        WithMouseMoveEventTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "WithMouseMoveEvent", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            WithMouseMoveEventTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, Point),
+    > VirtualDom for WithMouseMoveEventTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleMouseMoveWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithMouseMoveEvent", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleMouseMoveWidget::new(
+            MouseMoveWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithMouseMoveEvent", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "WithMouseMoveEvent",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::MouseMoved(pos)) = cx.app_data.dequeue_action(id) {
+            trace!("Processing callback for mouse move event");
+            (self.callback)(comp_ctx.state(md), pos);
+        }
+    }
+
+    #[instrument(
+        name = "WithMouseMoveEvent",
+        skip(self, children_state, widget_seq, cx)
+    )]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: Element,
+        Cb: Clone + Fn(&mut ComponentState, ClickEvent) + 'static,
+    > Element for ClickableArea<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = ClickableAreaTarget<ComponentEvent, ComponentState, Child::BuildOutput, Cb>;
+
+    #[instrument(name = "ClickableArea", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, state) = self.element.build(prev_state);
+        (
+            ClickableAreaTarget {
+                element,
+                callback: self.callback,
+                _metadata: Default::default(),
+            },
+            state,
+        )
+    }
+}
+
+impl<
+        ComponentEvent: 'static,
+        ComponentState: 'static,
+        Child: VirtualDom,
+        Cb: Clone + Fn(&mut ComponentState, ClickEvent),
+    > VirtualDom for ClickableAreaTarget<ComponentEvent, ComponentState, Child, Cb>
+{
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleClickAreaWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "ClickableArea", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleClickAreaWidget::new(
+            ClickAreaWidget::new(self.element.init_tree(), WidgetId::next()),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "ClickableArea", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        self.element.reconcile(
+            &prev_value.element,
+            &mut widget_seq.widget_mut().children,
+            ctx,
+        )
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.element.update_value(other.element);
+        self.callback = other.callback;
+    }
+
+    fn unmount(&self) {
+        self.element.unmount();
+    }
+
+    #[instrument(
+        name = "ClickableArea",
+        skip(self, comp_ctx, children_state, widget_seq, cx)
+    )]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.element.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+
+        let md = self._metadata;
+        let id = widget_seq.widget().id();
+        if let Some(Action::Clicked) = cx.app_data.dequeue_action(id) {
+            trace!("Processing callback for click area event");
+            (self.callback)(comp_ctx.state(md), ClickEvent);
+        }
+    }
+
+    #[instrument(name = "ClickableArea", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.element
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
 // Note - Tests related to with_event will be in component_caller.rs for now
+// (except for tests that need a test window)
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::{
+        Button, ButtonClick, ComponentOutput, Label, Skeleton, TextBox, TextChanged,
+    };
+    use crate::metadata::NoEvent;
+    use crate::test_harness::Harness;
+    use crate::Column;
+    use druid::commands::PASTE;
+    use druid::MouseButton;
+    use std::sync::mpsc::channel;
+    use test_env_log::test;
+
+    #[test]
+    fn key_down() {
+        let (key_sender, key_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // The button's layout rect is relative to the key listener; we make the button fill the
+        // width of the test window so that its rect matches window coordinates.
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_key_down(md, move |_state, key_down| {
+                key_sender.send(key_down).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            // The element doesn't have focus yet
+            harness.press_key(KbKey::Enter);
+            assert_eq!(key_receiver.try_recv().ok(), None);
+
+            harness.mouse_click_on(button_id);
+
+            harness.press_key(KbKey::Enter);
+            assert_eq!(
+                key_receiver.try_recv(),
+                Ok(KeyDown {
+                    key: KbKey::Enter,
+                    mods: Modifiers::empty(),
+                })
+            );
+
+            harness.press_key(KbKey::Escape);
+            assert_eq!(
+                key_receiver.try_recv(),
+                Ok(KeyDown {
+                    key: KbKey::Escape,
+                    mods: Modifiers::empty(),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn paste() {
+        let (paste_sender, paste_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // See key_down test
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_paste(md, move |_state, text| {
+                paste_sender.send(text).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            harness.stub_clipboard(Some(String::from("pasted text")));
+
+            // The element doesn't have focus yet
+            harness.submit_command(PASTE);
+            assert_eq!(paste_receiver.try_recv().ok(), None);
+
+            harness.mouse_click_on(button_id);
+            harness.submit_command(PASTE);
+            assert_eq!(paste_receiver.try_recv(), Ok(String::from("pasted text")));
+
+            // Contents other than text are ignored
+            harness.stub_clipboard(None);
+            harness.submit_command(PASTE);
+            assert_eq!(paste_receiver.try_recv().ok(), None);
+        });
+    }
+
+    #[test]
+    fn hover() {
+        let (hover_sender, hover_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // See key_down test
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_hover(md, move |_state, is_hovered| {
+                hover_sender.send(is_hovered).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            let rect = harness.get_state(button_id).layout_rect();
+
+            harness.mouse_move_to(button_id);
+            harness.mouse_move((rect.x0 + 5.0, rect.y0 + 5.0));
+            harness.mouse_move((rect.x0 + 10.0, rect.y0 + 5.0));
+            harness.mouse_move((rect.x0 + 5.0, rect.y1 + 100.0));
+            harness.mouse_move((rect.x0 + 10.0, rect.y1 + 100.0));
+
+            let hover_events: Vec<bool> = hover_receiver.try_iter().collect();
+            assert_eq!(hover_events, vec![true, false]);
+        });
+    }
+
+    #[test]
+    fn double_click() {
+        let (double_click_sender, double_click_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // See key_down test
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_double_click(md, move |_state, event| {
+                double_click_sender.send(event).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            // Two fast clicks make a double click; the single clicks still go through
+            harness.mouse_click_on(button_id);
+            harness.mouse_click_on(button_id);
+            assert_eq!(double_click_receiver.try_recv(), Ok(DoubleClick));
+            assert_eq!(harness.take_emitted_events::<ButtonClick>().len(), 2);
+
+            // Two slow clicks don't
+            harness.mouse_click_on(button_id);
+            std::thread::sleep(DOUBLE_CLICK_INTERVAL + Duration::from_millis(50));
+            harness.mouse_click_on(button_id);
+            assert_eq!(double_click_receiver.try_recv().ok(), None);
+        });
+    }
+
+    #[test]
+    fn long_press() {
+        let (press_sender, press_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // See key_down test
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_long_press(md, Duration::from_millis(500), move |_state, event| {
+                press_sender.send(event).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            // Holding the button fires the event once
+            harness.mouse_move_to(button_id);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.move_timers_forward(Duration::from_millis(300));
+            assert_eq!(press_receiver.try_recv().ok(), None);
+            harness.move_timers_forward(Duration::from_millis(300));
+            assert_eq!(press_receiver.try_recv(), Ok(LongPress));
+            harness.move_timers_forward(Duration::from_millis(600));
+            assert_eq!(press_receiver.try_recv().ok(), None);
+            harness.mouse_button_release(MouseButton::Left);
+            harness.take_emitted_events::<ButtonClick>();
+
+            // A quick click doesn't, but still reaches the button
+            harness.mouse_click_on(button_id);
+            harness.move_timers_forward(Duration::from_millis(600));
+            assert_eq!(press_receiver.try_recv().ok(), None);
+            assert_eq!(harness.take_emitted_events::<ButtonClick>().len(), 1);
+
+            // Small movements are tolerated, larger ones cancel the press
+            let center = harness.get_state(button_id).layout_rect().center();
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(center + (1.0, 1.0));
+            harness.move_timers_forward(Duration::from_millis(600));
+            assert_eq!(press_receiver.try_recv(), Ok(LongPress));
+            harness.mouse_button_release(MouseButton::Left);
+
+            harness.mouse_move(center);
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_move(center + (20.0, 0.0));
+            harness.move_timers_forward(Duration::from_millis(600));
+            assert_eq!(press_receiver.try_recv().ok(), None);
+            harness.mouse_button_release(MouseButton::Left);
+        });
+    }
+
+    #[test]
+    fn right_click() {
+        let (click_sender, click_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let button_id = WidgetId::reserved(1);
+        // See key_down test
+        let button = Button::new("Hello")
+            .with_reserved_id(button_id)
+            .fix_width(400.0)
+            .on_right_click(md, move |_state, pos| {
+                click_sender.send(pos).unwrap();
+            });
+
+        Harness::run_test_window(button, |harness| {
+            // Left clicks are ignored, but still reach the button
+            harness.mouse_click_on(button_id);
+            assert_eq!(click_receiver.try_recv().ok(), None);
+            assert_eq!(harness.take_emitted_events::<ButtonClick>().len(), 1);
+
+            let rect = harness.get_state(button_id).layout_rect();
+            let click_pos = Point::new(rect.x0 + 20.0, rect.y0 + 5.0);
+            harness.mouse_move(click_pos);
+            harness.mouse_button_press(MouseButton::Right);
+            harness.mouse_button_release(MouseButton::Right);
+            assert_eq!(click_receiver.try_recv(), Ok(click_pos));
+        });
+    }
+
+    #[test]
+    fn mouse_move() {
+        let (move_sender, move_receiver) = channel();
+        let (hover_sender, hover_receiver) = channel();
+        let (click_sender, click_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let skeleton_id = WidgetId::reserved(1);
+        let button_id = WidgetId::reserved(2);
+        // The skeleton pushes the button down, so that window and local coordinates differ
+        let tree = Column!(
+            Skeleton::new(400.0, 30.0).with_reserved_id(skeleton_id),
+            Button::new("Hello")
+                .with_reserved_id(button_id)
+                .fix_width(400.0)
+                .on(md, move |_state, _: ButtonClick| click_sender
+                    .send(())
+                    .unwrap())
+                .on_hover(md, move |_state, is_hovered| {
+                    hover_sender.send(is_hovered).unwrap();
+                })
+                .on_mouse_move(md, move |_state, pos| {
+                    move_sender.send(pos).unwrap();
+                }),
+        );
+
+        Harness::run_test_window(tree, |harness| {
+            let skeleton_rect = harness.get_state(skeleton_id).layout_rect();
+            let origin = Point::new(skeleton_rect.x0, skeleton_rect.y1);
+
+            // Moves outside of the element aren't reported
+            harness.mouse_move((origin.x + 5.0, origin.y - 10.0));
+            harness.mouse_move(origin + (5.0, 5.0));
+            harness.mouse_move(origin + (20.0, 8.0));
+            let positions: Vec<Point> = move_receiver.try_iter().collect();
+            assert_eq!(positions, vec![Point::new(5.0, 5.0), Point::new(20.0, 8.0)]);
+
+            // Other handlers still get their events
+            harness.mouse_button_press(MouseButton::Left);
+            harness.mouse_button_release(MouseButton::Left);
+            assert_eq!(click_receiver.try_recv(), Ok(()));
+
+            let button_rect = harness.get_state(button_id).layout_rect();
+            harness.mouse_move((origin.x + 5.0, origin.y + button_rect.height() + 100.0));
+            assert_eq!(move_receiver.try_recv().ok(), None);
+            let hover_events: Vec<bool> = hover_receiver.try_iter().collect();
+            assert_eq!(hover_events, vec![true, false]);
+        });
+    }
+
+    #[test]
+    fn any_event() {
+        let md: Metadata<String, ()> = Default::default();
+        // Text starting with a slash is forwarded as a command, other text is swallowed
+        let forward_commands = |_state: &mut (), event: TextChanged| {
+            if event.new_content.starts_with('/') {
+                Some(event.new_content)
+            } else {
+                None
+            }
+        };
+        let tree = ComponentOutput::new(
+            md,
+            Column!(
+                TextBox::new("hello").on_any(md, forward_commands),
+                TextBox::new("/quit").on_any(md, forward_commands),
+            ),
+        );
+
+        Harness::run_test_window(tree, |harness| {
+            harness.type_text("hello", "!");
+            assert_eq!(
+                harness.take_emitted_events::<String>(),
+                Vec::<String>::new()
+            );
+
+            harness.type_text("/quit", "!");
+            assert_eq!(
+                harness.take_emitted_events::<String>(),
+                vec!["/quit!".to_string()]
+            );
+        });
+    }
+
+    #[test]
+    fn click_area() {
+        let (area_sender, area_receiver) = channel();
+        let (button_sender, button_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let card = Column!(
+            Label::new("Card title"),
+            Button::new("Inner").on_click(md, move |_state, _| {
+                button_sender.send(()).unwrap();
+            }),
+        )
+        .on_click_area(md, move |_state, event| {
+            area_sender.send(event).unwrap();
+        });
+
+        Harness::run_test_window(card, |harness| {
+            harness.click_on("Card title");
+            assert_eq!(area_receiver.try_recv(), Ok(ClickEvent));
+            assert_eq!(button_receiver.try_recv().ok(), None);
+
+            // The inner button takes priority over the area
+            harness.click_on("Inner");
+            assert_eq!(button_receiver.try_recv(), Ok(()));
+            assert_eq!(area_receiver.try_recv().ok(), None);
+        });
+    }
+
+    #[test]
+    fn focus_change() {
+        let (focus_sender, focus_receiver) = channel();
+        let md: Metadata<NoEvent, ()> = Default::default();
+        let column = Column!(
+            TextBox::new("first"),
+            TextBox::new("second").on_focus_change(md, move |_state, has_focus| {
+                focus_sender.send(has_focus).unwrap();
+            }),
+        );
+
+        Harness::run_test_window(column, |harness| {
+            harness.click_on("first");
+            assert_eq!(focus_receiver.try_recv().ok(), None);
+
+            // Tab moves the focus to the second text box, then back to the first one
+            harness.press_key(KbKey::Tab);
+            harness.press_key(KbKey::Tab);
+
+            let focus_events: Vec<bool> = focus_receiver.try_iter().collect();
+            assert_eq!(focus_events, vec![true, false]);
+        });
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq)]
+    struct Form {
+        name: String,
+        email: String,
+    }
+
+    #[test]
+    fn map_state() {
+        let (email_sender, email_receiver) = channel();
+        let (form_sender, form_receiver) = channel();
+        let md: Metadata<NoEvent, Form> = Default::default();
+
+        // The first button is written against a String, and edits the form's email through
+        // the projection
+        let tree = ComponentOutput::new(
+            md,
+            Column!(
+                Button::new("Edit email")
+                    .on(
+                        md.scoped::<String>(),
+                        move |email: &mut String, _: ButtonClick| {
+                            email.push('x');
+                            email_sender.send(email.clone()).unwrap();
+                        }
+                    )
+                    .map_state(md, |form: &mut Form| &mut form.email),
+                Button::new("Show form").on(md, move |form: &mut Form, _: ButtonClick| {
+                    form_sender.send(form.clone()).unwrap();
+                }),
+            ),
+        );
+
+        Harness::run_test_window(tree, |harness| {
+            harness.click_on("Edit email");
+            harness.click_on("Edit email");
+            let emails: Vec<String> = email_receiver.try_iter().collect();
+            assert_eq!(emails, vec!["x".to_string(), "xx".to_string()]);
+
+            harness.click_on("Show form");
+            assert_eq!(
+                form_receiver.try_recv(),
+                Ok(Form {
+                    name: String::new(),
+                    email: "xx".to_string(),
+                })
+            );
+        });
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum Edit {
+        Renamed(String),
+        Saved,
+    }
+
+    #[test]
+    fn flat_map_event() {
+        let md: Metadata<Edit, ()> = Default::default();
+        // Renaming an item also saves it
+        let tree = ComponentOutput::new(
+            md,
+            TextBox::new("draft").flat_map_event(md, |_state: &mut (), event: TextChanged| {
+                vec![Edit::Renamed(event.new_content), Edit::Saved]
+            }),
+        );
+
+        Harness::run_test_window(tree, |harness| {
+            harness.type_text("draft", "!");
+            assert_eq!(
+                harness.take_emitted_events::<Edit>(),
+                vec![Edit::Renamed("draft!".to_string()), Edit::Saved]
+            );
+        });
+    }
+}
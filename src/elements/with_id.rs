@@ -0,0 +1,169 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::{GlobalEventCx, WidgetId};
+use crate::metadata::NoState;
+use crate::widgets::{SingleWithIdWidget, WithIdWidget};
+
+use tracing::{debug_span, info, instrument};
+
+/// Wrapper which gives a fixed [`WidgetId`] to the root widget of the child.
+///
+/// Usually, widgets get a fresh id when they're created, and that id isn't known outside the
+/// element tree. With this wrapper, the widget keeps the given id for as long as the element
+/// stays in the tree, so it can be the target of commands, or be looked up in tests. Ids
+/// minted with [`CompCtx::gen_id`](crate::CompCtx::gen_id) are stable for the lifetime of the
+/// component that minted them.
+///
+/// Two widgets with the same id in a window lead to impredictable behavior. If the id changes,
+/// the child's widgets are created again, and lose their internal state.
+///
+/// Usually created with [`ElementExt::with_id`](crate::ElementExt::with_id).
+///
+/// ## Events
+///
+/// Emits the same events as the wrapped element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithId<Child: Element> {
+    pub child: Child,
+    pub id: WidgetId,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithIdData<Child: VirtualDom> {
+    pub child: Child,
+    pub id: WidgetId,
+}
+
+//
+// --- IMPLS
+
+impl<Child: Element> WithId<Child> {
+    pub fn new(child: Child, id: WidgetId) -> Self {
+        WithId { child, id }
+    }
+}
+
+impl<Child: Element> Element for WithId<Child> {
+    type Event = Child::Event;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WithIdData<Child::BuildOutput>;
+
+    #[instrument(name = "WithId", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (child, state) = self.child.build(prev_state);
+        (WithIdData { child, id: self.id }, state)
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for WithIdData<Child> {
+    type Event = Child::Event;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleWithIdWidget<Child::TargetWidgetSeq>;
+
+    #[instrument(name = "WithId", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        SingleWithIdWidget::new(
+            WithIdWidget::new(self.child.init_tree(), self.id),
+            FlexParams {
+                flex: None,
+                alignment: None,
+            },
+        )
+    }
+
+    #[instrument(name = "WithId", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.id == prev_value.id {
+            self.child.reconcile(
+                &prev_value.child,
+                &mut widget_seq.widget_mut().children,
+                ctx,
+            );
+        } else {
+            // A widget's id can't change; create new widgets with the new id
+            ctx.stats.removed += 1;
+            ctx.stats.created += 1;
+            debug_span!("init_tree").in_scope(|| {
+                info!("id changed, creating widgets");
+                *widget_seq = self.init_tree();
+            });
+        }
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.id = other.id;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "WithId", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children,
+            cx,
+        );
+    }
+
+    #[instrument(name = "WithId", skip(self, children_state, widget_seq, cx))]
+    fn process_local_event(
+        &self,
+        children_state: &mut Self::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) -> Option<Self::Event> {
+        self.child
+            .process_local_event(children_state, &mut widget_seq.widget_mut().children, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_tree::ElementExt;
+    use crate::elements::Label;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    #[test]
+    fn with_id_widget() {
+        let pinned_id = WidgetId::reserved(1);
+        let other_id = WidgetId::reserved(2);
+        let label = |text: &str, id: WidgetId| Label::new(text).with_id(id);
+
+        Harness::run_test_window(label("Hello", pinned_id), |harness| {
+            assert_eq!(harness.get_debug_state(pinned_id).display_name, "WithId");
+
+            // The id survives reconciles, and the child is updated in place
+            harness.update_root_element(label("Hello world", pinned_id));
+            harness.update_root_element(label("Goodbye", pinned_id));
+            assert!(harness.try_get_state(pinned_id).is_some());
+            assert!(harness.find_by_name("Goodbye").is_some());
+
+            harness.update_root_element(label("Goodbye", other_id));
+            assert!(harness.try_get_state(pinned_id).is_none());
+            assert!(harness.try_get_state(other_id).is_some());
+            assert!(harness.find_by_name("Goodbye").is_some());
+        });
+    }
+}
@@ -0,0 +1,238 @@
+use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+use crate::element_tree::{Element, VirtualDom};
+use crate::flex::FlexParams;
+use crate::glue::GlobalEventCx;
+use crate::metadata::{NoEvent, NoState};
+use crate::widgets::{SingleWidget, WrapWidget};
+
+use druid::EventCtx;
+
+use tracing::instrument;
+
+/// A container which lays out its children left-to-right, wrapping them to a new row when
+/// they overflow its width.
+///
+/// Useful for lists of tags, or toolbars. Children keep their own size, and the last row
+/// isn't stretched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Wrap<Child: Element> {
+    pub child: Child,
+    pub flex: FlexParams,
+    pub spacing: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WrapData<Child: VirtualDom> {
+    pub child: Child,
+    pub flex: FlexParams,
+    pub spacing: f64,
+}
+
+// ----
+
+impl<Child: Element> Wrap<Child> {
+    /// Build a wrapping container.
+    ///
+    /// `child` is usually a tuple or a list of elements.
+    pub fn new(child: Child) -> Self {
+        Wrap {
+            child,
+            flex: FlexParams {
+                flex: None,
+                alignment: None,
+            },
+            spacing: 0.0,
+        }
+    }
+
+    pub fn with_flex_params(self, flex_params: FlexParams) -> Self {
+        Wrap {
+            flex: flex_params,
+            ..self
+        }
+    }
+
+    /// Set the space between adjacent children and adjacent rows.
+    pub fn with_spacing(self, spacing: f64) -> Self {
+        Wrap { spacing, ..self }
+    }
+}
+
+// ----
+
+impl<Child: Element> Element for Wrap<Child> {
+    type Event = NoEvent;
+    type ComponentState = NoState;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type BuildOutput = WrapData<Child::BuildOutput>;
+
+    #[instrument(name = "Wrap", skip(self, prev_state))]
+    fn build(
+        self,
+        prev_state: Self::AggregateChildrenState,
+    ) -> (Self::BuildOutput, Self::AggregateChildrenState) {
+        let (element, children_state) = self.child.build(prev_state);
+        (
+            WrapData {
+                child: element,
+                flex: self.flex,
+                spacing: self.spacing,
+            },
+            children_state,
+        )
+    }
+}
+
+impl<Child: VirtualDom> VirtualDom for WrapData<Child> {
+    type Event = NoEvent;
+    type AggregateChildrenState = Child::AggregateChildrenState;
+    type TargetWidgetSeq = SingleWidget<WrapWidget<Child::TargetWidgetSeq>>;
+
+    #[instrument(name = "Wrap", skip(self))]
+    fn init_tree(&self) -> Self::TargetWidgetSeq {
+        let wrap = WrapWidget {
+            spacing: self.spacing,
+            children_seq: self.child.init_tree(),
+        };
+        SingleWidget::new(wrap, self.flex)
+    }
+
+    #[instrument(name = "Wrap", skip(self, prev_value, widget_seq, ctx))]
+    fn reconcile(
+        &self,
+        prev_value: &Self,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        ctx: &mut ReconcileCtx,
+    ) {
+        if self.spacing != prev_value.spacing {
+            widget_seq.widget_mut().spacing = self.spacing;
+            widget_seq
+                .pod
+                .with_event_context(ctx.event_ctx, |_widget, ctx: &mut EventCtx| {
+                    ctx.request_layout();
+                });
+        }
+        widget_seq.flex = self.flex;
+
+        self.child.reconcile(
+            &prev_value.child,
+            &mut widget_seq.widget_mut().children_seq,
+            ctx,
+        );
+    }
+
+    fn update_value(&mut self, other: Self) {
+        self.child.update_value(other.child);
+        self.flex = other.flex;
+        self.spacing = other.spacing;
+    }
+
+    fn unmount(&self) {
+        self.child.unmount();
+    }
+
+    #[instrument(name = "Wrap", skip(self, comp_ctx, children_state, widget_seq, cx))]
+    fn process_event(
+        &self,
+        comp_ctx: &mut ProcessEventCtx,
+        children_state: &mut Child::AggregateChildrenState,
+        widget_seq: &mut Self::TargetWidgetSeq,
+        cx: &mut GlobalEventCx,
+    ) {
+        self.child.process_event(
+            comp_ctx,
+            children_state,
+            &mut widget_seq.widget_mut().children_seq,
+            cx,
+        )
+    }
+}
+
+// ----
+
+/// Builds a wrapping container of up to 12 Elements.
+///
+/// Returns [`Wrap`].
+///
+/// ## Events
+///
+/// Returned element doesn't emit events.
+///
+/// ## Example
+///
+/// ```rust
+/// # use panoramix::Wrap;
+/// # use panoramix::elements::Button;
+/// let tags = Wrap!(
+///     Button::new("rust"),
+///     Button::new("gui"),
+///     Button::new("declarative"),
+/// )
+/// .with_spacing(4.0);
+/// ```
+#[macro_export]
+macro_rules! Wrap {
+    ( $($arg:expr),* $(,)?) => {
+        $crate::elements::Wrap::new(
+            $crate::Tuple!($($arg,)*)
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::SizedBox;
+    use crate::glue::WidgetId;
+    use crate::test_harness::Harness;
+    use test_env_log::test;
+
+    #[test]
+    fn new_wrap() {
+        let wrap =
+            Wrap!(SizedBox::empty(10.0, 10.0), SizedBox::empty(10.0, 10.0)).with_spacing(4.0);
+        let (wrap_data, _) = wrap.build(Default::default());
+
+        assert_eq!(wrap_data.spacing, 4.0);
+    }
+
+    #[test]
+    fn wrap_at_two_widths() {
+        let ids: Vec<_> = (1..=5).map(WidgetId::reserved).collect();
+        let wrap = |width: f64| {
+            let child = |i: usize| SizedBox::empty(100.0, 20.0).with_reserved_id(ids[i]);
+            SizedBox::new(
+                Wrap!(child(0), child(1), child(2), child(3), child(4)).with_spacing(10.0),
+            )
+            .with_width(width)
+        };
+
+        Harness::run_test_window(wrap(400.0), |harness| {
+            let mut origin = |id| {
+                let origin = harness.get_state(id).layout_rect().origin();
+                (origin.x, origin.y)
+            };
+
+            // Three children fit in the first row
+            assert_eq!(origin(ids[0]), (0.0, 0.0));
+            assert_eq!(origin(ids[1]), (110.0, 0.0));
+            assert_eq!(origin(ids[2]), (220.0, 0.0));
+            assert_eq!(origin(ids[3]), (0.0, 30.0));
+            // The last row isn't stretched
+            assert_eq!(origin(ids[4]), (110.0, 30.0));
+            assert_eq!(harness.get_state(ids[4]).layout_rect().width(), 100.0);
+
+            harness.update_root_element(wrap(250.0));
+            let mut origin = |id| {
+                let origin = harness.get_state(id).layout_rect().origin();
+                (origin.x, origin.y)
+            };
+
+            // Only two children fit in each row
+            assert_eq!(origin(ids[1]), (110.0, 0.0));
+            assert_eq!(origin(ids[2]), (0.0, 30.0));
+            assert_eq!(origin(ids[3]), (110.0, 30.0));
+            assert_eq!(origin(ids[4]), (0.0, 60.0));
+        });
+    }
+}
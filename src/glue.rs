@@ -2,8 +2,16 @@
 
 #![allow(unused)]
 
-use druid::Data;
+use crate::elements::{CanvasMouse, KeyDown};
+use crate::hotkey::HotkeyToken;
+use crate::widget_sequence::WidgetSequence;
+use druid::{Color, Command, Data, Point, TimerToken};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::rc::{Rc, Weak};
 use std::sync::Arc;
 
 pub use druid::debug_state::DebugState;
@@ -11,22 +19,50 @@ pub use druid::WidgetId;
 
 pub struct GlobalEventCx<'a> {
     pub app_data: &'a mut DruidAppData,
+    /// The timer which triggered this event pass, if any.
+    pub fired_timer: Option<TimerToken>,
+    /// The command which triggered this event pass, if any.
+    pub command: Option<Command>,
+    /// The nanoseconds elapsed since the previous animation frame, if this event pass was
+    /// triggered by one.
+    pub anim_frame: Option<u64>,
+    /// The keyboard shortcut pressed in this event pass, if any.
+    pub hotkey: Option<HotkeyToken>,
+    // Set when this event pass changes the state of a component, so that the components
+    // above it aren't skipped when they're built again.
+    pub(crate) state_changed: bool,
 }
 
 impl<'a> GlobalEventCx<'a> {
     pub fn new(app_data: &'a mut DruidAppData) -> Self {
-        Self { app_data }
+        Self {
+            app_data,
+            fired_timer: None,
+            command: None,
+            anim_frame: None,
+            hotkey: None,
+            state_changed: false,
+        }
     }
 }
 
 /// The type we use for app data for Druid integration.
 ///
-/// Currently this is action queues.
+/// Currently this is action queues, the payload of the drag in progress, and the widgets
+/// rendered at the root of the window by portals.
 ///
 /// It should probably be a vec of actions, but we can refine
 /// later. For button clicks it doesn't matter.
 #[derive(Clone, Data, Default)]
-pub struct DruidAppData(Arc<HashMap<WidgetId, Action>>);
+pub struct DruidAppData {
+    actions: Arc<HashMap<WidgetId, Action>>,
+    // Set by the drag source when a drag starts, and cleared by the root widget when the
+    // mouse is released
+    drag_payload: Option<Rc<dyn Any>>,
+    // Registered by portal widgets when they're added, and dropped with them. Shared between
+    // clones, since the registry belongs to the window rather than to a version of the data.
+    portals: Rc<RefCell<Vec<Weak<RefCell<dyn WidgetSequence>>>>>,
+}
 
 /// Actions that can be produced by widgets,
 #[derive(Clone)]
@@ -34,16 +70,39 @@ pub enum Action {
     Clicked,
     FutureResolved,
     TextChanged(String),
+    ValueChanged(f64),
+    Incremented,
+    Decremented,
+    KeyDown(KeyDown),
+    Hovered(bool),
+    RightClicked(Point),
+    MouseMoved(Point),
+    FocusChanged(bool),
+    PressChanged(bool),
+    Selected(usize),
+    Toggled(usize),
+    VisibleRangeChanged(Range<usize>),
+    BreakpointCrossed(bool),
+    BecameVisible,
+    TimerFired,
+    AnimFrame(u64),
+    DragStarted,
+    Dropped,
+    ColorChanged(Color),
+    FileChosen(PathBuf),
+    CanvasMouse(CanvasMouse),
+    Submitted,
+    Pasted(String),
 }
 
 impl DruidAppData {
     pub(crate) fn queue_action(&mut self, id: WidgetId, action: Action) {
-        Arc::make_mut(&mut self.0).insert(id, action);
+        Arc::make_mut(&mut self.actions).insert(id, action);
     }
 
     pub(crate) fn dequeue_action(&mut self, id: WidgetId) -> Option<Action> {
-        if self.0.contains_key(&id) {
-            Arc::make_mut(&mut self.0).remove(&id)
+        if self.actions.contains_key(&id) {
+            Arc::make_mut(&mut self.actions).remove(&id)
         } else {
             None
         }
@@ -51,7 +110,56 @@ impl DruidAppData {
 
     /// Report whether the id has a non-empty action queue.
     pub(crate) fn has_action(&self, id: WidgetId) -> bool {
-        self.0.contains_key(&id)
+        self.actions.contains_key(&id)
+    }
+
+    /// Report how many ids have a non-empty action queue.
+    pub(crate) fn action_count(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Start a drag carrying `payload`, replacing the drag in progress if there is one.
+    pub(crate) fn start_drag(&mut self, payload: Rc<dyn Any>) {
+        self.drag_payload = Some(payload);
+    }
+
+    /// Stop the drag in progress, if any.
+    pub(crate) fn end_drag(&mut self) {
+        self.drag_payload = None;
+    }
+
+    /// Return the payload of the drag in progress, if any.
+    pub(crate) fn drag_payload(&self) -> Option<&dyn Any> {
+        self.drag_payload.as_deref()
+    }
+
+    /// Return the type of the payload of the drag in progress, if any.
+    pub(crate) fn drag_payload_type(&self) -> Option<TypeId> {
+        self.drag_payload().map(|payload| payload.type_id())
+    }
+
+    /// Display `children` at the root of the window, until they're dropped.
+    pub(crate) fn add_portal(&self, children: Weak<RefCell<dyn WidgetSequence>>) {
+        self.portals.borrow_mut().push(children);
+    }
+
+    /// Return the widgets displayed at the root of the window, in the order they were added.
+    pub(crate) fn portals(&self) -> Vec<Rc<RefCell<dyn WidgetSequence>>> {
+        self.portals
+            .borrow()
+            .iter()
+            .filter_map(|portal| portal.upgrade())
+            .collect()
+    }
+
+    /// Forget the widgets of portals which have been removed.
+    ///
+    /// Returns true if any were.
+    pub(crate) fn remove_dropped_portals(&self) -> bool {
+        let mut portals = self.portals.borrow_mut();
+        let count = portals.len();
+        portals.retain(|portal| portal.strong_count() > 0);
+        portals.len() != count
     }
 }
 
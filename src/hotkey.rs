@@ -0,0 +1,194 @@
+//! Keyboard shortcuts registered by components with [`CompCtx::use_hotkey`](crate::CompCtx::use_hotkey).
+//!
+//! Druid only sends keyboard events to the focused widget and its parents, so the root widget
+//! takes focus while a shortcut is registered and no other widget has it. When a key is
+//! pressed, the root widget looks up the matching shortcut in a registry shared by all
+//! components, and passes its token down the tree in [`GlobalEventCx`](crate::glue::GlobalEventCx);
+//! the component which registered it then runs its callback.
+
+use druid::{HotKey, KeyEvent};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use tracing::trace;
+
+thread_local! {
+    // The shortcuts of mounted components, in the order they were registered.
+    static REGISTRY: RefCell<Vec<Weak<Registration>>> = Default::default();
+    static NEXT_TOKEN: Cell<u64> = Cell::new(0);
+}
+
+/// Identifies a shortcut registered by a component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HotkeyToken(u64);
+
+/// Returns the token of the shortcut matching `event`, if there is one.
+///
+/// If several shortcuts match, the most recently registered one wins.
+pub(crate) fn resolve(event: &KeyEvent) -> Option<HotkeyToken> {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.retain(|registration| is_live(registration));
+        registry
+            .iter()
+            .rev()
+            .filter_map(|registration| registration.upgrade())
+            .find(|registration| registration.hotkey.borrow().matches(event))
+            .map(|registration| registration.token)
+    })
+}
+
+/// Returns true if a mounted component has registered a shortcut.
+pub(crate) fn has_hotkeys() -> bool {
+    REGISTRY.with(|registry| registry.borrow().iter().any(is_live))
+}
+
+fn is_live(registration: &Weak<Registration>) -> bool {
+    registration
+        .upgrade()
+        .map_or(false, |registration| !registration.cancelled.get())
+}
+
+fn register(registration: &Rc<Registration>) {
+    REGISTRY.with(|registry| registry.borrow_mut().push(Rc::downgrade(registration)));
+}
+
+type HotkeyCallback = Rc<dyn Fn(&mut dyn Any)>;
+
+/// The shortcuts registered by a component, in the order they were registered.
+///
+/// Stored in the component's state, so that shortcuts registered again keep their place in
+/// the registry, and so that they can be removed when the component is unmounted.
+#[derive(Clone, Default)]
+pub struct HotkeyState {
+    slots: Vec<HotkeySlot>,
+}
+
+#[derive(Clone)]
+struct HotkeySlot {
+    registration: Rc<Registration>,
+    callback: HotkeyCallback,
+}
+
+// Shortcuts can't be compared, so the n-th shortcut registered by a component keeps the same
+// registration, and its key is replaced on every build.
+struct Registration {
+    token: HotkeyToken,
+    hotkey: RefCell<HotKey>,
+    cancelled: Cell<bool>,
+}
+
+impl HotkeyState {
+    /// Register `callback` to be called with the component's local state when `hotkey` is
+    /// pressed.
+    ///
+    /// The shortcut isn't added to the registry until the state is passed to
+    /// [`HotkeyState::replace`].
+    pub(crate) fn add<ComponentState: 'static>(
+        &mut self,
+        hotkey: HotKey,
+        callback: impl Fn(&mut ComponentState) + 'static,
+    ) {
+        let callback: HotkeyCallback = Rc::new(move |state: &mut dyn Any| {
+            let state = state
+                .downcast_mut::<ComponentState>()
+                .expect("internal type error: use_hotkey got the wrong component state type");
+            callback(state);
+        });
+        let token = NEXT_TOKEN.with(|next_token| next_token.replace(next_token.get() + 1));
+        self.slots.push(HotkeySlot {
+            registration: Rc::new(Registration {
+                token: HotkeyToken(token),
+                hotkey: RefCell::new(hotkey),
+                cancelled: Cell::new(false),
+            }),
+            callback,
+        });
+    }
+
+    /// Replace these shortcuts with `new_state`.
+    ///
+    /// Shortcuts registered again keep their registration, and only their key and callback
+    /// are replaced. Shortcuts which aren't registered anymore are removed from the registry,
+    /// and new ones are added to it.
+    pub(crate) fn replace(&self, new_state: HotkeyState) -> HotkeyState {
+        let mut slots = Vec::new();
+        for (index, new_slot) in new_state.slots.into_iter().enumerate() {
+            match self.slots.get(index) {
+                Some(prev_slot) => {
+                    prev_slot
+                        .registration
+                        .hotkey
+                        .swap(&new_slot.registration.hotkey);
+                    slots.push(HotkeySlot {
+                        registration: prev_slot.registration.clone(),
+                        callback: new_slot.callback,
+                    });
+                }
+                None => {
+                    trace!(
+                        "Registering hotkey: {:?}",
+                        new_slot.registration.hotkey.borrow()
+                    );
+                    register(&new_slot.registration);
+                    slots.push(new_slot);
+                }
+            }
+        }
+        for prev_slot in self.slots.iter().skip(slots.len()) {
+            prev_slot.registration.cancelled.set(true);
+        }
+        HotkeyState { slots }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Remove the shortcuts from the registry. Their callbacks won't be called again.
+    pub(crate) fn cancel(&self) {
+        for slot in &self.slots {
+            trace!("Removing hotkey: {:?}", slot.registration.hotkey.borrow());
+            slot.registration.cancelled.set(true);
+        }
+    }
+
+    /// If `pressed` is one of these shortcuts, call its callback.
+    pub(crate) fn handle(&self, pressed: Option<HotkeyToken>, state: &mut dyn Any) {
+        for slot in &self.slots {
+            let registration = &slot.registration;
+            if Some(registration.token) == pressed && !registration.cancelled.get() {
+                trace!("Running hotkey handler: {:?}", registration.hotkey.borrow());
+                (slot.callback)(state);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for HotkeyState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.slots
+                    .iter()
+                    .map(|slot| slot.registration.hotkey.borrow()),
+            )
+            .finish()
+    }
+}
+
+// Like command handlers, callbacks are only replaced when a component is built, so comparing
+// them by identity is enough.
+impl PartialEq for HotkeyState {
+    fn eq(&self, other: &Self) -> bool {
+        self.slots.len() == other.slots.len()
+            && self
+                .slots
+                .iter()
+                .zip(other.slots.iter())
+                .all(|(slot, other_slot)| {
+                    Rc::ptr_eq(&slot.registration, &other_slot.registration)
+                        && Rc::ptr_eq(&slot.callback, &other_slot.callback)
+                })
+    }
+}
@@ -0,0 +1,161 @@
+//! Periodic timers registered by components with [`CompCtx::use_interval`](crate::CompCtx::use_interval).
+//!
+//! Components don't own a widget, so their timers are requested by the root widget: requests
+//! are queued while the element tree is built or processes events, then sent to druid with
+//! [`request_timers`]. When a timer fires, its token is passed down the tree in
+//! [`GlobalEventCx`](crate::glue::GlobalEventCx), and the component it belongs to runs its
+//! callback.
+
+use druid::{EventCtx, TimerToken};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+thread_local! {
+    // Timers to request the next time the root widget has an EventCtx.
+    static TIMER_REQUESTS: RefCell<Vec<(Duration, Rc<IntervalTimer>)>> = Default::default();
+}
+
+/// Request the timers queued by intervals since the last call.
+pub(crate) fn request_timers(ctx: &mut EventCtx) {
+    let requests = TIMER_REQUESTS.with(|requests| requests.take());
+    for (period, timer) in requests {
+        // The interval may have been cancelled in the meantime
+        if !timer.cancelled.get() {
+            timer.token.set(Some(ctx.request_timer(period)));
+        }
+    }
+}
+
+type IntervalCallback = Rc<dyn Fn(&mut dyn Any)>;
+
+/// The interval registered by a component, if it has one.
+///
+/// Stored in the component's state, so that the timer is kept between rebuilds, and can be
+/// cancelled when the component is unmounted.
+#[derive(Clone, Default)]
+pub struct IntervalState {
+    interval: Option<Interval>,
+}
+
+#[derive(Clone)]
+struct Interval {
+    period: Duration,
+    deadline: Instant,
+    timer: Rc<IntervalTimer>,
+    callback: IntervalCallback,
+}
+
+// Druid can't cancel a timer once it's requested; instead, we ignore it when it fires.
+#[derive(Default)]
+struct IntervalTimer {
+    token: Cell<Option<TimerToken>>,
+    cancelled: Cell<bool>,
+}
+
+impl IntervalState {
+    /// Create an interval calling `callback` with the component's local state every `period`.
+    ///
+    /// The timer isn't started until the interval is passed to [`IntervalState::replace`].
+    pub(crate) fn new<ComponentState: 'static>(
+        period: Duration,
+        callback: impl Fn(&mut ComponentState) + 'static,
+    ) -> Self {
+        let callback: IntervalCallback = Rc::new(move |state: &mut dyn Any| {
+            let state = state
+                .downcast_mut::<ComponentState>()
+                .expect("internal type error: use_interval got the wrong component state type");
+            callback(state);
+        });
+        IntervalState {
+            interval: Some(Interval {
+                period,
+                deadline: Instant::now() + period,
+                timer: Default::default(),
+                callback,
+            }),
+        }
+    }
+
+    /// Replace this interval with `new_interval`.
+    ///
+    /// If both have the same period, the running timer is kept, and only the callback is
+    /// replaced. Otherwise, the current timer is cancelled and a new one is started.
+    pub(crate) fn replace(&self, new_interval: IntervalState) -> IntervalState {
+        match (&self.interval, new_interval.interval) {
+            (Some(prev), Some(new)) if prev.period == new.period => IntervalState {
+                interval: Some(Interval {
+                    callback: new.callback,
+                    ..prev.clone()
+                }),
+            },
+            (_, new) => {
+                self.cancel();
+                if let Some(new) = &new {
+                    trace!("Starting interval timer: {:?}", new.period);
+                    queue_timer_request(new.period, new.timer.clone());
+                }
+                IntervalState { interval: new }
+            }
+        }
+    }
+
+    pub(crate) fn is_none(&self) -> bool {
+        self.interval.is_none()
+    }
+
+    /// Stop the timer. The callback won't be called again.
+    pub(crate) fn cancel(&self) {
+        if let Some(interval) = &self.interval {
+            trace!("Cancelling interval timer");
+            interval.timer.cancelled.set(true);
+        }
+    }
+
+    /// If `fired_timer` is this interval's timer, call the callback, and restart the timer.
+    pub(crate) fn fire(&mut self, fired_timer: Option<TimerToken>, state: &mut dyn Any) {
+        if let Some(interval) = &mut self.interval {
+            let is_own_timer = fired_timer.is_some() && interval.timer.token.get() == fired_timer;
+            if is_own_timer && !interval.timer.cancelled.get() {
+                trace!("Interval timer fired");
+                interval.timer.token.set(None);
+                interval.deadline = Instant::now() + interval.period;
+                queue_timer_request(interval.period, interval.timer.clone());
+                (interval.callback)(state);
+            }
+        }
+    }
+}
+
+fn queue_timer_request(period: Duration, timer: Rc<IntervalTimer>) {
+    TIMER_REQUESTS.with(|requests| requests.borrow_mut().push((period, timer)));
+}
+
+impl std::fmt::Debug for IntervalState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.interval {
+            Some(interval) => f
+                .debug_struct("IntervalState")
+                .field("period", &interval.period)
+                .field("deadline", &interval.deadline)
+                .finish(),
+            None => f.debug_struct("IntervalState").finish(),
+        }
+    }
+}
+
+impl PartialEq for IntervalState {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.interval, &other.interval) {
+            (Some(interval), Some(other_interval)) => {
+                interval.period == other_interval.period
+                    && interval.deadline == other_interval.deadline
+                    && Rc::ptr_eq(&interval.timer, &other_interval.timer)
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
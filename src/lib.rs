@@ -68,11 +68,22 @@
 //!
 //! For information on how to write a component, see [these tutorials](tutorials).
 
+mod animation;
+mod app_menu;
+mod async_task;
+mod autofocus;
+mod clipboard;
+mod command;
 mod ctx;
 mod element_tree;
 mod glue;
+mod hotkey;
+mod interval;
 mod metadata;
+mod open_url;
+mod reducer;
 mod root_handler;
+mod tab_order;
 mod widget_sequence;
 
 pub mod test_harness;
@@ -86,7 +97,8 @@ pub use panoramix_derive::component;
 
 pub use crate::ctx::CompCtx;
 pub use element_tree::{Element, ElementExt};
-pub use metadata::{Metadata, NoEvent};
+pub use metadata::{Metadata, NoEvent, NoState};
+pub use reducer::Dispatch;
 pub use root_handler::{PlatformError, RootHandler, RootWidget};
 
 /// Traits and type used internally to compute the GUI.
@@ -98,9 +110,17 @@ pub mod internals {
     // to have a clean separation in the documentation between the items required to write
     // a GUI and the items required to create a GUI element.
 
-    pub use crate::ctx::{ProcessEventCtx, ReconcileCtx};
+    pub use crate::animation::AnimationState;
+    pub use crate::async_task::AsyncTaskState;
+    pub use crate::command::CommandState;
+    pub use crate::ctx::{
+        EffectsState, IdsState, ProcessEventCtx, ReconcileCtx, ReconcileStats, SelectorsState,
+    };
     pub use crate::element_tree::VirtualDom;
     pub use crate::glue::{Action, DruidAppData, GlobalEventCx, WidgetId};
+    pub use crate::hotkey::{HotkeyState, HotkeyToken};
+    pub use crate::interval::IntervalState;
+    pub use crate::reducer::ReducerState;
     pub use crate::widget_sequence::{FlexWidget, WidgetSequence};
 }
 
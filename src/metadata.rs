@@ -30,3 +30,13 @@ pub struct NoState;
 pub struct Metadata<ComponentEvent, ComponentState> {
     _marker: std::marker::PhantomData<(ComponentEvent, ComponentState)>,
 }
+
+impl<ComponentEvent, ComponentState> Metadata<ComponentEvent, ComponentState> {
+    /// Metadata for callbacks which operate on `SubState`, a part of the component's local state.
+    ///
+    /// Elements whose callbacks use this metadata must be wrapped with
+    /// [`ElementExt::map_state`](crate::ElementExt::map_state).
+    pub fn scoped<SubState>(self) -> Metadata<ComponentEvent, SubState> {
+        Default::default()
+    }
+}
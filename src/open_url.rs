@@ -0,0 +1,55 @@
+//! URLs opened in the system browser when a [`Link`](crate::elements::Link) is clicked.
+//!
+//! The URL isn't opened right away: the click is queued while events are processed, so that a
+//! callback registered with [`Link::on_click`](crate::elements::Link::on_click) can handle it
+//! instead. Once events have been processed, the root widget opens the URL with
+//! [`open_pending_url`], unless the click was handled.
+
+use std::cell::{Cell, RefCell};
+use tracing::{trace, warn};
+
+thread_local! {
+    // The URL of the link clicked during the current event pass.
+    static PENDING_URL: RefCell<Option<String>> = Default::default();
+    // Replaced in unit tests, so that they don't launch a browser.
+    static URL_OPENER: Cell<fn(&str)> = Cell::new(open_in_browser);
+}
+
+pub(crate) fn queue_url(url: String) {
+    PENDING_URL.with(|pending_url| *pending_url.borrow_mut() = Some(url));
+}
+
+/// Don't open the URL queued by the last click.
+pub(crate) fn cancel_url() {
+    PENDING_URL.with(|pending_url| pending_url.borrow_mut().take());
+}
+
+/// Open the URL queued since the last call, if any.
+pub(crate) fn open_pending_url() {
+    if let Some(url) = PENDING_URL.with(|pending_url| pending_url.borrow_mut().take()) {
+        trace!("Opening URL {}", url);
+        URL_OPENER.with(|opener| opener.get()(&url));
+    }
+}
+
+/// Replace the function opening URLs in the current thread.
+#[cfg(test)]
+pub(crate) fn set_url_opener(opener: fn(&str)) {
+    URL_OPENER.with(|url_opener| url_opener.set(opener));
+}
+
+fn open_in_browser(url: &str) {
+    use std::process::Command;
+
+    // The empty argument is the window title, which `start` expects before the URL
+    #[cfg(target_os = "windows")]
+    let result = Command::new("cmd").args(&["/C", "start", "", url]).spawn();
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg(url).spawn();
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = Command::new("xdg-open").arg(url).spawn();
+
+    if let Err(error) = result {
+        warn!("Could not open URL {:?}: {}", url, error);
+    }
+}
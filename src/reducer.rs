@@ -0,0 +1,117 @@
+//! Actions dispatched to the reducer registered by a component with
+//! [`CompCtx::use_reducer`](crate::CompCtx::use_reducer).
+//!
+//! Event callbacks don't apply actions directly: [`Dispatch::dispatch`] pushes them to a
+//! queue, which the component drains once its children have processed the current event,
+//! passing each action to the reducer in the order it was dispatched.
+
+use derivative::Derivative;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use tracing::trace;
+
+type ActionQueue = Rc<RefCell<VecDeque<Box<dyn Any>>>>;
+type Reducer = Rc<dyn Fn(&mut dyn Any, Box<dyn Any>)>;
+
+/// Handle returned by [`CompCtx::use_reducer`](crate::CompCtx::use_reducer), used to send
+/// actions to the component's reducer.
+///
+/// The handle can be cloned and moved into event callbacks.
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct Dispatch<Action> {
+    queue: ActionQueue,
+    _marker: PhantomData<Action>,
+}
+
+/// The reducer registered by a component, if it has one, and the actions dispatched to it.
+///
+/// The reducer is registered again every time the component is built, but the queue is kept
+/// between builds, so that actions dispatched from any callback are applied.
+#[derive(Clone, Default)]
+pub struct ReducerState {
+    queue: ActionQueue,
+    reducer: Option<Reducer>,
+}
+
+impl<Action: 'static> Dispatch<Action> {
+    /// Queue `action`, to be passed to the reducer once the current event is processed.
+    pub fn dispatch(&self, action: Action) {
+        self.queue.borrow_mut().push_back(Box::new(action));
+    }
+}
+
+impl ReducerState {
+    /// Create a reducer state calling `reducer` with the component's local state, keeping
+    /// the queue of `prev_state`.
+    pub(crate) fn new<ComponentState: 'static, Action: 'static>(
+        prev_state: &ReducerState,
+        reducer: impl Fn(&ComponentState, Action) -> ComponentState + 'static,
+    ) -> Self {
+        let reducer: Reducer = Rc::new(move |state: &mut dyn Any, action: Box<dyn Any>| {
+            let state = state
+                .downcast_mut::<ComponentState>()
+                .expect("internal type error: use_reducer got the wrong component state type");
+            let action = action
+                .downcast::<Action>()
+                .expect("internal type error: use_reducer got the wrong action type");
+            *state = reducer(state, *action);
+        });
+        ReducerState {
+            queue: prev_state.queue.clone(),
+            reducer: Some(reducer),
+        }
+    }
+
+    pub(crate) fn dispatch<Action>(&self) -> Dispatch<Action> {
+        Dispatch {
+            queue: self.queue.clone(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pass the actions dispatched since the last call to the reducer, in order.
+    pub(crate) fn apply(&self, state: &mut dyn Any) {
+        let reducer = match &self.reducer {
+            Some(reducer) => reducer,
+            None => return,
+        };
+        let actions = std::mem::take(&mut *self.queue.borrow_mut());
+        for action in actions {
+            trace!("Applying reducer action");
+            reducer(state, action);
+        }
+    }
+}
+
+impl<Action> std::fmt::Debug for Dispatch<Action> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dispatch")
+            .field("pending", &self.queue.borrow().len())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for ReducerState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReducerState")
+            .field("reducer", &self.reducer.is_some())
+            .field("pending", &self.queue.borrow().len())
+            .finish()
+    }
+}
+
+// The reducer is only replaced when a component is built, and actions are applied in the same
+// pass they're dispatched in, so comparing reducers by identity is enough.
+impl PartialEq for ReducerState {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.reducer, &other.reducer) {
+            (Some(reducer), Some(other_reducer)) => Rc::ptr_eq(reducer, other_reducer),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
@@ -1,14 +1,19 @@
 use crate::elements::{Component, ElementBox};
 use crate::flex;
 use crate::glue::{DruidAppData, GlobalEventCx};
-use crate::internals::{ProcessEventCtx, ReconcileCtx, VirtualDom};
-use crate::widgets::FlexWidget;
+use crate::hotkey::HotkeyToken;
+use crate::internals::{ProcessEventCtx, ReconcileCtx, ReconcileStats, VirtualDom};
+use crate::widgets::{set_window_size, FlexWidget};
 use crate::{Element, NoEvent};
 
 use crate::glue::DebugState;
 
 use druid::widget::prelude::*;
-use druid::{widget, AppLauncher, Point, Widget, WidgetPod, WindowDesc};
+use druid::{
+    widget, AppLauncher, Command, KbKey, Point, Selector, TimerToken, Widget, WidgetPod, WindowDesc,
+};
+use std::any::Any;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use tracing::{debug_span, info, instrument, trace};
 
@@ -16,6 +21,10 @@ pub use druid::PlatformError;
 
 // TODO - trait Element: 'static
 
+// Sent by the root widget to itself when the env changes, since the element tree can't be
+// built again during update.
+const REBUILD_ROOT: Selector = Selector::new("panoramix.rebuild-root");
+
 /// Implements [`druid::Widget`] from a component
 ///
 /// You should probably use [`RootHandler`] directly instead.
@@ -33,6 +42,18 @@ pub struct RootWidget<RootElem: Element> {
             >,
         >,
     >,
+    // Set when the event being processed is a timer requested by a component.
+    fired_timer: Option<TimerToken>,
+    // Set when the event being processed is a command, which components may handle.
+    received_command: Option<Command>,
+    // Set when the event being processed is an animation frame.
+    anim_frame: Option<u64>,
+    // Set when the event being processed is a key press matching a component's shortcut.
+    pressed_hotkey: Option<HotkeyToken>,
+    // Events emitted by the root element, until they're taken by the test harness.
+    emitted_events: Vec<RootElem::Event>,
+    // Children created, updated and removed by the last reconcile pass.
+    last_reconcile_stats: ReconcileStats,
 }
 
 impl<LocalEvent: Clone + Debug + PartialEq + 'static> RootWidget<ElementBox<LocalEvent>> {
@@ -45,6 +66,12 @@ impl<LocalEvent: Clone + Debug + PartialEq + 'static> RootWidget<ElementBox<Loca
             vdom: None,
             default_widget: WidgetPod::new(widget::Flex::row()),
             widget: None,
+            fired_timer: None,
+            received_command: None,
+            anim_frame: None,
+            pressed_hotkey: None,
+            emitted_events: Vec::new(),
+            last_reconcile_stats: Default::default(),
         }
     }
 }
@@ -57,16 +84,28 @@ impl<RootElem: Element> RootWidget<RootElem> {
             vdom: None,
             default_widget: WidgetPod::new(widget::Flex::row()),
             widget: None,
+            fired_timer: None,
+            received_command: None,
+            anim_frame: None,
+            pressed_hotkey: None,
+            emitted_events: Vec::new(),
+            last_reconcile_stats: Default::default(),
         }
     }
 }
 
 impl<RootElem: Element> RootWidget<RootElem> {
-    #[instrument(level = "debug", skip(self, ctx))]
-    pub fn init(&mut self, ctx: &mut EventCtx) {
-        let (new_vdom, state) =
-            debug_span!("build").in_scope(|| self.root_element.clone().build(Default::default()));
+    #[instrument(level = "debug", skip(self, ctx, env))]
+    pub fn init(&mut self, ctx: &mut EventCtx, env: &Env) {
+        crate::async_task::set_ext_event_sink(ctx.get_external_handle());
+        crate::autofocus::reset_autofocus();
+        let (new_vdom, state) = debug_span!("build").in_scope(|| {
+            crate::ctx::with_build_env(env, || self.root_element.clone().build(Default::default()))
+        });
         self.root_state = state;
+        crate::interval::request_timers(ctx);
+        crate::animation::request_anim_frame(ctx);
+        crate::command::submit_commands(ctx);
 
         info!("Initial aggregate app state: {:?}", self.root_state);
 
@@ -81,6 +120,7 @@ impl<RootElem: Element> RootWidget<RootElem> {
                     main_alignment: flex::MainAxisAlignment::Start,
                     fill_major_axis: false,
                 },
+                spacing: 0.0,
                 children_seq: widget_seq,
             })
             .vertical(),
@@ -88,6 +128,7 @@ impl<RootElem: Element> RootWidget<RootElem> {
         ctx.children_changed();
         self.widget = Some(flex_widget);
         self.vdom = Some(new_vdom);
+        crate::autofocus::request_focus(ctx);
 
         ctx.request_paint();
     }
@@ -113,14 +154,15 @@ impl<RootElem: Element> RootWidget<RootElem> {
             let prev_vdom = self.vdom.as_mut().unwrap();
             let flex_widget = self.widget.as_mut().unwrap().widget_mut();
             let mut cx = GlobalEventCx::new(data);
+            cx.fired_timer = self.fired_timer.take();
+            cx.command = self.received_command.take();
+            cx.anim_frame = self.anim_frame.take();
+            cx.hotkey = self.pressed_hotkey.take();
 
-            // We ignore the root event for now.
-            // This might change in cases where we want the user to control
-            // when RootWidget::run() is called.
             // Types are default types, because ProcessEventCtx will
             // normally be unused in root element.
             let mut ctx = ProcessEventCtx {
-                event_queue: &mut Vec::<NoEvent>::new(),
+                event_queue: &mut VecDeque::<NoEvent>::new(),
                 state: &mut (),
             };
             prev_vdom.process_event(
@@ -129,7 +171,23 @@ impl<RootElem: Element> RootWidget<RootElem> {
                 &mut flex_widget.child_mut().children_seq,
                 &mut cx,
             );
+
+            // The root element's events are only kept for the test harness.
+            // This might change in cases where we want the user to control
+            // when RootWidget::run() is called.
+            while let Some(event) = prev_vdom.process_local_event(
+                &mut self.root_state,
+                &mut flex_widget.child_mut().children_seq,
+                &mut cx,
+            ) {
+                trace!("Root element emitted event: {:?}", event);
+                self.emitted_events.push(event);
+            }
         });
+        crate::interval::request_timers(ctx);
+        crate::animation::request_anim_frame(ctx);
+        crate::command::submit_commands(ctx);
+        crate::open_url::open_pending_url();
 
         if !force_update && self.root_state == prev_root_state {
             trace!("State is unchanged. Skipping virtual DOM update.");
@@ -138,10 +196,14 @@ impl<RootElem: Element> RootWidget<RootElem> {
 
         info!("New aggregate app state: {:?}", self.root_state);
 
+        crate::async_task::set_ext_event_sink(ctx.get_external_handle());
+        crate::autofocus::reset_autofocus();
         let (new_vdom, state) = debug_span!("build").in_scope(|| {
-            self.root_element
-                .clone()
-                .build(std::mem::take(&mut self.root_state))
+            crate::ctx::with_build_env(env, || {
+                self.root_element
+                    .clone()
+                    .build(std::mem::take(&mut self.root_state))
+            })
         });
         self.root_state = state;
 
@@ -151,6 +213,7 @@ impl<RootElem: Element> RootWidget<RootElem> {
             event_ctx: ctx,
             data,
             env,
+            stats: Default::default(),
         };
 
         debug_span!("reconcile").in_scope(|| {
@@ -160,7 +223,15 @@ impl<RootElem: Element> RootWidget<RootElem> {
                 &mut reconcile_ctx,
             );
         });
-        *prev_vdom = new_vdom;
+        self.last_reconcile_stats = reconcile_ctx.stats;
+        if data.remove_dropped_portals() {
+            ctx.children_changed();
+        }
+        prev_vdom.update_value(new_vdom);
+        crate::interval::request_timers(ctx);
+        crate::animation::request_anim_frame(ctx);
+        crate::command::submit_commands(ctx);
+        crate::autofocus::request_focus(ctx);
 
         ctx.request_update();
         ctx.request_paint();
@@ -170,25 +241,83 @@ impl<RootElem: Element> RootWidget<RootElem> {
 impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
         let mut force_update = false;
+        let mut tab_handled = false;
 
+        if let Event::Timer(token) = event {
+            self.fired_timer = Some(*token);
+        }
+        if let Event::AnimFrame(interval) = event {
+            self.anim_frame = Some(*interval);
+        }
+        if let Event::KeyDown(key_event) = event {
+            self.pressed_hotkey = crate::hotkey::resolve(key_event);
+            if key_event.key == KbKey::Tab {
+                if let Some(id) = crate::tab_order::next_stop(key_event.mods.shift()) {
+                    ctx.set_focus(id);
+                    tab_handled = true;
+                }
+            }
+        }
+        // Keyboard events only reach the root widget if it or a descendant has focus
+        if !ctx.has_focus() && crate::hotkey::has_hotkeys() {
+            ctx.request_focus();
+        }
         if let Event::Command(command) = event {
+            self.received_command = Some(command.clone());
+            if let Some(found) = command.get(crate::test_harness::FIND_WIDGETS) {
+                found.set_window_size(ctx.size());
+            }
+            if let Some(events) = command.get(crate::test_harness::TAKE_EMITTED_EVENTS) {
+                let emitted_events = std::mem::take(&mut self.emitted_events);
+                events.borrow_mut().extend(
+                    emitted_events
+                        .into_iter()
+                        .map(|event| Box::new(event) as Box<dyn Any>),
+                );
+            }
+            if let Some(stats) = command.get(crate::test_harness::LAST_RECONCILE_STATS) {
+                stats.set(self.last_reconcile_stats);
+            }
+        }
+
+        if let Event::Command(command) = event {
+            if command.is(REBUILD_ROOT) {
+                ctx.set_handled();
+                force_update = true;
+            }
             let selector = druid::Selector::new("update_root_element");
             if let Some(new_root) = command.get::<RootElem>(selector) {
                 self.root_element = new_root.clone();
                 force_update = true;
             }
         };
-        if let Some(widget) = &mut self.widget {
-            widget.event(ctx, event, data, env);
+        if self.pressed_hotkey.is_some() || tab_handled {
+            // Shortcuts and explicit focus order take precedence over the focused widget
+            ctx.set_handled();
         } else {
-            self.default_widget.event(ctx, event, data, env);
+            // Portals are painted over the rest of the window, so they get events first
+            for portal in data.portals() {
+                for child in portal.borrow_mut().widgets_mut() {
+                    child.event(ctx, event, data, env);
+                }
+            }
+            if let Some(widget) = &mut self.widget {
+                widget.event(ctx, event, data, env);
+            } else {
+                self.default_widget.event(ctx, event, data, env);
+            }
         }
 
         if self.vdom.is_none() {
-            self.init(ctx);
+            self.init(ctx, env);
         } else {
             self.run(ctx, data, env, force_update);
         }
+
+        // Drop targets have handled the release by now
+        if let Event::MouseUp(_) = event {
+            data.end_drag();
+        }
     }
 
     fn lifecycle(
@@ -203,20 +332,35 @@ impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
         } else {
             self.default_widget.lifecycle(ctx, event, data, env);
         }
+        // Portals added by the main widget are included
+        for portal in data.portals() {
+            for child in portal.borrow_mut().widgets_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
     }
 
     fn update(
         &mut self,
         ctx: &mut UpdateCtx,
-        _old_data: &DruidAppData,
+        old_data: &DruidAppData,
         data: &DruidAppData,
         env: &Env,
     ) {
+        // Components may read the env, so the tree is built again with the new env
+        if ctx.env_changed() && self.vdom.is_some() {
+            ctx.submit_command(REBUILD_ROOT.to(ctx.widget_id()));
+        }
         if let Some(widget) = &mut self.widget {
             widget.update(ctx, data, env);
         } else {
             self.default_widget.update(ctx, data, env);
         }
+        for portal in data.portals() {
+            for child in portal.borrow_mut().widgets_mut() {
+                child.update(ctx, old_data, data, env);
+            }
+        }
     }
 
     fn layout(
@@ -228,6 +372,8 @@ impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
     ) -> Size {
         let size;
 
+        // Popovers are laid out within the window, not within their parent
+        set_window_size(bc.max());
         if let Some(widget) = &mut self.widget {
             size = widget.layout(ctx, bc, data, env);
             widget.set_origin(ctx, data, env, Point::ZERO);
@@ -236,6 +382,14 @@ impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
             self.default_widget.set_origin(ctx, data, env, Point::ZERO);
         }
 
+        // Portals are laid out within the window, at its top-left corner
+        for portal in data.portals() {
+            for child in portal.borrow_mut().widgets_mut() {
+                child.layout(ctx, &bc.loosen(), data, env);
+                child.set_origin(ctx, data, env, Point::ZERO);
+            }
+        }
+
         size
     }
 
@@ -245,6 +399,11 @@ impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
         } else {
             self.default_widget.paint(ctx, data, env);
         }
+        for portal in data.portals() {
+            for child in portal.borrow_mut().widgets_mut() {
+                child.paint(ctx, data, env);
+            }
+        }
     }
 
     fn debug_state(&self, data: &DruidAppData) -> DebugState {
@@ -254,9 +413,20 @@ impl<RootElem: Element> Widget<DruidAppData> for RootWidget<RootElem> {
             self.default_widget.widget()
         };
 
+        let mut children = vec![child.debug_state(data)];
+        for portal in data.portals() {
+            let portal_children = portal.borrow();
+            children.extend(
+                portal_children
+                    .widgets()
+                    .iter()
+                    .map(|child| child.debug_state(data)),
+            );
+        }
+
         DebugState {
             display_name: self.short_type_name().to_string(),
-            children: vec![child.debug_state(data)],
+            children,
             ..Default::default()
         }
     }
@@ -297,7 +467,8 @@ impl<RootElem: Element> RootHandler<RootElem> {
         }
 
         let widget = self.root_widget;
-        let main_window = WindowDesc::new(widget);
+        let main_window =
+            WindowDesc::new(widget).menu(|_window_id, _data, _env| crate::app_menu::build_menu());
         let data = Default::default();
 
         AppLauncher::with_window(main_window).launch(data)
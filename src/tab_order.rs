@@ -0,0 +1,59 @@
+//! Explicit focus order set with [`ElementExt::tab_index`](crate::ElementExt::tab_index).
+//!
+//! Druid moves the focus in tree order when Tab is pressed. Each element with a tab index
+//! creates a widget which registers a tab stop in a registry shared by the window. When Tab
+//! is pressed while a tab stop has focus, the root widget looks up the stop with the next
+//! index, and focuses it; the widget of the stop then passes the focus on to its first
+//! focusable descendant. Otherwise, druid moves the focus in tree order as usual.
+
+use crate::glue::WidgetId;
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use tracing::trace;
+
+thread_local! {
+    // The tab stops of mounted widgets, in the order they were created.
+    static REGISTRY: RefCell<Vec<Weak<TabStop>>> = Default::default();
+}
+
+/// A widget with a tab index. Removed from the registry when the widget is dropped.
+pub(crate) struct TabStop {
+    pub index: Cell<u32>,
+    pub id: WidgetId,
+    /// Whether the widget or one of its descendants has focus.
+    pub has_focus: Cell<bool>,
+}
+
+pub(crate) fn register(index: u32, id: WidgetId) -> Rc<TabStop> {
+    trace!("Registering tab stop {:?} with index {}", id, index);
+    let stop = Rc::new(TabStop {
+        index: Cell::new(index),
+        id,
+        has_focus: Cell::new(false),
+    });
+    REGISTRY.with(|registry| registry.borrow_mut().push(Rc::downgrade(&stop)));
+    stop
+}
+
+/// Returns the tab stop to focus when Tab is pressed, or Shift+Tab if `backward` is true.
+///
+/// Returns `None` if the focus isn't in a tab stop, or if it's in the last one (the first one
+/// if `backward` is true); druid then moves the focus in tree order.
+pub(crate) fn next_stop(backward: bool) -> Option<WidgetId> {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.retain(|stop| stop.strong_count() > 0);
+
+        // Stops with the same index keep the order they were created in
+        let mut stops: Vec<_> = registry.iter().filter_map(Weak::upgrade).collect();
+        stops.sort_by_key(|stop| stop.index.get());
+
+        let current = stops.iter().position(|stop| stop.has_focus.get())?;
+        let next = if backward {
+            current.checked_sub(1)?
+        } else {
+            current + 1
+        };
+        stops.get(next).map(|stop| stop.id)
+    })
+}
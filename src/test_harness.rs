@@ -1,19 +1,222 @@
 //! Harness used to mock a druid-and-panoramix environment on a headless target.
 
+use crate::ctx::ReconcileStats;
 use crate::glue::{DebugState, DruidAppData};
 use crate::Element;
 use crate::RootWidget;
 
 use druid::tests::harness::Harness as DruidHarness;
+use druid::widget::TextBox;
 use druid::{
-    Command, Event, KeyEvent, Modifiers, MouseButton, MouseButtons, MouseEvent, Point, RawMods,
-    Vec2, WidgetId, WidgetState,
+    BoxConstraints, Command, Env, Event, EventCtx, KbKey, KeyEvent, LayoutCtx, LifeCycle,
+    LifeCycleCtx, Modifiers, MouseButton, MouseButtons, MouseEvent, PaintCtx, Point, RawMods, Rect,
+    Selector, Size, Target, UpdateCtx, Vec2, Widget, WidgetId, WidgetState,
 };
-use std::any::Any;
+use std::any::{type_name, Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
 
 // TODO
 // use tracing::instrument;
 
+/// Command sent by the harness to collect the widgets which have a name.
+pub(crate) const FIND_WIDGETS: Selector<Rc<FoundWidgets>> = Selector::new("panoramix.find-widgets");
+
+/// Payload of [`FIND_WIDGETS`].
+#[derive(Default)]
+pub(crate) struct FoundWidgets {
+    window_size: Cell<Size>,
+    widgets: RefCell<Vec<WidgetQuery>>,
+}
+
+impl FoundWidgets {
+    /// Set by the root widget, to compute the visibility of the other widgets.
+    pub(crate) fn set_window_size(&self, size: Size) {
+        self.window_size.set(size);
+    }
+
+    /// Add a widget to the results, unless its name is empty.
+    ///
+    /// `layout_rect` is in the coordinate space of `ctx`.
+    pub(crate) fn report(&self, ctx: &EventCtx, layout_rect: Rect, debug_state: DebugState) {
+        let name = displayed_value(&debug_state);
+        if name.is_empty() {
+            return;
+        }
+        let layout_rect = layout_rect.with_origin(ctx.to_window(layout_rect.origin()));
+        let window_rect = Rect::from_origin_size(Point::ZERO, self.window_size.get());
+        let is_visible =
+            layout_rect.area() > 0.0 && layout_rect.intersect(window_rect).area() > 0.0;
+        self.widgets.borrow_mut().push(WidgetQuery {
+            name,
+            layout_rect,
+            is_visible,
+            debug_state,
+        });
+    }
+}
+
+/// Returns the value displayed by a widget, eg the text of a button.
+///
+/// Druid widgets are often wrapped to give them an id or a controller; the value is held by
+/// the wrapped widget. Containers usually don't have a value, and are skipped.
+fn displayed_value(debug_state: &DebugState) -> String {
+    let mut debug_state = debug_state;
+    while debug_state.main_value.is_empty() && is_wrapper(debug_state) {
+        debug_state = &debug_state.children[0];
+    }
+    debug_state.main_value.clone()
+}
+
+fn is_wrapper(debug_state: &DebugState) -> bool {
+    let name = debug_state.display_name.as_str();
+    (name == "IdentityWrapper" || name == "ControllerHost") && debug_state.children.len() == 1
+}
+
+/// A widget found with [`Harness::find_by_name`] or [`Harness::find_all`].
+///
+/// This is a snapshot of the widget's state when it was found; it isn't updated afterwards.
+#[derive(Clone, Debug)]
+pub struct WidgetQuery {
+    name: String,
+    layout_rect: Rect,
+    is_visible: bool,
+    debug_state: DebugState,
+}
+
+impl WidgetQuery {
+    /// The value displayed by the widget, eg the text of a label or a button.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The widget's layout rect, in window coordinates.
+    pub fn layout_rect(&self) -> Rect {
+        self.layout_rect
+    }
+
+    /// Whether the widget has a non-zero size, and is at least partially inside the window.
+    ///
+    /// Clipping by scroll areas is ignored.
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn debug_state(&self) -> &DebugState {
+        &self.debug_state
+    }
+}
+
+/// Command sent by [`Harness::take_emitted_events`] to get the events emitted by the root element.
+pub(crate) const TAKE_EMITTED_EVENTS: Selector<Rc<RefCell<Vec<Box<dyn Any>>>>> =
+    Selector::new("panoramix.take-emitted-events");
+
+/// Command sent by [`Harness::last_reconcile_stats`] to get the stats of the root widget.
+pub(crate) const LAST_RECONCILE_STATS: Selector<Rc<Cell<ReconcileStats>>> =
+    Selector::new("panoramix.last-reconcile-stats");
+
+/// Command sent by [`Harness::press_key`] before the key events, so that the focused text
+/// widget can apply the key to its content.
+const SIMULATED_KEY: Selector<KbKey> = Selector::new("panoramix.simulated-key");
+
+thread_local! {
+    // Whether a harness is running; text widgets are only given simulated input then.
+    static SIMULATED_INPUT: Cell<bool> = Cell::new(false);
+}
+
+// Stops giving simulated input to new text widgets when the harness is done, even if the
+// test panicked.
+struct SimulatedInputGuard;
+
+impl SimulatedInputGuard {
+    fn new() -> Self {
+        SIMULATED_INPUT.with(|simulated_input| simulated_input.set(true));
+        SimulatedInputGuard
+    }
+}
+
+impl Drop for SimulatedInputGuard {
+    fn drop(&mut self) {
+        SIMULATED_INPUT.with(|simulated_input| simulated_input.set(false));
+    }
+}
+
+/// Wrap the druid text box of a text widget, so that it accepts the keys sent by
+/// [`Harness::press_key`] if it's created in a harness.
+///
+/// Outside of the harness, the text box is returned as-is.
+pub(crate) fn with_simulated_input(
+    textbox: TextBox<String>,
+    multiline: bool,
+) -> Box<dyn Widget<String>> {
+    if SIMULATED_INPUT.with(Cell::get) {
+        Box::new(SimulatedInput { textbox, multiline })
+    } else {
+        Box::new(textbox)
+    }
+}
+
+/// A text box which applies simulated keys to its content.
+///
+/// Druid text boxes get their input from the platform's text input system, which the mock
+/// window doesn't have; instead, keys are applied as if the cursor was at the end of the
+/// content, ignoring the selection.
+// TODO - Mock IME events, so that keys go through the text box itself
+struct SimulatedInput {
+    textbox: TextBox<String>,
+    multiline: bool,
+}
+
+impl Widget<String> for SimulatedInput {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut String, env: &Env) {
+        if let Event::Command(command) = event {
+            if let Some(key) = command.get(SIMULATED_KEY) {
+                if ctx.has_focus() {
+                    match key {
+                        KbKey::Character(chars) => data.push_str(chars),
+                        KbKey::Backspace => {
+                            data.pop();
+                        }
+                        KbKey::Enter if self.multiline => data.push('\n'),
+                        _ => {}
+                    }
+                    ctx.request_update();
+                }
+                return;
+            }
+        }
+        self.textbox.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &String, env: &Env) {
+        self.textbox.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &String, data: &String, env: &Env) {
+        self.textbox.update(ctx, old_data, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &String,
+        env: &Env,
+    ) -> Size {
+        self.textbox.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &String, env: &Env) {
+        self.textbox.paint(ctx, data, env);
+    }
+
+    fn debug_state(&self, data: &String) -> DebugState {
+        self.textbox.debug_state(data)
+    }
+}
+
 /// Harness used to create a mock test environment.
 ///
 /// A typical panoramix test will look like:
@@ -48,6 +251,7 @@ impl<RootElem: 'static + Element> Harness<'_, '_, RootElem> {
         element: RootElem,
         callback: impl FnMut(&mut Harness<'_, '_, RootElem>),
     ) {
+        let _simulated_input = SimulatedInputGuard::new();
         let root_widget = RootWidget::from_element(element);
         let data: DruidAppData = Default::default();
         let mut callback = callback;
@@ -102,11 +306,118 @@ impl<RootElem: 'static + Element> Harness<'_, '_, RootElem> {
         self.druid_harness.try_get_debug_state(widget_id)
     }
 
+    /// Move the mock clock forward, and send Timer events for every timer that expired.
+    pub fn move_timers_forward(&mut self, duration: Duration) {
+        self.druid_harness.move_timers_forward(duration)
+    }
+
+    /// Send an animation frame event to the widgets which requested one, as if `interval` had
+    /// elapsed since the previous frame.
+    pub fn animate_frame(&mut self, interval: Duration) {
+        self.druid_harness
+            .event(Event::AnimFrame(interval.as_nanos() as u64));
+    }
+
+    /// Resize the window, and lay it out again.
+    pub fn resize_window(&mut self, size: impl Into<Size>) {
+        self.druid_harness.event(Event::WindowSize(size.into()));
+    }
+
     /// Send a druid command through the widget tree.
     pub fn submit_command(&mut self, cmd: impl Into<Command>) {
         self.druid_harness.submit_command(cmd)
     }
 
+    /// Make the clipboard read and written by components and paste handlers hold `contents`,
+    /// instead of using the system clipboard. `None` simulates a clipboard holding something
+    /// other than text.
+    pub fn stub_clipboard(&mut self, contents: Option<String>) {
+        crate::clipboard::stub_clipboard(contents)
+    }
+
+    /// Make the file dialogs opened by [`FileButton`](crate::elements::FileButton) immediately
+    /// return `result` instead of showing a native dialog. `None` simulates a cancelled dialog.
+    pub fn stub_file_dialog(&mut self, result: Option<PathBuf>) {
+        crate::widgets::stub_file_dialog(result)
+    }
+
+    /// Simulate the user selecting the item with the given text in the application menu
+    /// declared with [`MenuBar`](crate::elements::MenuBar).
+    ///
+    /// Selecting a disabled item does nothing.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if there is no such item.
+    pub fn select_menu_item(&mut self, menu: &str, item: &str) {
+        let (menu_index, item_index, enabled) = crate::app_menu::find_item(menu, item)
+            .unwrap_or_else(|| panic!("error: no menu item {:?} in menu {:?}", item, menu));
+        if enabled {
+            self.druid_harness
+                .submit_command(crate::app_menu::MENU_ITEM_SELECTED.with((menu_index, item_index)));
+        }
+    }
+
+    /// Wait for the background tasks spawned with [`CompCtx::spawn_async`](crate::CompCtx::spawn_async)
+    /// to complete, then pass their output to their components.
+    ///
+    /// Panics if the tasks take more than a few seconds to complete.
+    pub fn pump_async_tasks(&mut self) {
+        let start = std::time::Instant::now();
+        while crate::async_task::tasks_in_flight() > 0 {
+            if start.elapsed() > Duration::from_secs(5) {
+                panic!("error: background tasks took too long to complete");
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // The mock window doesn't receive commands sent from other threads, so we send it ourselves.
+        self.druid_harness
+            .submit_command(crate::async_task::ASYNC_TASK_DONE);
+    }
+
+    /// Returns the events emitted by the root element since the last call, eg the events
+    /// bubbled up by the root component.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `Event` isn't the event type of the root element.
+    pub fn take_emitted_events<Event: 'static>(&mut self) -> Vec<Event> {
+        if TypeId::of::<Event>() != TypeId::of::<RootElem::Event>() {
+            panic!(
+                "error: 'take_emitted_events' called with {}, but the root element emits {}",
+                type_name::<Event>(),
+                type_name::<RootElem::Event>(),
+            );
+        }
+
+        let events = Rc::new(RefCell::new(Vec::new()));
+        self.druid_harness.submit_command(Command::new(
+            TAKE_EMITTED_EVENTS,
+            events.clone(),
+            Target::Global,
+        ));
+
+        let events = events.take();
+        events
+            .into_iter()
+            .map(|event| *event.downcast::<Event>().unwrap())
+            .collect()
+    }
+
+    /// Get the number of children created, updated and removed the last time the element
+    /// tree was reconciled.
+    ///
+    /// Useful to check that keyed children are reused instead of being created again.
+    pub fn last_reconcile_stats(&mut self) -> ReconcileStats {
+        let stats = Rc::new(Cell::new(ReconcileStats::default()));
+        self.druid_harness.submit_command(Command::new(
+            LAST_RECONCILE_STATS,
+            stats.clone(),
+            Target::Global,
+        ));
+        stats.get()
+    }
+
     /// Update the entire harness with a new element, which replaces the one passed to [`Harness::run_test_window`].
     ///
     /// This is especially useful to test implementations of [`VirtualDom::reconcile`](crate::internals::VirtualDom::reconcile)
@@ -148,6 +459,16 @@ impl<RootElem: 'static + Element> Harness<'_, '_, RootElem> {
             .event(Event::MouseUp(self.mouse_state.clone()));
     }
 
+    /// Send a Wheel event to the window, at the current mouse position.
+    pub fn mouse_wheel(&mut self, delta: impl Into<Vec2>) {
+        self.mouse_state.wheel_delta = delta.into();
+        self.mouse_state.button = MouseButton::None;
+
+        self.druid_harness
+            .event(Event::Wheel(self.mouse_state.clone()));
+        self.mouse_state.wheel_delta = Vec2::ZERO;
+    }
+
     /// Send events that lead to a given widget being clicked.
     ///
     /// Combines [`mouse_move`](Self::mouse_move), [`mouse_button_press`](Self::mouse_button_press), and [`mouse_button_release`](Self::mouse_button_release).
@@ -160,6 +481,92 @@ impl<RootElem: 'static + Element> Harness<'_, '_, RootElem> {
         self.mouse_button_release(MouseButton::Left);
     }
 
+    /// Send events that lead to the widget with the given name being clicked.
+    ///
+    /// The name of a widget is the value it displays, eg the text of a button, or the content
+    /// of a text box.
+    ///
+    /// Unlike [`mouse_click_on`](Self::mouse_click_on), this works with widgets nested in
+    /// other containers.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if no widget has this name. The panic message lists the available names.
+    pub fn click_on(&mut self, name: &str) {
+        let widget_center = self.find_widget_center(name);
+
+        self.mouse_move(widget_center);
+        self.mouse_button_press(MouseButton::Left);
+        self.mouse_button_release(MouseButton::Left);
+    }
+
+    /// Focus the text widget with the given name, then send a key press for each character
+    /// of `text`.
+    ///
+    /// The name of a text widget is its current content. `'\n'` is sent as the Enter key,
+    /// and `'\u{8}'` as the Backspace key. Characters are always inserted at the end of the
+    /// content.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if no widget has this name. The panic message lists the available names.
+    pub fn type_text(&mut self, name: &str, text: &str) {
+        self.click_on(name);
+        for c in text.chars() {
+            let key = match c {
+                '\n' => KbKey::Enter,
+                '\u{8}' => KbKey::Backspace,
+                c => KbKey::Character(c.to_string()),
+            };
+            self.press_key(key);
+        }
+    }
+
+    /// Find the first widget with the given name.
+    ///
+    /// The name of a widget is the value it displays, eg the text of a label or a button, or
+    /// the content of a text box. Widgets are searched in depth-first order.
+    pub fn find_by_name(&mut self, name: &str) -> Option<WidgetQuery> {
+        self.find_all(|widget| widget.name() == name)
+            .into_iter()
+            .next()
+    }
+
+    /// Find all widgets with a name matching the predicate, in depth-first order.
+    ///
+    /// See [`find_by_name`](Self::find_by_name) for what a widget's name is.
+    pub fn find_all(&mut self, predicate: impl Fn(&WidgetQuery) -> bool) -> Vec<WidgetQuery> {
+        let found = Rc::new(FoundWidgets::default());
+        self.druid_harness.submit_command(Command::new(
+            FIND_WIDGETS,
+            found.clone(),
+            Target::Global,
+        ));
+
+        let widgets = found.widgets.take();
+        widgets
+            .into_iter()
+            .filter(|widget| predicate(widget))
+            .collect()
+    }
+
+    fn find_widget_center(&mut self, name: &str) -> Point {
+        match self.find_by_name(name) {
+            Some(widget) => widget.layout_rect().center(),
+            None => {
+                let names: Vec<_> = self
+                    .find_all(|_| true)
+                    .into_iter()
+                    .map(|widget| widget.name)
+                    .collect();
+                panic!(
+                    "error: no widget named {:?}; available names: {:?}",
+                    name, names
+                )
+            }
+        }
+    }
+
     /// Use [`mouse_move`](Self::mouse_move) to set the internal mouse pos to the center of the given widget.
     pub fn mouse_move_to(&mut self, id: WidgetId) {
         let widget_rect = self.druid_harness.get_state(id).layout_rect();
@@ -175,4 +582,29 @@ impl<RootElem: 'static + Element> Harness<'_, '_, RootElem> {
         self.druid_harness.event(Event::KeyDown(event.clone()));
         self.druid_harness.event(Event::KeyUp(event.clone()));
     }
+
+    /// Send a KeyDown and a KeyUp event to the window, with the modifiers `mods` held.
+    pub fn keyboard_shortcut(&mut self, mods: RawMods, key: &str) {
+        let event = KeyEvent::for_test(mods, key);
+
+        self.druid_harness.event(Event::KeyDown(event.clone()));
+        self.druid_harness.event(Event::KeyUp(event.clone()));
+    }
+
+    /// Send a KeyDown and a KeyUp event to the window, for any key (eg Enter or Escape).
+    ///
+    /// If a text widget has focus, its content is edited as if the cursor was at the end of the
+    /// text; the cursor position and the selection are ignored, because IME events aren't mocked.
+    pub fn press_key(&mut self, key: KbKey) {
+        self.druid_harness
+            .submit_command(Command::new(SIMULATED_KEY, key.clone(), Target::Global));
+
+        let event = KeyEvent {
+            key,
+            ..Default::default()
+        };
+
+        self.druid_harness.event(Event::KeyDown(event.clone()));
+        self.druid_harness.event(Event::KeyUp(event.clone()));
+    }
 }
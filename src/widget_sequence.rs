@@ -3,8 +3,10 @@ use druid::kurbo::{Point, Rect, Size};
 use crate::flex::FlexParams;
 use crate::glue::DebugState;
 use crate::glue::DruidAppData;
+use crate::test_harness::FIND_WIDGETS;
 use druid::{
-    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UnitPoint,
+    UpdateCtx,
 };
 
 pub trait WidgetSequence {
@@ -15,11 +17,65 @@ pub trait WidgetSequence {
     fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget>;
 }
 
+/// The union of the layout rects of the widgets in `widget_seq`, or `None` if it has no
+/// widgets.
+pub(crate) fn sequence_layout_rect(widget_seq: &impl WidgetSequence) -> Option<Rect> {
+    widget_seq
+        .widgets()
+        .iter()
+        .map(|widget| widget.layout_rect())
+        .reduce(|rect, other_rect| rect.union(other_rect))
+}
+
+/// Whether `event` should be sent to hidden widgets, eg the inactive bodies of tabs.
+///
+/// Like [`Event::should_propagate_to_hidden`], except that hidden widgets aren't laid out, so
+/// the test harness can't find them.
+pub(crate) fn should_propagate_to_hidden(event: &Event) -> bool {
+    match event {
+        Event::Command(command) if command.is(FIND_WIDGETS) => false,
+        _ => event.should_propagate_to_hidden(),
+    }
+}
+
 // Essentially a boilerplate trait for SingleWidget
 pub trait FlexWidget {
     fn flex_params(&self) -> FlexParams;
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env);
+    /// Whether the widget is an empty spacer, which takes all the space its flex factor
+    /// allots to it on the container's main axis.
+    fn is_spacer(&self) -> bool {
+        false
+    }
+
+    /// Number of columns the widget takes up when it's the child of a grid.
+    fn grid_span(&self) -> usize {
+        1
+    }
+
+    /// Where the widget is placed when it's the child of a stack, if it overrides the
+    /// stack's own alignment.
+    fn stack_alignment(&self) -> Option<UnitPoint> {
+        None
+    }
+
+    /// Send an event to the widget.
+    ///
+    /// This is where the test harness finds widgets: each widget is reported along with its
+    /// layout rect, before the event reaches its own children. Implementations handle the
+    /// event in [`on_event`](Self::on_event).
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if let Some(found) = command.get(FIND_WIDGETS) {
+                if !self.is_spacer() {
+                    found.report(ctx, self.layout_rect(), self.debug_state(data));
+                }
+            }
+        }
+        self.on_event(ctx, event, data, env);
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env);
     fn lifecycle(
         &mut self,
         ctx: &mut LifeCycleCtx,
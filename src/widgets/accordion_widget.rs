@@ -0,0 +1,182 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::{FlexWidget, WidgetSequence};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{Button, Click, ControllerHost};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like TabsWidget, we implement Widget instead of FlexWidget, and wrap
+// AccordionWidget in a SingleWidget.
+
+type SectionButton = ControllerHost<Button<DruidAppData>, Click<DruidAppData>>;
+
+/// A column of section headers, each followed by the body of the section if it's expanded.
+///
+/// Clicking a header queues [`Action::Selected`] with the index of the section; expanding and
+/// collapsing sections is left to the element.
+pub struct AccordionWidget<Body: WidgetSequence> {
+    pub titles: Vec<String>,
+    pub headers: Vec<SingleWidget<SectionButton>>,
+    pub bodies: Vec<Option<Body>>,
+    id: WidgetId,
+}
+
+impl<Body: WidgetSequence> AccordionWidget<Body> {
+    pub fn new(titles: Vec<String>, bodies: Vec<Option<Body>>, id: WidgetId) -> Self {
+        AccordionWidget {
+            headers: Self::new_headers(&titles, id),
+            titles,
+            bodies,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the section headers.
+    pub fn set_titles(&mut self, titles: Vec<String>, ctx: &mut EventCtx) {
+        self.headers = Self::new_headers(&titles, self.id);
+        self.titles = titles;
+        ctx.children_changed();
+    }
+
+    fn new_headers(titles: &[String], id: WidgetId) -> Vec<SingleWidget<SectionButton>> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| {
+                let header =
+                    Button::new(title.clone()).on_click(move |_, data: &mut DruidAppData, _| {
+                        data.queue_action(id, Action::Selected(index))
+                    });
+                SingleWidget::new(header, Default::default())
+            })
+            .collect()
+    }
+}
+
+impl<Body: WidgetSequence> Widget<DruidAppData> for AccordionWidget<Body> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for header in &mut self.headers {
+            header.event(ctx, event, data, env);
+        }
+        for child in self.bodies.iter_mut().flatten().flat_map(Body::widgets_mut) {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for header in &mut self.headers {
+            header.lifecycle(ctx, event, data, env);
+        }
+        for child in self.bodies.iter_mut().flatten().flat_map(Body::widgets_mut) {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for header in &mut self.headers {
+            header.update(ctx, old_data, data, env);
+        }
+        for child in self.bodies.iter_mut().flatten().flat_map(Body::widgets_mut) {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Accordion");
+        let child_bc = bc.loosen();
+
+        // Each header is followed by the body of its section, if it's expanded
+        let mut size = Size::ZERO;
+        for (header, body) in self.headers.iter_mut().zip(&mut self.bodies) {
+            let header_size = header.layout(ctx, &child_bc, data, env);
+            header.set_origin(ctx, data, env, Point::new(0.0, size.height));
+            size = Size::new(
+                size.width.max(header_size.width),
+                size.height + header_size.height,
+            );
+
+            for child in body.iter_mut().flat_map(Body::widgets_mut) {
+                let child_size = child.layout(ctx, &child_bc, data, env);
+                child.set_origin(ctx, data, env, Point::new(0.0, size.height));
+                size = Size::new(
+                    size.width.max(child_size.width),
+                    size.height + child_size.height,
+                );
+            }
+        }
+
+        let size = bc.constrain(size);
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for header in &mut self.headers {
+            header.paint(ctx, data, env);
+        }
+        for child in self.bodies.iter_mut().flatten().flat_map(Body::widgets_mut) {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let body_state = self
+            .bodies
+            .iter()
+            .flatten()
+            .flat_map(Body::widgets)
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let expanded: Vec<_> = self
+            .titles
+            .iter()
+            .zip(&self.bodies)
+            .filter(|(_, body)| body.is_some())
+            .map(|(title, _)| title.as_str())
+            .collect();
+        let mut other_values = HashMap::new();
+        other_values.insert("expanded".to_string(), expanded.join(", "));
+        DebugState {
+            display_name: "Accordion".to_string(),
+            other_values,
+            children: body_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleAccordionWidget<Body> = SingleWidget<AccordionWidget<Body>>;
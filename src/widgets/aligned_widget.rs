@@ -0,0 +1,243 @@
+use crate::flex::FlexParams;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::FlexWidget;
+use crate::widget_sequence::WidgetSequence;
+
+use crate::glue::DebugState;
+use druid::kurbo::{Point, Rect, Size};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UnitPoint,
+    UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like SingleSpanWidget, SingleAlignedWidget implements FlexWidget directly,
+// because the Stack container needs to know it's aligned.
+
+/// A widget which places its children within the space it's given, like druid's `Align`.
+///
+/// Along each axis, the widget is as big as the constraints allow if they're bounded, and
+/// as big as its children otherwise. If it has no children, it takes no space.
+pub struct AlignedWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub alignment: UnitPoint,
+    id: WidgetId,
+}
+
+pub struct SingleAlignedWidget<Children: WidgetSequence> {
+    pub pod: WidgetPod<DruidAppData, AlignedWidget<Children>>,
+    pub flex: FlexParams,
+}
+
+impl<Children: WidgetSequence> AlignedWidget<Children> {
+    pub fn new(children: Children, alignment: UnitPoint, id: WidgetId) -> Self {
+        AlignedWidget {
+            children,
+            alignment,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> SingleAlignedWidget<Children> {
+    pub fn new(widget: AlignedWidget<Children>, flex: FlexParams) -> Self {
+        SingleAlignedWidget {
+            pod: WidgetPod::new(widget),
+            flex,
+        }
+    }
+
+    pub fn widget(&self) -> &AlignedWidget<Children> {
+        self.pod.widget()
+    }
+
+    pub fn widget_mut(&mut self) -> &mut AlignedWidget<Children> {
+        self.pod.widget_mut()
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for AlignedWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Aligned");
+        let mut child_widgets = self.children.widgets_mut();
+        if child_widgets.is_empty() {
+            return bc.min();
+        }
+
+        let loosened_bc = bc.loosen();
+        let mut children_size = Size::ZERO;
+        for child in &mut child_widgets {
+            let child_size = child.layout(ctx, &loosened_bc, data, env);
+            children_size = Size::new(
+                children_size.width.max(child_size.width),
+                children_size.height.max(child_size.height),
+            );
+        }
+
+        let mut my_size = children_size;
+        if bc.is_width_bounded() {
+            my_size.width = bc.max().width;
+        }
+        if bc.is_height_bounded() {
+            my_size.height = bc.max().height;
+        }
+        let my_size = bc.constrain(my_size);
+
+        for child in &mut child_widgets {
+            let extra = my_size - child.layout_rect().size();
+            let extra = Size::new(extra.width.max(0.0), extra.height.max(0.0));
+            let origin = self
+                .alignment
+                .resolve(Rect::from_origin_size(Point::ZERO, extra))
+                .round();
+            child.set_origin(ctx, data, env, origin);
+        }
+
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Aligned".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Children: WidgetSequence> FlexWidget for SingleAlignedWidget<Children> {
+    fn flex_params(&self) -> FlexParams {
+        self.flex
+    }
+
+    fn stack_alignment(&self) -> Option<UnitPoint> {
+        Some(self.pod.widget().alignment)
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.pod.layout(ctx, bc, data, env)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        self.pod.paint_rect()
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &DruidAppData, env: &Env, origin: Point) {
+        self.pod.set_origin(ctx, data, env, origin)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        self.pod.layout_rect()
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        self.pod.baseline_offset()
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, data, env);
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(data)
+    }
+}
+
+impl<Children: WidgetSequence> WidgetSequence for SingleAlignedWidget<Children> {
+    fn widgets(&self) -> Vec<&dyn FlexWidget> {
+        vec![self]
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget> {
+        vec![self]
+    }
+}
@@ -7,16 +7,19 @@ use crate::glue::DebugState;
 use druid::kurbo::{Point, Rect, Size};
 use druid::widget::{Button, Click, ControllerHost, IdentityWrapper};
 use druid::{
-    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
-    Widget, WidgetExt, WidgetPod,
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    RenderContext, UpdateCtx, Widget, WidgetExt, WidgetPod,
 };
 
+const DISABLED_OVERLAY_ALPHA: f64 = 0.6;
+
 pub struct ButtonWidget {
     pub pod: WidgetPod<
         DruidAppData,
         IdentityWrapper<ControllerHost<Button<DruidAppData>, Click<DruidAppData>>>,
     >,
     pub flex: FlexParams,
+    pub disabled: bool,
     id: WidgetId,
 }
 
@@ -29,6 +32,7 @@ impl ButtonWidget {
         ButtonWidget {
             pod: WidgetPod::new(button),
             flex,
+            disabled: false,
             id,
         }
     }
@@ -36,6 +40,13 @@ impl ButtonWidget {
     pub fn id(&self) -> WidgetId {
         self.id
     }
+
+    /// Enable or disable the button, and request a repaint.
+    pub fn set_disabled(&mut self, disabled: bool, ctx: &mut EventCtx) {
+        self.disabled = disabled;
+        self.pod
+            .with_event_context(ctx, |_button, ctx: &mut EventCtx| ctx.request_paint());
+    }
 }
 
 impl FlexWidget for ButtonWidget {
@@ -43,7 +54,13 @@ impl FlexWidget for ButtonWidget {
         self.flex
     }
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if self.disabled {
+            match event {
+                Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) => return,
+                _ => {}
+            }
+        }
         self.pod.event(ctx, event, data, env);
     }
 
@@ -95,10 +112,24 @@ impl FlexWidget for ButtonWidget {
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
         self.pod.paint(ctx, data, env);
+
+        if self.disabled {
+            // Grey out the button by painting over it with the background color
+            let overlay = env
+                .get(theme::WINDOW_BACKGROUND_COLOR)
+                .with_alpha(DISABLED_OVERLAY_ALPHA);
+            ctx.fill(self.pod.layout_rect(), &overlay);
+        }
     }
 
     fn debug_state(&self, data: &DruidAppData) -> DebugState {
-        self.pod.widget().debug_state(data)
+        let mut debug_state = self.pod.widget().debug_state(data);
+        if self.disabled {
+            debug_state
+                .other_values
+                .insert("disabled".to_string(), "true".to_string());
+        }
+        debug_state
     }
 }
 
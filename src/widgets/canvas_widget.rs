@@ -0,0 +1,134 @@
+use crate::elements::CanvasMouse;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
+    UpdateCtx, Widget,
+};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use tracing::trace;
+
+// Note: Like DividerWidget, we implement Widget instead of FlexWidget, and wrap
+// CanvasWidget in a SingleWidget.
+
+// Used when the constraints don't bound the canvas
+const DEFAULT_SIZE: f64 = 100.0;
+
+pub type PaintFn<State> = Rc<dyn Fn(&mut PaintCtx, Size, &State)>;
+
+/// Paints its area with a user-provided function, given the state the canvas was last built
+/// with.
+///
+/// Mouse presses, moves and releases over the canvas queue [`Action::CanvasMouse`]. After a
+/// press, the widget is active until the mouse is released, so that drags which leave the
+/// canvas are still reported.
+pub struct CanvasWidget<State> {
+    state: State,
+    paint: PaintFn<State>,
+    id: WidgetId,
+}
+
+impl<State: Debug> CanvasWidget<State> {
+    pub fn new(state: State, paint: PaintFn<State>, id: WidgetId) -> Self {
+        CanvasWidget { state, paint, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the paint function, without requesting a repaint.
+    pub fn set_paint(&mut self, paint: PaintFn<State>) {
+        self.paint = paint;
+    }
+
+    /// Replace the state passed to the paint function, and request a repaint.
+    pub fn set_state(&mut self, state: State, ctx: &mut EventCtx) {
+        self.state = state;
+        ctx.request_paint();
+    }
+}
+
+impl<State: Debug> Widget<DruidAppData> for CanvasWidget<State> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, _env: &Env) {
+        let mouse_event = match event {
+            Event::MouseDown(mouse_event) => {
+                ctx.set_active(true);
+                CanvasMouse::Down(mouse_event.pos)
+            }
+            Event::MouseMove(mouse_event) if ctx.is_hot() || ctx.is_active() => {
+                CanvasMouse::Move(mouse_event.pos)
+            }
+            Event::MouseUp(mouse_event) if ctx.is_active() => {
+                ctx.set_active(false);
+                CanvasMouse::Up(mouse_event.pos)
+            }
+            _ => return,
+        };
+        trace!("Canvas {:?} got {:?}", self.id(), mouse_event);
+        data.queue_action(self.id(), Action::CanvasMouse(mouse_event));
+    }
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) -> Size {
+        bc.debug_check("Canvas");
+        // The canvas fills the space it's given
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            DEFAULT_SIZE
+        };
+        let height = if bc.is_height_bounded() {
+            bc.max().height
+        } else {
+            DEFAULT_SIZE
+        };
+        bc.constrain(Size::new(width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, _env: &Env) {
+        let size = ctx.size();
+        (self.paint)(ctx, size, &self.state);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Canvas".to_string(),
+            main_value: format!("{:?}", self.state),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleCanvasWidget<State> = SingleWidget<CanvasWidget<State>>;
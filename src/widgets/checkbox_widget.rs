@@ -2,10 +2,10 @@ use crate::glue::{Action, DruidAppData, WidgetId};
 use crate::widgets::SingleWidget;
 
 use crate::glue::DebugState;
-use druid::widget::{Checkbox, IdentityWrapper};
+use druid::widget::Checkbox;
 use druid::{
     BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
-    Size, UpdateCtx, Widget, WidgetExt, WidgetPod,
+    Size, UpdateCtx, Widget, WidgetPod,
 };
 
 use tracing::trace;
@@ -14,16 +14,20 @@ use tracing::trace;
 // because otherwise  mouse events are filtered correctly (eg we get mouse events even if the
 // cursor isn't over our checkbox)
 
+// Note: The widget id is carried by CheckboxWidget itself (see Widget::id below) rather than by
+// the inner checkbox, so that the id's layout rect is expressed relative to the parent flex
+// container, like every other widget.
+
 pub struct CheckboxWidget {
     pub value: bool,
-    pub pod: WidgetPod<bool, IdentityWrapper<Checkbox>>,
+    pub pod: WidgetPod<bool, Checkbox>,
     id: WidgetId,
 }
 
 impl CheckboxWidget {
     pub fn new(text: String, value: bool, id: WidgetId) -> Self {
         // TODO - handle label in a more idiomatic way
-        let checkbox = Checkbox::new(text).with_id(id);
+        let checkbox = Checkbox::new(text);
 
         CheckboxWidget {
             value,
@@ -48,16 +52,12 @@ impl CheckboxWidget {
 
 impl Widget<DruidAppData> for CheckboxWidget {
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
-        if let Event::MouseUp(_) = event {
-            if ctx.is_hot() {
-                trace!("Checkbox {:?} value changed: {}", self.id(), self.value);
-                data.queue_action(self.id(), Action::Clicked);
-            }
-        }
-        if let Event::MouseDown(_) = event {
-            trace!("MouseDown");
-        }
+        let prev_value = self.value;
         self.pod.event(ctx, event, &mut self.value, env);
+        if self.value != prev_value {
+            trace!("Checkbox {:?} value changed: {}", self.id(), self.value);
+            data.queue_action(self.id(), Action::Clicked);
+        }
     }
 
     fn lifecycle(
@@ -96,6 +96,10 @@ impl Widget<DruidAppData> for CheckboxWidget {
         self.pod.paint(ctx, &mut self.value, env);
     }
 
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
     fn debug_state(&self, _data: &DruidAppData) -> DebugState {
         self.pod.widget().debug_state(&self.value)
     }
@@ -0,0 +1,129 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, MouseButton,
+    PaintCtx, Point, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// ClickAreaWidget in a SingleWidget.
+
+/// An invisible click target covering its children.
+///
+/// Queues [`Action::Clicked`] when clicked, unless a child widget handled the click (for
+/// instance, a button queued its own action).
+pub struct ClickAreaWidget<Children: WidgetSequence> {
+    pub children: Children,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> ClickAreaWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        ClickAreaWidget { children, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ClickAreaWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // Children get the event first, so that inner click handlers take priority
+        let action_count = data.action_count();
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+        let child_acted = data.action_count() > action_count;
+
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button == MouseButton::Left => {
+                ctx.set_active(true);
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                if ctx.is_active() && ctx.is_hot() && !child_acted && !ctx.is_handled() {
+                    trace!("ClickArea {:?} clicked", self.id());
+                    data.queue_action(self.id(), Action::Clicked);
+                    ctx.set_handled();
+                }
+                ctx.set_active(false);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "ClickArea".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleClickAreaWidget<Children> = SingleWidget<ClickAreaWidget<Children>>;
@@ -23,7 +23,6 @@ impl<Children: WidgetSequence> UnwrapSingleWidget<Children> {
         UnwrapSingleWidget { children }
     }
 
-    #[allow(dead_code)]
     fn child(&self) -> &dyn FlexWidget {
         let mut children = self.children.widgets();
         assert_eq!(children.len(), 1);
@@ -79,9 +78,8 @@ impl<Children: WidgetSequence> Widget<DruidAppData> for UnwrapSingleWidget<Child
         self.child_mut().paint(ctx, data, env);
     }
 
-    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
-        todo!();
-        //self.child_mut().widget().debug_state(&self.value)
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        self.child().debug_state(data)
     }
 }
 
@@ -135,7 +133,7 @@ impl<Children: WidgetSequence> FlexWidget for ClickableWidget<Children> {
         self.flex
     }
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
         self.pod.event(ctx, event, data, env);
     }
 
@@ -0,0 +1,212 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    LinearGradient, MouseButton, PaintCtx, Point, Rect, RenderContext, Size, UnitPoint, UpdateCtx,
+    Widget,
+};
+
+use tracing::trace;
+
+// Note: Like DividerWidget, we implement Widget instead of FlexWidget, and wrap
+// ColorPickerWidget in a SingleWidget.
+
+const DEFAULT_WIDTH: f64 = 200.0;
+const SWATCH_HEIGHT: f64 = 24.0;
+const TRACK_HEIGHT: f64 = 16.0;
+const TRACK_SPACING: f64 = 6.0;
+const KNOB_WIDTH: f64 = 4.0;
+
+/// Displays a preview of a color, and a track for each of its channels.
+///
+/// Pressing or dragging the mouse on a track sets the channel, and queues
+/// [`Action::ColorChanged`].
+pub struct ColorPickerWidget {
+    // Red, green, blue and alpha, from 0.0 to 1.0
+    pub channels: [f64; 4],
+    pub alpha: bool,
+    // The channel being dragged
+    active_channel: Option<usize>,
+    id: WidgetId,
+}
+
+/// The rect of the track for the given channel, in a picker of the given width.
+pub(crate) fn track_rect(channel: usize, width: f64) -> Rect {
+    let y0 = SWATCH_HEIGHT + TRACK_SPACING + channel as f64 * (TRACK_HEIGHT + TRACK_SPACING);
+    Rect::new(0.0, y0, width, y0 + TRACK_HEIGHT)
+}
+
+impl ColorPickerWidget {
+    pub fn new(color: &Color, alpha: bool, id: WidgetId) -> Self {
+        ColorPickerWidget {
+            channels: channels(color),
+            alpha,
+            active_channel: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    pub fn color(&self) -> Color {
+        let [r, g, b, a] = self.channels;
+        Color::rgba(r, g, b, if self.alpha { a } else { 1.0 })
+    }
+
+    /// Display the given color, and request a repaint.
+    pub fn set_color(&mut self, color: &Color, ctx: &mut EventCtx) {
+        self.channels = channels(color);
+        ctx.request_paint();
+    }
+
+    /// Show or hide the alpha track, and request a new layout.
+    pub fn set_alpha(&mut self, alpha: bool, ctx: &mut EventCtx) {
+        self.alpha = alpha;
+        ctx.request_layout();
+    }
+
+    fn channel_count(&self) -> usize {
+        if self.alpha {
+            4
+        } else {
+            3
+        }
+    }
+
+    fn channel_at(&self, pos: Point, width: f64) -> Option<usize> {
+        (0..self.channel_count()).find(|channel| track_rect(*channel, width).contains(pos))
+    }
+
+    // The value of the dragged channel follows the mouse, within the track's bounds
+    fn drag_to(&mut self, channel: usize, pos: Point, width: f64, data: &mut DruidAppData) {
+        let value = (pos.x / width).max(0.0).min(1.0);
+        if self.channels[channel] != value {
+            self.channels[channel] = value;
+            trace!(
+                "ColorPicker {:?} channel {} set to {}",
+                self.id(),
+                channel,
+                value
+            );
+            data.queue_action(self.id(), Action::ColorChanged(self.color()));
+        }
+    }
+}
+
+fn channels(color: &Color) -> [f64; 4] {
+    let (r, g, b, a) = color.as_rgba();
+    [r, g, b, a]
+}
+
+impl Widget<DruidAppData> for ColorPickerWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, _env: &Env) {
+        let width = ctx.size().width;
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button == MouseButton::Left => {
+                if let Some(channel) = self.channel_at(mouse_event.pos, width) {
+                    self.active_channel = Some(channel);
+                    ctx.set_active(true);
+                    self.drag_to(channel, mouse_event.pos, width, data);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseMove(mouse_event) => {
+                if let Some(channel) = self.active_channel {
+                    self.drag_to(channel, mouse_event.pos, width, data);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                self.active_channel = None;
+                ctx.set_active(false);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) -> Size {
+        bc.debug_check("ColorPicker");
+        let last_track = track_rect(self.channel_count() - 1, DEFAULT_WIDTH);
+        bc.constrain(Size::new(DEFAULT_WIDTH, last_track.y1))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        let width = ctx.size().width;
+        let border = env.get(theme::BORDER_DARK);
+
+        let swatch = Rect::new(0.0, 0.0, width, SWATCH_HEIGHT);
+        ctx.fill(swatch, &self.color());
+        ctx.stroke(swatch, &border, 1.0);
+
+        // Each track shows the colors obtained by only changing its channel
+        for channel in 0..self.channel_count() {
+            let rect = track_rect(channel, width);
+            let mut start = self.channels;
+            let mut end = self.channels;
+            start[channel] = 0.0;
+            end[channel] = 1.0;
+            let gradient = LinearGradient::new(
+                UnitPoint::LEFT,
+                UnitPoint::RIGHT,
+                (
+                    Color::rgba(start[0], start[1], start[2], start[3]),
+                    Color::rgba(end[0], end[1], end[2], end[3]),
+                ),
+            );
+            ctx.fill(rect, &gradient);
+            ctx.stroke(rect, &border, 1.0);
+
+            let knob_x = rect.x0 + self.channels[channel] * rect.width();
+            let knob = Rect::new(
+                knob_x - KNOB_WIDTH / 2.0,
+                rect.y0,
+                knob_x + KNOB_WIDTH / 2.0,
+                rect.y1,
+            );
+            ctx.fill(knob, &env.get(theme::FOREGROUND_LIGHT));
+            ctx.stroke(knob, &border, 1.0);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "ColorPicker".to_string(),
+            main_value: format!("#{:08x}", self.color().as_rgba_u32()),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleColorPickerWidget = SingleWidget<ColorPickerWidget>;
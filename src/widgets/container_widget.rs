@@ -0,0 +1,97 @@
+use crate::glue::DruidAppData;
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::{Container, SingleWidget};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+// Container paints its background and border around a single child widget; ContainerContent
+// is that child, and lays out the children of the Container element on top of each other.
+
+pub struct ContainerContent<Children: WidgetSequence> {
+    pub children: Children,
+}
+
+impl<Children: WidgetSequence> ContainerContent<Children> {
+    pub fn new(children: Children) -> Self {
+        ContainerContent { children }
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ContainerContent<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "ContainerContent".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleContainerWidget<Children> =
+    SingleWidget<Container<DruidAppData, ContainerContent<Children>>>;
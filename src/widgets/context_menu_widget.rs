@@ -0,0 +1,270 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, MouseButton,
+    PaintCtx, Point, Rect, RenderContext, Size, TextLayout, UpdateCtx, Vec2, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like TooltipWidget, we implement Widget instead of FlexWidget, and wrap
+// ContextMenuWidget in a SingleWidget.
+
+pub(crate) const MENU_ITEM_HEIGHT: f64 = 24.0;
+const MENU_ITEM_PADDING: f64 = 8.0;
+const MENU_MIN_WIDTH: f64 = 120.0;
+// Painted above siblings
+const MENU_Z_INDEX: u32 = 1;
+
+/// Opens a menu under the mouse when the children are right-clicked.
+///
+/// A right click queues [`Action::RightClicked`]; the menu is only displayed once its position
+/// is set with [`set_menu_pos`](ContextMenuWidget::set_menu_pos). While the menu is open, the
+/// widget is active, so that it gets all mouse events in the window: a mouse press on an
+/// enabled item queues [`Action::Selected`], and a mouse press outside of the menu queues
+/// [`Action::Clicked`].
+pub struct ContextMenuWidget<Children: WidgetSequence> {
+    pub children: Children,
+    // The text of each item, and whether it's enabled
+    items: Vec<(String, bool)>,
+    layouts: Vec<TextLayout<String>>,
+    menu_pos: Option<Point>,
+    menu_width: f64,
+    hovered_item: Option<usize>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> ContextMenuWidget<Children> {
+    pub fn new(
+        children: Children,
+        items: Vec<(String, bool)>,
+        menu_pos: Option<Point>,
+        id: WidgetId,
+    ) -> Self {
+        let mut widget = ContextMenuWidget {
+            children,
+            items: Vec::new(),
+            layouts: Vec::new(),
+            menu_pos,
+            menu_width: MENU_MIN_WIDTH,
+            hovered_item: None,
+            id,
+        };
+        widget.update_items(items);
+        widget
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the items of the menu, and request a new layout.
+    pub fn set_items(&mut self, items: Vec<(String, bool)>, ctx: &mut EventCtx) {
+        self.update_items(items);
+        ctx.request_layout();
+    }
+
+    /// Open the menu at the given position, relative to the widget, or close it if `menu_pos`
+    /// is `None`.
+    pub fn set_menu_pos(&mut self, menu_pos: Option<Point>, ctx: &mut EventCtx) {
+        self.menu_pos = menu_pos;
+        self.hovered_item = None;
+        ctx.set_active(menu_pos.is_some());
+        ctx.request_paint();
+    }
+
+    fn update_items(&mut self, items: Vec<(String, bool)>) {
+        self.layouts = items
+            .iter()
+            .map(|(text, enabled)| {
+                let mut layout = TextLayout::from_text(text.clone());
+                if *enabled {
+                    layout.set_text_color(theme::TEXT_COLOR);
+                } else {
+                    layout.set_text_color(theme::DISABLED_TEXT_COLOR);
+                }
+                layout
+            })
+            .collect();
+        self.items = items;
+    }
+
+    fn item_rect(&self, menu_pos: Point, index: usize) -> Rect {
+        let origin = menu_pos + Vec2::new(0.0, index as f64 * MENU_ITEM_HEIGHT);
+        Rect::from_origin_size(origin, Size::new(self.menu_width, MENU_ITEM_HEIGHT))
+    }
+
+    fn item_at(&self, pos: Point) -> Option<usize> {
+        let menu_pos = self.menu_pos?;
+        (0..self.items.len()).find(|index| self.item_rect(menu_pos, *index).contains(pos))
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ContextMenuWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if self.menu_pos.is_some() {
+            match event {
+                Event::MouseDown(mouse_event) => {
+                    match self.item_at(mouse_event.pos) {
+                        Some(index) if self.items[index].1 => {
+                            trace!("ContextMenu {:?} item {} selected", self.id(), index);
+                            data.queue_action(self.id(), Action::Selected(index));
+                        }
+                        // Pressing a disabled item does nothing
+                        Some(_) => {}
+                        None if mouse_event.button == MouseButton::Right && ctx.is_hot() => {
+                            trace!(
+                                "ContextMenu {:?} reopened at {}",
+                                self.id(),
+                                mouse_event.pos
+                            );
+                            data.queue_action(self.id(), Action::RightClicked(mouse_event.pos));
+                        }
+                        None => {
+                            trace!("ContextMenu {:?} dismissed", self.id());
+                            data.queue_action(self.id(), Action::Clicked);
+                        }
+                    }
+                    ctx.set_handled();
+                    return;
+                }
+                Event::MouseUp(_) => {
+                    ctx.set_handled();
+                    return;
+                }
+                Event::MouseMove(mouse_event) => {
+                    let hovered_item = self.item_at(mouse_event.pos);
+                    if hovered_item != self.hovered_item {
+                        self.hovered_item = hovered_item;
+                        ctx.request_paint();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        if let Event::MouseDown(mouse_event) = event {
+            if mouse_event.button == MouseButton::Right && ctx.is_hot() {
+                trace!("ContextMenu {:?} opened at {}", self.id(), mouse_event.pos);
+                data.queue_action(self.id(), Action::RightClicked(mouse_event.pos));
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut menu_width = MENU_MIN_WIDTH;
+        for layout in &mut self.layouts {
+            layout.rebuild_if_needed(ctx.text(), env);
+            menu_width = menu_width.max(layout.size().width + 2.0 * MENU_ITEM_PADDING);
+        }
+        self.menu_width = menu_width;
+
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if let Some(menu_pos) = self.menu_pos {
+            let item_rects: Vec<_> = (0..self.items.len())
+                .map(|index| self.item_rect(menu_pos, index))
+                .collect();
+            let layouts = self.layouts.clone();
+            let hovered_item = self.hovered_item.filter(|index| self.items[*index].1);
+            let background = env.get(theme::BACKGROUND_LIGHT);
+            let highlight = env.get(theme::PRIMARY_DARK);
+            let border = env.get(theme::BORDER_DARK);
+            ctx.paint_with_z_index(MENU_Z_INDEX, move |ctx| {
+                let menu_rect = item_rects.iter().fold(
+                    Rect::from_origin_size(menu_pos, Size::ZERO),
+                    |rect, item| rect.union(*item),
+                );
+                ctx.fill(menu_rect, &background);
+                for (index, (rect, layout)) in item_rects.iter().zip(layouts.iter()).enumerate() {
+                    if hovered_item == Some(index) {
+                        ctx.fill(*rect, &highlight);
+                    }
+                    let text_y = rect.y0 + (rect.height() - layout.size().height) / 2.0;
+                    layout.draw(ctx, Point::new(rect.x0 + MENU_ITEM_PADDING, text_y));
+                }
+                ctx.stroke(menu_rect, &border, 1.0);
+            });
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "ContextMenu".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        if self.menu_pos.is_some() {
+            let items: Vec<_> = self.items.iter().map(|(text, _)| text.as_str()).collect();
+            debug_state
+                .other_values
+                .insert("menu".to_string(), items.join(", "));
+        }
+        debug_state
+    }
+}
+
+pub type SingleContextMenuWidget<Children> = SingleWidget<ContextMenuWidget<Children>>;
@@ -0,0 +1,137 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, TimerToken, UpdateCtx, Widget,
+};
+use std::time::Duration;
+
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// DebounceWidget in a SingleWidget.
+
+// The widget only holds the timer; the pending event and its deadline are stored in the
+// element's state. Restarting the timer replaces the token, so timers requested before the
+// last restart are ignored when they fire.
+
+pub struct DebounceWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub timer: Option<TimerToken>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> DebounceWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        DebounceWidget {
+            children,
+            timer: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Request a timer firing after `delay`, replacing the previous one.
+    pub fn restart_timer(&mut self, delay: Duration, ctx: &mut EventCtx) {
+        self.timer = Some(ctx.request_timer(delay));
+    }
+
+    /// Ignore the currently running timer, if any.
+    pub fn cancel_timer(&mut self) {
+        self.timer = None;
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for DebounceWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Timer(token) = event {
+            if self.timer == Some(*token) {
+                trace!("Debounce {:?} timer fired", self.id());
+                self.timer = None;
+                data.queue_action(self.id(), Action::TimerFired);
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Debounce".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleDebounceWidget<Children> = SingleWidget<DebounceWidget<Children>>;
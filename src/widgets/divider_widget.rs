@@ -0,0 +1,106 @@
+use crate::flex::Axis;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+/// A thin line, spanning the available space along its axis.
+pub struct DividerWidget {
+    pub axis: Axis,
+    // Uses the theme's border color if None
+    pub color: Option<Color>,
+    pub thickness: f64,
+    id: WidgetId,
+}
+
+impl DividerWidget {
+    pub fn new(axis: Axis, color: Option<Color>, thickness: f64, id: WidgetId) -> Self {
+        DividerWidget {
+            axis,
+            color,
+            thickness,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl Widget<DruidAppData> for DividerWidget {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut DruidAppData, _env: &Env) {
+    }
+
+    fn lifecycle(
+        &mut self,
+        _ctx: &mut LifeCycleCtx,
+        _event: &LifeCycle,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) -> Size {
+        bc.debug_check("Divider");
+        // Fill the available length, unless it's infinite (eg in a scroll area)
+        let length = if bc.is_width_bounded() && self.axis == Axis::Horizontal
+            || bc.is_height_bounded() && self.axis == Axis::Vertical
+        {
+            self.axis.major(bc.max())
+        } else {
+            self.axis.major(bc.min())
+        };
+        let size = bc.constrain(self.axis.pack(length, self.thickness));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        let color = self
+            .color
+            .clone()
+            .unwrap_or_else(|| env.get(theme::BORDER_LIGHT));
+        let rect = ctx.size().to_rect();
+        ctx.fill(rect, &color);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let main_value = match self.axis {
+            Axis::Horizontal => "horizontal",
+            Axis::Vertical => "vertical",
+        };
+        DebugState {
+            display_name: "Divider".to_string(),
+            main_value: main_value.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleDividerWidget = SingleWidget<DividerWidget>;
@@ -0,0 +1,130 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// DoubleClickWidget in a SingleWidget.
+
+// The widget reports every click; the element decides whether two clicks are close enough
+// to make a double click.
+
+pub struct DoubleClickWidget<Children: WidgetSequence> {
+    pub children: Children,
+    // Whether the mouse was pressed over the widget and hasn't been released yet
+    pressed: bool,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> DoubleClickWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        DoubleClickWidget {
+            children,
+            pressed: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for DoubleClickWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        match event {
+            Event::MouseDown(_) => {
+                self.pressed = ctx.is_hot();
+            }
+            Event::MouseUp(_) => {
+                if self.pressed && ctx.is_hot() {
+                    trace!("DoubleClick {:?} clicked", self.id());
+                    data.queue_action(self.id(), Action::Clicked);
+                }
+                self.pressed = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "DoubleClick".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleDoubleClickWidget<Children> = SingleWidget<DoubleClickWidget<Children>>;
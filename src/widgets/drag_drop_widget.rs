@@ -0,0 +1,310 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Cursor, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    MouseButton, PaintCtx, Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// DragSourceWidget and DropTargetWidget in a SingleWidget.
+
+/// Distance the mouse must travel with the button pressed before a drag starts, so that
+/// clicks on the source don't start drags.
+const DRAG_THRESHOLD: f64 = 4.0;
+
+/// Queues [`Action::DragStarted`] when the mouse is dragged from its children.
+///
+/// The payload is put in the app data by the element; the widget only dims its children
+/// while the drag is in progress.
+pub struct DragSourceWidget<Children: WidgetSequence> {
+    pub children: Children,
+    // Where the mouse was pressed, until it's released
+    press_pos: Option<Point>,
+    is_dragging: bool,
+    id: WidgetId,
+}
+
+/// Queues [`Action::Dropped`] when the mouse is released over its children during a drag
+/// of a payload of the accepted type.
+///
+/// Its children are highlighted while such a drag hovers them.
+pub struct DropTargetWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub accepted_type: TypeId,
+    is_drag_over: bool,
+    id: WidgetId,
+}
+
+// Both widgets lay out their children on top of each other, like HoverWidget
+fn layout_children(
+    children: &mut impl WidgetSequence,
+    ctx: &mut LayoutCtx,
+    bc: &BoxConstraints,
+    data: &DruidAppData,
+    env: &Env,
+) -> Size {
+    let mut size = Size::ZERO;
+    for child in children.widgets_mut() {
+        let child_size = child.layout(ctx, bc, data, env);
+        child.set_origin(ctx, data, env, Point::ZERO);
+        size = Size::new(
+            size.width.max(child_size.width),
+            size.height.max(child_size.height),
+        );
+    }
+    bc.constrain(size)
+}
+
+impl<Children: WidgetSequence> DragSourceWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        DragSourceWidget {
+            children,
+            press_pos: None,
+            is_dragging: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> DropTargetWidget<Children> {
+    pub fn new(children: Children, accepted_type: TypeId, id: WidgetId) -> Self {
+        DropTargetWidget {
+            children,
+            accepted_type,
+            is_drag_over: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn accepts_drag(&self, data: &DruidAppData) -> bool {
+        data.drag_payload_type() == Some(self.accepted_type)
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for DragSourceWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        // The source stays active during the drag, so that it keeps getting mouse moves
+        // outside of its bounds
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button == MouseButton::Left => {
+                self.press_pos = Some(mouse_event.pos);
+                ctx.set_active(true);
+            }
+            Event::MouseMove(mouse_event) => {
+                if let Some(press_pos) = self.press_pos {
+                    if !self.is_dragging && press_pos.distance(mouse_event.pos) >= DRAG_THRESHOLD {
+                        trace!("DragSource {:?} started drag", self.id());
+                        data.queue_action(self.id(), Action::DragStarted);
+                        self.is_dragging = true;
+                        ctx.request_paint();
+                    }
+                }
+                if self.is_dragging {
+                    ctx.set_cursor(&Cursor::OpenHand);
+                }
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                if self.is_dragging {
+                    ctx.clear_cursor();
+                    ctx.request_paint();
+                }
+                self.press_pos = None;
+                self.is_dragging = false;
+                ctx.set_active(false);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("DragSource");
+        layout_children(&mut self.children, ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        // The dragged element is dimmed, like OpacityWidget does
+        if self.is_dragging {
+            let overlay = env.get(theme::WINDOW_BACKGROUND_COLOR).with_alpha(0.5);
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut other_values = HashMap::new();
+        other_values.insert("dragging".to_string(), self.is_dragging.to_string());
+        DebugState {
+            display_name: "DragSource".to_string(),
+            children: children_state,
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for DropTargetWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        match event {
+            Event::MouseMove(_) => {
+                let is_drag_over = ctx.is_hot() && self.accepts_drag(data);
+                if is_drag_over != self.is_drag_over {
+                    self.is_drag_over = is_drag_over;
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                if ctx.is_hot() && self.accepts_drag(data) {
+                    trace!("DropTarget {:?} received drop", self.id());
+                    data.queue_action(self.id(), Action::Dropped);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+
+        // The highlight is removed once the drag ends, wherever the mouse was released
+        if self.is_drag_over && !self.accepts_drag(data) {
+            self.is_drag_over = false;
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("DropTarget");
+        layout_children(&mut self.children, ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if self.is_drag_over {
+            let border = env.get(theme::PRIMARY_LIGHT);
+            let rect = ctx.size().to_rect().inset(-1.0);
+            ctx.stroke(rect, &border, 2.0);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut other_values = HashMap::new();
+        other_values.insert("drag_over".to_string(), self.is_drag_over.to_string());
+        DebugState {
+            display_name: "DropTarget".to_string(),
+            children: children_state,
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleDragSourceWidget<Children> = SingleWidget<DragSourceWidget<Children>>;
+pub type SingleDropTargetWidget<Children> = SingleWidget<DropTargetWidget<Children>>;
@@ -0,0 +1,149 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::commands::{OPEN_FILE, OPEN_PANEL_CANCELLED, SHOW_OPEN_PANEL};
+use druid::widget::{Button, Click, ControllerHost};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, FileDialogOptions, FileInfo, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use tracing::trace;
+
+// Note: Like SwitchWidget, we implement Widget instead of FlexWidget, and wrap
+// FileButtonWidget in a SingleWidget.
+
+thread_local! {
+    // If set, file dialogs aren't shown, and immediately return this result instead.
+    static STUBBED_DIALOG: RefCell<Option<Option<PathBuf>>> = Default::default();
+}
+
+/// Make the file dialogs opened by file buttons return `result` instead of showing a native
+/// dialog. `None` simulates the user cancelling the dialog.
+pub(crate) fn stub_file_dialog(result: Option<PathBuf>) {
+    STUBBED_DIALOG.with(|stubbed| *stubbed.borrow_mut() = Some(result));
+}
+
+/// A button which opens a file dialog when clicked.
+///
+/// The dialog's result is sent by druid as a command to the whole window; the widget only
+/// handles it if it opened the dialog, and queues [`Action::FileChosen`] if a file was chosen.
+pub struct FileButtonWidget {
+    pub options: FileDialogOptions,
+    // The button sets this flag when it's clicked
+    pub pod: WidgetPod<bool, ControllerHost<Button<bool>, Click<bool>>>,
+    // Set while the dialog opened by this widget is shown
+    dialog_open: bool,
+    id: WidgetId,
+}
+
+impl FileButtonWidget {
+    pub fn new(text: String, options: FileDialogOptions, id: WidgetId) -> Self {
+        let button = Button::new(text).on_click(|_, clicked: &mut bool, _| *clicked = true);
+
+        FileButtonWidget {
+            options,
+            pod: WidgetPod::new(button),
+            dialog_open: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn open_dialog(&mut self, ctx: &mut EventCtx) {
+        trace!("FileButton {:?} opening file dialog", self.id());
+        self.dialog_open = true;
+        match STUBBED_DIALOG.with(|stubbed| stubbed.borrow().clone()) {
+            Some(Some(path)) => {
+                let info = FileInfo { path, format: None };
+                ctx.submit_command(OPEN_FILE.with(info).to(self.id));
+            }
+            Some(None) => ctx.submit_command(OPEN_PANEL_CANCELLED.to(self.id)),
+            None => ctx.submit_command(SHOW_OPEN_PANEL.with(self.options.clone())),
+        }
+    }
+}
+
+impl Widget<DruidAppData> for FileButtonWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if self.dialog_open {
+                if let Some(info) = command.get(OPEN_FILE) {
+                    trace!("FileButton {:?} got file: {:?}", self.id(), info.path);
+                    self.dialog_open = false;
+                    data.queue_action(self.id(), Action::FileChosen(info.path.clone()));
+                    ctx.set_handled();
+                    return;
+                }
+                if command.is(OPEN_PANEL_CANCELLED) {
+                    trace!("FileButton {:?} dialog cancelled", self.id());
+                    self.dialog_open = false;
+                    ctx.set_handled();
+                    return;
+                }
+            }
+        }
+
+        let mut clicked = false;
+        self.pod.event(ctx, event, &mut clicked, env);
+        if clicked {
+            self.open_dialog(ctx);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &false, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &false, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let size = self.pod.layout(ctx, bc, &false, env);
+        self.pod.set_origin(ctx, &false, env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &false, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "FileButton".to_string(),
+            children: vec![self.pod.widget().debug_state(&false)],
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleFileButtonWidget = SingleWidget<FileButtonWidget>;
@@ -21,6 +21,8 @@ use tracing::trace;
 pub struct FlexWidget<Children: WidgetSequence> {
     pub(crate) direction: Axis,
     pub(crate) flex_params: FlexContainerParams,
+    /// Space inserted between adjacent children on the main axis.
+    pub(crate) spacing: f64,
     pub children_seq: Children,
 }
 
@@ -79,8 +81,11 @@ impl<Children: WidgetSequence> Widget<DruidAppData> for FlexWidget<Children> {
 
         let mut child_widgets = self.children_seq.widgets_mut();
 
+        // Space between children is reserved like a non-flex child.
+        let total_spacing = self.spacing * child_widgets.len().saturating_sub(1) as f64;
+
         // Measure non-flex children.
-        let mut major_non_flex = 0.0;
+        let mut major_non_flex = total_spacing;
         let mut minor = self.direction.minor(bc.min());
         let mut flex_sum = 0.0;
         for child in &mut child_widgets {
@@ -124,7 +129,11 @@ impl<Children: WidgetSequence> Widget<DruidAppData> for FlexWidget<Children> {
                 let actual_major = desired_major.round();
                 remainder = desired_major - actual_major;
 
-                let child_bc = self.direction.constraints(&loosened_bc, 0.0, actual_major);
+                // Spacers have no content; they're stretched to the space they're given
+                let min_major = if child.is_spacer() { actual_major } else { 0.0 };
+                let child_bc = self
+                    .direction
+                    .constraints(&loosened_bc, min_major, actual_major);
                 let child_size = child.layout(ctx, &child_bc, data, env);
                 let baseline_offset = child.baseline_offset();
 
@@ -157,7 +166,8 @@ impl<Children: WidgetSequence> Widget<DruidAppData> for FlexWidget<Children> {
 
         let mut major = spacing.next().unwrap_or(0.);
         let mut child_paint_rect = Rect::ZERO;
-        for child in &mut child_widgets {
+        let child_count = child_widgets.len();
+        for (i, child) in child_widgets.iter_mut().enumerate() {
             let child_size = child.layout_rect().size();
             let alignment = child
                 .flex_params()
@@ -191,6 +201,9 @@ impl<Children: WidgetSequence> Widget<DruidAppData> for FlexWidget<Children> {
             child_paint_rect = child_paint_rect.union(child.paint_rect());
             major += self.direction.major(child_size).expand();
             major += spacing.next().unwrap_or(0.);
+            if i + 1 < child_count {
+                major += self.spacing;
+            }
         }
 
         if flex_sum > 0.0 && total_major.is_infinite() {
@@ -0,0 +1,132 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Selector, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// FocusWidget in a SingleWidget.
+
+// Focus changes are notified in lifecycle(), where the app data can't be changed; the widget
+// sends itself this command to queue the action from event().
+const FOCUS_CHANGED: Selector<bool> = Selector::new("panoramix.focus-changed");
+
+pub struct FocusWidget<Children: WidgetSequence> {
+    pub children: Children,
+    // Whether the widget or one of its descendants had focus last time we checked
+    has_focus: bool,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> FocusWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        FocusWidget {
+            children,
+            has_focus: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for FocusWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if let Some(has_focus) = command.get(FOCUS_CHANGED) {
+                trace!("Focus {:?} has focus: {}", self.id(), has_focus);
+                data.queue_action(self.id(), Action::FocusChanged(*has_focus));
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+
+        // The children have merged their focus state into ours by now
+        if ctx.has_focus() != self.has_focus {
+            self.has_focus = ctx.has_focus();
+            ctx.submit_command(FOCUS_CHANGED.with(self.has_focus).to(self.id));
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Focus".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleFocusWidget<Children> = SingleWidget<FocusWidget<Children>>;
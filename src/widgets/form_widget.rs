@@ -0,0 +1,152 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::{ButtonWidget, SingleWidget};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, KbKey, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like KeyListenerWidget, we implement Widget instead of FlexWidget, and wrap
+// FormWidget in a SingleWidget.
+
+const SUBMIT_BUTTON_SPACING: f64 = 8.0;
+
+/// Groups the inputs of a form, and an optional submit button below them.
+///
+/// Druid sends keyboard events to the focused widget and its parents, so the form sees the
+/// keys pressed in any of its fields. Enter presses that no field handled queue a submission.
+pub struct FormWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub submit_button: Option<ButtonWidget>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> FormWidget<Children> {
+    pub fn new(children: Children, submit_button: Option<ButtonWidget>, id: WidgetId) -> Self {
+        FormWidget {
+            children,
+            submit_button,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for FormWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+        for button in self.submit_button.widgets_mut() {
+            button.event(ctx, event, data, env);
+        }
+
+        if let Event::KeyDown(key_event) = event {
+            if key_event.key == KbKey::Enter && !ctx.is_handled() {
+                trace!("Form {:?} submitted with Enter", self.id());
+                data.queue_action(self.id(), Action::Submitted);
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+        for button in self.submit_button.widgets_mut() {
+            button.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+        for button in self.submit_button.widgets_mut() {
+            button.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Form");
+        let child_bc = bc.loosen();
+
+        // The children are stacked, with the submit button below them
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, &child_bc, data, env);
+            child.set_origin(ctx, data, env, Point::new(0.0, size.height));
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height + child_size.height,
+            );
+        }
+        for button in self.submit_button.widgets_mut() {
+            let y = size.height + SUBMIT_BUTTON_SPACING;
+            let button_size = button.layout(ctx, &child_bc, data, env);
+            button.set_origin(ctx, data, env, Point::new(0.0, y));
+            size = Size::new(size.width.max(button_size.width), y + button_size.height);
+        }
+
+        let size = bc.constrain(size);
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+        for button in self.submit_button.widgets_mut() {
+            button.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .into_iter()
+            .chain(self.submit_button.widgets())
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Form".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleFormWidget<Children> = SingleWidget<FormWidget<Children>>;
@@ -0,0 +1,152 @@
+//! A widget that arranges its children in rows of equal-width columns.
+
+use druid::kurbo::common::FloatExt;
+use druid::kurbo::{Point, Rect, Size};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    Widget,
+};
+
+use crate::widget_sequence::WidgetSequence;
+
+use tracing::trace;
+
+/// A container Druid widget which lays out its children left-to-right, top-to-bottom.
+///
+/// Each child takes up [`grid_span`](crate::widget_sequence::FlexWidget::grid_span) columns.
+/// If a child doesn't fit in what's left of the current row, it's moved to the next row.
+pub struct GridWidget<Children: WidgetSequence> {
+    pub(crate) columns: usize,
+    /// Space inserted between adjacent columns and adjacent rows.
+    pub(crate) spacing: f64,
+    pub children_seq: Children,
+}
+
+use crate::glue::DruidAppData;
+impl<Children: WidgetSequence> Widget<DruidAppData> for GridWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // FIXME
+        ctx.children_changed();
+
+        for child in self.children_seq.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Grid");
+        let columns = self.columns.max(1);
+        let mut child_widgets = self.children_seq.widgets_mut();
+
+        let column_width = if bc.max().width.is_finite() {
+            let total_spacing = self.spacing * (columns - 1) as f64;
+            ((bc.max().width - total_spacing) / columns as f64).max(0.0)
+        } else {
+            // Without a width limit, columns are as wide as the widest child needs.
+            let mut column_width: f64 = 0.0;
+            for child in &mut child_widgets {
+                let span = child.grid_span().max(1).min(columns) as f64;
+                let child_bc =
+                    BoxConstraints::new(Size::ZERO, Size::new(std::f64::INFINITY, bc.max().height));
+                let child_size = child.layout(ctx, &child_bc, data, env);
+                let width = child_size.width.expand() - self.spacing * (span - 1.0);
+                column_width = column_width.max(width / span);
+            }
+            column_width
+        };
+
+        let mut column = 0;
+        let mut row_y = 0.0;
+        let mut row_height: f64 = 0.0;
+        let mut child_paint_rect = Rect::ZERO;
+        for child in &mut child_widgets {
+            let span = child.grid_span().max(1).min(columns);
+            if column + span > columns {
+                row_y += row_height + self.spacing;
+                row_height = 0.0;
+                column = 0;
+            }
+
+            let cell_width = column_width * span as f64 + self.spacing * (span - 1) as f64;
+            let child_bc =
+                BoxConstraints::new(Size::ZERO, Size::new(cell_width, std::f64::INFINITY));
+            let child_size = child.layout(ctx, &child_bc, data, env);
+
+            let x = column as f64 * (column_width + self.spacing);
+            child.set_origin(ctx, data, env, Point::new(x, row_y));
+            child_paint_rect = child_paint_rect.union(child.paint_rect());
+
+            row_height = row_height.max(child_size.height.expand());
+            column += span;
+        }
+        let height = if child_widgets.is_empty() {
+            0.0
+        } else {
+            row_y + row_height
+        };
+
+        let width = column_width * columns as f64 + self.spacing * (columns - 1) as f64;
+        let my_size = bc.constrain(Size::new(width, height));
+
+        let my_bounds = Rect::ZERO.with_size(my_size);
+        let insets = child_paint_rect - my_bounds;
+        ctx.set_paint_insets(insets);
+
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children_seq.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children_seq
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Grid".to_string(),
+            main_value: format!("{} columns", self.columns),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
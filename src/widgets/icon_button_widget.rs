@@ -0,0 +1,169 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, MouseButton,
+    PaintCtx, Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like TooltipWidget, we implement Widget instead of FlexWidget, and wrap
+// IconButtonWidget in a SingleWidget.
+
+/// Space around the icon which still reacts to clicks.
+const HIT_PADDING: f64 = 8.0;
+const CORNER_RADIUS: f64 = 4.0;
+
+/// A button displaying its children, usually an icon, in a square of `icon_size`.
+///
+/// The button is larger than the icon, so that it's easy to hit. Releasing the left mouse
+/// button over the widget after pressing it queues [`Action::Clicked`].
+pub struct IconButtonWidget<Children: WidgetSequence> {
+    pub icon: Children,
+    icon_size: f64,
+    label: String,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> IconButtonWidget<Children> {
+    /// Create the widget; `label` is the name of the button in the debug state.
+    pub fn new(icon: Children, icon_size: f64, label: String, id: WidgetId) -> Self {
+        IconButtonWidget {
+            icon,
+            icon_size,
+            label,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Change the size of the icon, and request a new layout.
+    pub fn set_icon_size(&mut self, icon_size: f64, ctx: &mut EventCtx) {
+        self.icon_size = icon_size;
+        ctx.request_layout();
+    }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = label;
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for IconButtonWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.icon.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button == MouseButton::Left => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                if ctx.is_active() && ctx.is_hot() {
+                    trace!("IconButton {:?} clicked", self.id());
+                    data.queue_action(self.id(), Action::Clicked);
+                }
+                ctx.set_active(false);
+                ctx.request_paint();
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::HotChanged(_) = event {
+            ctx.request_paint();
+        }
+
+        for child in self.icon.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.icon.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("IconButton");
+        let side = self.icon_size + 2.0 * HIT_PADDING;
+        let size = bc.constrain(Size::new(side, side));
+
+        // The icon is centered, even if the constraints shrink the button
+        let icon_bc = BoxConstraints::tight(Size::new(self.icon_size, self.icon_size));
+        let icon_origin = Point::new(
+            (size.width - self.icon_size) / 2.0,
+            (size.height - self.icon_size) / 2.0,
+        );
+        for child in self.icon.widgets_mut() {
+            child.layout(ctx, &icon_bc, data, env);
+            child.set_origin(ctx, data, env, icon_origin);
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        if ctx.is_hot() || ctx.is_active() {
+            let color = if ctx.is_active() {
+                env.get(theme::BUTTON_DARK)
+            } else {
+                env.get(theme::BUTTON_LIGHT)
+            };
+            let rect = ctx.size().to_rect().to_rounded_rect(CORNER_RADIUS);
+            ctx.fill(rect, &color);
+        }
+
+        for child in self.icon.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .icon
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "IconButton".to_string(),
+            main_value: self.label.clone(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleIconButtonWidget<Children> = SingleWidget<IconButtonWidget<Children>>;
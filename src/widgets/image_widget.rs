@@ -0,0 +1,121 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{FillStrat, Image, Label};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, ImageBuf, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Size, UpdateCtx, Widget, WidgetPod,
+};
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// ImageWidget in a SingleWidget.
+
+const PLACEHOLDER_TEXT: &str = "Image could not be loaded";
+
+pub struct ImageWidget {
+    pub image: Option<ImageBuf>,
+    pub fill_mode: FillStrat,
+    pub pod: WidgetPod<(), Box<dyn Widget<()>>>,
+    id: WidgetId,
+}
+
+impl ImageWidget {
+    /// Create the widget. If `image` is `None`, a placeholder is displayed instead.
+    pub fn new(image: Option<ImageBuf>, fill_mode: FillStrat, id: WidgetId) -> Self {
+        ImageWidget {
+            pod: Self::new_pod(&image, fill_mode),
+            image,
+            fill_mode,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the displayed image.
+    pub fn set_image(&mut self, image: Option<ImageBuf>, ctx: &mut EventCtx) {
+        self.image = image;
+        self.pod = Self::new_pod(&self.image, self.fill_mode);
+        ctx.children_changed();
+    }
+
+    /// Change the way the image is scaled to fit the widget.
+    pub fn set_fill_mode(&mut self, fill_mode: FillStrat, ctx: &mut EventCtx) {
+        self.fill_mode = fill_mode;
+        self.pod = Self::new_pod(&self.image, self.fill_mode);
+        ctx.children_changed();
+    }
+
+    fn new_pod(
+        image: &Option<ImageBuf>,
+        fill_mode: FillStrat,
+    ) -> WidgetPod<(), Box<dyn Widget<()>>> {
+        match image {
+            Some(image) => WidgetPod::new(Box::new(Image::new(image.clone()).fill_mode(fill_mode))),
+            None => WidgetPod::new(Box::new(Label::new(PLACEHOLDER_TEXT))),
+        }
+    }
+}
+
+impl Widget<DruidAppData> for ImageWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, &mut (), env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &(), env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &(), env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let size = self.pod.layout(ctx, bc, &(), env);
+        self.pod.set_origin(ctx, &(), env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &(), env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let main_value = match &self.image {
+            Some(image) => format!("{}x{}", image.width(), image.height()),
+            None => PLACEHOLDER_TEXT.to_string(),
+        };
+        DebugState {
+            display_name: "Image".to_string(),
+            main_value,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleImageWidget = SingleWidget<ImageWidget>;
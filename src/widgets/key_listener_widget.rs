@@ -0,0 +1,132 @@
+use crate::elements::KeyDown;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// KeyListenerWidget in a SingleWidget.
+
+// Druid only sends keyboard events to the focused widget and its parents. The listener takes
+// focus when it's clicked, unless one of its children takes it first.
+
+pub struct KeyListenerWidget<Children: WidgetSequence> {
+    pub children: Children,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> KeyListenerWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        KeyListenerWidget { children, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for KeyListenerWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::MouseDown(_) = event {
+            ctx.request_focus();
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        if let Event::KeyDown(key_event) = event {
+            if !ctx.is_handled() {
+                trace!("KeyListener {:?} got key: {:?}", self.id(), key_event.key);
+                data.queue_action(
+                    self.id(),
+                    Action::KeyDown(KeyDown {
+                        key: key_event.key.clone(),
+                        mods: key_event.mods,
+                    }),
+                );
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "KeyListener".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleKeyListenerWidget<Children> = SingleWidget<KeyListenerWidget<Children>>;
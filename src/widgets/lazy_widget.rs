@@ -0,0 +1,175 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::{window_size, SingleWidget};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Rect, Selector, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like SuspenseWidget, we implement Widget instead of FlexWidget, and wrap
+// LazyWidget in a SingleWidget.
+
+// Sent by the widget to itself when it moves or is resized, since actions can't be queued
+// during lifecycle events.
+const CHECK_VISIBILITY: Selector = Selector::new("panoramix.lazy-check-visibility");
+
+/// Displays its child once it's built, or an empty placeholder of a given size until then.
+///
+/// Until the child is built, the widget checks whether it overlaps the window every time it
+/// moves, eg because a scroll area around it was scrolled. The first time it does, the widget
+/// queues [`Action::BecameVisible`]; building the child is left to the element.
+pub struct LazyWidget<Child: WidgetSequence> {
+    pub child: Option<Child>,
+    pub size_hint: Size,
+    // Set once the widget has been seen, so that it's only reported once
+    pub is_revealed: bool,
+    checked_rect: Option<Rect>,
+    id: WidgetId,
+}
+
+impl<Child: WidgetSequence> LazyWidget<Child> {
+    pub fn new(child: Option<Child>, size_hint: Size, is_revealed: bool, id: WidgetId) -> Self {
+        LazyWidget {
+            child,
+            size_hint,
+            is_revealed,
+            checked_rect: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    // Clipping by scroll areas is ignored; in practice, lazy elements are usually in a scroll
+    // area which fills most of the window.
+    fn is_in_window(rect: Rect) -> bool {
+        let window = window_size();
+        rect.x0 < window.width && rect.x1 >= 0.0 && rect.y0 < window.height && rect.y1 >= 0.0
+    }
+}
+
+impl<Child: WidgetSequence> Widget<DruidAppData> for LazyWidget<Child> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if command.is(CHECK_VISIBILITY) {
+                ctx.set_handled();
+                let rect = Rect::from_origin_size(ctx.to_window(Point::ORIGIN), ctx.size());
+                if !self.is_revealed && Self::is_in_window(rect) {
+                    trace!("Lazy {:?} became visible at {:?}", self.id(), rect);
+                    self.is_revealed = true;
+                    data.queue_action(self.id(), Action::BecameVisible);
+                }
+                return;
+            }
+        }
+
+        if let Some(child) = &mut self.child {
+            for child in child.widgets_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if !self.is_revealed {
+            let rect = Rect::from_origin_size(ctx.to_window(Point::ORIGIN), ctx.size());
+            if self.checked_rect != Some(rect) {
+                self.checked_rect = Some(rect);
+                ctx.submit_command(CHECK_VISIBILITY.to(self.id));
+            }
+        }
+
+        if let Some(child) = &mut self.child {
+            for child in child.widgets_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let Some(child) = &mut self.child {
+            for child in child.widgets_mut() {
+                child.update(ctx, old_data, data, env);
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Lazy");
+
+        // The placeholder takes the size the child is expected to have, so that building the
+        // child doesn't move the widgets around it
+        let child = match &mut self.child {
+            Some(child) => child,
+            None => return bc.constrain(self.size_hint),
+        };
+
+        let mut size = Size::ZERO;
+        for child in child.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        if let Some(child) = &mut self.child {
+            for child in child.widgets_mut() {
+                child.paint(ctx, data, env);
+            }
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .child
+            .iter()
+            .flat_map(|child| child.widgets())
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Lazy".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("is_revealed".to_string(), self.is_revealed.to_string());
+        debug_state
+    }
+}
+
+pub type SingleLazyWidget<Child> = SingleWidget<LazyWidget<Child>>;
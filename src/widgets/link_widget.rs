@@ -0,0 +1,127 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::kurbo::Line;
+use druid::widget as druid_w;
+use druid::{
+    BoxConstraints, Color, Cursor, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, RenderContext, Size, UpdateCtx, Widget,
+};
+use std::collections::HashMap;
+
+const UNDERLINE_WIDTH: f64 = 1.0;
+
+/// Underlined text, which queues [`Action::Clicked`] when clicked.
+pub struct LinkWidget {
+    pub label: druid_w::Label<DruidAppData>,
+    pub text: String,
+    pub url: String,
+    pub text_color: Color,
+    id: WidgetId,
+}
+
+impl LinkWidget {
+    pub fn new(text: String, url: String, text_color: Color, id: WidgetId) -> Self {
+        let mut label = druid_w::Label::new(text.clone());
+        label.set_text_color(text_color.clone());
+        LinkWidget {
+            label,
+            text,
+            url,
+            text_color,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.label.set_text(text.clone());
+        self.text = text;
+    }
+
+    pub fn set_text_color(&mut self, text_color: Color) {
+        self.label.set_text_color(text_color.clone());
+        self.text_color = text_color;
+    }
+}
+
+impl Widget<DruidAppData> for LinkWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        match event {
+            Event::MouseDown(mouse_event) if mouse_event.button.is_left() => {
+                ctx.set_active(true);
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button.is_left() => {
+                if ctx.is_active() && ctx.is_hot() {
+                    data.queue_action(self.id, Action::Clicked);
+                }
+                ctx.set_active(false);
+            }
+            Event::MouseMove(_) if ctx.is_hot() => {
+                ctx.set_cursor(&Cursor::Pointer);
+            }
+            _ => {}
+        }
+        self.label.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.label.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.label.update(ctx, old_data, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.label.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.label.paint(ctx, data, env);
+
+        let size = ctx.size();
+        let y = size.height - UNDERLINE_WIDTH;
+        let underline = Line::new((0.0, y), (size.width, y));
+        ctx.stroke(underline, &self.text_color, UNDERLINE_WIDTH);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let mut other_values = HashMap::new();
+        other_values.insert("url".to_string(), self.url.clone());
+        DebugState {
+            display_name: "Link".to_string(),
+            main_value: self.text.clone(),
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleLinkWidget = SingleWidget<LinkWidget>;
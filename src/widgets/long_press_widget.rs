@@ -0,0 +1,170 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, MouseButton,
+    PaintCtx, Point, Size, TimerToken, UpdateCtx, Widget,
+};
+use std::time::Duration;
+
+use tracing::trace;
+
+// Note: Like DebounceWidget, we implement Widget instead of FlexWidget, and wrap
+// LongPressWidget in a SingleWidget.
+
+// The widget reports when a press starts and ends, and holds the timer; the deadline of the
+// pending long press is stored in the element's state, like in Debounce.
+
+/// Distance the pointer can move during a press before the press is cancelled.
+const MOVE_THRESHOLD: f64 = 4.0;
+
+pub struct LongPressWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub timer: Option<TimerToken>,
+    // Where the current press started, if the pointer is held down over the widget
+    press_origin: Option<Point>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> LongPressWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        LongPressWidget {
+            children,
+            timer: None,
+            press_origin: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Request a timer firing after `delay`, replacing the previous one.
+    pub fn restart_timer(&mut self, delay: Duration, ctx: &mut EventCtx) {
+        self.timer = Some(ctx.request_timer(delay));
+    }
+
+    /// Ignore the currently running timer, if any.
+    pub fn cancel_timer(&mut self) {
+        self.timer = None;
+    }
+
+    fn end_press(&mut self, data: &mut DruidAppData) {
+        if self.press_origin.take().is_some() {
+            trace!("LongPress {:?} press ended", self.id());
+            data.queue_action(self.id(), Action::PressChanged(false));
+        }
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for LongPressWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Timer(token) = event {
+            if self.timer == Some(*token) {
+                trace!("LongPress {:?} timer fired", self.id());
+                self.timer = None;
+                data.queue_action(self.id(), Action::TimerFired);
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        match event {
+            Event::MouseDown(mouse_event)
+                if mouse_event.button == MouseButton::Left && ctx.is_hot() =>
+            {
+                trace!("LongPress {:?} press started", self.id());
+                self.press_origin = Some(mouse_event.pos);
+                data.queue_action(self.id(), Action::PressChanged(true));
+            }
+            Event::MouseMove(mouse_event) => {
+                if let Some(origin) = self.press_origin {
+                    if origin.distance(mouse_event.pos) > MOVE_THRESHOLD {
+                        self.end_press(data);
+                    }
+                }
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == MouseButton::Left => {
+                self.end_press(data);
+            }
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "LongPress".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleLongPressWidget<Children> = SingleWidget<LongPressWidget<Children>>;
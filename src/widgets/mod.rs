@@ -1,26 +1,152 @@
 //! Wrapper types around druid widgets.
 
+mod accordion_widget;
+mod aligned_widget;
 mod any_widget_seq;
 mod button_widget;
+mod canvas_widget;
 mod checkbox_widget;
+mod click_area_widget;
 mod clickable_widget;
+mod color_picker_widget;
+mod container_widget;
+mod context_menu_widget;
+mod debounce_widget;
+mod divider_widget;
+mod double_click_widget;
+mod drag_drop_widget;
 mod empty_sequence;
+mod file_button_widget;
 mod flex_widget;
+mod flexed_widget;
+mod focus_widget;
+mod form_widget;
+mod grid_widget;
+mod hover_widget;
+mod icon_button_widget;
+mod image_widget;
+mod key_listener_widget;
+mod lazy_widget;
+mod link_widget;
+mod long_press_widget;
+mod modal_widget;
+mod mouse_move_widget;
+mod opacity_widget;
 mod optional_widget;
+mod paste_widget;
+mod popover_widget;
+mod portal_widget;
+mod progress_bar_widget;
+mod radio_group_widget;
+mod responsive_widget;
+mod right_click_widget;
+mod scroll_widget;
+mod segmented_control_widget;
 mod single_widget;
+mod sized_box_widget;
+mod skeleton_widget;
+mod slider_widget;
+mod spacer_widget;
+mod span_widget;
+mod split_pane_widget;
+mod stack_widget;
+mod stepper_widget;
 mod styled_container;
+mod suspense_widget;
+mod svg_widget;
+mod switch_widget;
+mod tab_index_widget;
+mod table_widget;
+mod tabs_widget;
+mod textarea_widget;
 mod textbox_widget;
+mod toast_widget;
+mod tooltip_widget;
+mod transition_widget;
+mod tree_view_widget;
+mod validated_textbox_widget;
+mod virtual_list_widget;
+mod visibility_widget;
 mod widget_list;
 mod widget_tuple;
+mod with_id_widget;
+mod wrap_widget;
 
+pub use accordion_widget::{AccordionWidget, SingleAccordionWidget};
+pub use aligned_widget::{AlignedWidget, SingleAlignedWidget};
 pub use any_widget_seq::WidgetSeqBox;
 pub use button_widget::ButtonWidget;
+pub use canvas_widget::{CanvasWidget, PaintFn, SingleCanvasWidget};
 pub use checkbox_widget::{CheckboxWidget, SingleCheckboxWidget};
+pub use click_area_widget::{ClickAreaWidget, SingleClickAreaWidget};
 pub use clickable_widget::ClickableWidget;
+#[cfg(test)]
+pub(crate) use color_picker_widget::track_rect as color_picker_track_rect;
+pub use color_picker_widget::{ColorPickerWidget, SingleColorPickerWidget};
+pub use container_widget::{ContainerContent, SingleContainerWidget};
+#[cfg(test)]
+pub(crate) use context_menu_widget::MENU_ITEM_HEIGHT as CONTEXT_MENU_ITEM_HEIGHT;
+pub use context_menu_widget::{ContextMenuWidget, SingleContextMenuWidget};
+pub use debounce_widget::{DebounceWidget, SingleDebounceWidget};
+pub use divider_widget::{DividerWidget, SingleDividerWidget};
+pub use double_click_widget::{DoubleClickWidget, SingleDoubleClickWidget};
+pub use drag_drop_widget::{
+    DragSourceWidget, DropTargetWidget, SingleDragSourceWidget, SingleDropTargetWidget,
+};
 pub use empty_sequence::EmptySequence;
+pub(crate) use file_button_widget::stub_file_dialog;
+pub use file_button_widget::{FileButtonWidget, SingleFileButtonWidget};
 pub use flex_widget::FlexWidget;
+pub use flexed_widget::{FlexedWidget, SingleFlexedWidget};
+pub use focus_widget::{FocusWidget, SingleFocusWidget};
+pub use form_widget::{FormWidget, SingleFormWidget};
+pub use grid_widget::GridWidget;
+pub use hover_widget::{HoverWidget, SingleHoverWidget};
+pub use icon_button_widget::{IconButtonWidget, SingleIconButtonWidget};
+pub use image_widget::{ImageWidget, SingleImageWidget};
+pub use key_listener_widget::{KeyListenerWidget, SingleKeyListenerWidget};
+pub use lazy_widget::{LazyWidget, SingleLazyWidget};
+pub use link_widget::{LinkWidget, SingleLinkWidget};
+pub use long_press_widget::{LongPressWidget, SingleLongPressWidget};
+pub use modal_widget::{ModalWidget, SingleModalWidget};
+pub use mouse_move_widget::{MouseMoveWidget, SingleMouseMoveWidget};
+pub use opacity_widget::{OpacityWidget, SingleOpacityWidget};
+pub use paste_widget::{PasteWidget, SinglePasteWidget};
+pub(crate) use popover_widget::{set_window_size, window_size};
+pub use popover_widget::{PopoverWidget, SinglePopoverWidget};
+pub use portal_widget::{PortalWidget, SinglePortalWidget};
+pub use progress_bar_widget::{ProgressBarWidget, SingleProgressBarWidget};
+pub use radio_group_widget::{RadioGroupWidget, SingleRadioGroupWidget};
+pub use responsive_widget::{ResponsiveWidget, SingleResponsiveWidget};
+pub use right_click_widget::{RightClickWidget, SingleRightClickWidget};
+pub use scroll_widget::{ScrollContent, ScrollWidget, SingleScrollWidget};
+pub use segmented_control_widget::{SegmentedControlWidget, SingleSegmentedControlWidget};
 pub use single_widget::SingleWidget;
+pub use sized_box_widget::{SingleSizedBoxWidget, SizedBoxWidget};
+pub use skeleton_widget::{SingleSkeletonWidget, SkeletonWidget};
+pub use slider_widget::{SingleSliderWidget, SliderWidget};
+pub use spacer_widget::SpacerWidget;
+pub use span_widget::{SingleSpanWidget, SpanWidget};
+pub use split_pane_widget::{SingleSplitPaneWidget, SplitPaneWidget};
+pub use stack_widget::StackWidget;
+pub use stepper_widget::{SingleStepperWidget, StepperWidget};
 pub use styled_container::Container;
+pub use suspense_widget::{SingleSuspenseWidget, SuspenseWidget};
+pub use svg_widget::{SingleSvgWidget, SvgWidget};
+pub use switch_widget::{SingleSwitchWidget, SwitchWidget};
+pub use tab_index_widget::{SingleTabIndexWidget, TabIndexWidget};
+pub use table_widget::{SingleTableWidget, TableWidget};
+pub use tabs_widget::{SingleTabsWidget, TabsWidget};
+pub use textarea_widget::TextAreaWidget;
 pub use textbox_widget::TextBoxWidget;
+pub use toast_widget::{SingleToastWidget, ToastWidget};
+pub use tooltip_widget::{SingleTooltipWidget, TooltipWidget};
+pub use transition_widget::{SingleTransitionWidget, TransitionWidget};
+pub use tree_view_widget::{SingleTreeViewWidget, TreeRowWidget, TreeViewWidget};
+pub use validated_textbox_widget::{SingleValidatedTextBoxWidget, ValidatedTextBoxWidget};
+pub use virtual_list_widget::{SingleVirtualListWidget, VirtualListWidget};
+pub use visibility_widget::{SingleVisibilityWidget, VisibilityWidget};
 pub use widget_list::WidgetList;
 pub use widget_tuple::WidgetTuple;
+pub use with_id_widget::{SingleWithIdWidget, WithIdWidget};
+pub use wrap_widget::WrapWidget;
@@ -0,0 +1,273 @@
+use crate::flex::FlexParams;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::FlexWidget;
+use crate::widget_sequence::WidgetSequence;
+
+use crate::glue::DebugState;
+use druid::kurbo::{Point, Rect, Size};
+use druid::{
+    theme, BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, RenderContext, UnitPoint, UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like SingleAlignedWidget, SingleModalWidget implements FlexWidget directly,
+// because the Stack container needs to know it fills the stack.
+
+const BACKDROP_COLOR: Color = Color::rgba8(0, 0, 0, 0x66);
+
+/// A dimmed backdrop, with its children centered over it.
+///
+/// The backdrop takes as much space as it's given, and handles all mouse events, so that
+/// widgets below it in a stack can't be clicked. Clicking the backdrop outside of the
+/// children queues [`Action::Clicked`].
+pub struct ModalWidget<Children: WidgetSequence> {
+    pub children: Children,
+    id: WidgetId,
+}
+
+pub struct SingleModalWidget<Children: WidgetSequence> {
+    pub pod: WidgetPod<DruidAppData, ModalWidget<Children>>,
+    pub flex: FlexParams,
+}
+
+impl<Children: WidgetSequence> ModalWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        ModalWidget { children, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn content_contains(&self, pos: Point) -> bool {
+        self.children
+            .widgets()
+            .iter()
+            .any(|child| child.layout_rect().contains(pos))
+    }
+}
+
+impl<Children: WidgetSequence> SingleModalWidget<Children> {
+    pub fn new(widget: ModalWidget<Children>, flex: FlexParams) -> Self {
+        SingleModalWidget {
+            pod: WidgetPod::new(widget),
+            flex,
+        }
+    }
+
+    pub fn widget(&self) -> &ModalWidget<Children> {
+        self.pod.widget()
+    }
+
+    pub fn widget_mut(&mut self) -> &mut ModalWidget<Children> {
+        self.pod.widget_mut()
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ModalWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        // Mouse events are handled even if the children ignored them, so that they don't
+        // reach the widgets under the backdrop.
+        match event {
+            Event::MouseDown(mouse_event) => {
+                if !self.content_contains(mouse_event.pos) {
+                    ctx.set_active(true);
+                }
+                ctx.set_handled();
+            }
+            Event::MouseUp(mouse_event) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    if ctx.is_hot() && !self.content_contains(mouse_event.pos) {
+                        trace!("Modal {:?} backdrop clicked", self.id());
+                        data.queue_action(self.id(), Action::Clicked);
+                    }
+                }
+                ctx.set_handled();
+            }
+            Event::MouseMove(_) | Event::Wheel(_) => ctx.set_handled(),
+            _ => {}
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Modal");
+        let mut child_widgets = self.children.widgets_mut();
+
+        let loosened_bc = bc.loosen();
+        let mut children_size = Size::ZERO;
+        for child in &mut child_widgets {
+            let child_size = child.layout(ctx, &loosened_bc, data, env);
+            children_size = Size::new(
+                children_size.width.max(child_size.width),
+                children_size.height.max(child_size.height),
+            );
+        }
+
+        let mut my_size = children_size;
+        if bc.is_width_bounded() {
+            my_size.width = bc.max().width;
+        }
+        if bc.is_height_bounded() {
+            my_size.height = bc.max().height;
+        }
+        let my_size = bc.constrain(my_size);
+
+        for child in &mut child_widgets {
+            let extra = my_size - child.layout_rect().size();
+            let extra = Size::new(extra.width.max(0.0), extra.height.max(0.0));
+            let origin = UnitPoint::CENTER
+                .resolve(Rect::from_origin_size(Point::ZERO, extra))
+                .round();
+            child.set_origin(ctx, data, env, origin);
+        }
+
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        let backdrop = ctx.size().to_rect();
+        ctx.fill(backdrop, &BACKDROP_COLOR);
+
+        // The content is painted over an opaque background, so that it's readable
+        let background = env.get(theme::WINDOW_BACKGROUND_COLOR);
+        for child in self.children.widgets_mut() {
+            ctx.fill(child.layout_rect(), &background);
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Modal".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Children: WidgetSequence> FlexWidget for SingleModalWidget<Children> {
+    fn flex_params(&self) -> FlexParams {
+        self.flex
+    }
+
+    fn stack_alignment(&self) -> Option<UnitPoint> {
+        Some(UnitPoint::CENTER)
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.pod.layout(ctx, bc, data, env)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        self.pod.paint_rect()
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &DruidAppData, env: &Env, origin: Point) {
+        self.pod.set_origin(ctx, data, env, origin)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        self.pod.layout_rect()
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        self.pod.baseline_offset()
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, data, env);
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(data)
+    }
+}
+
+impl<Children: WidgetSequence> WidgetSequence for SingleModalWidget<Children> {
+    fn widgets(&self) -> Vec<&dyn FlexWidget> {
+        vec![self]
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget> {
+        vec![self]
+    }
+}
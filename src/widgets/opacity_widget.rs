@@ -0,0 +1,150 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// OpacityWidget in a SingleWidget.
+
+/// Paints its children partially transparent.
+///
+/// Druid can't paint a group of widgets with transparency, so like the disabled buttons of
+/// StepperWidget, the children are faded by painting the window background over them. A fully
+/// transparent widget doesn't paint its children at all, but they still take up space.
+pub struct OpacityWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub opacity: f64,
+    pub interactive: bool,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> OpacityWidget<Children> {
+    pub fn new(children: Children, opacity: f64, interactive: bool, id: WidgetId) -> Self {
+        OpacityWidget {
+            children,
+            opacity,
+            interactive,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Change the opacity, and request a repaint.
+    pub fn set_opacity(&mut self, opacity: f64, ctx: &mut EventCtx) {
+        self.opacity = opacity;
+        ctx.request_paint();
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for OpacityWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        let is_mouse_event = matches!(
+            event,
+            Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) | Event::Wheel(_)
+        );
+
+        // Non-interactive children don't get mouse events, so they can't be clicked
+        if self.interactive || !is_mouse_event {
+            for child in self.children.widgets_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        if self.opacity <= 0.0 {
+            trace!("Skipping paint of transparent {:?}", self.id());
+            return;
+        }
+
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if self.opacity < 1.0 {
+            let overlay = env
+                .get(theme::WINDOW_BACKGROUND_COLOR)
+                .with_alpha(1.0 - self.opacity);
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Opacity".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("opacity".to_string(), self.opacity.to_string());
+        debug_state
+    }
+}
+
+pub type SingleOpacityWidget<Children> = SingleWidget<OpacityWidget<Children>>;
@@ -0,0 +1,137 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::commands::PASTE;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, HotKey, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Size, SysMods, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like KeyListenerWidget, we implement Widget instead of FlexWidget, and wrap
+// PasteWidget in a SingleWidget.
+
+// Like KeyListenerWidget, the widget takes focus when it's clicked. It handles the paste
+// command sent by the platform's Edit menu, and the paste shortcut, while it or one of its
+// children has focus; children which handle pasting themselves, eg text boxes, take precedence.
+
+pub struct PasteWidget<Children: WidgetSequence> {
+    pub children: Children,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> PasteWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        PasteWidget { children, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for PasteWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::MouseDown(_) = event {
+            ctx.request_focus();
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        if ctx.is_handled() || !ctx.has_focus() {
+            return;
+        }
+        let is_paste = match event {
+            Event::Command(command) => command.is(PASTE),
+            Event::KeyDown(key_event) => HotKey::new(SysMods::Cmd, "v").matches(key_event),
+            _ => false,
+        };
+        if is_paste {
+            ctx.set_handled();
+            // Non-text contents are ignored
+            if let Some(text) = crate::clipboard::get_clipboard() {
+                trace!("Paste {:?} got text: {:?}", self.id(), text);
+                data.queue_action(self.id(), Action::Pasted(text));
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Paste".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SinglePasteWidget<Children> = SingleWidget<PasteWidget<Children>>;
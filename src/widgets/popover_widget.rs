@@ -0,0 +1,349 @@
+use crate::elements::Placement;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use std::cell::Cell;
+use tracing::trace;
+
+// Note: Like TooltipWidget, we implement Widget instead of FlexWidget, and wrap
+// PopoverWidget in a SingleWidget.
+
+thread_local! {
+    // The size of the window being laid out, set by the root widget.
+    static WINDOW_SIZE: Cell<Size> = Cell::new(Size::ZERO);
+}
+
+/// Set the size of the window, in which popovers must fit.
+pub(crate) fn set_window_size(size: Size) {
+    WINDOW_SIZE.with(|window_size| window_size.set(size));
+}
+
+/// The size of the window, as of its last layout.
+pub(crate) fn window_size() -> Size {
+    WINDOW_SIZE.with(|window_size| window_size.get())
+}
+
+/// Displays its anchor children, and its content children next to them, over the
+/// surrounding widgets.
+///
+/// Only the anchor takes space in the layout. While the content is displayed, the widget is
+/// active, so that it gets all mouse events in the window: a mouse press outside of the
+/// anchor and the content queues [`Action::Clicked`].
+pub struct PopoverWidget<Anchor: WidgetSequence, Content: WidgetSequence> {
+    pub anchor: Anchor,
+    pub content: Option<Content>,
+    pub placement: Placement,
+    // The placement actually used, which is flipped if the content doesn't fit
+    resolved_placement: Placement,
+    // Layout doesn't know where the widget is in the window; we use the last known position
+    window_origin: Point,
+    id: WidgetId,
+}
+
+impl Placement {
+    fn flipped(self) -> Self {
+        match self {
+            Placement::Below => Placement::Above,
+            Placement::Above => Placement::Below,
+            Placement::Left => Placement::Right,
+            Placement::Right => Placement::Left,
+        }
+    }
+
+    // Whether content placed this way fits in the window
+    fn fits(
+        self,
+        window_origin: Point,
+        anchor_size: Size,
+        content_size: Size,
+        window: Size,
+    ) -> bool {
+        match self {
+            Placement::Below => {
+                window_origin.y + anchor_size.height + content_size.height <= window.height
+            }
+            Placement::Above => window_origin.y - content_size.height >= 0.0,
+            Placement::Right => {
+                window_origin.x + anchor_size.width + content_size.width <= window.width
+            }
+            Placement::Left => window_origin.x - content_size.width >= 0.0,
+        }
+    }
+}
+
+/// Compute where to place content of size `content_size` next to an anchor, so that it
+/// stays within the window if possible.
+///
+/// Returns the placement used, and the origin of the content relative to the anchor.
+pub(crate) fn place_content(
+    placement: Placement,
+    window_origin: Point,
+    anchor_size: Size,
+    content_size: Size,
+    window: Size,
+) -> (Placement, Point) {
+    // The window size is unknown until the root widget is laid out
+    if window.is_empty() {
+        return (placement, offset(placement, anchor_size, content_size));
+    }
+
+    let placement = if !placement.fits(window_origin, anchor_size, content_size, window)
+        && placement
+            .flipped()
+            .fits(window_origin, anchor_size, content_size, window)
+    {
+        placement.flipped()
+    } else {
+        placement
+    };
+
+    // Slide the content along the anchor, so that it doesn't overflow the window
+    let mut origin = offset(placement, anchor_size, content_size);
+    match placement {
+        Placement::Below | Placement::Above => {
+            let max_x = window.width - content_size.width - window_origin.x;
+            origin.x = origin.x.min(max_x).max(-window_origin.x);
+        }
+        Placement::Left | Placement::Right => {
+            let max_y = window.height - content_size.height - window_origin.y;
+            origin.y = origin.y.min(max_y).max(-window_origin.y);
+        }
+    }
+    (placement, origin)
+}
+
+fn offset(placement: Placement, anchor_size: Size, content_size: Size) -> Point {
+    match placement {
+        Placement::Below => Point::new(0.0, anchor_size.height),
+        Placement::Above => Point::new(0.0, -content_size.height),
+        Placement::Right => Point::new(anchor_size.width, 0.0),
+        Placement::Left => Point::new(-content_size.width, 0.0),
+    }
+}
+
+impl<Anchor: WidgetSequence, Content: WidgetSequence> PopoverWidget<Anchor, Content> {
+    pub fn new(
+        anchor: Anchor,
+        content: Option<Content>,
+        placement: Placement,
+        id: WidgetId,
+    ) -> Self {
+        PopoverWidget {
+            anchor,
+            content,
+            placement,
+            resolved_placement: placement,
+            window_origin: Point::ZERO,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Show the given content next to the anchor, or hide it if `content` is `None`.
+    pub fn set_content(&mut self, content: Option<Content>, ctx: &mut EventCtx) {
+        self.window_origin = ctx.to_window(Point::ORIGIN);
+        ctx.set_active(content.is_some());
+        self.content = content;
+        ctx.children_changed();
+    }
+
+    /// Set the side of the anchor the content is displayed on.
+    pub fn set_placement(&mut self, placement: Placement, ctx: &mut EventCtx) {
+        self.placement = placement;
+        ctx.request_layout();
+    }
+
+    fn contains(&self, pos: Point) -> bool {
+        let anchor_widgets = self.anchor.widgets();
+        let content_widgets = self
+            .content
+            .as_ref()
+            .map(|content| content.widgets())
+            .unwrap_or_default();
+        anchor_widgets
+            .iter()
+            .chain(content_widgets.iter())
+            .any(|child| child.layout_rect().contains(pos))
+    }
+}
+
+impl<Anchor: WidgetSequence, Content: WidgetSequence> Widget<DruidAppData>
+    for PopoverWidget<Anchor, Content>
+{
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // The content may have to be placed on another side once the anchor has moved
+        let window_origin = ctx.to_window(Point::ORIGIN);
+        if window_origin != self.window_origin {
+            self.window_origin = window_origin;
+            if self.content.is_some() {
+                ctx.request_layout();
+            }
+        }
+
+        for child in self.anchor.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+        if let Some(content) = &mut self.content {
+            for child in content.widgets_mut() {
+                child.event(ctx, event, data, env);
+            }
+
+            if let Event::MouseDown(mouse_event) = event {
+                if !self.contains(mouse_event.pos) {
+                    trace!("Popover {:?} dismissed", self.id());
+                    data.queue_action(self.id(), Action::Clicked);
+                    ctx.set_handled();
+                }
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.anchor.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+        if let Some(content) = &mut self.content {
+            for child in content.widgets_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.anchor.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+        if let Some(content) = &mut self.content {
+            for child in content.widgets_mut() {
+                child.update(ctx, old_data, data, env);
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Popover");
+
+        let mut anchor_size = Size::ZERO;
+        for child in self.anchor.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            anchor_size = Size::new(
+                anchor_size.width.max(child_size.width),
+                anchor_size.height.max(child_size.height),
+            );
+        }
+        let size = bc.constrain(anchor_size);
+
+        // The content isn't constrained by the anchor's parent, only by the window
+        let window = window_size();
+        let mut paint_rect = size.to_rect();
+        if let Some(content) = &mut self.content {
+            let content_bc = BoxConstraints::new(Size::ZERO, window);
+            let mut content_size = Size::ZERO;
+            for child in content.widgets_mut() {
+                let child_size = child.layout(ctx, &content_bc, data, env);
+                content_size = Size::new(
+                    content_size.width.max(child_size.width),
+                    content_size.height.max(child_size.height),
+                );
+            }
+
+            let (placement, origin) = place_content(
+                self.placement,
+                self.window_origin,
+                size,
+                content_size,
+                window,
+            );
+            self.resolved_placement = placement;
+            for child in content.widgets_mut() {
+                child.set_origin(ctx, data, env, origin);
+            }
+            paint_rect = paint_rect.union(Rect::from_origin_size(origin, content_size));
+        }
+        ctx.set_paint_insets(paint_rect - size.to_rect());
+
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.anchor.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        // The content is painted over an opaque background, so that it's readable
+        if let Some(content) = &mut self.content {
+            let background = env.get(theme::WINDOW_BACKGROUND_COLOR);
+            let border = env.get(theme::BORDER_DARK);
+            for child in content.widgets_mut() {
+                let rect = child.layout_rect();
+                ctx.fill(rect, &background);
+                ctx.stroke(rect, &border, 1.0);
+                child.paint(ctx, data, env);
+            }
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let content_widgets = self
+            .content
+            .as_ref()
+            .map(|content| content.widgets())
+            .unwrap_or_default();
+        let children_state = self
+            .anchor
+            .widgets()
+            .iter()
+            .chain(content_widgets.iter())
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Popover".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        if self.content.is_some() {
+            debug_state.other_values.insert(
+                "placement".to_string(),
+                format!("{:?}", self.resolved_placement),
+            );
+        }
+        debug_state
+    }
+}
+
+pub type SinglePopoverWidget<Anchor, Content> = SingleWidget<PopoverWidget<Anchor, Content>>;
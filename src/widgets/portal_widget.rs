@@ -0,0 +1,93 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
+    UpdateCtx, Widget,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use tracing::trace;
+
+// Note: Like SkeletonWidget, we implement Widget instead of FlexWidget, and wrap
+// PortalWidget in a SingleWidget.
+
+/// An empty placeholder, standing in for children displayed at the root of the window.
+///
+/// The children are shared with the root widget, which dispatches events to them, lays them
+/// out and paints them over the rest of the window. The placeholder registers them when it's
+/// added to the widget tree; the root widget forgets them once the placeholder is dropped.
+pub struct PortalWidget<Children: WidgetSequence + 'static> {
+    pub children: Rc<RefCell<Children>>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence + 'static> PortalWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        PortalWidget {
+            children: Rc::new(RefCell::new(children)),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence + 'static> Widget<DruidAppData> for PortalWidget<Children> {
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut DruidAppData, _env: &Env) {
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        _env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            trace!("Portal {:?} registering its children", self.id());
+            let children: Rc<RefCell<dyn WidgetSequence>> = self.children.clone();
+            data.add_portal(Rc::downgrade(&children));
+            ctx.children_changed();
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) -> Size {
+        bc.min()
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, _data: &DruidAppData, _env: &Env) {}
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Portal".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SinglePortalWidget<Children> = SingleWidget<PortalWidget<Children>>;
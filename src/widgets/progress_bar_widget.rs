@@ -0,0 +1,119 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::ProgressBar;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like SliderWidget, we implement Widget instead of FlexWidget, and wrap
+// ProgressBarWidget in a SingleWidget.
+
+pub struct ProgressBarWidget {
+    // None if the progress is indeterminate
+    pub progress: Option<f64>,
+    pub pod: WidgetPod<f64, ProgressBar>,
+    id: WidgetId,
+}
+
+impl ProgressBarWidget {
+    pub fn new(progress: Option<f64>, id: WidgetId) -> Self {
+        ProgressBarWidget {
+            progress,
+            pod: WidgetPod::new(ProgressBar::new()),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    // TODO - merge with SingleWidget::request_druid_update ?
+    pub fn request_druid_update(&mut self, ctx: &mut EventCtx) {
+        self.pod
+            .with_event_context(ctx, |_widget, ctx: &mut EventCtx| {
+                trace!("request_druid_update: {:?}", ctx.widget_id());
+                ctx.request_update();
+            });
+    }
+
+    fn pod_data(&self) -> f64 {
+        self.progress.unwrap_or(0.0)
+    }
+}
+
+impl Widget<DruidAppData> for ProgressBarWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut DruidAppData, env: &Env) {
+        let mut progress = self.pod_data();
+        self.pod.event(ctx, event, &mut progress, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &self.pod_data(), env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &self.pod_data(), env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let progress = self.pod_data();
+        let size = self.pod.layout(ctx, bc, &progress, env);
+        self.pod.set_origin(ctx, &progress, env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &self.pod_data(), env);
+
+        if self.progress.is_none() {
+            // Druid's ProgressBar has no indeterminate mode; paint a segment in the middle
+            // of the empty bar instead.
+            let size = self.pod.layout_rect().size();
+            let segment = Rect::new(size.width / 3.0, 0.0, size.width * 2.0 / 3.0, size.height)
+                .to_rounded_rect(env.get(theme::PROGRESS_BAR_RADIUS));
+            ctx.fill(segment, &env.get(theme::PRIMARY_LIGHT));
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let main_value = match self.progress {
+            Some(progress) => format!("{:.0}%", progress * 100.0),
+            None => "indeterminate".to_string(),
+        };
+        DebugState {
+            display_name: "ProgressBar".to_string(),
+            main_value,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleProgressBarWidget = SingleWidget<ProgressBarWidget>;
@@ -0,0 +1,122 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::RadioGroup;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// RadioGroupWidget in a SingleWidget.
+
+// Druid's RadioGroup requires its values to implement druid::Data, which we don't want to
+// require from users. Instead, the widget stores the index of the selected option.
+
+pub struct RadioGroupWidget {
+    pub selected_index: Option<usize>,
+    pub pod: WidgetPod<Option<usize>, Box<dyn Widget<Option<usize>>>>,
+    id: WidgetId,
+}
+
+impl RadioGroupWidget {
+    pub fn new(labels: Vec<String>, selected_index: Option<usize>, id: WidgetId) -> Self {
+        RadioGroupWidget {
+            selected_index,
+            pod: Self::new_pod(labels),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the radio buttons with a new set of options.
+    pub fn set_labels(&mut self, labels: Vec<String>, ctx: &mut EventCtx) {
+        self.pod = Self::new_pod(labels);
+        ctx.children_changed();
+    }
+
+    fn new_pod(labels: Vec<String>) -> WidgetPod<Option<usize>, Box<dyn Widget<Option<usize>>>> {
+        let variants = labels
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| (label, Some(i)));
+        WidgetPod::new(Box::new(RadioGroup::new(variants)))
+    }
+
+    // TODO - merge with SingleWidget::request_druid_update ?
+    pub fn request_druid_update(&mut self, ctx: &mut EventCtx) {
+        self.pod
+            .with_event_context(ctx, |_widget, ctx: &mut EventCtx| {
+                trace!("request_druid_update: {:?}", ctx.widget_id());
+                ctx.request_update();
+            });
+    }
+}
+
+impl Widget<DruidAppData> for RadioGroupWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        let prev_index = self.selected_index;
+        self.pod.event(ctx, event, &mut self.selected_index, env);
+        if self.selected_index != prev_index {
+            trace!(
+                "RadioGroup {:?} selection changed: {:?}",
+                self.id(),
+                self.selected_index
+            );
+            data.queue_action(self.id(), Action::Clicked);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &self.selected_index, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &self.selected_index, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let size = self.pod.layout(ctx, bc, &self.selected_index, env);
+        self.pod
+            .set_origin(ctx, &self.selected_index, env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &self.selected_index, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(&self.selected_index)
+    }
+}
+
+pub type SingleRadioGroupWidget = SingleWidget<RadioGroupWidget>;
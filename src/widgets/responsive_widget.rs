@@ -0,0 +1,174 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Selector, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like VirtualListWidget, we implement Widget instead of FlexWidget, and wrap
+// ResponsiveWidget in a SingleWidget.
+
+// Sent by the widget to itself when the available width crosses the breakpoint, since
+// actions can't be queued during layout.
+const REFRESH_BREAKPOINT: Selector = Selector::new("panoramix.responsive-refresh");
+
+/// Displays its children, and tracks which side of a breakpoint the available width is on.
+///
+/// The width is measured during layout. When it moves to the other side of the breakpoint,
+/// by more than the hysteresis margin, the widget queues [`Action::BreakpointCrossed`];
+/// replacing the children is left to the element.
+pub struct ResponsiveWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub breakpoint: f64,
+    pub hysteresis: f64,
+    // Whether the width is past the breakpoint, once the widget has been laid out
+    pub is_wide: Option<bool>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> ResponsiveWidget<Children> {
+    pub fn new(
+        children: Children,
+        breakpoint: f64,
+        hysteresis: f64,
+        is_wide: Option<bool>,
+        id: WidgetId,
+    ) -> Self {
+        ResponsiveWidget {
+            children,
+            breakpoint,
+            hysteresis,
+            is_wide,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    // Once a side has been picked, the width must go past the breakpoint by the hysteresis
+    // margin to switch sides, so that resizing around the breakpoint doesn't flicker.
+    fn compute_is_wide(&self, width: f64) -> bool {
+        match self.is_wide {
+            None => width >= self.breakpoint,
+            Some(true) => width >= self.breakpoint - self.hysteresis,
+            Some(false) => width >= self.breakpoint + self.hysteresis,
+        }
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ResponsiveWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if command.is(REFRESH_BREAKPOINT) {
+                ctx.set_handled();
+                if let Some(is_wide) = self.is_wide {
+                    data.queue_action(self.id(), Action::BreakpointCrossed(is_wide));
+                }
+                return;
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Responsive");
+
+        // Without a width limit, there's no breakpoint to cross
+        let width = bc.max().width;
+        if width.is_finite() {
+            let is_wide = self.compute_is_wide(width);
+            if self.is_wide != Some(is_wide) {
+                trace!(
+                    "Responsive {:?} crossed breakpoint: width={}, is_wide={}",
+                    self.id(),
+                    width,
+                    is_wide
+                );
+                self.is_wide = Some(is_wide);
+                ctx.submit_command(REFRESH_BREAKPOINT.to(self.id));
+            }
+        }
+
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Responsive".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("is_wide".to_string(), format!("{:?}", self.is_wide));
+        debug_state
+    }
+}
+
+pub type SingleResponsiveWidget<Children> = SingleWidget<ResponsiveWidget<Children>>;
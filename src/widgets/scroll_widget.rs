@@ -0,0 +1,203 @@
+use crate::elements::ScrollDirection;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::Scroll as DruidScroll;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Rect, Size, UpdateCtx, Widget,
+};
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// ScrollWidget in a SingleWidget.
+
+/// Lays out a widget sequence for druid's Scroll container.
+///
+/// Children are stacked on top of each other; in practice, the sequence usually holds
+/// a single widget.
+pub struct ScrollContent<Children: WidgetSequence> {
+    pub children: Children,
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ScrollContent<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "ScrollContent".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub struct ScrollWidget<Children: WidgetSequence> {
+    pub scroll: DruidScroll<DruidAppData, ScrollContent<Children>>,
+    pub direction: ScrollDirection,
+    /// The key of the last descendant the widget was scrolled to.
+    pub scroll_target: Option<String>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> ScrollWidget<Children> {
+    pub fn new(children: Children, direction: ScrollDirection, id: WidgetId) -> Self {
+        ScrollWidget {
+            scroll: Self::new_scroll(ScrollContent { children }, direction),
+            direction,
+            scroll_target: None,
+            id,
+        }
+    }
+
+    fn new_scroll(
+        content: ScrollContent<Children>,
+        direction: ScrollDirection,
+    ) -> DruidScroll<DruidAppData, ScrollContent<Children>> {
+        let scroll = DruidScroll::new(content);
+        match direction {
+            ScrollDirection::All => scroll,
+            ScrollDirection::Vertical => scroll.vertical(),
+            ScrollDirection::Horizontal => scroll.horizontal(),
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    pub fn children(&self) -> &Children {
+        &self.scroll.child().children
+    }
+
+    pub fn children_mut(&mut self) -> &mut Children {
+        &mut self.scroll.child_mut().children
+    }
+
+    /// Scroll the minimal distance to show `rect`, given in the content's coordinate space,
+    /// and remember `key` as the last target.
+    pub fn scroll_to(&mut self, key: String, rect: Rect, ctx: &mut EventCtx) {
+        trace!("Scrolling to {:?} at {:?}", key, rect);
+        self.scroll_target = Some(key);
+        if self.scroll.scroll_to(rect) {
+            ctx.request_paint();
+        }
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for ScrollWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        self.scroll.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.scroll.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.scroll.update(ctx, old_data, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.scroll.layout(ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.scroll.paint(ctx, data, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Scroll".to_string(),
+            main_value: format!("{:?}", self.scroll.offset()),
+            children: vec![self.scroll.child().debug_state(data)],
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleScrollWidget<Children> = SingleWidget<ScrollWidget<Children>>;
@@ -0,0 +1,207 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::FlexWidget;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{Button, Click, ControllerHost};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, KbKey, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like TabsWidget, we implement Widget instead of FlexWidget, and wrap
+// SegmentedControlWidget in a SingleWidget.
+
+const SELECTED_OVERLAY_ALPHA: f64 = 0.4;
+
+type SegmentButton = ControllerHost<Button<DruidAppData>, Click<DruidAppData>>;
+
+/// A row of adjacent buttons, of which at most one is highlighted.
+///
+/// Clicking a segment, or pressing the left and right arrow keys while the control has focus,
+/// queues [`Action::Selected`] with the index of the segment; changing the highlighted
+/// segment is left to the element.
+pub struct SegmentedControlWidget {
+    pub labels: Vec<String>,
+    pub selected_index: Option<usize>,
+    pub segments: Vec<SingleWidget<SegmentButton>>,
+    id: WidgetId,
+}
+
+impl SegmentedControlWidget {
+    pub fn new(labels: Vec<String>, selected_index: Option<usize>, id: WidgetId) -> Self {
+        SegmentedControlWidget {
+            segments: Self::new_segments(&labels, id),
+            labels,
+            selected_index,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the segments.
+    pub fn set_labels(&mut self, labels: Vec<String>, ctx: &mut EventCtx) {
+        self.segments = Self::new_segments(&labels, self.id);
+        self.labels = labels;
+        ctx.children_changed();
+    }
+
+    /// Highlight another segment.
+    pub fn set_selected_index(&mut self, selected_index: Option<usize>, ctx: &mut EventCtx) {
+        self.selected_index = selected_index;
+        ctx.request_paint();
+    }
+
+    fn new_segments(labels: &[String], id: WidgetId) -> Vec<SingleWidget<SegmentButton>> {
+        labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                let segment =
+                    Button::new(label.clone()).on_click(move |_, data: &mut DruidAppData, _| {
+                        data.queue_action(id, Action::Selected(index))
+                    });
+                SingleWidget::new(segment, Default::default())
+            })
+            .collect()
+    }
+
+    // The segment selected by an arrow key; arrows don't wrap around
+    fn next_index(&self, key: &KbKey) -> Option<usize> {
+        let count = self.labels.len();
+        if count == 0 {
+            return None;
+        }
+        match (key, self.selected_index) {
+            (KbKey::ArrowLeft, Some(index)) => index.checked_sub(1),
+            (KbKey::ArrowRight, Some(index)) => Some(index + 1).filter(|index| *index < count),
+            (KbKey::ArrowLeft, None) => Some(count - 1),
+            (KbKey::ArrowRight, None) => Some(0),
+            _ => None,
+        }
+    }
+}
+
+impl Widget<DruidAppData> for SegmentedControlWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // Keyboard events are only sent to the focused widget and its parents
+        if let Event::MouseDown(_) = event {
+            ctx.request_focus();
+        }
+        if let Event::KeyDown(key_event) = event {
+            if ctx.is_focused() {
+                if let Some(index) = self.next_index(&key_event.key) {
+                    trace!("SegmentedControl {:?} key selection: {}", self.id(), index);
+                    ctx.set_handled();
+                    data.queue_action(self.id(), Action::Selected(index));
+                    return;
+                }
+            }
+        }
+
+        for segment in &mut self.segments {
+            segment.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        if let LifeCycle::FocusChanged(_) = event {
+            ctx.request_paint();
+        }
+
+        for segment in &mut self.segments {
+            segment.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for segment in &mut self.segments {
+            segment.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("SegmentedControl");
+
+        // Segments are laid out in a row, with no space between them
+        let segment_bc = bc.loosen();
+        let mut width = 0.0;
+        let mut height: f64 = 0.0;
+        for segment in &mut self.segments {
+            let segment_size = segment.layout(ctx, &segment_bc, data, env);
+            segment.set_origin(ctx, data, env, Point::new(width, 0.0));
+            width += segment_size.width;
+            height = height.max(segment_size.height);
+        }
+
+        let size = bc.constrain(Size::new(width, height));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for segment in &mut self.segments {
+            segment.paint(ctx, data, env);
+        }
+
+        if let Some(selected_segment) = self
+            .selected_index
+            .and_then(|index| self.segments.get(index))
+        {
+            let highlight = env
+                .get(theme::PRIMARY_LIGHT)
+                .with_alpha(SELECTED_OVERLAY_ALPHA);
+            ctx.fill(selected_segment.layout_rect(), &highlight);
+        }
+        if ctx.has_focus() {
+            let focus_rect = ctx.size().to_rect().inset(-0.5);
+            ctx.stroke(focus_rect, &env.get(theme::PRIMARY_LIGHT), 1.0);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    // The selected label isn't the main value, so that the harness finds the segment instead
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let mut other_values = HashMap::new();
+        if let Some(label) = self.selected_index.and_then(|index| self.labels.get(index)) {
+            other_values.insert("selected".to_string(), label.clone());
+        }
+        DebugState {
+            display_name: "SegmentedControl".to_string(),
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleSegmentedControlWidget = SingleWidget<SegmentedControlWidget>;
@@ -45,7 +45,7 @@ impl<W: Widget<DruidAppData>> FlexWidget for SingleWidget<W> {
         self.flex
     }
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
         self.pod.event(ctx, event, data, env);
     }
 
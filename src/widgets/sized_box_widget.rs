@@ -0,0 +1,174 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+use tracing::{trace, warn};
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// SizedBoxWidget in a SingleWidget.
+
+// Druid's SizedBox owns its child as a Box<dyn Widget>, which doesn't let us reach the
+// children during reconcile. Instead, we reimplement its constraint logic on top of a
+// WidgetSequence.
+
+pub struct SizedBoxWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> SizedBoxWidget<Children> {
+    pub fn new(children: Children, width: Option<f64>, height: Option<f64>, id: WidgetId) -> Self {
+        SizedBoxWidget {
+            children,
+            width,
+            height,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Change the size of the box, and request a new layout pass.
+    pub fn set_size(&mut self, width: Option<f64>, height: Option<f64>, ctx: &mut EventCtx) {
+        trace!(
+            "SizedBox {:?} size changed: {:?}x{:?}",
+            self.id(),
+            width,
+            height
+        );
+        self.width = width;
+        self.height = height;
+        ctx.request_layout();
+    }
+
+    // Copy-pasted from druid::widget::SizedBox
+    fn child_constraints(&self, bc: &BoxConstraints) -> BoxConstraints {
+        let (min_width, max_width) = match self.width {
+            Some(width) => {
+                let w = width.max(bc.min().width).min(bc.max().width);
+                (w, w)
+            }
+            None => (bc.min().width, bc.max().width),
+        };
+
+        let (min_height, max_height) = match self.height {
+            Some(height) => {
+                let h = height.max(bc.min().height).min(bc.max().height);
+                (h, h)
+            }
+            None => (bc.min().height, bc.max().height),
+        };
+
+        BoxConstraints::new(
+            Size::new(min_width, min_height),
+            Size::new(max_width, max_height),
+        )
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for SizedBoxWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("SizedBox");
+
+        let child_bc = self.child_constraints(bc);
+        let mut children = self.children.widgets_mut();
+
+        let size = if children.is_empty() {
+            bc.constrain((self.width.unwrap_or(0.0), self.height.unwrap_or(0.0)))
+        } else {
+            // Children are stacked on top of each other
+            let mut size = Size::ZERO;
+            for child in children.iter_mut() {
+                let child_size = child.layout(ctx, &child_bc, data, env);
+                child.set_origin(ctx, data, env, Point::ZERO);
+                size = Size::new(
+                    size.width.max(child_size.width),
+                    size.height.max(child_size.height),
+                );
+            }
+            child_bc.constrain(size)
+        };
+
+        if size.width.is_infinite() {
+            warn!("SizedBox is returning an infinite width.");
+        }
+        if size.height.is_infinite() {
+            warn!("SizedBox is returning an infinite height.");
+        }
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "SizedBox".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleSizedBoxWidget<Children> = SingleWidget<SizedBoxWidget<Children>>;
@@ -0,0 +1,146 @@
+use crate::elements::SkeletonShape;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::kurbo::Circle;
+use druid::piet::GradientStop;
+use druid::{
+    theme, BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    LinearGradient, PaintCtx, RenderContext, Size, UnitPoint, UpdateCtx, Widget,
+};
+
+// Note: Like CanvasWidget, we implement Widget instead of FlexWidget, and wrap
+// SkeletonWidget in a SingleWidget.
+
+/// Time for the shimmer to sweep across the placeholder once, in seconds.
+const SHIMMER_PERIOD: f64 = 1.2;
+/// Width of the shimmer, as a fraction of the placeholder's width.
+const SHIMMER_WIDTH: f64 = 0.4;
+const CORNER_RADIUS: f64 = 4.0;
+
+/// A placeholder of fixed size, with a highlight sweeping over it continuously.
+///
+/// The widget requests animation frames for as long as it's mounted; the shimmer is purely
+/// visual, so frames are handled by the widget itself.
+pub struct SkeletonWidget {
+    pub shape: SkeletonShape,
+    pub size: Size,
+    // Position of the shimmer, between 0 and 1
+    phase: f64,
+    id: WidgetId,
+}
+
+impl SkeletonWidget {
+    pub fn new(shape: SkeletonShape, size: Size, id: WidgetId) -> Self {
+        SkeletonWidget {
+            shape,
+            size,
+            phase: 0.0,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Change the shape and size of the placeholder, and request a new layout.
+    pub fn set_shape(&mut self, shape: SkeletonShape, size: Size, ctx: &mut EventCtx) {
+        self.shape = shape;
+        self.size = size;
+        ctx.request_layout();
+    }
+}
+
+impl Widget<DruidAppData> for SkeletonWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut DruidAppData, _env: &Env) {
+        if let Event::AnimFrame(interval) = event {
+            let elapsed = *interval as f64 / 1e9;
+            self.phase = (self.phase + elapsed / SHIMMER_PERIOD).fract();
+            ctx.request_paint();
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        _env: &Env,
+    ) -> Size {
+        bc.debug_check("Skeleton");
+        bc.constrain(self.size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        let size = ctx.size();
+        let base = env.get(theme::BACKGROUND_LIGHT);
+        let highlight = env.get(theme::BORDER_LIGHT);
+
+        // The shimmer starts fully left of the placeholder and ends fully right of it
+        let center = -SHIMMER_WIDTH + self.phase * (1.0 + 2.0 * SHIMMER_WIDTH);
+        let stop = |offset: f64, color: Color| GradientStop {
+            pos: (center + offset).max(0.0).min(1.0) as f32,
+            color,
+        };
+        let stops = vec![
+            stop(-SHIMMER_WIDTH / 2.0, base.clone()),
+            stop(0.0, highlight),
+            stop(SHIMMER_WIDTH / 2.0, base),
+        ];
+        let gradient = LinearGradient::new(UnitPoint::LEFT, UnitPoint::RIGHT, stops.as_slice());
+
+        match self.shape {
+            SkeletonShape::Rectangle => {
+                let rect = size.to_rect().to_rounded_rect(CORNER_RADIUS);
+                ctx.fill(rect, &gradient);
+            }
+            SkeletonShape::Circle => {
+                let radius = size.width.min(size.height) / 2.0;
+                let circle = Circle::new(size.to_rect().center(), radius);
+                ctx.fill(circle, &gradient);
+            }
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let mut debug_state = DebugState {
+            display_name: "Skeleton".to_string(),
+            main_value: format!("{}x{}", self.size.width, self.size.height),
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("shape".to_string(), format!("{:?}", self.shape));
+        debug_state
+    }
+}
+
+pub type SingleSkeletonWidget = SingleWidget<SkeletonWidget>;
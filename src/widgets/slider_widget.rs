@@ -0,0 +1,113 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::Slider;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap SliderWidget
+// in a SingleWidget.
+
+pub struct SliderWidget {
+    pub value: f64,
+    pub pod: WidgetPod<f64, Slider>,
+    pub release_only: bool,
+    // Set when the value changed during a drag, in release-only mode
+    value_changed: bool,
+    id: WidgetId,
+}
+
+impl SliderWidget {
+    pub fn new(min: f64, max: f64, value: f64, release_only: bool, id: WidgetId) -> Self {
+        let slider = Slider::new().with_range(min, max);
+
+        SliderWidget {
+            value,
+            pod: WidgetPod::new(slider),
+            release_only,
+            value_changed: false,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    // TODO - merge with SingleWidget::request_druid_update ?
+    pub fn request_druid_update(&mut self, ctx: &mut EventCtx) {
+        self.pod
+            .with_event_context(ctx, |_widget, ctx: &mut EventCtx| {
+                trace!("request_druid_update: {:?}", ctx.widget_id());
+                ctx.request_update();
+            });
+    }
+}
+
+impl Widget<DruidAppData> for SliderWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        let prev_value = self.value;
+        self.pod.event(ctx, event, &mut self.value, env);
+        if self.value != prev_value {
+            self.value_changed = true;
+        }
+
+        let released = matches!(event, Event::MouseUp(_));
+        if self.value_changed && (!self.release_only || released) {
+            trace!("Slider {:?} value changed: {}", self.id(), self.value);
+            data.queue_action(self.id(), Action::ValueChanged(self.value));
+            self.value_changed = false;
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &self.value, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &self.value, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let size = self.pod.layout(ctx, bc, &self.value, env);
+        self.pod.set_origin(ctx, &self.value, env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &self.value, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(&self.value)
+    }
+}
+
+pub type SingleSliderWidget = SingleWidget<SliderWidget>;
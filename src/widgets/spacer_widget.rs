@@ -0,0 +1,109 @@
+use crate::flex::FlexParams;
+use crate::glue::DruidAppData;
+use crate::widget_sequence::FlexWidget;
+use crate::widget_sequence::WidgetSequence;
+
+use crate::glue::DebugState;
+use druid::kurbo::{Point, Rect, Size};
+use druid::widget::SizedBox;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    WidgetPod,
+};
+
+// Note: Unlike other widgets, the spacer implements FlexWidget directly, because the Flex
+// container needs to know it's a spacer to give it all the space its flex factor allots.
+
+pub struct SpacerWidget {
+    pub pod: WidgetPod<DruidAppData, SizedBox<DruidAppData>>,
+    pub flex: FlexParams,
+}
+
+impl SpacerWidget {
+    pub fn new(flex: FlexParams) -> Self {
+        SpacerWidget {
+            pod: WidgetPod::new(SizedBox::empty()),
+            flex,
+        }
+    }
+}
+
+impl FlexWidget for SpacerWidget {
+    fn flex_params(&self) -> FlexParams {
+        self.flex
+    }
+
+    fn is_spacer(&self) -> bool {
+        true
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.pod.layout(ctx, bc, data, env)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        self.pod.paint_rect()
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &DruidAppData, env: &Env, origin: Point) {
+        self.pod.set_origin(ctx, data, env, origin)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        self.pod.layout_rect()
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        self.pod.baseline_offset()
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, _data: &DruidAppData, _env: &Env) {}
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Spacer".to_string(),
+            main_value: format!("{:?}", self.flex.flex),
+            ..Default::default()
+        }
+    }
+}
+
+impl WidgetSequence for SpacerWidget {
+    fn widgets(&self) -> Vec<&dyn FlexWidget> {
+        vec![self]
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget> {
+        vec![self]
+    }
+}
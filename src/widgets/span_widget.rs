@@ -0,0 +1,207 @@
+use crate::flex::FlexParams;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::FlexWidget;
+use crate::widget_sequence::WidgetSequence;
+
+use crate::glue::DebugState;
+use druid::kurbo::{Point, Rect, Size};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    Widget, WidgetPod,
+};
+
+// Note: Unlike other wrapper widgets, SingleSpanWidget implements FlexWidget directly,
+// because the Grid container needs to know how many columns it spans.
+
+pub struct SpanWidget<Children: WidgetSequence> {
+    pub children: Children,
+    id: WidgetId,
+}
+
+pub struct SingleSpanWidget<Children: WidgetSequence> {
+    pub pod: WidgetPod<DruidAppData, SpanWidget<Children>>,
+    pub span: usize,
+    pub flex: FlexParams,
+}
+
+impl<Children: WidgetSequence> SpanWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        SpanWidget { children, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Children: WidgetSequence> SingleSpanWidget<Children> {
+    pub fn new(widget: SpanWidget<Children>, span: usize, flex: FlexParams) -> Self {
+        SingleSpanWidget {
+            pod: WidgetPod::new(widget),
+            span,
+            flex,
+        }
+    }
+
+    pub fn widget(&self) -> &SpanWidget<Children> {
+        self.pod.widget()
+    }
+
+    pub fn widget_mut(&mut self) -> &mut SpanWidget<Children> {
+        self.pod.widget_mut()
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for SpanWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Span".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Children: WidgetSequence> FlexWidget for SingleSpanWidget<Children> {
+    fn flex_params(&self) -> FlexParams {
+        self.flex
+    }
+
+    fn grid_span(&self) -> usize {
+        self.span
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.pod.layout(ctx, bc, data, env)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        self.pod.paint_rect()
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, data: &DruidAppData, env: &Env, origin: Point) {
+        self.pod.set_origin(ctx, data, env, origin)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        self.pod.layout_rect()
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        self.pod.baseline_offset()
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, data, env);
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(data)
+    }
+}
+
+impl<Children: WidgetSequence> WidgetSequence for SingleSpanWidget<Children> {
+    fn widgets(&self) -> Vec<&dyn FlexWidget> {
+        vec![self]
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget> {
+        vec![self]
+    }
+}
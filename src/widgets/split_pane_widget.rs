@@ -0,0 +1,317 @@
+use crate::flex::Axis;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Cursor, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    MouseButton, PaintCtx, Point, Rect, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like PopoverWidget, we implement Widget instead of FlexWidget, and wrap
+// SplitPaneWidget in a SingleWidget.
+
+const DEFAULT_SIZE: f64 = 400.0;
+const BAR_THICKNESS: f64 = 6.0;
+// The bar can be grabbed slightly outside of its painted area
+const BAR_HIT_PADDING: f64 = 4.0;
+
+/// Displays its first children and its second children side by side along `axis`, separated
+/// by a bar which can be dragged to resize them.
+///
+/// The split pane takes all the space it's given. Dragging the bar queues
+/// [`Action::ValueChanged`] with the new split fraction.
+pub struct SplitPaneWidget<First: WidgetSequence, Second: WidgetSequence> {
+    pub first: First,
+    pub second: Second,
+    pub axis: Axis,
+    // The fraction of the available space given to the first pane
+    pub split: f64,
+    pub min_sizes: (f64, f64),
+    // The space available to both panes on the main axis, set on layout
+    available: f64,
+    // Distance from the start of the bar to the mouse, while the bar is dragged
+    drag_offset: Option<f64>,
+    id: WidgetId,
+}
+
+// Clamp the size of the first pane, so that both panes get their minimum size if possible.
+//
+// The minimum size of the first pane takes precedence over the minimum size of the second.
+fn clamp_first_size(first_size: f64, available: f64, min_sizes: (f64, f64)) -> f64 {
+    let (first_min, second_min) = min_sizes;
+    first_size
+        .min(available - second_min)
+        .max(first_min)
+        .min(available)
+        .max(0.0)
+}
+
+impl<First: WidgetSequence, Second: WidgetSequence> SplitPaneWidget<First, Second> {
+    pub fn new(
+        first: First,
+        second: Second,
+        axis: Axis,
+        split: f64,
+        min_sizes: (f64, f64),
+        id: WidgetId,
+    ) -> Self {
+        SplitPaneWidget {
+            first,
+            second,
+            axis,
+            split,
+            min_sizes,
+            available: 0.0,
+            drag_offset: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Set the fraction of the space given to the first pane, and request a new layout.
+    pub fn set_split(&mut self, split: f64, ctx: &mut EventCtx) {
+        self.split = split;
+        ctx.request_layout();
+    }
+
+    /// Set the axis the panes are placed along, and request a new layout.
+    pub fn set_axis(&mut self, axis: Axis, ctx: &mut EventCtx) {
+        self.axis = axis;
+        ctx.request_layout();
+    }
+
+    /// Set the minimum sizes of the panes, and request a new layout.
+    pub fn set_min_sizes(&mut self, min_sizes: (f64, f64), ctx: &mut EventCtx) {
+        self.min_sizes = min_sizes;
+        ctx.request_layout();
+    }
+
+    fn bar_start(&self) -> f64 {
+        clamp_first_size(self.split * self.available, self.available, self.min_sizes)
+    }
+
+    fn major_pos(&self, pos: Point) -> f64 {
+        match self.axis {
+            Axis::Horizontal => pos.x,
+            Axis::Vertical => pos.y,
+        }
+    }
+
+    fn bar_rect(&self, size: Size) -> Rect {
+        let start = self.bar_start();
+        let minor = self.axis.minor(size);
+        let origin: Point = self.axis.pack(start, 0.0).into();
+        let bar_size: Size = self.axis.pack(BAR_THICKNESS, minor).into();
+        Rect::from_origin_size(origin, bar_size)
+    }
+
+    fn bar_hit_test(&self, pos: Point, size: Size) -> bool {
+        let padding: Size = self.axis.pack(BAR_HIT_PADDING, 0.0).into();
+        self.bar_rect(size)
+            .inflate(padding.width, padding.height)
+            .contains(pos)
+    }
+
+    fn cursor(&self) -> Cursor {
+        match self.axis {
+            Axis::Horizontal => Cursor::ResizeLeftRight,
+            Axis::Vertical => Cursor::ResizeUpDown,
+        }
+    }
+}
+
+impl<First: WidgetSequence, Second: WidgetSequence> Widget<DruidAppData>
+    for SplitPaneWidget<First, Second>
+{
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        let size = ctx.size();
+        match event {
+            Event::MouseDown(mouse_event)
+                if mouse_event.button == MouseButton::Left
+                    && self.bar_hit_test(mouse_event.pos, size) =>
+            {
+                self.drag_offset = Some(self.major_pos(mouse_event.pos) - self.bar_start());
+                ctx.set_active(true);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse_event) if ctx.is_active() => {
+                if let Some(drag_offset) = self.drag_offset {
+                    let first_size = clamp_first_size(
+                        self.major_pos(mouse_event.pos) - drag_offset,
+                        self.available,
+                        self.min_sizes,
+                    );
+                    let split = first_size / self.available;
+                    if self.available > 0.0 && split != self.split {
+                        self.split = split;
+                        trace!("SplitPane {:?} moved to {}", self.id(), split);
+                        data.queue_action(self.id(), Action::ValueChanged(split));
+                        ctx.request_layout();
+                    }
+                    ctx.set_cursor(&self.cursor());
+                    ctx.set_handled();
+                    return;
+                }
+            }
+            Event::MouseUp(mouse_event)
+                if mouse_event.button == MouseButton::Left && self.drag_offset.is_some() =>
+            {
+                self.drag_offset = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+                return;
+            }
+            Event::MouseMove(mouse_event) => {
+                if self.bar_hit_test(mouse_event.pos, size) {
+                    ctx.set_cursor(&self.cursor());
+                } else {
+                    ctx.clear_cursor();
+                }
+            }
+            _ => {}
+        }
+
+        for child in self.first.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+        for child in self.second.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.first.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+        for child in self.second.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.first.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+        for child in self.second.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("SplitPane");
+
+        let mut size = Size::new(DEFAULT_SIZE, DEFAULT_SIZE);
+        if bc.is_width_bounded() {
+            size.width = bc.max().width;
+        }
+        if bc.is_height_bounded() {
+            size.height = bc.max().height;
+        }
+        let size = bc.constrain(size);
+
+        self.available = (self.axis.major(size) - BAR_THICKNESS).max(0.0);
+        let first_size = self.bar_start();
+        let second_size = self.available - first_size;
+        let minor = self.axis.minor(size);
+
+        let first_bc = BoxConstraints::tight(self.axis.pack(first_size, minor).into());
+        for child in self.first.widgets_mut() {
+            child.layout(ctx, &first_bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+        }
+        let second_bc = BoxConstraints::tight(self.axis.pack(second_size, minor).into());
+        let second_origin = self.axis.pack(first_size + BAR_THICKNESS, 0.0).into();
+        for child in self.second.widgets_mut() {
+            child.layout(ctx, &second_bc, data, env);
+            child.set_origin(ctx, data, env, second_origin);
+        }
+
+        trace!("Computed layout: size={}, split={}", size, self.split);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        let size = ctx.size();
+        let first_size = self.bar_start();
+        let minor = self.axis.minor(size);
+        let first_rect =
+            Rect::from_origin_size(Point::ZERO, Size::from(self.axis.pack(first_size, minor)));
+        let second_rect = Rect::from_origin_size(
+            Point::from(self.axis.pack(first_size + BAR_THICKNESS, 0.0)),
+            Size::from(self.axis.pack(self.available - first_size, minor)),
+        );
+
+        // Each pane is clipped, so that overflowing children don't cover the other one
+        for child in self.first.widgets_mut() {
+            ctx.with_save(|ctx| {
+                ctx.clip(first_rect);
+                child.paint(ctx, data, env);
+            });
+        }
+        for child in self.second.widgets_mut() {
+            ctx.with_save(|ctx| {
+                ctx.clip(second_rect);
+                child.paint(ctx, data, env);
+            });
+        }
+
+        let bar_color = if ctx.is_active() || ctx.is_hot() {
+            env.get(theme::BORDER_LIGHT)
+        } else {
+            env.get(theme::BORDER_DARK)
+        };
+        ctx.fill(self.bar_rect(size), &bar_color);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .first
+            .widgets()
+            .iter()
+            .chain(self.second.widgets().iter())
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "SplitPane".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("split".to_string(), self.split.to_string());
+        debug_state
+    }
+}
+
+pub type SingleSplitPaneWidget<First, Second> = SingleWidget<SplitPaneWidget<First, Second>>;
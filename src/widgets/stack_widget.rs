@@ -0,0 +1,142 @@
+//! A widget that layers its children on top of each other.
+
+use druid::kurbo::{Point, Rect, Size};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UnitPoint,
+    UpdateCtx, Widget,
+};
+
+use crate::widget_sequence::WidgetSequence;
+
+use tracing::trace;
+
+/// A container Druid widget which draws its children on top of each other.
+///
+/// Children are painted in order, so the last child is on top. The stack is as big as its
+/// biggest child, not counting children with a
+/// [`stack_alignment`](crate::widget_sequence::FlexWidget::stack_alignment), which fill
+/// the stack instead. Other children are placed according to the stack's alignment.
+pub struct StackWidget<Children: WidgetSequence> {
+    pub(crate) alignment: UnitPoint,
+    pub children_seq: Children,
+}
+
+use crate::glue::DruidAppData;
+impl<Children: WidgetSequence> Widget<DruidAppData> for StackWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // FIXME
+        ctx.children_changed();
+
+        // Children on top get events first, so that they can hide them from the children
+        // below by marking them as handled.
+        for child in self.children_seq.widgets_mut().into_iter().rev() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Stack");
+        let loosened_bc = bc.loosen();
+        let mut child_widgets = self.children_seq.widgets_mut();
+
+        // Aligned children fill the stack and place their content themselves, so unless all
+        // children are aligned, the size of the stack only depends on the other children.
+        let all_aligned = child_widgets
+            .iter()
+            .all(|child| child.stack_alignment().is_some());
+
+        let mut size = Size::ZERO;
+        for child in &mut child_widgets {
+            if !all_aligned && child.stack_alignment().is_some() {
+                continue;
+            }
+            let child_size = child.layout(ctx, &loosened_bc, data, env);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        let my_size = bc.constrain(size);
+
+        if !all_aligned {
+            let aligned_bc = BoxConstraints::tight(my_size);
+            for child in &mut child_widgets {
+                if child.stack_alignment().is_some() {
+                    child.layout(ctx, &aligned_bc, data, env);
+                }
+            }
+        }
+
+        let mut child_paint_rect = Rect::ZERO;
+        for child in &mut child_widgets {
+            let alignment = child.stack_alignment().unwrap_or(self.alignment);
+            let extra = my_size - child.layout_rect().size();
+            let extra = Size::new(extra.width.max(0.0), extra.height.max(0.0));
+            let origin = alignment
+                .resolve(Rect::from_origin_size(Point::ZERO, extra))
+                .round();
+            child.set_origin(ctx, data, env, origin);
+            child_paint_rect = child_paint_rect.union(child.paint_rect());
+        }
+
+        let my_bounds = Rect::ZERO.with_size(my_size);
+        let insets = child_paint_rect - my_bounds;
+        ctx.set_paint_insets(insets);
+
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children_seq.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children_seq
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Stack".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{Button, Click, ControllerHost, Label};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like SwitchWidget, we implement Widget instead of FlexWidget, and wrap
+// StepperWidget in a SingleWidget.
+
+const BUTTON_SPACING: f64 = 8.0;
+const DISABLED_OVERLAY_ALPHA: f64 = 0.6;
+
+type StepButton = ControllerHost<Button<DruidAppData>, Click<DruidAppData>>;
+
+/// A value display between a "-" and a "+" button.
+///
+/// The buttons queue [`Action::Decremented`] and [`Action::Incremented`]; computing the new
+/// value is left to the element.
+pub struct StepperWidget {
+    pub text: String,
+    pub can_decrement: bool,
+    pub can_increment: bool,
+    pub decrement: WidgetPod<DruidAppData, StepButton>,
+    pub display: WidgetPod<DruidAppData, Label<DruidAppData>>,
+    pub increment: WidgetPod<DruidAppData, StepButton>,
+    id: WidgetId,
+}
+
+impl StepperWidget {
+    pub fn new(text: String, can_decrement: bool, can_increment: bool, id: WidgetId) -> Self {
+        let decrement = Button::new("-").on_click(move |_, data: &mut DruidAppData, _| {
+            data.queue_action(id, Action::Decremented)
+        });
+        let increment = Button::new("+").on_click(move |_, data: &mut DruidAppData, _| {
+            data.queue_action(id, Action::Incremented)
+        });
+
+        StepperWidget {
+            display: WidgetPod::new(Label::new(text.clone())),
+            text,
+            can_decrement,
+            can_increment,
+            decrement: WidgetPod::new(decrement),
+            increment: WidgetPod::new(increment),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the displayed value.
+    pub fn set_text(&mut self, text: String, ctx: &mut EventCtx) {
+        self.display.widget_mut().set_text(text.clone());
+        self.text = text;
+        ctx.request_layout();
+    }
+
+    /// Enable or disable each button, and request a repaint.
+    pub fn set_enabled(&mut self, can_decrement: bool, can_increment: bool, ctx: &mut EventCtx) {
+        self.can_decrement = can_decrement;
+        self.can_increment = can_increment;
+        ctx.request_paint();
+    }
+}
+
+impl Widget<DruidAppData> for StepperWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        let is_mouse_event = matches!(
+            event,
+            Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_)
+        );
+
+        // Disabled buttons don't get mouse events, so they can't be clicked
+        if self.can_decrement || !is_mouse_event {
+            self.decrement.event(ctx, event, data, env);
+        }
+        self.display.event(ctx, event, data, env);
+        if self.can_increment || !is_mouse_event {
+            self.increment.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.decrement.lifecycle(ctx, event, data, env);
+        self.display.lifecycle(ctx, event, data, env);
+        self.increment.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.decrement.update(ctx, data, env);
+        self.display.update(ctx, data, env);
+        self.increment.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Stepper");
+        let child_bc = bc.loosen();
+        let decrement_size = self.decrement.layout(ctx, &child_bc, data, env);
+        let display_size = self.display.layout(ctx, &child_bc, data, env);
+        let increment_size = self.increment.layout(ctx, &child_bc, data, env);
+
+        let height = decrement_size
+            .height
+            .max(display_size.height)
+            .max(increment_size.height);
+
+        // Children are laid out in a row, centered vertically
+        let mut x = 0.0;
+        self.decrement.set_origin(
+            ctx,
+            data,
+            env,
+            Point::new(x, (height - decrement_size.height) / 2.0),
+        );
+        x += decrement_size.width + BUTTON_SPACING;
+        self.display.set_origin(
+            ctx,
+            data,
+            env,
+            Point::new(x, (height - display_size.height) / 2.0),
+        );
+        x += display_size.width + BUTTON_SPACING;
+        self.increment.set_origin(
+            ctx,
+            data,
+            env,
+            Point::new(x, (height - increment_size.height) / 2.0),
+        );
+        x += increment_size.width;
+
+        let size = bc.constrain(Size::new(x, height));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.decrement.paint(ctx, data, env);
+        self.display.paint(ctx, data, env);
+        self.increment.paint(ctx, data, env);
+
+        // Grey out disabled buttons by painting over them with the background color
+        let overlay = env
+            .get(theme::WINDOW_BACKGROUND_COLOR)
+            .with_alpha(DISABLED_OVERLAY_ALPHA);
+        if !self.can_decrement {
+            ctx.fill(self.decrement.layout_rect(), &overlay);
+        }
+        if !self.can_increment {
+            ctx.fill(self.increment.layout_rect(), &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let mut other_values = HashMap::new();
+        other_values.insert("can_decrement".to_string(), self.can_decrement.to_string());
+        other_values.insert("can_increment".to_string(), self.can_increment.to_string());
+        DebugState {
+            display_name: "Stepper".to_string(),
+            main_value: self.text.clone(),
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleStepperWidget = SingleWidget<StepperWidget>;
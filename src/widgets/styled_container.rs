@@ -2,11 +2,12 @@
 // TODO - merge upstream
 
 use crate::flex::BorderStyle;
-use crate::glue::DebugState;
+use crate::glue::{DebugState, WidgetId};
 
 use druid::widget::prelude::*;
 use druid::widget::BackgroundBrush;
 use druid::{Color, Data, KeyOrValue, Point, WidgetPod};
+use std::collections::HashMap;
 use tracing::{instrument, trace, trace_span};
 
 /// A widget that provides simple visual styling options to a child.
@@ -16,6 +17,7 @@ pub struct Container<T, W> {
     corner_radius: KeyOrValue<f64>,
 
     child: WidgetPod<T, W>,
+    id: Option<WidgetId>,
 }
 
 impl<T: Data, W: Widget<T>> Container<T, W> {
@@ -26,9 +28,15 @@ impl<T: Data, W: Widget<T>> Container<T, W> {
             border: None,
             corner_radius: 0.0.into(),
             child: WidgetPod::new(child),
+            id: None,
         }
     }
 
+    /// Give the container an explicit id, eg to find it in tests.
+    pub fn set_id(&mut self, id: WidgetId) {
+        self.id = Some(id);
+    }
+
     #[allow(dead_code)]
     pub fn child(&self) -> &W {
         self.child.widget()
@@ -223,11 +231,44 @@ impl<T: Data, W: Widget<T>> Widget<T> for Container<T, W> {
         self.child.paint(ctx, data, env);
     }
 
+    fn id(&self) -> Option<WidgetId> {
+        self.id
+    }
+
     fn debug_state(&self, data: &T) -> DebugState {
+        // Only styles which are actually painted are listed.
+        let mut other_values = HashMap::new();
+        if let Some(background) = &self.background {
+            let background = match background {
+                BackgroundBrush::Color(color) => format!("{:?}", color),
+                BackgroundBrush::ColorKey(key) => format!("{:?}", key),
+                _ => "custom".to_string(),
+            };
+            other_values.insert("background".to_string(), background);
+        }
+        if let Some(border) = &self.border {
+            other_values.insert("border_color".to_string(), debug_value(&border.color));
+            other_values.insert("border_width".to_string(), debug_value(&border.width));
+        }
+        if self.corner_radius != KeyOrValue::Concrete(0.0) {
+            other_values.insert(
+                "corner_radius".to_string(),
+                debug_value(&self.corner_radius),
+            );
+        }
+
         DebugState {
             display_name: self.short_type_name().to_string(),
+            other_values,
             children: vec![self.child.widget().debug_state(data)],
             ..Default::default()
         }
     }
 }
+
+fn debug_value<T: std::fmt::Debug>(value: &KeyOrValue<T>) -> String {
+    match value {
+        KeyOrValue::Concrete(value) => format!("{:?}", value),
+        KeyOrValue::Key(key) => format!("{:?}", key),
+    }
+}
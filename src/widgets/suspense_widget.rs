@@ -0,0 +1,151 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like TransitionWidget, we implement Widget instead of FlexWidget, and wrap
+// SuspenseWidget in a SingleWidget.
+
+/// Time for the content to fade in once loaded, in seconds.
+const FADE_DURATION: f64 = 0.15;
+
+/// Displays either a placeholder or the loaded content.
+///
+/// When the content replaces the placeholder, it fades in from the window background, so that
+/// it doesn't pop in abruptly. Like SkeletonWidget, the fade is purely visual, and animation
+/// frames are handled by the widget itself.
+pub struct SuspenseWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub opacity: f64,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> SuspenseWidget<Children> {
+    pub fn new(children: Children, id: WidgetId) -> Self {
+        SuspenseWidget {
+            children,
+            opacity: 1.0,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Start fading the children in. Call after replacing the placeholder with the content.
+    pub fn fade_in(&mut self, ctx: &mut EventCtx) {
+        trace!("Suspense {:?} fading in", self.id());
+        self.opacity = 0.0;
+        ctx.children_changed();
+        ctx.request_anim_frame();
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for SuspenseWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::AnimFrame(interval) = event {
+            if self.opacity < 1.0 {
+                let elapsed = *interval as f64 / 1e9;
+                self.opacity = (self.opacity + elapsed / FADE_DURATION).min(1.0);
+                if self.opacity < 1.0 {
+                    ctx.request_anim_frame();
+                }
+                ctx.request_paint();
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if self.opacity < 1.0 {
+            let overlay = env
+                .get(theme::WINDOW_BACKGROUND_COLOR)
+                .with_alpha(1.0 - self.opacity);
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Suspense".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("opacity".to_string(), format!("{:.2}", self.opacity));
+        debug_state
+    }
+}
+
+pub type SingleSuspenseWidget<Children> = SingleWidget<SuspenseWidget<Children>>;
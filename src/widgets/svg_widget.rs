@@ -0,0 +1,133 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{FillStrat, Label, Svg, SvgData};
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget, WidgetPod,
+};
+
+// Note: Like ImageWidget, we implement Widget instead of FlexWidget, and wrap
+// SvgWidget in a SingleWidget.
+
+const PLACEHOLDER_TEXT: &str = "SVG could not be loaded";
+
+/// Displays an SVG document, or a placeholder if it couldn't be parsed.
+///
+/// The widget takes the intrinsic size of the document, within its constraints; the
+/// document is scaled to the widget's size according to its fill mode.
+pub struct SvgWidget {
+    pub svg: Option<SvgData>,
+    pub fill_mode: FillStrat,
+    pub pod: WidgetPod<(), Box<dyn Widget<()>>>,
+    // The size of the document, or of the placeholder; computed during layout
+    intrinsic_size: Size,
+    id: WidgetId,
+}
+
+impl SvgWidget {
+    /// Create the widget. If `svg` is `None`, a placeholder is displayed instead.
+    pub fn new(svg: Option<SvgData>, fill_mode: FillStrat, id: WidgetId) -> Self {
+        SvgWidget {
+            pod: Self::new_pod(&svg, fill_mode),
+            svg,
+            fill_mode,
+            intrinsic_size: Size::ZERO,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the displayed document.
+    pub fn set_svg(&mut self, svg: Option<SvgData>, ctx: &mut EventCtx) {
+        self.svg = svg;
+        self.pod = Self::new_pod(&self.svg, self.fill_mode);
+        ctx.children_changed();
+    }
+
+    /// Change the way the document is scaled to fit the widget.
+    pub fn set_fill_mode(&mut self, fill_mode: FillStrat, ctx: &mut EventCtx) {
+        self.fill_mode = fill_mode;
+        self.pod = Self::new_pod(&self.svg, self.fill_mode);
+        ctx.children_changed();
+    }
+
+    fn new_pod(svg: &Option<SvgData>, fill_mode: FillStrat) -> WidgetPod<(), Box<dyn Widget<()>>> {
+        match svg {
+            Some(svg) => WidgetPod::new(Box::new(Svg::new(svg.clone()).fill_mode(fill_mode))),
+            None => WidgetPod::new(Box::new(Label::new(PLACEHOLDER_TEXT))),
+        }
+    }
+}
+
+impl Widget<DruidAppData> for SvgWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut DruidAppData, env: &Env) {
+        self.pod.event(ctx, event, &mut (), env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &(), env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &(), env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Svg");
+        // Druid's Svg widget fills bounded constraints, and takes the size of the document
+        // otherwise
+        self.intrinsic_size = self.pod.layout(ctx, &BoxConstraints::UNBOUNDED, &(), env);
+        let size = bc.constrain(self.intrinsic_size);
+        self.pod.layout(ctx, &BoxConstraints::tight(size), &(), env);
+        self.pod.set_origin(ctx, &(), env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &(), env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let main_value = match &self.svg {
+            Some(_) => format!(
+                "{}x{}",
+                self.intrinsic_size.width, self.intrinsic_size.height
+            ),
+            None => PLACEHOLDER_TEXT.to_string(),
+        };
+        DebugState {
+            display_name: "Svg".to_string(),
+            main_value,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleSvgWidget = SingleWidget<SvgWidget>;
@@ -0,0 +1,149 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{Flex, Label, Switch};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Widget, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: Like CheckboxWidget, we implement Widget instead of FlexWidget, and wrap
+// SwitchWidget in a SingleWidget.
+
+const LABEL_SPACING: f64 = 8.0;
+const DISABLED_OVERLAY_ALPHA: f64 = 0.6;
+
+pub struct SwitchWidget {
+    pub value: bool,
+    pub disabled: bool,
+    pub pod: WidgetPod<bool, Box<dyn Widget<bool>>>,
+    id: WidgetId,
+}
+
+impl SwitchWidget {
+    pub fn new(label: Option<String>, value: bool, disabled: bool, id: WidgetId) -> Self {
+        SwitchWidget {
+            value,
+            disabled,
+            pod: Self::new_pod(label),
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the label next to the switch.
+    pub fn set_label(&mut self, label: Option<String>, ctx: &mut EventCtx) {
+        self.pod = Self::new_pod(label);
+        ctx.children_changed();
+    }
+
+    /// Enable or disable the switch, and request a repaint.
+    pub fn set_disabled(&mut self, disabled: bool, ctx: &mut EventCtx) {
+        self.disabled = disabled;
+        ctx.request_paint();
+    }
+
+    fn new_pod(label: Option<String>) -> WidgetPod<bool, Box<dyn Widget<bool>>> {
+        match label {
+            Some(label) => WidgetPod::new(Box::new(
+                Flex::row()
+                    .with_child(Label::new(label))
+                    .with_spacer(LABEL_SPACING)
+                    .with_child(Switch::new()),
+            )),
+            None => WidgetPod::new(Box::new(Switch::new())),
+        }
+    }
+
+    // TODO - merge with SingleWidget::request_druid_update ?
+    pub fn request_druid_update(&mut self, ctx: &mut EventCtx) {
+        self.pod
+            .with_event_context(ctx, |_widget, ctx: &mut EventCtx| {
+                trace!("request_druid_update: {:?}", ctx.widget_id());
+                ctx.request_update();
+            });
+    }
+}
+
+impl Widget<DruidAppData> for SwitchWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if self.disabled {
+            match event {
+                Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) => return,
+                _ => {}
+            }
+        }
+
+        let prev_value = self.value;
+        self.pod.event(ctx, event, &mut self.value, env);
+        if self.value != prev_value {
+            trace!("Switch {:?} value changed: {}", self.id(), self.value);
+            data.queue_action(self.id(), Action::Clicked);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &self.value, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &self.value, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let size = self.pod.layout(ctx, bc, &self.value, env);
+        self.pod.set_origin(ctx, &self.value, env, Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &self.value, env);
+
+        if self.disabled {
+            // Grey out the switch by painting over it with the background color
+            let overlay = env
+                .get(theme::WINDOW_BACKGROUND_COLOR)
+                .with_alpha(DISABLED_OVERLAY_ALPHA);
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Switch".to_string(),
+            main_value: self.value.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleSwitchWidget = SingleWidget<SwitchWidget>;
@@ -0,0 +1,165 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::tab_order::TabStop;
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, InternalLifeCycle, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, Selector, Size, UpdateCtx, Widget,
+};
+use std::rc::Rc;
+
+use tracing::trace;
+
+// Note: Like FocusWidget, we implement Widget instead of FlexWidget, and wrap
+// TabIndexWidget in a SingleWidget.
+
+// Focus changes are notified in lifecycle(), where the focus can't be moved; the widget sends
+// itself this command to move it from event(). The payload is true if the focus must move
+// backward.
+const PASS_FOCUS: Selector<bool> = Selector::new("panoramix.tab-index-pass-focus");
+
+/// A tab stop, which passes the focus on to its children.
+///
+/// The widget is in druid's focus chain, right before its children. When it gets focus, it
+/// moves it to the next widget in the chain, ie its first focusable descendant; if the focus
+/// came back from a descendant (because Shift+Tab was pressed), it moves it to the previous
+/// widget instead.
+pub struct TabIndexWidget<Children: WidgetSequence> {
+    pub children: Children,
+    stop: Rc<TabStop>,
+    // Set when the focus moves from a descendant to this widget
+    entered_backward: bool,
+}
+
+impl<Children: WidgetSequence> TabIndexWidget<Children> {
+    pub fn new(children: Children, index: u32, id: WidgetId) -> Self {
+        TabIndexWidget {
+            children,
+            stop: crate::tab_order::register(index, id),
+            entered_backward: false,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.stop.id
+    }
+
+    pub fn set_index(&mut self, index: u32) {
+        self.stop.index.set(index);
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for TabIndexWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if let Some(backward) = command.get(PASS_FOCUS) {
+                trace!("TabIndex {:?} passing focus on", self.id());
+                if *backward {
+                    ctx.focus_prev();
+                } else {
+                    ctx.focus_next();
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        match event {
+            // Registered before the children, so that the next widget is a descendant
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            LifeCycle::Internal(InternalLifeCycle::RouteFocusChanged { old, new })
+                if *new == Some(self.id()) =>
+            {
+                self.entered_backward = self.stop.has_focus.get() && *old != Some(self.id());
+            }
+            LifeCycle::FocusChanged(true) => {
+                let backward = std::mem::take(&mut self.entered_backward);
+                ctx.submit_command(PASS_FOCUS.with(backward).to(self.id()));
+            }
+            _ => {}
+        }
+
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+
+        // The children have merged their focus state into ours by now
+        self.stop.has_focus.set(ctx.has_focus());
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id())
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "TabIndex".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("tab_index".to_string(), self.stop.index.get().to_string());
+        debug_state
+    }
+}
+
+pub type SingleTabIndexWidget<Children> = SingleWidget<TabIndexWidget<Children>>;
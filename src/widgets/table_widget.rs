@@ -0,0 +1,285 @@
+use crate::elements::Column;
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::{FlexWidget, WidgetSequence};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::kurbo::common::FloatExt;
+use druid::kurbo::Line;
+use druid::widget::Label;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like GridWidget, we implement Widget instead of FlexWidget, and wrap
+// TableWidget in a SingleWidget.
+
+const CELL_PADDING: f64 = 4.0;
+
+/// A header row above rows of cells, laid out in aligned columns.
+///
+/// Each row holds at most one cell per column. A row with fewer cells than there are columns
+/// leaves its last columns empty.
+pub struct TableWidget<Cell: WidgetSequence> {
+    pub headers: Vec<String>,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<Cell>>,
+    header_labels: Vec<SingleWidget<Label<DruidAppData>>>,
+    header_height: f64,
+    id: WidgetId,
+}
+
+impl<Cell: WidgetSequence> TableWidget<Cell> {
+    pub fn new(
+        headers: Vec<String>,
+        columns: Vec<Column>,
+        rows: Vec<Vec<Cell>>,
+        id: WidgetId,
+    ) -> Self {
+        TableWidget {
+            header_labels: Self::make_labels(&headers),
+            headers,
+            columns,
+            rows,
+            header_height: 0.0,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the column headers.
+    pub fn set_headers(&mut self, headers: Vec<String>, ctx: &mut EventCtx) {
+        self.header_labels = Self::make_labels(&headers);
+        self.headers = headers;
+        ctx.children_changed();
+    }
+
+    fn make_labels(headers: &[String]) -> Vec<SingleWidget<Label<DruidAppData>>> {
+        headers
+            .iter()
+            .map(|header| SingleWidget::new(Label::new(header.clone()), Default::default()))
+            .collect()
+    }
+
+    fn cell_widgets_mut(&mut self) -> impl Iterator<Item = &mut dyn FlexWidget> + '_ {
+        self.rows
+            .iter_mut()
+            .flatten()
+            .flat_map(|cell| cell.widgets_mut())
+    }
+
+    // Fixed columns get their width; the rest of the available width is shared between flex
+    // columns. If the available width is unbounded, flex columns are as wide as their widest
+    // cell or header.
+    fn column_widths(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Vec<f64> {
+        let fixed_width: f64 = self
+            .columns
+            .iter()
+            .map(|column| match column {
+                Column::Fixed(width) => *width,
+                Column::Flex(_) => 0.0,
+            })
+            .sum();
+        let total_flex: f64 = self
+            .columns
+            .iter()
+            .map(|column| match column {
+                Column::Fixed(_) => 0.0,
+                Column::Flex(flex) => *flex,
+            })
+            .sum();
+
+        if bc.max().width.is_finite() {
+            let flex_space = (bc.max().width - fixed_width).max(0.0);
+            return self
+                .columns
+                .iter()
+                .map(|column| match column {
+                    Column::Fixed(width) => *width,
+                    Column::Flex(flex) if total_flex > 0.0 => flex_space * flex / total_flex,
+                    Column::Flex(_) => 0.0,
+                })
+                .collect();
+        }
+
+        let unbounded_bc =
+            BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, bc.max().height));
+        let mut natural_widths: Vec<f64> = self
+            .header_labels
+            .iter_mut()
+            .map(|label| label.layout(ctx, &unbounded_bc, data, env).width + 2.0 * CELL_PADDING)
+            .collect();
+        for row in &mut self.rows {
+            for (column, cell) in row.iter_mut().enumerate() {
+                let mut cell_width = 0.0;
+                for child in cell.widgets_mut() {
+                    cell_width += child.layout(ctx, &unbounded_bc, data, env).width.expand();
+                }
+                natural_widths[column] =
+                    natural_widths[column].max(cell_width + 2.0 * CELL_PADDING);
+            }
+        }
+        self.columns
+            .iter()
+            .zip(natural_widths)
+            .map(|(column, natural_width)| match column {
+                Column::Fixed(width) => *width,
+                Column::Flex(_) => natural_width,
+            })
+            .collect()
+    }
+}
+
+impl<Cell: WidgetSequence> Widget<DruidAppData> for TableWidget<Cell> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for label in &mut self.header_labels {
+            label.event(ctx, event, data, env);
+        }
+        for child in self.cell_widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for label in &mut self.header_labels {
+            label.lifecycle(ctx, event, data, env);
+        }
+        for child in self.cell_widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for label in &mut self.header_labels {
+            label.update(ctx, old_data, data, env);
+        }
+        for child in self.cell_widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Table");
+
+        let column_widths = self.column_widths(ctx, bc, data, env);
+        let column_xs: Vec<f64> = column_widths
+            .iter()
+            .scan(0.0, |x, width| {
+                let column_x = *x;
+                *x += width;
+                Some(column_x)
+            })
+            .collect();
+        let cell_bc = |column: usize| {
+            let width = (column_widths[column] - 2.0 * CELL_PADDING).max(0.0);
+            BoxConstraints::new(Size::ZERO, Size::new(width, f64::INFINITY))
+        };
+
+        let mut header_height: f64 = 0.0;
+        for (column, label) in self.header_labels.iter_mut().enumerate() {
+            let label_size = label.layout(ctx, &cell_bc(column), data, env);
+            let origin = Point::new(column_xs[column] + CELL_PADDING, CELL_PADDING);
+            label.set_origin(ctx, data, env, origin);
+            header_height = header_height.max(label_size.height + 2.0 * CELL_PADDING);
+        }
+        self.header_height = header_height;
+
+        // The widgets of a cell are laid out left-to-right
+        let mut y = header_height;
+        for row in &mut self.rows {
+            let mut row_height: f64 = 0.0;
+            for (column, cell) in row.iter_mut().enumerate() {
+                let mut x = column_xs[column] + CELL_PADDING;
+                for child in cell.widgets_mut() {
+                    let child_size = child.layout(ctx, &cell_bc(column), data, env);
+                    child.set_origin(ctx, data, env, Point::new(x, y + CELL_PADDING));
+                    x += child_size.width.expand();
+                    row_height = row_height.max(child_size.height.expand() + 2.0 * CELL_PADDING);
+                }
+            }
+            y += row_height;
+        }
+
+        let width = column_widths.iter().sum();
+        let size = bc.constrain(Size::new(width, y));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for label in &mut self.header_labels {
+            label.paint(ctx, data, env);
+        }
+
+        let separator = Line::new(
+            (0.0, self.header_height),
+            (ctx.size().width, self.header_height),
+        );
+        ctx.stroke(separator, &env.get(theme::BORDER_DARK), 1.0);
+
+        for child in self.cell_widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let rows_state = self
+            .rows
+            .iter()
+            .map(|row| {
+                let cells_state = row
+                    .iter()
+                    .flat_map(|cell| cell.widgets())
+                    .map(|child| child.debug_state(data))
+                    .collect();
+                DebugState {
+                    display_name: "TableRow".to_string(),
+                    children: cells_state,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        DebugState {
+            display_name: "Table".to_string(),
+            children: rows_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleTableWidget<Cell> = SingleWidget<TableWidget<Cell>>;
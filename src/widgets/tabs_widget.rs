@@ -0,0 +1,248 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::{should_propagate_to_hidden, FlexWidget, WidgetSequence};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::{Button, Click, ControllerHost};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, UpdateCtx, Widget,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like TooltipWidget, we implement Widget instead of FlexWidget, and wrap
+// TabsWidget in a SingleWidget.
+
+const HEADER_UNDERLINE_WIDTH: f64 = 2.0;
+
+type TabButton = ControllerHost<Button<DruidAppData>, Click<DruidAppData>>;
+
+/// A row of tab headers, above the body of the active tab.
+///
+/// Clicking a header queues [`Action::Selected`] with the index of the tab; changing the
+/// active tab is left to the element.
+///
+/// Bodies of inactive tabs may be kept alive; they still get lifecycle events, commands and
+/// updates, but they aren't laid out, painted, or sent user input.
+pub struct TabsWidget<Body: WidgetSequence> {
+    pub titles: Vec<String>,
+    pub active: usize,
+    pub headers: Vec<SingleWidget<TabButton>>,
+    pub bodies: Vec<Option<Body>>,
+    id: WidgetId,
+}
+
+impl<Body: WidgetSequence> TabsWidget<Body> {
+    pub fn new(
+        titles: Vec<String>,
+        active: usize,
+        bodies: Vec<Option<Body>>,
+        id: WidgetId,
+    ) -> Self {
+        TabsWidget {
+            headers: Self::new_headers(&titles, id),
+            titles,
+            active,
+            bodies,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the tab headers.
+    pub fn set_titles(&mut self, titles: Vec<String>, ctx: &mut EventCtx) {
+        self.headers = Self::new_headers(&titles, self.id);
+        self.titles = titles;
+        ctx.children_changed();
+    }
+
+    /// Show the body of another tab.
+    pub fn set_active(&mut self, active: usize, ctx: &mut EventCtx) {
+        self.active = active;
+        ctx.request_layout();
+        ctx.request_paint();
+    }
+
+    fn new_headers(titles: &[String], id: WidgetId) -> Vec<SingleWidget<TabButton>> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| {
+                let header =
+                    Button::new(title.clone()).on_click(move |_, data: &mut DruidAppData, _| {
+                        data.queue_action(id, Action::Selected(index))
+                    });
+                SingleWidget::new(header, Default::default())
+            })
+            .collect()
+    }
+
+    fn active_body(&mut self) -> Option<&mut Body> {
+        self.bodies.get_mut(self.active)?.as_mut()
+    }
+}
+
+impl<Body: WidgetSequence> Widget<DruidAppData> for TabsWidget<Body> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for header in &mut self.headers {
+            header.event(ctx, event, data, env);
+        }
+
+        let active = self.active;
+        for (index, body) in self.bodies.iter_mut().enumerate() {
+            if index != active && !should_propagate_to_hidden(event) {
+                continue;
+            }
+            for child in body.iter_mut().flat_map(|body| body.widgets_mut()) {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for header in &mut self.headers {
+            header.lifecycle(ctx, event, data, env);
+        }
+        for child in self
+            .bodies
+            .iter_mut()
+            .flatten()
+            .flat_map(|body| body.widgets_mut())
+        {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for header in &mut self.headers {
+            header.update(ctx, old_data, data, env);
+        }
+        for child in self
+            .bodies
+            .iter_mut()
+            .flatten()
+            .flat_map(|body| body.widgets_mut())
+        {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Tabs");
+
+        // Headers are laid out in a row, above the body
+        let header_bc = bc.loosen();
+        let mut headers_width = 0.0;
+        let mut headers_height: f64 = 0.0;
+        for header in &mut self.headers {
+            let header_size = header.layout(ctx, &header_bc, data, env);
+            header.set_origin(ctx, data, env, Point::new(headers_width, 0.0));
+            headers_width += header_size.width;
+            headers_height = headers_height.max(header_size.height);
+        }
+        let body_top = headers_height + HEADER_UNDERLINE_WIDTH;
+
+        let body_bc = bc.shrink((0.0, body_top));
+        let mut body_size = Size::ZERO;
+        if let Some(body) = self.active_body() {
+            for child in body.widgets_mut() {
+                let child_size = child.layout(ctx, &body_bc, data, env);
+                child.set_origin(ctx, data, env, Point::new(0.0, body_top));
+                body_size = Size::new(
+                    body_size.width.max(child_size.width),
+                    body_size.height.max(child_size.height),
+                );
+            }
+        }
+
+        let size = bc.constrain(Size::new(
+            headers_width.max(body_size.width),
+            body_top + body_size.height,
+        ));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for header in &mut self.headers {
+            header.paint(ctx, data, env);
+        }
+
+        // Underline the header row, and highlight the header of the active tab
+        let headers_bottom = self
+            .headers
+            .iter()
+            .map(|header| header.layout_rect().y1)
+            .fold(0.0, f64::max);
+        let underline = Rect::new(
+            0.0,
+            headers_bottom,
+            ctx.size().width,
+            headers_bottom + HEADER_UNDERLINE_WIDTH,
+        );
+        ctx.fill(underline, &env.get(theme::BORDER_LIGHT));
+        if let Some(active_header) = self.headers.get(self.active) {
+            let header_rect = active_header.layout_rect();
+            let highlight = Rect::new(header_rect.x0, underline.y0, header_rect.x1, underline.y1);
+            ctx.fill(highlight, &env.get(theme::PRIMARY_LIGHT));
+        }
+
+        if let Some(body) = self.active_body() {
+            for child in body.widgets_mut() {
+                child.paint(ctx, data, env);
+            }
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let body_state = match self.bodies.get(self.active) {
+            Some(Some(body)) => body
+                .widgets()
+                .iter()
+                .map(|child| child.debug_state(data))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        // The active title isn't the main value, so that the harness finds the header instead
+        let mut other_values = HashMap::new();
+        if let Some(title) = self.titles.get(self.active) {
+            other_values.insert("active".to_string(), title.clone());
+        }
+        DebugState {
+            display_name: "Tabs".to_string(),
+            other_values,
+            children: body_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleTabsWidget<Body> = SingleWidget<TabsWidget<Body>>;
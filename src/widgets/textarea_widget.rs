@@ -0,0 +1,163 @@
+use crate::flex::FlexParams;
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::test_harness::with_simulated_input;
+use crate::widget_sequence::FlexWidget;
+use crate::widget_sequence::WidgetSequence;
+
+use crate::glue::DebugState;
+use druid::kurbo::{Point, Rect, Size};
+use druid::widget::{IdentityWrapper, TextBox};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    UpdateCtx, Widget, WidgetExt, WidgetPod,
+};
+
+use tracing::trace;
+
+// Note: This is mostly a copy of TextBoxWidget, with a multi-line druid TextBox and a minimum
+// height.
+
+// Approximate height of a line of text, as a multiple of the font size
+const LINE_HEIGHT_FACTOR: f64 = 1.2;
+
+pub struct TextAreaWidget {
+    pub text: String,
+    pub rows: Option<usize>,
+    pub pod: WidgetPod<String, IdentityWrapper<Box<dyn Widget<String>>>>,
+    pub flex: FlexParams,
+    id: WidgetId,
+}
+
+impl TextAreaWidget {
+    pub fn new(
+        text: String,
+        placeholder: String,
+        rows: Option<usize>,
+        flex: FlexParams,
+        id: WidgetId,
+    ) -> Self {
+        TextAreaWidget {
+            text,
+            rows,
+            pod: Self::new_pod(placeholder, id),
+            flex,
+            id,
+        }
+    }
+
+    /// Replace the placeholder text displayed when the area is empty.
+    pub fn set_placeholder(&mut self, placeholder: String, ctx: &mut EventCtx) {
+        self.pod = Self::new_pod(placeholder, self.id);
+        ctx.children_changed();
+    }
+
+    fn new_pod(
+        placeholder: String,
+        id: WidgetId,
+    ) -> WidgetPod<String, IdentityWrapper<Box<dyn Widget<String>>>> {
+        let textbox =
+            with_simulated_input(TextBox::multiline().with_placeholder(placeholder), true)
+                .with_id(id);
+        WidgetPod::new(textbox)
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    // TODO - merge with SingleWidget::request_druid_update ?
+    pub fn request_druid_update(&mut self, ctx: &mut EventCtx) {
+        self.pod
+            .with_event_context(ctx, |_widget, ctx: &mut EventCtx| {
+                trace!("request_druid_update: {:?}", ctx.widget_id());
+                ctx.request_update();
+            });
+    }
+}
+
+impl FlexWidget for TextAreaWidget {
+    fn flex_params(&self) -> FlexParams {
+        self.flex
+    }
+
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::KeyUp(_) = event {
+            trace!("TextArea {:?} content changed: {}", self.id(), self.text);
+            data.queue_action(self.id(), Action::TextChanged(self.text.clone()));
+        }
+        self.pod.event(ctx, event, &mut self.text, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.lifecycle(ctx, event, &mut self.text, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        _data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.pod.update(ctx, &mut self.text, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        let bc = match self.rows {
+            Some(rows) => {
+                let min_height =
+                    rows as f64 * env.get(theme::TEXT_SIZE_NORMAL) * LINE_HEIGHT_FACTOR;
+                let min_height = min_height.max(bc.min().height).min(bc.max().height);
+                BoxConstraints::new(Size::new(bc.min().width, min_height), bc.max())
+            }
+            None => *bc,
+        };
+        self.pod.layout(ctx, &bc, &mut self.text, env)
+    }
+
+    fn paint_rect(&self) -> Rect {
+        self.pod.paint_rect()
+    }
+
+    fn set_origin(&mut self, ctx: &mut LayoutCtx, _data: &DruidAppData, env: &Env, origin: Point) {
+        self.pod.set_origin(ctx, &mut self.text, env, origin)
+    }
+
+    fn layout_rect(&self) -> Rect {
+        self.pod.layout_rect()
+    }
+
+    fn baseline_offset(&self) -> f64 {
+        self.pod.baseline_offset()
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &DruidAppData, env: &Env) {
+        self.pod.paint(ctx, &mut self.text, env);
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        self.pod.widget().debug_state(&self.text)
+    }
+}
+
+impl WidgetSequence for TextAreaWidget {
+    fn widgets(&self) -> Vec<&dyn FlexWidget> {
+        vec![self]
+    }
+
+    fn widgets_mut(&mut self) -> Vec<&mut dyn FlexWidget> {
+        vec![self]
+    }
+}
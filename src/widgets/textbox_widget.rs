@@ -1,5 +1,6 @@
 use crate::flex::FlexParams;
 use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::test_harness::with_simulated_input;
 use crate::widget_sequence::FlexWidget;
 use crate::widget_sequence::WidgetSequence;
 
@@ -15,14 +16,14 @@ use tracing::trace;
 
 pub struct TextBoxWidget {
     pub text: String,
-    pub pod: WidgetPod<String, IdentityWrapper<TextBox<String>>>,
+    pub pod: WidgetPod<String, IdentityWrapper<Box<dyn Widget<String>>>>,
     pub flex: FlexParams,
     id: WidgetId,
 }
 
 impl TextBoxWidget {
     pub fn new(text: String, flex: FlexParams, id: WidgetId) -> Self {
-        let textbox = TextBox::new().with_id(id);
+        let textbox = with_simulated_input(TextBox::new(), false).with_id(id);
 
         TextBoxWidget {
             text,
@@ -51,7 +52,7 @@ impl FlexWidget for TextBoxWidget {
         self.flex
     }
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+    fn on_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
         if let Event::KeyUp(_) = event {
             trace!("TextBox {:?} content changed: {}", self.id(), self.text);
             data.queue_action(self.id(), Action::TextChanged(self.text.clone()));
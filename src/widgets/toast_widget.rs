@@ -0,0 +1,145 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::widget::Label;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, TimerToken, UpdateCtx, Widget, WidgetPod,
+};
+use std::time::Instant;
+
+use tracing::trace;
+
+// Note: Like DebounceWidget, we implement Widget instead of FlexWidget, and wrap
+// ToastWidget in a SingleWidget.
+
+// The deadline is stored in the element's state; the widget only requests the timer. The
+// timer belongs to the widget, so it's dropped along with it when the toast is removed.
+
+const TOAST_PADDING: f64 = 8.0;
+const TOAST_RADIUS: f64 = 4.0;
+
+/// A message in a rounded box, which queues [`Action::TimerFired`] when its deadline is reached.
+pub struct ToastWidget {
+    pub text: String,
+    pub deadline: Instant,
+    pub timer: Option<TimerToken>,
+    pub label: WidgetPod<DruidAppData, Label<DruidAppData>>,
+    id: WidgetId,
+}
+
+impl ToastWidget {
+    pub fn new(text: String, deadline: Instant, id: WidgetId) -> Self {
+        ToastWidget {
+            label: WidgetPod::new(Label::new(text.clone())),
+            text,
+            deadline,
+            timer: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the displayed message.
+    pub fn set_text(&mut self, text: String, ctx: &mut EventCtx) {
+        self.label.widget_mut().set_text(text.clone());
+        self.text = text;
+        ctx.request_layout();
+    }
+
+    /// Request a timer firing at `deadline`, replacing the previous one.
+    pub fn restart_timer(&mut self, deadline: Instant, ctx: &mut EventCtx) {
+        self.deadline = deadline;
+        self.timer = Some(ctx.request_timer(deadline.saturating_duration_since(Instant::now())));
+    }
+}
+
+impl Widget<DruidAppData> for ToastWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Timer(token) = event {
+            if self.timer == Some(*token) {
+                trace!("Toast {:?} timer fired", self.id());
+                self.timer = None;
+                data.queue_action(self.id(), Action::TimerFired);
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        self.label.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        // Timers can only be requested once the widget is part of the tree
+        if let LifeCycle::WidgetAdded = event {
+            let delay = self.deadline.saturating_duration_since(Instant::now());
+            self.timer = Some(ctx.request_timer(delay));
+        }
+
+        self.label.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        _old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.label.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Toast");
+        let label_bc = bc
+            .shrink((2.0 * TOAST_PADDING, 2.0 * TOAST_PADDING))
+            .loosen();
+        let label_size = self.label.layout(ctx, &label_bc, data, env);
+        self.label
+            .set_origin(ctx, data, env, Point::new(TOAST_PADDING, TOAST_PADDING));
+
+        let size = bc.constrain(Size::new(
+            label_size.width + 2.0 * TOAST_PADDING,
+            label_size.height + 2.0 * TOAST_PADDING,
+        ));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        let background = ctx.size().to_rounded_rect(TOAST_RADIUS);
+        ctx.fill(background, &env.get(theme::BACKGROUND_LIGHT));
+        ctx.stroke(background, &env.get(theme::BORDER_DARK), 1.0);
+        self.label.paint(ctx, data, env);
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        DebugState {
+            display_name: "Toast".to_string(),
+            main_value: self.text.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleToastWidget = SingleWidget<ToastWidget>;
@@ -0,0 +1,171 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, RenderContext, Size, TextLayout, UpdateCtx, Vec2, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like HoverWidget, we implement Widget instead of FlexWidget, and wrap
+// TooltipWidget in a SingleWidget.
+
+const TOOLTIP_PADDING: f64 = 4.0;
+// Offset from the mouse cursor, so that the tooltip isn't hidden under it
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(8.0, 16.0);
+// Painted above siblings
+const TOOLTIP_Z_INDEX: u32 = 1;
+
+/// Shows a text box next to the mouse while it's over the children.
+///
+/// Events are forwarded to the children untouched, so the tooltip doesn't change how they
+/// react to the mouse.
+pub struct TooltipWidget<Children: WidgetSequence> {
+    pub children: Children,
+    text: String,
+    layout: TextLayout<String>,
+    mouse_pos: Option<Point>,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> TooltipWidget<Children> {
+    pub fn new(children: Children, text: String, id: WidgetId) -> Self {
+        TooltipWidget {
+            children,
+            layout: TextLayout::from_text(text.clone()),
+            text,
+            mouse_pos: None,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Replace the text of the tooltip.
+    pub fn set_text(&mut self, text: String, ctx: &mut EventCtx) {
+        self.layout.set_text(text.clone());
+        self.text = text;
+        ctx.request_layout();
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for TooltipWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+
+        if let Event::MouseMove(mouse_event) = event {
+            let mouse_pos = if ctx.is_hot() {
+                Some(mouse_event.pos)
+            } else {
+                None
+            };
+            if mouse_pos != self.mouse_pos {
+                self.mouse_pos = mouse_pos;
+                ctx.request_paint();
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::HotChanged(false) = event {
+            self.mouse_pos = None;
+            ctx.request_paint();
+        }
+
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        self.layout.rebuild_if_needed(ctx.text(), env);
+
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if let Some(mouse_pos) = self.mouse_pos {
+            trace!("Painting tooltip {:?} at {}", self.id(), mouse_pos);
+            let layout = self.layout.clone();
+            let origin = mouse_pos + TOOLTIP_OFFSET;
+            let background = env.get(theme::BACKGROUND_LIGHT);
+            let border = env.get(theme::BORDER_DARK);
+            ctx.paint_with_z_index(TOOLTIP_Z_INDEX, move |ctx| {
+                let rect = Rect::from_origin_size(origin, layout.size())
+                    .inflate(TOOLTIP_PADDING, TOOLTIP_PADDING);
+                ctx.fill(rect, &background);
+                ctx.stroke(rect, &border, 1.0);
+                layout.draw(ctx, origin);
+            });
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Tooltip".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("tooltip".to_string(), self.text.clone());
+        debug_state
+    }
+}
+
+pub type SingleTooltipWidget<Children> = SingleWidget<TooltipWidget<Children>>;
@@ -0,0 +1,176 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, RenderContext, Size, UpdateCtx, Vec2, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like DebounceWidget, we implement Widget instead of FlexWidget, and wrap
+// TransitionWidget in a SingleWidget.
+
+// The widget only requests animation frames and paints the current progress; the progress
+// itself is stored in the element's state, which advances it each time a frame is queued.
+
+/// Fades and slides its children according to the progress of a transition.
+///
+/// While the progress hasn't reached its target, the widget requests animation frames, and
+/// queues [`Action::AnimFrame`] with the time elapsed since the previous frame. Like
+/// OpacityWidget, the children are faded by painting the window background over them.
+pub struct TransitionWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub progress: f64,
+    pub visible: bool,
+    pub offset: Vec2,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> TransitionWidget<Children> {
+    pub fn new(
+        children: Children,
+        progress: f64,
+        visible: bool,
+        offset: Vec2,
+        id: WidgetId,
+    ) -> Self {
+        TransitionWidget {
+            children,
+            progress,
+            visible,
+            offset,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Whether the progress still has to move towards its target.
+    pub fn is_animating(&self) -> bool {
+        let target = if self.visible { 1.0 } else { 0.0 };
+        self.progress != target
+    }
+
+    /// Update the progress and its target, and request an animation frame if they differ.
+    pub fn set_progress(&mut self, progress: f64, visible: bool, ctx: &mut EventCtx) {
+        self.progress = progress;
+        self.visible = visible;
+        if self.is_animating() {
+            ctx.request_anim_frame();
+        }
+        ctx.request_layout();
+    }
+
+    // The children slide from `offset` to their place as the progress goes from 0 to 1
+    fn children_origin(&self) -> Point {
+        (self.offset * (1.0 - self.progress)).to_point()
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for TransitionWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::AnimFrame(interval) = event {
+            if self.is_animating() {
+                trace!("Transition {:?} animation frame: {}ns", self.id(), interval);
+                data.queue_action(self.id(), Action::AnimFrame(*interval));
+            }
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        // The offset doesn't change the space taken by the children
+        let origin = self.children_origin();
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, origin);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        if self.progress <= 0.0 {
+            return;
+        }
+
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+
+        if self.progress < 1.0 {
+            let overlay = env
+                .get(theme::WINDOW_BACKGROUND_COLOR)
+                .with_alpha(1.0 - self.progress);
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &overlay);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Transition".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("opacity".to_string(), format!("{:.2}", self.progress));
+        debug_state
+    }
+}
+
+pub type SingleTransitionWidget<Children> = SingleWidget<TransitionWidget<Children>>;
@@ -0,0 +1,246 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::kurbo::{BezPath, Point, Rect, Size};
+use druid::{
+    theme, BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    RenderContext, UpdateCtx, Widget,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like StackWidget, we implement Widget instead of FlexWidget, and wrap
+// TreeViewWidget in a SingleWidget.
+
+// Width of each indentation level. The toggle arrow of a row is drawn in the last level,
+// just before the row's content.
+const INDENT_WIDTH: f64 = 16.0;
+const ARROW_SIZE: f64 = 8.0;
+
+/// A visible node of a tree view.
+///
+/// `expanded` is `None` if the node has no children.
+pub struct TreeRowWidget<Content: WidgetSequence> {
+    pub content: Content,
+    pub depth: usize,
+    pub expanded: Option<bool>,
+    pub selected: bool,
+    rect: Rect,
+}
+
+/// The visible nodes of a tree, as a column of indented rows.
+///
+/// Clicking the toggle arrow of a row queues [`Action::Toggled`], and clicking anywhere else
+/// in the row queues [`Action::Selected`], both with the index of the row. Expanding nodes
+/// and changing the selection is left to the element.
+pub struct TreeViewWidget<Content: WidgetSequence> {
+    pub rows: Vec<TreeRowWidget<Content>>,
+    id: WidgetId,
+}
+
+impl<Content: WidgetSequence> TreeRowWidget<Content> {
+    pub fn new(content: Content, depth: usize, expanded: Option<bool>, selected: bool) -> Self {
+        TreeRowWidget {
+            content,
+            depth,
+            expanded,
+            selected,
+            rect: Rect::ZERO,
+        }
+    }
+
+    fn toggle_rect(&self) -> Rect {
+        let x0 = self.depth as f64 * INDENT_WIDTH;
+        Rect::new(x0, self.rect.y0, x0 + INDENT_WIDTH, self.rect.y1)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .content
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut other_values = HashMap::new();
+        if let Some(expanded) = self.expanded {
+            other_values.insert("expanded".to_string(), expanded.to_string());
+        }
+        if self.selected {
+            other_values.insert("selected".to_string(), "true".to_string());
+        }
+        DebugState {
+            display_name: "TreeRow".to_string(),
+            other_values,
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+impl<Content: WidgetSequence> TreeViewWidget<Content> {
+    pub fn new(rows: Vec<TreeRowWidget<Content>>, id: WidgetId) -> Self {
+        TreeViewWidget { rows, id }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+}
+
+impl<Content: WidgetSequence> Widget<DruidAppData> for TreeViewWidget<Content> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        for row in &mut self.rows {
+            for child in row.content.widgets_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+
+        if let Event::MouseDown(mouse_event) = event {
+            let clicked_row = self
+                .rows
+                .iter()
+                .position(|row| row.rect.contains(mouse_event.pos));
+            if let Some(index) = clicked_row {
+                let row = &self.rows[index];
+                if row.expanded.is_some() && row.toggle_rect().contains(mouse_event.pos) {
+                    trace!("TreeView {:?} row {} toggled", self.id(), index);
+                    data.queue_action(self.id(), Action::Toggled(index));
+                } else {
+                    trace!("TreeView {:?} row {} selected", self.id(), index);
+                    data.queue_action(self.id(), Action::Selected(index));
+                }
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for row in &mut self.rows {
+            for child in row.content.widgets_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for row in &mut self.rows {
+            for child in row.content.widgets_mut() {
+                child.update(ctx, old_data, data, env);
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("TreeView");
+
+        let mut width: f64 = 0.0;
+        let mut y = 0.0;
+        for row in &mut self.rows {
+            let content_x = (row.depth + 1) as f64 * INDENT_WIDTH;
+            let content_bc = BoxConstraints::new(
+                Size::ZERO,
+                Size::new((bc.max().width - content_x).max(0.0), f64::INFINITY),
+            );
+
+            // Children of the row's content are laid out in a row, centered vertically
+            let mut child_widgets = row.content.widgets_mut();
+            let mut row_height = INDENT_WIDTH;
+            let mut child_sizes = Vec::with_capacity(child_widgets.len());
+            for child in &mut child_widgets {
+                let child_size = child.layout(ctx, &content_bc, data, env);
+                row_height = row_height.max(child_size.height);
+                child_sizes.push(child_size);
+            }
+            let mut x = content_x;
+            for (child, child_size) in child_widgets.iter_mut().zip(child_sizes) {
+                let origin = Point::new(x, y + (row_height - child_size.height) / 2.0);
+                child.set_origin(ctx, data, env, origin);
+                x += child_size.width;
+            }
+
+            width = width.max(x);
+            row.rect = Rect::new(0.0, y, 0.0, y + row_height);
+            y += row_height;
+        }
+
+        let size = bc.constrain(Size::new(width, y));
+        // Rows span the whole width, so that clicking next to the content selects it
+        for row in &mut self.rows {
+            row.rect.x1 = size.width;
+        }
+
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        let arrow_color = env.get(theme::FOREGROUND_LIGHT);
+        let selection_color = env.get(theme::PRIMARY_DARK);
+
+        for row in &mut self.rows {
+            if row.selected {
+                ctx.fill(row.rect, &selection_color);
+            }
+
+            if let Some(expanded) = row.expanded {
+                let center = row.toggle_rect().center();
+                let half = ARROW_SIZE / 2.0;
+                let mut arrow = BezPath::new();
+                if expanded {
+                    // Pointing down
+                    arrow.move_to((center.x - half, center.y - half / 2.0));
+                    arrow.line_to((center.x + half, center.y - half / 2.0));
+                    arrow.line_to((center.x, center.y + half / 2.0));
+                } else {
+                    // Pointing right
+                    arrow.move_to((center.x - half / 2.0, center.y - half));
+                    arrow.line_to((center.x + half / 2.0, center.y));
+                    arrow.line_to((center.x - half / 2.0, center.y + half));
+                }
+                arrow.close_path();
+                ctx.fill(arrow, &arrow_color);
+            }
+
+            for child in row.content.widgets_mut() {
+                child.paint(ctx, data, env);
+            }
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let rows_state = self.rows.iter().map(|row| row.debug_state(data)).collect();
+
+        DebugState {
+            display_name: "TreeView".to_string(),
+            children: rows_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleTreeViewWidget<Content> = SingleWidget<TreeViewWidget<Content>>;
@@ -0,0 +1,172 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::FlexWidget;
+use crate::widgets::{SingleWidget, TextBoxWidget};
+
+use crate::glue::DebugState;
+use druid::widget::Label;
+use druid::{
+    BoxConstraints, Color, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Selector, Size, UpdateCtx, Widget,
+};
+
+use std::collections::HashMap;
+use tracing::trace;
+
+// Note: Like StepperWidget, we implement Widget instead of FlexWidget, and wrap
+// ValidatedTextBoxWidget in a SingleWidget.
+
+// Focus changes are notified in lifecycle(), where the app data can't be changed; like
+// FocusWidget, the widget sends itself this command to queue the action from event().
+const FOCUS_LOST: Selector = Selector::new("panoramix.validated-textbox-focus-lost");
+
+const ERROR_COLOR: Color = Color::rgb8(0xe0, 0x4f, 0x4f);
+const ERROR_SPACING: f64 = 4.0;
+
+/// A text box, with an error message displayed below it.
+///
+/// Editing the text queues [`Action::TextChanged`], and leaving the box queues
+/// [`Action::FocusChanged`]; validating the text is left to the element.
+pub struct ValidatedTextBoxWidget {
+    // The text box has our id, and queues the TextChanged actions
+    pub textbox: TextBoxWidget,
+    pub error: Option<String>,
+    pub error_label: SingleWidget<Label<DruidAppData>>,
+    // Whether the text box had focus last time we checked
+    has_focus: bool,
+}
+
+impl ValidatedTextBoxWidget {
+    pub fn new(text: String, error: Option<String>, id: WidgetId) -> Self {
+        let mut error_label = Label::new(error.clone().unwrap_or_default());
+        error_label.set_text_color(ERROR_COLOR);
+        ValidatedTextBoxWidget {
+            textbox: TextBoxWidget::new(text, Default::default(), id),
+            error,
+            error_label: SingleWidget::new(error_label, Default::default()),
+            has_focus: false,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.textbox.id()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.textbox.text
+    }
+
+    /// Replace the text being edited.
+    pub fn set_text(&mut self, text: String, ctx: &mut EventCtx) {
+        self.textbox.text = text;
+        self.textbox.request_druid_update(ctx);
+    }
+
+    /// Show the given error message below the text box, or hide it if `error` is `None`.
+    pub fn set_error(&mut self, error: Option<String>, ctx: &mut EventCtx) {
+        if let Some(message) = &error {
+            self.error_label.widget_mut().set_text(message.clone());
+        }
+        self.error = error;
+        ctx.request_layout();
+    }
+}
+
+impl Widget<DruidAppData> for ValidatedTextBoxWidget {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if command.is(FOCUS_LOST) {
+                trace!("ValidatedTextBox {:?} lost focus", self.id());
+                data.queue_action(self.id(), Action::FocusChanged(false));
+                ctx.set_handled();
+                return;
+            }
+        }
+        self.textbox.event(ctx, event, data, env);
+        if self.error.is_some() {
+            self.error_label.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.textbox.lifecycle(ctx, event, data, env);
+        self.error_label.lifecycle(ctx, event, data, env);
+
+        // The text box has merged its focus state into ours by now
+        if ctx.has_focus() != self.has_focus {
+            self.has_focus = ctx.has_focus();
+            if !self.has_focus {
+                ctx.submit_command(FOCUS_LOST.to(self.id()));
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        self.textbox.update(ctx, old_data, data, env);
+        self.error_label.update(ctx, old_data, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("ValidatedTextBox");
+        let child_bc = bc.loosen();
+        let textbox_size = self.textbox.layout(ctx, &child_bc, data, env);
+        self.textbox.set_origin(ctx, data, env, Point::ORIGIN);
+
+        // The error message is laid out below the text box, only when there is one
+        let mut size = textbox_size;
+        if self.error.is_some() {
+            let error_size = self.error_label.layout(ctx, &child_bc, data, env);
+            let y = textbox_size.height + ERROR_SPACING;
+            self.error_label
+                .set_origin(ctx, data, env, Point::new(0.0, y));
+            size = Size::new(
+                textbox_size.width.max(error_size.width),
+                y + error_size.height,
+            );
+        }
+
+        let size = bc.constrain(size);
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        self.textbox.paint(ctx, data, env);
+        if self.error.is_some() {
+            self.error_label.paint(ctx, data, env);
+        }
+    }
+
+    // The text isn't the main value, so that the harness finds the text box instead
+    fn debug_state(&self, _data: &DruidAppData) -> DebugState {
+        let mut other_values = HashMap::new();
+        other_values.insert("text".to_string(), self.text().to_string());
+        if let Some(error) = &self.error {
+            other_values.insert("error".to_string(), error.clone());
+        }
+        DebugState {
+            display_name: "ValidatedTextBox".to_string(),
+            other_values,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleValidatedTextBoxWidget = SingleWidget<ValidatedTextBoxWidget>;
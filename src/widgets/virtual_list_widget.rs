@@ -0,0 +1,245 @@
+use crate::glue::{Action, DruidAppData, WidgetId};
+use crate::widget_sequence::WidgetSequence;
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    RenderContext, Selector, Size, UpdateCtx, Widget,
+};
+use std::collections::HashMap;
+use std::ops::Range;
+
+use tracing::trace;
+
+// Note: Like ScrollWidget, we implement Widget instead of FlexWidget, and wrap
+// VirtualListWidget in a SingleWidget.
+
+// Unlike ScrollWidget, the list doesn't use druid's Scroll: items are direct children of the
+// list, so that the ones created when scrolling get their WidgetAdded event.
+
+/// Number of items built above and below the ones which are actually visible.
+const VIRTUAL_LIST_BUFFER: usize = 5;
+
+// Sent by the list to itself when its size changes, since the visible range can't be
+// updated during layout.
+const REFRESH_VISIBLE_RANGE: Selector = Selector::new("panoramix.virtual-list-refresh");
+
+/// A vertical scroll container, over a list of items of which only a few are built.
+///
+/// Built items are laid out at the position of their index, as if all items were built.
+/// When scrolling changes which items are visible, the widget queues
+/// [`Action::VisibleRangeChanged`]; building the newly visible items is left to the element.
+pub struct VirtualListWidget<Item: WidgetSequence> {
+    pub count: usize,
+    pub item_height: f64,
+    pub items: Vec<(usize, Item)>,
+    offset: f64,
+    viewport_height: f64,
+    visible_range: Range<usize>,
+    id: WidgetId,
+}
+
+impl<Item: WidgetSequence> VirtualListWidget<Item> {
+    pub fn new(
+        count: usize,
+        item_height: f64,
+        items: Vec<(usize, Item)>,
+        visible_range: Range<usize>,
+        id: WidgetId,
+    ) -> Self {
+        VirtualListWidget {
+            count,
+            item_height,
+            items,
+            offset: 0.0,
+            viewport_height: 0.0,
+            visible_range,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Check which items are visible once the current event pass is done.
+    ///
+    /// Used when the item count changes.
+    pub fn refresh_visible_range(&self, ctx: &mut EventCtx) {
+        ctx.submit_command(REFRESH_VISIBLE_RANGE.to(self.id));
+    }
+
+    fn max_offset(&self) -> f64 {
+        (self.count as f64 * self.item_height - self.viewport_height).max(0.0)
+    }
+
+    // The items overlapping the viewport, plus a buffer on each side.
+    fn compute_visible_range(&self) -> Range<usize> {
+        if self.item_height <= 0.0 {
+            return 0..self.count;
+        }
+        let end = ((self.offset + self.viewport_height) / self.item_height).ceil() as usize;
+        let end = (end + VIRTUAL_LIST_BUFFER).min(self.count);
+        let start = ((self.offset / self.item_height).floor() as usize)
+            .saturating_sub(VIRTUAL_LIST_BUFFER)
+            .min(end);
+        start..end
+    }
+
+    fn update_visible_range(&mut self, data: &mut DruidAppData) {
+        let visible_range = self.compute_visible_range();
+        if visible_range != self.visible_range {
+            trace!(
+                "VirtualList {:?} visible range: {:?}",
+                self.id(),
+                visible_range
+            );
+            self.visible_range = visible_range.clone();
+            data.queue_action(self.id(), Action::VisibleRangeChanged(visible_range));
+        }
+    }
+}
+
+impl<Item: WidgetSequence> Widget<DruidAppData> for VirtualListWidget<Item> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if let Event::Command(command) = event {
+            if command.is(REFRESH_VISIBLE_RANGE) {
+                ctx.set_handled();
+                self.update_visible_range(data);
+                return;
+            }
+        }
+
+        for (_index, item) in &mut self.items {
+            for child in item.widgets_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+
+        if let Event::Wheel(mouse_event) = event {
+            if !ctx.is_handled() {
+                let offset = (self.offset + mouse_event.wheel_delta.y)
+                    .max(0.0)
+                    .min(self.max_offset());
+                if offset != self.offset {
+                    self.offset = offset;
+                    ctx.request_layout();
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+                self.update_visible_range(data);
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        if let LifeCycle::Size(_) = event {
+            ctx.submit_command(REFRESH_VISIBLE_RANGE.to(self.id));
+        }
+
+        for (_index, item) in &mut self.items {
+            for child in item.widgets_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for (_index, item) in &mut self.items {
+            for child in item.widgets_mut() {
+                child.update(ctx, old_data, data, env);
+            }
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("VirtualList");
+
+        // Without a height limit, the list is as tall as all its items
+        let total_height = self.count as f64 * self.item_height;
+        let height = if bc.max().height.is_finite() {
+            bc.max().height
+        } else {
+            total_height
+        };
+        self.viewport_height = height;
+        self.offset = self.offset.min(self.max_offset());
+
+        let item_bc = BoxConstraints::new(
+            Size::new(0.0, self.item_height),
+            Size::new(bc.max().width, self.item_height),
+        );
+        let mut width: f64 = 0.0;
+        for (index, item) in &mut self.items {
+            let y = *index as f64 * self.item_height - self.offset;
+            for child in item.widgets_mut() {
+                let child_size = child.layout(ctx, &item_bc, data, env);
+                child.set_origin(ctx, data, env, Point::new(0.0, y));
+                width = width.max(child_size.width);
+            }
+        }
+
+        let size = bc.constrain(Size::new(width, height));
+        trace!("Computed layout: size={}", size);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        let viewport = ctx.size().to_rect();
+        ctx.with_save(|ctx| {
+            ctx.clip(viewport);
+            for (_index, item) in &mut self.items {
+                for child in item.widgets_mut() {
+                    child.paint(ctx, data, env);
+                }
+            }
+        });
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .items
+            .iter()
+            .flat_map(|(_index, item)| item.widgets())
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut other_values = HashMap::new();
+        other_values.insert("offset".to_string(), self.offset.to_string());
+        other_values.insert(
+            "visible_range".to_string(),
+            format!("{:?}", self.visible_range),
+        );
+        DebugState {
+            display_name: "VirtualList".to_string(),
+            other_values,
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
+
+pub type SingleVirtualListWidget<Item> = SingleWidget<VirtualListWidget<Item>>;
@@ -0,0 +1,139 @@
+use crate::glue::{DruidAppData, WidgetId};
+use crate::widget_sequence::{should_propagate_to_hidden, WidgetSequence};
+use crate::widgets::SingleWidget;
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Point,
+    Size, UpdateCtx, Widget,
+};
+
+use tracing::trace;
+
+// Note: Like OpacityWidget, we implement Widget instead of FlexWidget, and wrap
+// VisibilityWidget in a SingleWidget.
+
+/// Shows or hides its children without removing them.
+///
+/// Like the inactive bodies of TabsWidget, hidden children aren't laid out or painted, and
+/// don't get user input, but they stay mounted, so they keep their state.
+pub struct VisibilityWidget<Children: WidgetSequence> {
+    pub children: Children,
+    pub visible: bool,
+    id: WidgetId,
+}
+
+impl<Children: WidgetSequence> VisibilityWidget<Children> {
+    pub fn new(children: Children, visible: bool, id: WidgetId) -> Self {
+        VisibilityWidget {
+            children,
+            visible,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    /// Show or hide the children, and request a new layout.
+    pub fn set_visible(&mut self, visible: bool, ctx: &mut EventCtx) {
+        trace!("Visibility {:?} set to {}", self.id(), visible);
+        self.visible = visible;
+        ctx.request_layout();
+    }
+}
+
+impl<Children: WidgetSequence> Widget<DruidAppData> for VisibilityWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        if !self.visible && !should_propagate_to_hidden(event) {
+            return;
+        }
+
+        for child in self.children.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        if !self.visible {
+            return bc.min();
+        }
+
+        let mut size = Size::ZERO;
+        for child in self.children.widgets_mut() {
+            let child_size = child.layout(ctx, bc, data, env);
+            child.set_origin(ctx, data, env, Point::ZERO);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        if !self.visible {
+            return;
+        }
+
+        for child in self.children.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.id)
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        let mut debug_state = DebugState {
+            display_name: "Visibility".to_string(),
+            children: children_state,
+            ..Default::default()
+        };
+        debug_state
+            .other_values
+            .insert("visible".to_string(), self.visible.to_string());
+        debug_state
+    }
+}
+
+pub type SingleVisibilityWidget<Children> = SingleWidget<VisibilityWidget<Children>>;
@@ -0,0 +1,132 @@
+//! A widget that lays out its children in rows, wrapping them to a new row when they overflow.
+
+use druid::kurbo::common::FloatExt;
+use druid::kurbo::{Point, Rect, Size};
+
+use crate::glue::DebugState;
+use druid::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    Widget,
+};
+
+use crate::widget_sequence::WidgetSequence;
+
+use tracing::trace;
+
+/// A container Druid widget which lays out its children left-to-right, and starts a new row
+/// when a child doesn't fit in what's left of the current one.
+///
+/// Children keep their own size; rows are as tall as their tallest child, and aren't
+/// stretched to the container's width.
+pub struct WrapWidget<Children: WidgetSequence> {
+    /// Space inserted between adjacent children and adjacent rows.
+    pub(crate) spacing: f64,
+    pub children_seq: Children,
+}
+
+use crate::glue::DruidAppData;
+impl<Children: WidgetSequence> Widget<DruidAppData> for WrapWidget<Children> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut DruidAppData, env: &Env) {
+        // FIXME
+        ctx.children_changed();
+
+        for child in self.children_seq.widgets_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(
+        &mut self,
+        ctx: &mut UpdateCtx,
+        old_data: &DruidAppData,
+        data: &DruidAppData,
+        env: &Env,
+    ) {
+        for child in self.children_seq.widgets_mut() {
+            child.update(ctx, old_data, data, env);
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &DruidAppData,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Wrap");
+        let max_width = bc.max().width;
+        let child_bc = BoxConstraints::new(Size::ZERO, Size::new(max_width, std::f64::INFINITY));
+
+        let mut row_x = 0.0;
+        let mut row_y = 0.0;
+        let mut row_height: f64 = 0.0;
+        let mut width: f64 = 0.0;
+        let mut child_paint_rect = Rect::ZERO;
+        let mut child_widgets = self.children_seq.widgets_mut();
+        for child in &mut child_widgets {
+            let child_size = child.layout(ctx, &child_bc, data, env);
+
+            // The first child of a row is never wrapped, even if it overflows
+            if row_x > 0.0 && row_x + child_size.width > max_width {
+                row_y += row_height + self.spacing;
+                row_x = 0.0;
+                row_height = 0.0;
+            }
+
+            child.set_origin(ctx, data, env, Point::new(row_x, row_y));
+            child_paint_rect = child_paint_rect.union(child.paint_rect());
+
+            width = width.max(row_x + child_size.width.expand());
+            row_height = row_height.max(child_size.height.expand());
+            row_x += child_size.width.expand() + self.spacing;
+        }
+        let height = if child_widgets.is_empty() {
+            0.0
+        } else {
+            row_y + row_height
+        };
+
+        let my_size = bc.constrain(Size::new(width, height));
+
+        let my_bounds = Rect::ZERO.with_size(my_size);
+        let insets = child_paint_rect - my_bounds;
+        ctx.set_paint_insets(insets);
+
+        trace!("Computed layout: size={}", my_size);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &DruidAppData, env: &Env) {
+        for child in self.children_seq.widgets_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    fn debug_state(&self, data: &DruidAppData) -> DebugState {
+        let children_state = self
+            .children_seq
+            .widgets()
+            .iter()
+            .map(|child| child.debug_state(data))
+            .collect();
+
+        DebugState {
+            display_name: "Wrap".to_string(),
+            children: children_state,
+            ..Default::default()
+        }
+    }
+}
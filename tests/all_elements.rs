@@ -1,6 +1,6 @@
 use panoramix::elements::{
-    Button, Checkbox, ComponentOutput, ElementBox, ElementList, Label, TextBox, TextChanged,
-    Toggled,
+    Button, ButtonClick, Checkbox, CheckboxToggled, ComponentOutput, ElementBox, ElementList,
+    Label, TextBox, TextChanged,
 };
 use panoramix::flex::FlexParams;
 use panoramix::internals::WidgetId;
@@ -45,7 +45,7 @@ impl Default for AppState {
     }
 }
 
-type ItemEvent = Toggled;
+type ItemEvent = CheckboxToggled;
 
 #[component]
 fn TaskRow(ctx: &CompCtx, props: TaskItem) -> impl Element<Event = ItemEvent> {
@@ -53,7 +53,9 @@ fn TaskRow(ctx: &CompCtx, props: TaskItem) -> impl Element<Event = ItemEvent> {
     let text = props.text.clone();
 
     let checkbox = ElementBox::new(
-        Checkbox::new("", props.is_completed).with_reserved_id(WidgetId::reserved(props.id + 10)),
+        Checkbox::new("")
+            .with_checked(props.is_completed)
+            .with_reserved_id(WidgetId::reserved(props.id + 10)),
     )
     .bubble_up::<ItemEvent, _, _>(md);
     let row = if props.is_completed {
@@ -109,7 +111,7 @@ fn AwesomeEditableList(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEven
         TaskRow::new(task_item.clone()).on::<ItemEvent, _, _, _>(
             md,
             move |state: &mut AppState, event| {
-                state.tasks[i].is_completed = event.new_value;
+                state.tasks[i].is_completed = event.0;
             },
         )
     });
@@ -134,6 +136,18 @@ fn AwesomeEditableList(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEven
     )
 }
 
+type ToolbarEvent = ButtonClick;
+
+#[component]
+fn Toolbar(ctx: &CompCtx, _props: ()) -> impl Element<Event = ToolbarEvent> {
+    let md = ctx.use_metadata::<ToolbarEvent, ()>();
+
+    let button_save = Button::new("Save").bubble_up::<ToolbarEvent, _, _>(md);
+    let button_cancel = Button::new("Cancel");
+
+    ComponentOutput::new(md, Row!(button_save, button_cancel))
+}
+
 use insta::assert_debug_snapshot;
 use panoramix::test_harness::Harness;
 use test_env_log::test;
@@ -171,3 +185,65 @@ fn test_all_widgets() {
         assert_debug_snapshot!(root_state_4);
     });
 }
+
+#[test]
+fn click_buttons_by_name() {
+    let list = AwesomeEditableList::new(());
+
+    Harness::run_test_window(list, |harness| {
+        let root_text =
+            |harness: &Harness<'_, '_, _>| format!("{:?}", harness.get_root_debug_state());
+        assert!(!root_text(harness).contains("(complete)"));
+
+        // The button is nested in a row; clicking it adds a task
+        harness.click_on("New task");
+        assert!(root_text(harness).contains("(complete)"));
+    });
+}
+
+#[test]
+fn type_text_in_textbox() {
+    let list = AwesomeEditableList::new(());
+
+    Harness::run_test_window(list, |harness| {
+        let root_text =
+            |harness: &Harness<'_, '_, _>| format!("{:?}", harness.get_root_debug_state());
+
+        // Text boxes are named after their content; Enter doesn't change a single-line box
+        harness.type_text("Next task", "\u{8}\u{8}\u{8}\u{8}milk\n");
+        harness.click_on("New task");
+
+        // The new task uses the typed name, and the text box is cleared
+        assert!(root_text(harness).contains("\"Next milk\""));
+        assert!(root_text(harness).contains("(complete)"));
+        assert!(!root_text(harness).contains("\"Next task\""));
+    });
+}
+
+#[test]
+#[should_panic(expected = "no widget named \"Save\"")]
+fn click_missing_button() {
+    let list = AwesomeEditableList::new(());
+
+    Harness::run_test_window(list, |harness| {
+        harness.click_on("Save");
+    });
+}
+
+#[test]
+fn root_emitted_events() {
+    Harness::run_test_window(Toolbar::new(()), |harness| {
+        assert_eq!(harness.take_emitted_events::<ToolbarEvent>(), vec![]);
+
+        harness.click_on("Save");
+        harness.click_on("Cancel");
+        harness.click_on("Save");
+
+        // Only the events bubbled up to the component are emitted
+        assert_eq!(
+            harness.take_emitted_events::<ToolbarEvent>(),
+            vec![ButtonClick, ButtonClick]
+        );
+        assert_eq!(harness.take_emitted_events::<ToolbarEvent>(), vec![]);
+    });
+}
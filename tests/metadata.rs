@@ -1,4 +1,4 @@
-use panoramix::elements::{Button, ComponentOutput, EmptyElement};
+use panoramix::elements::{Button, ComponentOutput, EmptyElement, Label};
 use panoramix::internals::WidgetId;
 use panoramix::Metadata;
 use panoramix::{component, CompCtx, Element, ElementExt, NoEvent};
@@ -20,8 +20,9 @@ fn NoComponentOutput(ctx: &CompCtx, _props: ()) -> impl Element<Event = NoEvent>
     EmptyElement::new()
 }
 
+// The declared local state matches the returned ComponentOutput, but not use_metadata
 #[component]
-fn WrongState(ctx: &CompCtx, _props: ()) -> impl Element<Event = MyEvent> {
+fn WrongState(ctx: &CompCtx, _props: ()) -> impl Element<Event = MyEvent, ComponentState = i32> {
     let md = ctx.use_metadata::<MyEvent, ()>();
     let _local_state = ctx.get_local_state(md);
 
@@ -49,6 +50,13 @@ fn CorrectMetadata(ctx: &CompCtx, id: WidgetId) -> impl Element<Event = MyEvent>
     ComponentOutput::new(md, button)
 }
 
+#[component(initial_state = |start: &i32| *start)]
+fn StartsAt(ctx: &CompCtx, _start: i32) -> impl Element<Event = NoEvent> {
+    let md = ctx.use_metadata::<NoEvent, i32>();
+    let count = ctx.get_local_state(md);
+    ComponentOutput::new(md, Label::new(format!("Count: {}", count)))
+}
+
 use panoramix::test_harness::Harness;
 use test_env_log::test;
 
@@ -93,3 +101,14 @@ fn correct_metadata() {
         harness.mouse_click_on(button_id);
     });
 }
+
+#[test]
+fn initial_state() {
+    Harness::run_test_window(StartsAt::new(5), |harness| {
+        assert!(harness.find_by_name("Count: 5").is_some());
+
+        // The initial state is only used the first time the component is built
+        harness.update_root_element(StartsAt::new(7));
+        assert!(harness.find_by_name("Count: 5").is_some());
+    });
+}